@@ -1,8 +1,16 @@
 //! General purpose message-related types
 
-use der::{Sequence, ValueOrd};
+use alloc::vec::Vec;
+use der::asn1::ObjectIdentifier;
+use der::{Any, Decode, Encode, ErrorKind, Sequence, ValueOrd};
+
+use crmf::controls::Controls;
+use crmf::request::CertTemplate;
+use spki::AlgorithmIdentifierOwned;
 use x509_cert::attr::{AttributeType, AttributeValue};
 
+use crate::header::CmpCertificate;
+
 /// The `InfoTypeAndValue` type is defined in [RFC 4210 Section 5.3.19]
 ///
 /// ```text
@@ -28,7 +36,7 @@ pub struct InfoTypeAndValue {
 /// ```
 ///
 /// [RFC 4210 Section 5.3.19]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.19
-pub type GenMsgContent = alloc::vec::Vec<InfoTypeAndValue>;
+pub type GenMsgContent = Vec<InfoTypeAndValue>;
 
 /// The `GenRepContent` type is defined in [RFC 4210 Section 5.3.20]
 ///
@@ -37,4 +45,248 @@ pub type GenMsgContent = alloc::vec::Vec<InfoTypeAndValue>;
 /// ```
 ///
 /// [RFC 4210 Section 5.3.20]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.20
-pub type GenRepContent = alloc::vec::Vec<InfoTypeAndValue>;
+pub type GenRepContent = Vec<InfoTypeAndValue>;
+
+impl InfoTypeAndValue {
+    /// Create an `InfoTypeAndValue` of the given [`InfoType`] with the `infoValue` absent, as
+    /// used in `genm` messages to request the corresponding information.
+    pub fn request<T: InfoType>() -> Self {
+        Self {
+            oid: T::INFO_TYPE,
+            value: None,
+        }
+    }
+
+    /// Create an `InfoTypeAndValue` of the given [`InfoType`] carrying `value`.
+    pub fn new<T: InfoType>(value: &T::Value) -> der::Result<Self> {
+        Ok(Self {
+            oid: T::INFO_TYPE,
+            value: Some(Any::from_der(&value.to_der()?)?),
+        })
+    }
+
+    /// Decode the `infoValue` as the given [`InfoType`].
+    ///
+    /// Returns `None` if the `infoValue` is absent, and an error if the `infoType` is not
+    /// that of `T`.
+    pub fn value_as<T: InfoType>(&self) -> der::Result<Option<T::Value>> {
+        if self.oid != T::INFO_TYPE {
+            return Err(ErrorKind::OidUnknown { oid: self.oid }.into());
+        }
+
+        self.value
+            .as_ref()
+            .map(|value| T::Value::from_der(&value.to_der()?))
+            .transpose()
+    }
+}
+
+/// General message information types, associating an `infoType` OID with the type of the
+/// corresponding `infoValue`.
+pub trait InfoType: Sized {
+    /// The `infoType` object identifier.
+    const INFO_TYPE: ObjectIdentifier;
+
+    /// The type of the `infoValue`.
+    type Value: Encode + for<'a> Decode<'a>;
+
+    /// Build the content of a `genm` message requesting this information type.
+    fn genm_request() -> GenMsgContent {
+        alloc::vec![InfoTypeAndValue::request::<Self>()]
+    }
+
+    /// Find the first `InfoTypeAndValue` of this information type in the content of a `genp`
+    /// message and decode its `infoValue`.
+    ///
+    /// Returns `None` if no such `InfoTypeAndValue` is present or if its `infoValue` is
+    /// absent, as is the case when the server has no information to return.
+    fn find_in(content: &[InfoTypeAndValue]) -> der::Result<Option<Self::Value>> {
+        match content.iter().find(|itav| itav.oid == Self::INFO_TYPE) {
+            Some(itav) => itav.value_as::<Self>(),
+            None => Ok(None),
+        }
+    }
+}
+
+/// `id-it` arc as defined in [RFC 4210 Section 5.3.19].
+///
+/// [RFC 4210 Section 5.3.19]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.19
+pub const ID_IT: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.4");
+
+/// `id-it-caProtEncCert` as defined in [RFC 4210 Section 5.3.19.1].
+///
+/// [RFC 4210 Section 5.3.19.1]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.19.1
+pub const ID_IT_CA_PROT_ENC_CERT: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.4.1");
+
+/// `id-it-signKeyPairTypes` as defined in [RFC 4210 Section 5.3.19.2].
+///
+/// [RFC 4210 Section 5.3.19.2]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.19.2
+pub const ID_IT_SIGN_KEY_PAIR_TYPES: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.4.2");
+
+/// `id-it-rootCaKeyUpdate` as defined in [RFC 9480 Section 2.14].
+///
+/// [RFC 9480 Section 2.14]: https://www.rfc-editor.org/rfc/rfc9480#section-2.14
+pub const ID_IT_ROOT_CA_KEY_UPDATE: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.4.18");
+
+/// `id-it-certReqTemplate` as defined in [RFC 9480 Section 2.15].
+///
+/// [RFC 9480 Section 2.15]: https://www.rfc-editor.org/rfc/rfc9480#section-2.15
+pub const ID_IT_CERT_REQ_TEMPLATE: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.4.19");
+
+/// `id-it-rootCaCert` as defined in [RFC 9480 Section 2.14].
+///
+/// [RFC 9480 Section 2.14]: https://www.rfc-editor.org/rfc/rfc9480#section-2.14
+pub const ID_IT_ROOT_CA_CERT: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.4.20");
+
+/// The `CAProtEncCert` information type is defined in [RFC 4210 Section 5.3.19.1].
+///
+/// ```text
+///  CAProtEncCertValue ::= CMPCertificate
+/// ```
+///
+/// [RFC 4210 Section 5.3.19.1]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.19.1
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CaProtEncCert;
+
+impl InfoType for CaProtEncCert {
+    const INFO_TYPE: ObjectIdentifier = ID_IT_CA_PROT_ENC_CERT;
+    type Value = CaProtEncCertValue;
+}
+
+/// The `CAProtEncCertValue` type is defined in [RFC 4210 Section 5.3.19.1].
+///
+/// ```text
+///  CAProtEncCertValue ::= CMPCertificate
+/// ```
+///
+/// [RFC 4210 Section 5.3.19.1]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.19.1
+pub type CaProtEncCertValue = CmpCertificate;
+
+/// The `SignKeyPairTypes` information type is defined in [RFC 4210 Section 5.3.19.2].
+///
+/// ```text
+///  SignKeyPairTypesValue ::= SEQUENCE SIZE (1..MAX) OF
+///                                AlgorithmIdentifier{SIGNATURE-ALGORITHM, {...}}
+/// ```
+///
+/// [RFC 4210 Section 5.3.19.2]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.19.2
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SignKeyPairTypes;
+
+impl InfoType for SignKeyPairTypes {
+    const INFO_TYPE: ObjectIdentifier = ID_IT_SIGN_KEY_PAIR_TYPES;
+    type Value = SignKeyPairTypesValue;
+}
+
+/// The `SignKeyPairTypesValue` type is defined in [RFC 4210 Section 5.3.19.2].
+///
+/// ```text
+///  SignKeyPairTypesValue ::= SEQUENCE SIZE (1..MAX) OF
+///                                AlgorithmIdentifier{SIGNATURE-ALGORITHM, {...}}
+/// ```
+///
+/// [RFC 4210 Section 5.3.19.2]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.19.2
+pub type SignKeyPairTypesValue = Vec<AlgorithmIdentifierOwned>;
+
+/// The `CertReqTemplate` information type is defined in [RFC 9480 Section 2.15].
+///
+/// The `genm` request carries no `infoValue`. The `genp` response carries no `infoValue`
+/// either if the server has no template to offer.
+///
+/// ```text
+///  CertReqTemplateValue  ::= CertReqTemplateContent
+/// ```
+///
+/// [RFC 9480 Section 2.15]: https://www.rfc-editor.org/rfc/rfc9480#section-2.15
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CertReqTemplate;
+
+impl InfoType for CertReqTemplate {
+    const INFO_TYPE: ObjectIdentifier = ID_IT_CERT_REQ_TEMPLATE;
+    type Value = CertReqTemplateContent;
+}
+
+/// The `CertReqTemplateContent` type is defined in [RFC 9480 Section 2.15].
+///
+/// ```text
+///  CertReqTemplateContent ::= SEQUENCE {
+///     certTemplate           CertTemplate,
+///     keySpec                Controls OPTIONAL }
+/// ```
+///
+/// [RFC 9480 Section 2.15]: https://www.rfc-editor.org/rfc/rfc9480#section-2.15
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct CertReqTemplateContent {
+    pub cert_template: CertTemplate,
+    pub key_spec: Option<Controls>,
+}
+
+/// The `RootCaKeyUpdate` information type is defined in [RFC 9480 Section 2.14].
+///
+/// The `genm` request may carry the root CA certificate to be updated as an
+/// `id-it-rootCaCert` [`InfoTypeAndValue`], see [`RootCaCert`]. The `genp` response carries
+/// no `infoValue` if no update is available.
+///
+/// ```text
+///  RootCaKeyUpdateValue ::= RootCaKeyUpdateContent
+/// ```
+///
+/// [RFC 9480 Section 2.14]: https://www.rfc-editor.org/rfc/rfc9480#section-2.14
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RootCaKeyUpdate;
+
+impl InfoType for RootCaKeyUpdate {
+    const INFO_TYPE: ObjectIdentifier = ID_IT_ROOT_CA_KEY_UPDATE;
+    type Value = RootCaKeyUpdateContent;
+}
+
+/// The `RootCaKeyUpdateContent` type is defined in [RFC 9480 Section 2.14].
+///
+/// ```text
+///  RootCaKeyUpdateContent ::= SEQUENCE {
+///     newWithNew       CMPCertificate,
+///     newWithOld   [0] CMPCertificate OPTIONAL,
+///     oldWithNew   [1] CMPCertificate OPTIONAL
+///  }
+/// ```
+///
+/// [RFC 9480 Section 2.14]: https://www.rfc-editor.org/rfc/rfc9480#section-2.14
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct RootCaKeyUpdateContent {
+    pub new_with_new: CmpCertificate,
+    #[asn1(
+        context_specific = "0",
+        tag_mode = "EXPLICIT",
+        constructed = "true",
+        optional = "true"
+    )]
+    pub new_with_old: Option<CmpCertificate>,
+    #[asn1(
+        context_specific = "1",
+        tag_mode = "EXPLICIT",
+        constructed = "true",
+        optional = "true"
+    )]
+    pub old_with_new: Option<CmpCertificate>,
+}
+
+/// The `RootCaCert` information type is defined in [RFC 9480 Section 2.14].
+///
+/// ```text
+///  RootCaCertValue ::= CMPCertificate
+/// ```
+///
+/// [RFC 9480 Section 2.14]: https://www.rfc-editor.org/rfc/rfc9480#section-2.14
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RootCaCert;
+
+impl InfoType for RootCaCert {
+    const INFO_TYPE: ObjectIdentifier = ID_IT_ROOT_CA_CERT;
+    type Value = CmpCertificate;
+}
@@ -1,7 +1,13 @@
 use cmpv2::body::PkiBody;
+use cmpv2::gen::{
+    CertReqTemplate, CertReqTemplateContent, InfoType, InfoTypeAndValue, RootCaCert,
+    RootCaKeyUpdate, RootCaKeyUpdateContent, SignKeyPairTypes, ID_IT_SIGN_KEY_PAIR_TYPES,
+};
 use cmpv2::header::{PkiHeader, Pvno};
 use cmpv2::message::PkiMessage;
-use der::{Decode, Encode};
+use crmf::request::CertTemplate;
+use der::{Decode, Encode, ErrorKind};
+use x509_cert::Certificate;
 
 #[test]
 fn genm_req_header_test() {
@@ -81,3 +87,79 @@ fn genm_rsp_message_test() {
     println!("Reencoded: {:02X?}", reencoded_req_01);
     assert_eq!(req_01, reencoded_req_01.as_slice());
 }
+
+#[test]
+fn genm_req_info_types_test() {
+    // genm request for signKeyPairTypes, see genm_req_message_test
+    let req_01 = include_bytes!("examples/genm_req_01.bin");
+    let message = PkiMessage::from_der(req_01).unwrap();
+    let content = match message.body {
+        PkiBody::GenM(content) => content,
+        _ => panic!("expected genm body"),
+    };
+
+    assert_eq!(content, SignKeyPairTypes::genm_request());
+    assert_eq!(content[0].oid, ID_IT_SIGN_KEY_PAIR_TYPES);
+    assert_eq!(SignKeyPairTypes::find_in(&content).unwrap(), None);
+    assert_eq!(
+        content[0].value_as::<RootCaKeyUpdate>().unwrap_err().kind(),
+        ErrorKind::OidUnknown {
+            oid: ID_IT_SIGN_KEY_PAIR_TYPES
+        }
+    );
+}
+
+#[test]
+fn genp_root_ca_key_update_test() {
+    let enc_server_cert = include_bytes!("examples/ec384-server-key.der");
+    let server_cert = Certificate::from_der(enc_server_cert).unwrap();
+
+    // genm request carrying the root CA certificate to be updated
+    let genm = PkiBody::GenM(vec![
+        InfoTypeAndValue::request::<RootCaKeyUpdate>(),
+        InfoTypeAndValue::new::<RootCaCert>(&server_cert).unwrap(),
+    ]);
+    let der = genm.to_der().unwrap();
+    let content = match PkiBody::from_der(&der).unwrap() {
+        PkiBody::GenM(content) => content,
+        _ => panic!("expected genm body"),
+    };
+    assert_eq!(RootCaKeyUpdate::find_in(&content).unwrap(), None);
+    assert_eq!(
+        RootCaCert::find_in(&content).unwrap().as_ref(),
+        Some(&server_cert)
+    );
+
+    let update = RootCaKeyUpdateContent {
+        new_with_new: server_cert.clone(),
+        new_with_old: Some(server_cert.clone()),
+        old_with_new: None,
+    };
+    let template = CertReqTemplateContent {
+        cert_template: CertTemplate {
+            version: None,
+            serial_number: None,
+            signature: None,
+            issuer: None,
+            validity: None,
+            subject: Some(server_cert.tbs_certificate.subject.clone()),
+            subject_public_key_info: None,
+            issuer_unique_id: None,
+            subject_unique_id: None,
+            extensions: None,
+        },
+        key_spec: None,
+    };
+    let genp = PkiBody::GenP(vec![
+        InfoTypeAndValue::new::<RootCaKeyUpdate>(&update).unwrap(),
+        InfoTypeAndValue::new::<CertReqTemplate>(&template).unwrap(),
+    ]);
+    let der = genp.to_der().unwrap();
+    let content = match PkiBody::from_der(&der).unwrap() {
+        PkiBody::GenP(content) => content,
+        _ => panic!("expected genp body"),
+    };
+    assert_eq!(RootCaKeyUpdate::find_in(&content).unwrap(), Some(update));
+    assert_eq!(CertReqTemplate::find_in(&content).unwrap(), Some(template));
+    assert_eq!(SignKeyPairTypes::find_in(&content).unwrap(), None);
+}
@@ -1,7 +1,17 @@
 //! Attribute-related types
-use der::asn1::OctetString;
+use alloc::vec::Vec;
+use core::fmt;
 
+use const_oid::db::rfc5911::ID_COUNTERSIGNATURE;
+use const_oid::AssociatedOid;
+use der::asn1::{GeneralizedTime, ObjectIdentifier, OctetString, SetOfVec, UtcTime};
+use der::{Any, DateTime, Encode, Sequence};
+use spki::AlgorithmIdentifierOwned;
+
+use x509_cert::attr::pkcs9::{self, Pkcs9Attribute};
+use x509_cert::attr::{Attribute, Attributes};
 use x509_cert::time::Time;
+use x509_cert::Certificate;
 
 use crate::signed_data::SignerInfo;
 
@@ -23,6 +33,21 @@ pub type MessageDigest = OctetString;
 /// [RFC 5652 Section 11.3]: https://www.rfc-editor.org/rfc/rfc5652#section-11.3
 pub type SigningTime = Time;
 
+/// Create a [`SigningTime`] for `date_time` using the encoding required by
+/// [RFC 5652 Section 11.3]: dates through 2049 are encoded as UTCTime, later dates as
+/// GeneralizedTime.
+///
+/// Both encodings are accepted when reading the signing-time attribute.
+///
+/// [RFC 5652 Section 11.3]: https://www.rfc-editor.org/rfc/rfc5652#section-11.3
+pub fn signing_time_from_date_time(date_time: DateTime) -> der::Result<SigningTime> {
+    if date_time.year() <= UtcTime::MAX_YEAR {
+        Ok(UtcTime::from_date_time(date_time)?.into())
+    } else {
+        Ok(GeneralizedTime::from_date_time(date_time).into())
+    }
+}
+
 /// The `Countersignature` attribute is defined in [RFC 5652 Section 11.4].
 ///
 /// ```text
@@ -31,3 +56,318 @@ pub type SigningTime = Time;
 ///
 /// [RFC 5652 Section 11.4]: https://www.rfc-editor.org/rfc/rfc5652#section-11.4
 pub type Countersignature = SignerInfo;
+
+/// Object identifier of the `CMSAlgorithmProtection` attribute, defined in [RFC 6211 Section 2].
+///
+/// [RFC 6211 Section 2]: https://www.rfc-editor.org/rfc/rfc6211#section-2
+pub const ID_AA_CMS_ALGORITHM_PROTECTION: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.52");
+
+/// The `CMSAlgorithmProtection` attribute is defined in [RFC 6211 Section 2].
+///
+/// ```text
+///   CMSAlgorithmProtection ::= SEQUENCE {
+///       digestAlgorithm         DigestAlgorithmIdentifier,
+///       signatureAlgorithm  [1] SignatureAlgorithmIdentifier OPTIONAL,
+///       macAlgorithm        [2] MessageAuthenticationCodeAlgorithm
+///                                        OPTIONAL
+///   }
+///   (WITH COMPONENTS { signatureAlgorithm PRESENT,
+///                      macAlgorithm ABSENT } |
+///    WITH COMPONENTS { signatureAlgorithm ABSENT,
+///                      macAlgorithm PRESENT })
+/// ```
+///
+/// [RFC 6211 Section 2]: https://www.rfc-editor.org/rfc/rfc6211#section-2
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct CmsAlgorithmProtection {
+    pub digest_algorithm: AlgorithmIdentifierOwned,
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    pub signature_algorithm: Option<AlgorithmIdentifierOwned>,
+    #[asn1(context_specific = "2", tag_mode = "IMPLICIT", optional = "true")]
+    pub mac_algorithm: Option<AlgorithmIdentifierOwned>,
+}
+
+impl AssociatedOid for CmsAlgorithmProtection {
+    const OID: ObjectIdentifier = ID_AA_CMS_ALGORITHM_PROTECTION;
+}
+
+impl Pkcs9Attribute for CmsAlgorithmProtection {}
+
+/// Typed access to the attributes defined in [RFC 5652 Section 11] which are carried in
+/// [`SignedAttributes`][`crate::signed_data::SignedAttributes`].
+///
+/// The content-type, message-digest and signing-time attributes are single-valued, and a
+/// `SignedAttributes` set must not include multiple instances of them. The accessors return an
+/// error if these constraints are violated, and `Ok(None)` if the attribute is absent.
+///
+/// The setters replace any existing instance of the attribute, keeping the `SET OF` ordering
+/// required by DER intact.
+///
+/// [RFC 5652 Section 11]: https://www.rfc-editor.org/rfc/rfc5652#section-11
+pub trait SignedAttributesExt {
+    /// Get the value of the content-type attribute ([RFC 5652 Section 11.1]).
+    ///
+    /// [RFC 5652 Section 11.1]: https://www.rfc-editor.org/rfc/rfc5652#section-11.1
+    fn content_type(&self) -> der::Result<Option<ObjectIdentifier>>;
+
+    /// Get the value of the message-digest attribute ([RFC 5652 Section 11.2]).
+    ///
+    /// [RFC 5652 Section 11.2]: https://www.rfc-editor.org/rfc/rfc5652#section-11.2
+    fn message_digest(&self) -> der::Result<Option<MessageDigest>>;
+
+    /// Get the value of the signing-time attribute ([RFC 5652 Section 11.3]).
+    ///
+    /// [RFC 5652 Section 11.3]: https://www.rfc-editor.org/rfc/rfc5652#section-11.3
+    fn signing_time(&self) -> der::Result<Option<SigningTime>>;
+
+    /// Encode the signed attributes as the input of the signature, as described in
+    /// [RFC 5652 Section 5.4].
+    ///
+    /// The signature is computed over the DER encoding of the `SET OF Attribute` with its
+    /// universal `SET OF` tag (`0x31`), not over the encoding of the `signedAttrs` field of the
+    /// `SignerInfo`, which carries the `[0] IMPLICIT` tag (`0xA0`) instead.
+    ///
+    /// Attributes decoded from a message are kept sorted as DER requires, so this matches the
+    /// original encoding of the `signedAttrs` for DER-encoded messages.
+    ///
+    /// [RFC 5652 Section 5.4]: https://www.rfc-editor.org/rfc/rfc5652#section-5.4
+    fn to_der_for_signing(&self) -> der::Result<Vec<u8>>;
+
+    /// Set the content-type attribute ([RFC 5652 Section 11.1]).
+    ///
+    /// [RFC 5652 Section 11.1]: https://www.rfc-editor.org/rfc/rfc5652#section-11.1
+    fn set_content_type(&mut self, content_type: ObjectIdentifier) -> der::Result<()>;
+
+    /// Set the message-digest attribute ([RFC 5652 Section 11.2]).
+    ///
+    /// [RFC 5652 Section 11.2]: https://www.rfc-editor.org/rfc/rfc5652#section-11.2
+    fn set_message_digest(&mut self, message_digest: &[u8]) -> der::Result<()>;
+
+    /// Set the signing-time attribute ([RFC 5652 Section 11.3]).
+    ///
+    /// The time is encoded as UTCTime or GeneralizedTime depending on its year, as
+    /// described for [`signing_time_from_date_time`], regardless of the variant passed in.
+    ///
+    /// [RFC 5652 Section 11.3]: https://www.rfc-editor.org/rfc/rfc5652#section-11.3
+    fn set_signing_time(&mut self, signing_time: SigningTime) -> der::Result<()>;
+}
+
+impl SignedAttributesExt for Attributes {
+    fn content_type(&self) -> der::Result<Option<ObjectIdentifier>> {
+        Ok(pkcs9::ContentType::from_attributes(self)?.map(|content_type| content_type.0))
+    }
+
+    fn message_digest(&self) -> der::Result<Option<MessageDigest>> {
+        Ok(pkcs9::MessageDigest::from_attributes(self)?.map(|message_digest| message_digest.0))
+    }
+
+    fn signing_time(&self) -> der::Result<Option<SigningTime>> {
+        Ok(pkcs9::SigningTime::from_attributes(self)?.map(|signing_time| signing_time.0))
+    }
+
+    fn to_der_for_signing(&self) -> der::Result<Vec<u8>> {
+        // `SetOfVec` is encoded with the `SET OF` tag, the `[0] IMPLICIT` tag only being
+        // applied by the `SignerInfo` encoding
+        self.to_der()
+    }
+
+    fn set_content_type(&mut self, content_type: ObjectIdentifier) -> der::Result<()> {
+        replace_attribute(self, &pkcs9::ContentType(content_type))
+    }
+
+    fn set_message_digest(&mut self, message_digest: &[u8]) -> der::Result<()> {
+        let message_digest = OctetString::new(message_digest)?;
+        replace_attribute(self, &pkcs9::MessageDigest(message_digest))
+    }
+
+    fn set_signing_time(&mut self, signing_time: SigningTime) -> der::Result<()> {
+        let signing_time = signing_time_from_date_time(signing_time.to_date_time())?;
+        replace_attribute(self, &pkcs9::SigningTime(signing_time))
+    }
+}
+
+/// Typed access to the countersignature attribute ([RFC 5652 Section 11.4]) which is carried in
+/// [`UnsignedAttributes`][`crate::signed_data::UnsignedAttributes`].
+///
+/// Unlike the attributes handled by [`SignedAttributesExt`], the countersignature attribute may
+/// hold multiple values and may occur multiple times.
+///
+/// [RFC 5652 Section 11.4]: https://www.rfc-editor.org/rfc/rfc5652#section-11.4
+pub trait UnsignedAttributesExt {
+    /// Get all countersignatures.
+    fn countersignatures(&self) -> der::Result<Vec<Countersignature>>;
+
+    /// Add a countersignature, appending to an existing countersignature attribute if present.
+    fn add_countersignature(&mut self, countersignature: &Countersignature) -> der::Result<()>;
+}
+
+impl UnsignedAttributesExt for Attributes {
+    fn countersignatures(&self) -> der::Result<Vec<Countersignature>> {
+        self.iter()
+            .filter(|attr| attr.oid == ID_COUNTERSIGNATURE)
+            .flat_map(|attr| attr.values.iter())
+            .map(|value| value.decode_as())
+            .collect()
+    }
+
+    fn add_countersignature(&mut self, countersignature: &Countersignature) -> der::Result<()> {
+        let value = Any::encode_from(countersignature)?;
+        let mut attrs = core::mem::take(self).into_vec();
+
+        match attrs
+            .iter_mut()
+            .find(|attr| attr.oid == ID_COUNTERSIGNATURE)
+        {
+            Some(attr) => attr.values.insert(value)?,
+            None => attrs.push(Attribute {
+                oid: ID_COUNTERSIGNATURE,
+                values: SetOfVec::try_from([value])?,
+            }),
+        }
+
+        *self = Attributes::try_from(attrs)?;
+        Ok(())
+    }
+}
+
+/// Replace any instances of the attribute of type `T` with a single-valued attribute holding
+/// `value`.
+fn replace_attribute<T: Pkcs9Attribute>(attrs: &mut Attributes, value: &T) -> der::Result<()> {
+    let mut retained = core::mem::take(attrs).into_vec();
+    retained.retain(|attr| attr.oid != T::OID);
+    retained.push(value.to_attribute()?);
+
+    *attrs = Attributes::try_from(retained)?;
+    Ok(())
+}
+
+/// Policy applied to the signing-time attribute ([RFC 5652 Section 11.3]) of a signer during
+/// verification.
+///
+/// RFC 5652 does not require the signing time to be trustworthy, so relying parties that use it
+/// should at least check that it is consistent with the signer certificate and with any bounds
+/// known to the application, e.g. the time the message was received.
+///
+/// [RFC 5652 Section 11.3]: https://www.rfc-editor.org/rfc/rfc5652#section-11.3
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SigningTimePolicy {
+    /// Require the signing time to fall within the validity period of the signer certificate.
+    pub within_signer_validity: bool,
+
+    /// Earliest acceptable signing time.
+    pub not_before: Option<Time>,
+
+    /// Latest acceptable signing time.
+    pub not_after: Option<Time>,
+}
+
+impl SigningTimePolicy {
+    /// Check the signing-time attribute of `signer_info`, if present, against this policy and
+    /// the verification `time`, which the signing time must not follow.
+    ///
+    /// `signer` is the certificate of the signer identified by `signer_info`, if known. The
+    /// returned list of violations is empty if the signing time is absent or acceptable. Errors
+    /// are only returned if the signed attributes are malformed.
+    pub fn check(
+        &self,
+        signer_info: &SignerInfo,
+        signer: Option<&Certificate>,
+        time: DateTime,
+    ) -> der::Result<Vec<SigningTimeViolation>> {
+        let mut violations = Vec::new();
+
+        let signing_time = match &signer_info.signed_attrs {
+            Some(signed_attrs) => match signed_attrs.signing_time()? {
+                Some(signing_time) => signing_time.to_unix_duration(),
+                None => return Ok(violations),
+            },
+            None => return Ok(violations),
+        };
+
+        if signing_time > time.unix_duration() {
+            violations.push(SigningTimeViolation::AfterVerificationTime);
+        }
+
+        if self.within_signer_validity {
+            match signer {
+                Some(signer) => {
+                    let validity = &signer.tbs_certificate.validity;
+
+                    if signing_time < validity.not_before.to_unix_duration() {
+                        violations.push(SigningTimeViolation::BeforeSignerValidity);
+                    }
+
+                    if signing_time > validity.not_after.to_unix_duration() {
+                        violations.push(SigningTimeViolation::AfterSignerValidity);
+                    }
+                }
+                None => violations.push(SigningTimeViolation::UnknownSignerValidity),
+            }
+        }
+
+        if let Some(not_before) = &self.not_before {
+            if signing_time < not_before.to_unix_duration() {
+                violations.push(SigningTimeViolation::BeforeLowerBound);
+            }
+        }
+
+        if let Some(not_after) = &self.not_after {
+            if signing_time > not_after.to_unix_duration() {
+                violations.push(SigningTimeViolation::AfterUpperBound);
+            }
+        }
+
+        Ok(violations)
+    }
+}
+
+/// Violations of a [`SigningTimePolicy`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum SigningTimeViolation {
+    /// The signing time precedes the `notBefore` time of the signer certificate.
+    BeforeSignerValidity,
+
+    /// The signing time follows the `notAfter` time of the signer certificate.
+    AfterSignerValidity,
+
+    /// [`SigningTimePolicy::within_signer_validity`] is set but the signer certificate is not
+    /// known.
+    UnknownSignerValidity,
+
+    /// The signing time precedes [`SigningTimePolicy::not_before`].
+    BeforeLowerBound,
+
+    /// The signing time follows [`SigningTimePolicy::not_after`].
+    AfterUpperBound,
+
+    /// The signing time follows the verification time.
+    AfterVerificationTime,
+}
+
+impl fmt::Display for SigningTimeViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SigningTimeViolation::BeforeSignerValidity => {
+                write!(f, "signing time precedes signer certificate validity")
+            }
+            SigningTimeViolation::AfterSignerValidity => {
+                write!(f, "signing time follows signer certificate validity")
+            }
+            SigningTimeViolation::UnknownSignerValidity => {
+                write!(f, "signer certificate validity is unknown")
+            }
+            SigningTimeViolation::BeforeLowerBound => {
+                write!(f, "signing time precedes earliest acceptable time")
+            }
+            SigningTimeViolation::AfterUpperBound => {
+                write!(f, "signing time follows latest acceptable time")
+            }
+            SigningTimeViolation::AfterVerificationTime => {
+                write!(f, "signing time follows verification time")
+            }
+        }
+    }
+}
@@ -6,9 +6,8 @@ use der::Sequence;
 use spki::AlgorithmIdentifierOwned;
 use x509_cert::attr::Attributes;
 
-use crate::content_info::CmsVersion;
+use crate::content_info::{CmsVersion, EncapsulatedContentInfo};
 use crate::enveloped_data::{OriginatorInfo, RecipientInfos};
-use crate::signed_data::EncapsulatedContentInfo;
 
 /// The `AuthenticatedData` type is defined in [RFC 5652 Section 9.1].
 ///
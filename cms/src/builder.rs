@@ -2,6 +2,10 @@
 
 //! CMS Builder
 
+use crate::attr::{
+    signing_time_from_date_time, CmsAlgorithmProtection, SignedAttributesExt,
+    ID_AA_CMS_ALGORITHM_PROTECTION,
+};
 use crate::cert::CertificateChoices;
 use crate::content_info::{CmsVersion, ContentInfo};
 use crate::revocation::{RevocationInfoChoice, RevocationInfoChoices};
@@ -9,19 +13,15 @@ use crate::signed_data::{
     CertificateSet, DigestAlgorithmIdentifiers, EncapsulatedContentInfo, SignatureValue,
     SignedAttributes, SignedData, SignerIdentifier, SignerInfo, SignerInfos, UnsignedAttributes,
 };
+use crate::verify::get_hasher;
 use alloc::borrow::ToOwned;
-use alloc::boxed::Box;
 use alloc::string::String;
 use alloc::vec::Vec;
 use const_oid::ObjectIdentifier;
 use core::cmp::Ordering;
 use core::fmt;
-use der::asn1::{BitString, OctetStringRef, SetOfVec};
-use der::oid::db::DB;
-use der::{Any, AnyRef, DateTime, Decode, Encode, ErrorKind, Tag};
-use digest::Digest;
-use sha2::digest;
-use signature::digest::DynDigest;
+use der::asn1::{BitString, OctetString};
+use der::{Any, AnyRef, DateTime, Encode, ErrorKind};
 use signature::{Keypair, Signer};
 use spki::{
     AlgorithmIdentifierOwned, DynSignatureAlgorithmIdentifier, EncodePublicKey,
@@ -29,7 +29,8 @@ use spki::{
 };
 use std::time::SystemTime;
 use std::vec;
-use x509_cert::attr::{Attribute, AttributeValue};
+use x509_cert::attr::pkcs9::{self, Pkcs9Attribute};
+use x509_cert::attr::Attribute;
 use x509_cert::builder::Builder;
 
 /// Error type
@@ -184,20 +185,16 @@ where
                 }
                 external_content_digest.to_vec()
             }
-            None => match &self.encapsulated_content_info.econtent {
+            None => match self.encapsulated_content_info.econtent_digest_input() {
                 None => {
                     // Content missing, cannot sign
                     return Err(der::Error::from(ErrorKind::Failed));
                 }
-                Some(content) => {
+                Some(content_value) => {
                     let mut hasher = get_hasher(&self.digest_algorithm).ok_or_else(|| {
                         // Unsupported hash algorithm: {}, &self.digest_algorithm.oid.to_string()
                         der::Error::from(ErrorKind::Failed)
                     })?;
-                    // Only the octets comprising the value of the eContent
-                    // OCTET STRING are input to the message digest algorithm, not the tag
-                    // or the length octets.
-                    let content_value = content.value();
                     hasher.update(content_value);
                     hasher.finalize_reset().to_vec()
                 }
@@ -260,10 +257,7 @@ where
         // Now use `signer` to sign the DER encoded signed attributes
         let signed_attributes = SignedAttributes::try_from(signed_attributes.to_owned())
             .map_err(|_| der::Error::from(ErrorKind::Failed))?;
-        let mut signed_attributes_der = Vec::new();
-        signed_attributes.encode_to_vec(&mut signed_attributes_der)?;
-
-        Ok(signed_attributes_der)
+        signed_attributes.to_der_for_signing()
     }
 
     fn assemble(
@@ -318,6 +312,29 @@ impl<'s> SignedDataBuilder<'s> {
         }
     }
 
+    /// Create a builder from an existing `SignedData`, retaining its digest algorithms,
+    /// certificates, CRLs and signer infos.
+    ///
+    /// This is intended for migrating long-lived signed data to stronger algorithms: new
+    /// signer infos are added with [`SignedDataBuilder::add_migrated_signer_info`] next to
+    /// the existing ones, which are kept as is. Verifying the existing signatures is left to
+    /// the caller.
+    pub fn from_signed_data(signed_data: &'s SignedData) -> SignedDataBuilder<'s> {
+        Self {
+            digest_algorithms: signed_data.digest_algorithms.iter().cloned().collect(),
+            encapsulated_content_info: &signed_data.encap_content_info,
+            certificates: signed_data
+                .certificates
+                .as_ref()
+                .map(|certificates| certificates.0.iter().cloned().collect()),
+            crls: signed_data
+                .crls
+                .as_ref()
+                .map(|crls| crls.0.iter().cloned().collect()),
+            signer_infos: signed_data.signer_infos.0.iter().cloned().collect(),
+        }
+    }
+
     /// Add a digest algorithm to the collection of message digest algorithms.
     /// RFC 5652 § 5.1: digestAlgorithms is a collection of message digest algorithm
     /// identifiers.  There MAY be any number of elements in the
@@ -397,6 +414,78 @@ impl<'s> SignedDataBuilder<'s> {
         Ok(self)
     }
 
+    /// Add a signer info re-signing the content and the signed attributes of `previous`,
+    /// typically with a stronger digest and signature algorithm.
+    ///
+    /// The signed attributes of `previous` for which `carry_over` returns `true` are carried
+    /// over to the new signer info. Attributes bound to the previous signature are never carried
+    /// over: the content-type, message-digest and signing-time attributes are computed anew, a
+    /// `CMSAlgorithmProtection` attribute ([RFC 6211]) is regenerated for the new digest and
+    /// signature algorithms, and `signingCertificate`/`signingCertificateV2` attributes
+    /// ([RFC 5035]), which identify the previous signer's certificate, are dropped. Its unsigned
+    /// attributes, such as countersignatures or timestamps, relate to the previous signature and
+    /// are not carried over either. The digest algorithm of the new signer info is added to the
+    /// digest algorithms if not already present.
+    ///
+    /// [RFC 6211]: https://datatracker.ietf.org/doc/html/rfc6211
+    /// [RFC 5035]: https://datatracker.ietf.org/doc/html/rfc5035
+    pub fn add_migrated_signer_info<S, Signature>(
+        &mut self,
+        previous: &SignerInfo,
+        mut signer_info_builder: SignerInfoBuilder<'_, S>,
+        carry_over: &dyn Fn(&Attribute) -> bool,
+    ) -> Result<&mut Self>
+    where
+        S: Keypair + DynSignatureAlgorithmIdentifier,
+        S::VerifyingKey: EncodePublicKey,
+        S: Signer<Signature>,
+        Signature: SignatureBitStringEncoding,
+    {
+        if signer_info_builder.encapsulated_content_info != self.encapsulated_content_info {
+            return Err(Error::Builder(String::from(
+                "signer info must sign the encapsulated content of the signed data",
+            )));
+        }
+
+        if let Some(signed_attributes) = &previous.signed_attrs {
+            for attribute in signed_attributes.iter() {
+                match attribute.oid {
+                    const_oid::db::rfc5911::ID_CONTENT_TYPE
+                    | const_oid::db::rfc5911::ID_MESSAGE_DIGEST
+                    | const_oid::db::rfc5911::ID_SIGNING_TIME
+                    | const_oid::db::rfc5911::ID_AA_SIGNING_CERTIFICATE
+                    | const_oid::db::rfc5911::ID_AA_SIGNING_CERTIFICATE_V_2 => {}
+                    ID_AA_CMS_ALGORITHM_PROTECTION => {
+                        let algorithm_protection = CmsAlgorithmProtection {
+                            digest_algorithm: signer_info_builder.digest_algorithm.clone(),
+                            signature_algorithm: Some(
+                                signer_info_builder
+                                    .signer
+                                    .signature_algorithm_identifier()?,
+                            ),
+                            mac_algorithm: None,
+                        };
+                        signer_info_builder
+                            .add_signed_attribute(algorithm_protection.to_attribute()?)?;
+                    }
+                    _ if carry_over(attribute) => {
+                        signer_info_builder.add_signed_attribute(attribute.clone())?;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if !self
+            .digest_algorithms
+            .contains(&signer_info_builder.digest_algorithm)
+        {
+            self.add_digest_algorithm(signer_info_builder.digest_algorithm.clone())?;
+        }
+
+        self.add_signer_info(signer_info_builder)
+    }
+
     /// This method returns a `ContentInfo` of type `signedData`.
     pub fn build(&mut self) -> Result<ContentInfo> {
         let digest_algorithms =
@@ -461,24 +550,14 @@ impl<'s> SignedDataBuilder<'s> {
         } else {
             false
         };
-        // v1 and v2 currently not supported
-        // let v2_certificates_are_present = if let Some(certificates) = &self.certificates {
-        //     certificates.iter().any(|certificate| match certificate {
-        //         CertificateChoices::V2AttrCert(_) => true,
-        //         _ => false,
-        //     })
-        // } else {
-        //     false
-        // };
-        // let v1_certificates_are_present = if let Some(certificates) = &self.certificates {
-        //     certificates.iter().any(|certificate| match certificate {
-        //         CertificateChoices::V1AttrCert(_) => true,
-        //         _ => false,
-        //     })
-        // } else {
-        //     false
-        // };
-        let v2_certificates_are_present = false;
+        let v2_certificates_are_present = if let Some(certificates) = &self.certificates {
+            certificates
+                .iter()
+                .any(|certificate| matches!(certificate, CertificateChoices::V2AttrCert(_)))
+        } else {
+            false
+        };
+        // v1 attribute certificates are obsolete and not supported
         let v1_certificates_are_present = false;
         let other_crls_are_present = if let Some(crls) = &self.crls {
             crls.iter().any(|revocation_info_choice| {
@@ -506,52 +585,16 @@ impl<'s> SignedDataBuilder<'s> {
     }
 }
 
-/// Get a hasher for a given digest algorithm
-fn get_hasher(
-    digest_algorithm_identifier: &AlgorithmIdentifierOwned,
-) -> Option<Box<dyn DynDigest>> {
-    let digest_name = DB.by_oid(&digest_algorithm_identifier.oid)?;
-    match digest_name {
-        "id-sha1" => Some(Box::new(sha1::Sha1::new())),
-        "id-sha256" => Some(Box::new(sha2::Sha256::new())),
-        "id-sha384" => Some(Box::new(sha2::Sha384::new())),
-        "id-sha512" => Some(Box::new(sha2::Sha512::new())),
-        "id-sha224" => Some(Box::new(sha2::Sha224::new())),
-        "id-sha-3-224" => Some(Box::new(sha3::Sha3_224::new())),
-        "id-sha-3-256" => Some(Box::new(sha3::Sha3_256::new())),
-        "id-sha-3-384" => Some(Box::new(sha3::Sha3_384::new())),
-        "id-sha-3-512" => Some(Box::new(sha3::Sha3_512::new())),
-        _ => None,
-    }
-}
-
 /// Create a content-type attribute according to
 /// [RFC 5652 § 11.1](https://datatracker.ietf.org/doc/html/rfc5652#section-11.1)
 pub fn create_content_type_attribute(content_type: ObjectIdentifier) -> Result<Attribute> {
-    let content_type_attribute_value =
-        AttributeValue::new(Tag::ObjectIdentifier, content_type.as_bytes())?;
-    let mut values = SetOfVec::new();
-    values.insert(content_type_attribute_value)?;
-    let attribute = Attribute {
-        oid: const_oid::db::rfc5911::ID_CONTENT_TYPE,
-        values,
-    };
-    Ok(attribute)
+    Ok(pkcs9::ContentType(content_type).to_attribute()?)
 }
 
 /// Create a message digest attribute according to
 /// [RFC 5652 § 11.2](https://datatracker.ietf.org/doc/html/rfc5652#section-11.2)
 pub fn create_message_digest_attribute(message_digest: &[u8]) -> Result<Attribute> {
-    let message_digest_der = OctetStringRef::new(message_digest)?;
-    let message_digest_attribute_value =
-        AttributeValue::new(Tag::OctetString, message_digest_der.as_bytes())?;
-    let mut values = SetOfVec::new();
-    values.insert(message_digest_attribute_value)?;
-    let attribute = Attribute {
-        oid: const_oid::db::rfc5911::ID_MESSAGE_DIGEST,
-        values,
-    };
-    Ok(attribute)
+    Ok(pkcs9::MessageDigest(OctetString::new(message_digest)?).to_attribute()?)
 }
 
 /// Create a signing time attribute according to
@@ -561,25 +604,5 @@ pub fn create_message_digest_attribute(message_digest: &[u8]) -> Result<Attribut
 /// 2049 MUST be encoded as GeneralizedTime.
 pub fn create_signing_time_attribute() -> Result<Attribute> {
     let now = DateTime::from_system_time(SystemTime::now())?;
-    let tag = if now.year() < 1950 || now.year() > 2049 {
-        Tag::GeneralizedTime
-    } else {
-        Tag::UtcTime
-    };
-    // let mut signing_time_buf = Vec::new();
-    let time_der = if tag == Tag::GeneralizedTime {
-        // der::asn1::GeneralizedTime::from_date_time(now).encode_to_vec(&mut signing_time_buf)?;
-        der::asn1::GeneralizedTime::from_date_time(now).to_der()?
-    } else {
-        // der::asn1::UtcTime::from_date_time(now)?.encode_to_vec(&mut signing_time_buf)?;
-        der::asn1::UtcTime::from_date_time(now)?.to_der()?
-    };
-    let signing_time_attribute_value = AttributeValue::from_der(&time_der)?;
-    let mut values = SetOfVec::<AttributeValue>::new();
-    values.insert(signing_time_attribute_value)?;
-    let attribute = Attribute {
-        oid: const_oid::db::rfc5911::ID_SIGNING_TIME,
-        values,
-    };
-    Ok(attribute)
+    Ok(pkcs9::SigningTime(signing_time_from_date_time(now)?).to_attribute()?)
 }
@@ -2,14 +2,19 @@
 
 pub use x509_cert as x509;
 
-use core::cmp::Ordering;
-use der::{asn1::ObjectIdentifier, Any, Choice, Sequence, ValueOrd};
+use der::{
+    asn1::{ObjectIdentifier, SetOfVec},
+    Any, Choice, Sequence, ValueOrd,
+};
+use x509_cert::attr_cert::AttributeCertificate;
+use x509_cert::ext::pkix::SubjectKeyIdentifier;
+use x509_cert::impl_newtype;
 use x509_cert::name::Name;
 use x509_cert::serial_number::SerialNumber;
 use x509_cert::Certificate;
 
-/// The `CertificateChoices` type is defined in [RFC 5652 Section 10.2.2]. Attribute certificate
-/// support is not presently implemented.
+/// The `CertificateChoices` type is defined in [RFC 5652 Section 10.2.2]. The obsolete
+/// `extendedCertificate` and `v1AttrCert` alternatives are not supported.
 ///
 /// ```text
 ///   CertificateChoices ::= CHOICE {
@@ -24,27 +29,25 @@ use x509_cert::Certificate;
 /// ```
 ///
 /// [RFC 5652 Section 10.2.2]: https://www.rfc-editor.org/rfc/rfc5652#section-10.2.2
-#[derive(Clone, Debug, Eq, PartialEq, Choice)]
+#[derive(Clone, Debug, Eq, PartialEq, Choice, ValueOrd)]
 #[allow(missing_docs)]
 #[allow(clippy::large_enum_variant)]
 pub enum CertificateChoices {
     Certificate(Certificate),
+    #[asn1(context_specific = "2", tag_mode = "IMPLICIT", constructed = "true")]
+    V2AttrCert(AttributeCertificateV2),
     #[asn1(context_specific = "3", tag_mode = "EXPLICIT", constructed = "true")]
     Other(OtherCertificateFormat),
-    // TODO DEFER add more choices if desired (i.e., AttributeCertificateV2)
 }
 
-// TODO DEFER ValueOrd is not supported for CHOICE types (see new_enum in value_ord.rs)
-impl ValueOrd for CertificateChoices {
-    fn value_cmp(&self, other: &Self) -> der::Result<Ordering> {
-        use der::DerOrd;
-        use der::Encode;
-        self.to_der()?.der_cmp(&other.to_der()?)
-    }
-}
-
-// TODO DEFER implement support for attribute certs if desired
-//   AttributeCertificateV2 ::= AttributeCertificate
+/// The `AttributeCertificateV2` type is defined in [RFC 5652 Section 10.2.2].
+///
+/// ```text
+///   AttributeCertificateV2 ::= AttributeCertificate
+/// ```
+///
+/// [RFC 5652 Section 10.2.2]: https://www.rfc-editor.org/rfc/rfc5652#section-10.2.2
+pub type AttributeCertificateV2 = AttributeCertificate;
 
 /// The `OtherCertificateFormat` type is defined in [RFC 5652 Section 10.2.2].
 ///
@@ -57,7 +60,7 @@ impl ValueOrd for CertificateChoices {
 /// ```
 ///
 /// [RFC 5652 Section 10.2.2]: https://www.rfc-editor.org/rfc/rfc5652#section-10.2.2
-#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
 #[allow(missing_docs)]
 pub struct OtherCertificateFormat {
     pub other_cert_format: ObjectIdentifier,
@@ -73,9 +76,89 @@ pub struct OtherCertificateFormat {
 /// ```
 ///
 /// [RFC 5652 Section 10.2.4]: https://datatracker.ietf.org/doc/html/rfc5652#section-10.2.4
-#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
 #[allow(missing_docs)]
 pub struct IssuerAndSerialNumber {
     pub issuer: Name,
     pub serial_number: SerialNumber,
 }
+
+/// Identifier of a certificate and its key, as carried by the `SignerIdentifier`,
+/// `RecipientIdentifier` and `KeyAgreeRecipientIdentifier` types of [RFC 5652].
+///
+/// All three are converted into a `KeyHandle`, so that signers and recipients are matched
+/// against certificates and keys the same way.
+///
+/// [RFC 5652]: https://www.rfc-editor.org/rfc/rfc5652
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyHandle<'a> {
+    /// Certificate identified by its issuer and serial number.
+    IssuerAndSerial(&'a IssuerAndSerialNumber),
+
+    /// Subject key identifier of the certificate.
+    SubjectKeyId(&'a SubjectKeyIdentifier),
+}
+
+impl KeyHandle<'_> {
+    /// Is `cert` the identified certificate?
+    ///
+    /// Subject key identifiers are compared with the subject key identifier extension of
+    /// `cert`: certificates without it never match one.
+    pub fn matches_certificate(&self, cert: &Certificate) -> bool {
+        let tbs = &cert.tbs_certificate;
+
+        match self {
+            KeyHandle::IssuerAndSerial(iasn) => {
+                tbs.issuer == iasn.issuer && tbs.serial_number == iasn.serial_number
+            }
+            KeyHandle::SubjectKeyId(skid) => match tbs.get::<SubjectKeyIdentifier>() {
+                Ok(Some((_, cert_skid))) => cert_skid == **skid,
+                _ => false,
+            },
+        }
+    }
+
+    /// Is `key_id` the identified subject key identifier?
+    ///
+    /// This is used to look up keys which aren't stored along a certificate, such as raw
+    /// public or private keys indexed by their key identifier. Issuer and serial number
+    /// identifiers never match a key identifier.
+    pub fn matches_key_id(&self, key_id: &[u8]) -> bool {
+        match self {
+            KeyHandle::IssuerAndSerial(_) => false,
+            KeyHandle::SubjectKeyId(skid) => skid.0.as_bytes() == key_id,
+        }
+    }
+}
+
+impl<'a> From<&'a IssuerAndSerialNumber> for KeyHandle<'a> {
+    fn from(iasn: &'a IssuerAndSerialNumber) -> Self {
+        KeyHandle::IssuerAndSerial(iasn)
+    }
+}
+
+impl<'a> From<&'a SubjectKeyIdentifier> for KeyHandle<'a> {
+    fn from(skid: &'a SubjectKeyIdentifier) -> Self {
+        KeyHandle::SubjectKeyId(skid)
+    }
+}
+
+/// CertificateSet structure as defined in [RFC 5652 Section 10.2.3].
+///
+/// ```text
+///   CertificateSet ::= SET OF CertificateChoices
+/// ```
+///
+/// [RFC 5652 Section 10.2.3]: https://datatracker.ietf.org/doc/html/rfc5652#section-10.2.3
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct CertificateSet(pub SetOfVec<CertificateChoices>);
+impl_newtype!(CertificateSet, SetOfVec<CertificateChoices>);
+
+#[cfg(feature = "std")]
+impl TryFrom<std::vec::Vec<CertificateChoices>> for CertificateSet {
+    type Error = der::Error;
+
+    fn try_from(vec: std::vec::Vec<CertificateChoices>) -> der::Result<CertificateSet> {
+        Ok(CertificateSet(SetOfVec::try_from(vec)?))
+    }
+}
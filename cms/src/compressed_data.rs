@@ -1,9 +1,16 @@
 //! CompressedData-related types
-use der::Sequence;
+use der::{Any, AnyRef, Decode, Encode, Sequence};
 use spki::AlgorithmIdentifierOwned;
 
-use crate::content_info::CmsVersion;
-use crate::signed_data::EncapsulatedContentInfo;
+use crate::content_info::{CmsVersion, ContentInfo, EncapsulatedContentInfo};
+
+#[cfg(feature = "zlib")]
+use {
+    alloc::vec::Vec,
+    core::fmt,
+    der::asn1::{ObjectIdentifier, OctetString, OctetStringRef},
+    miniz_oxide::inflate::TINFLStatus,
+};
 
 /// The `CompressedData` type is defined in [RFC 3274 Section 1.1].
 ///
@@ -23,3 +30,158 @@ pub struct CompressedData {
     pub compression_alg: AlgorithmIdentifierOwned,
     pub encap_content_info: EncapsulatedContentInfo,
 }
+
+impl CompressedData {
+    /// Decode a BER-encoded `CompressedData`.
+    ///
+    /// The message is transcoded to DER with [`der::ber_to_der`] before being decoded, as
+    /// with [`ContentInfo::from_ber`]. In particular, an `eContent` using the constructed
+    /// `OCTET STRING` encoding, as produced by streaming encoders, is replaced by the
+    /// concatenation of its segments, which can then be inflated.
+    pub fn from_ber(bytes: &[u8]) -> der::Result<Self> {
+        Self::from_der(&der::ber_to_der(bytes)?)
+    }
+}
+
+/// Extract a `CompressedData` from a `ContentInfo` of type `id-ct-compressedData`
+impl TryFrom<ContentInfo> for CompressedData {
+    type Error = der::Error;
+
+    fn try_from(content_info: ContentInfo) -> der::Result<Self> {
+        if content_info.content_type != const_oid::db::rfc6268::ID_CT_COMPRESSED_DATA {
+            return Err(der::ErrorKind::OidUnknown {
+                oid: content_info.content_type,
+            }
+            .into());
+        }
+
+        content_info.content.decode_as()
+    }
+}
+
+/// Wrap a `CompressedData` in a `ContentInfo` of type `id-ct-compressedData`
+impl TryFrom<CompressedData> for ContentInfo {
+    type Error = der::Error;
+
+    fn try_from(compressed_data: CompressedData) -> der::Result<Self> {
+        let compressed_data = compressed_data.to_der()?;
+        let content = AnyRef::try_from(compressed_data.as_slice())?;
+
+        Ok(ContentInfo {
+            content_type: const_oid::db::rfc6268::ID_CT_COMPRESSED_DATA,
+            content: Any::from(content),
+        })
+    }
+}
+
+/// Errors which can occur when compressing or decompressing `CompressedData` content.
+#[cfg(feature = "zlib")]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors.
+    Asn1(der::Error),
+
+    /// The compression algorithm is not supported.
+    UnsupportedAlgorithm(ObjectIdentifier),
+
+    /// The encapsulated content is absent, i.e. it is carried externally.
+    MissingContent,
+
+    /// The compressed content could not be inflated.
+    Decompression,
+
+    /// The inflated content exceeds the maximum length given to
+    /// [`CompressedData::decompress_with_limit`].
+    LengthLimitExceeded,
+}
+
+#[cfg(feature = "zlib")]
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::UnsupportedAlgorithm(oid) => {
+                write!(f, "unsupported compression algorithm: {}", oid)
+            }
+            Error::MissingContent => write!(f, "encapsulated content is absent"),
+            Error::Decompression => write!(f, "malformed zlib stream"),
+            Error::LengthLimitExceeded => write!(f, "decompressed content is too long"),
+        }
+    }
+}
+
+#[cfg(all(feature = "zlib", feature = "std"))]
+impl std::error::Error for Error {}
+
+#[cfg(feature = "zlib")]
+impl From<der::Error> for Error {
+    fn from(err: der::Error) -> Error {
+        Error::Asn1(err)
+    }
+}
+
+#[cfg(feature = "zlib")]
+impl CompressedData {
+    /// Compression level used by [`CompressedData::compress`], matching zlib's default.
+    pub const DEFAULT_COMPRESSION_LEVEL: u8 = 6;
+
+    /// Maximum length of the content inflated by [`CompressedData::decompress`], 16 MiB.
+    pub const DEFAULT_MAX_DECOMPRESSED_LEN: usize = 16 * 1024 * 1024;
+
+    /// Compress `content` of type `econtent_type` using the zlib compression algorithm
+    /// ([RFC 3274 Section 2]).
+    ///
+    /// [RFC 3274 Section 2]: https://www.rfc-editor.org/rfc/rfc3274#section-2
+    pub fn compress(econtent_type: ObjectIdentifier, content: &[u8]) -> Result<Self, Error> {
+        let compressed =
+            miniz_oxide::deflate::compress_to_vec_zlib(content, Self::DEFAULT_COMPRESSION_LEVEL);
+        let econtent = Any::encode_from(&OctetString::new(compressed)?)?;
+
+        Ok(CompressedData {
+            version: CmsVersion::V0,
+            compression_alg: AlgorithmIdentifierOwned {
+                oid: const_oid::db::rfc6268::ID_ALG_ZLIB_COMPRESS,
+                parameters: None,
+            },
+            encap_content_info: EncapsulatedContentInfo {
+                econtent_type,
+                econtent: Some(econtent),
+            },
+        })
+    }
+
+    /// Inflate the encapsulated content, returning the original content octets.
+    ///
+    /// Only the zlib compression algorithm is supported. The content is limited to
+    /// [`CompressedData::DEFAULT_MAX_DECOMPRESSED_LEN`] octets: use
+    /// [`CompressedData::decompress_with_limit`] to choose another limit.
+    pub fn decompress(&self) -> Result<Vec<u8>, Error> {
+        self.decompress_with_limit(Self::DEFAULT_MAX_DECOMPRESSED_LEN)
+    }
+
+    /// Inflate the encapsulated content, failing with [`Error::LengthLimitExceeded`] if it is
+    /// longer than `max_len` octets.
+    ///
+    /// As a few octets of compressed content can inflate to gigabytes, the limit must be
+    /// chosen according to the largest content expected, in particular for messages from
+    /// untrusted sources.
+    pub fn decompress_with_limit(&self, max_len: usize) -> Result<Vec<u8>, Error> {
+        if self.compression_alg.oid != const_oid::db::rfc6268::ID_ALG_ZLIB_COMPRESS {
+            return Err(Error::UnsupportedAlgorithm(self.compression_alg.oid));
+        }
+
+        let econtent = self
+            .encap_content_info
+            .econtent
+            .as_ref()
+            .ok_or(Error::MissingContent)?;
+        let compressed = econtent.decode_as::<OctetStringRef<'_>>()?;
+
+        miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(compressed.as_bytes(), max_len)
+            .map_err(|err| match err.status {
+                TINFLStatus::HasMoreOutput => Error::LengthLimitExceeded,
+                _ => Error::Decompression,
+            })
+    }
+}
@@ -1,14 +1,22 @@
 //! ContentInfo types
 
-use crate::cert::CertificateChoices;
-use crate::revocation::RevocationInfoChoices;
-use crate::signed_data::EncapsulatedContentInfo;
-use crate::signed_data::{CertificateSet, SignedData, SignerInfos};
 use core::cmp::Ordering;
-use der::asn1::SetOfVec;
-use der::Encode;
-use der::{asn1::ObjectIdentifier, Any, AnyRef, Enumerated, Sequence, ValueOrd};
-use x509_cert::{Certificate, PkiPath};
+use der::{
+    asn1::ObjectIdentifier, Any, Decode, Enumerated, ErrorKind, Length, RawHeader, Reader,
+    Sequence, SliceReader, Tag, TagNumber, ValueOrd,
+};
+
+#[cfg(any(feature = "signed", all(feature = "enveloped", feature = "x509")))]
+use der::{AnyRef, Encode};
+
+#[cfg(feature = "signed")]
+use {
+    crate::cert::CertificateChoices,
+    crate::revocation::RevocationInfoChoices,
+    crate::signed_data::{CertificateSet, SignedData, SignedDataRef, SignerInfos},
+    der::asn1::SetOfVec,
+    x509_cert::{Certificate, PkiPath},
+};
 
 /// The `OtherCertificateFormat` type is defined in [RFC 5652 Section 10.2.5].
 ///
@@ -55,7 +63,206 @@ pub struct ContentInfo {
     pub content: Any,
 }
 
+impl ContentInfo {
+    /// Decode a BER-encoded `ContentInfo`.
+    ///
+    /// The message is transcoded to DER with [`der::ber_to_der`] before being decoded. In
+    /// particular, `OCTET STRING`s using the constructed encoding, such as the `eContent` of
+    /// messages produced in a streaming fashion, are replaced by the concatenation of their
+    /// segments.
+    pub fn from_ber(bytes: &[u8]) -> der::Result<Self> {
+        Self::from_der(&der::ber_to_der(bytes)?)
+    }
+
+    /// Decode a DER-encoded `ContentInfo`, rejecting BER encodings anywhere inside the
+    /// message, including inside of values which
+    /// [`ContentInfo::from_der`][der::Decode::from_der] decodes without interpreting them, such
+    /// as the `content` itself or attribute values.
+    ///
+    /// The message is first checked with [`der::check_der`], whose errors are located at
+    /// the first value which is not DER-encoded. As this check is schema-less, the ordering
+    /// of implicitly tagged `SET OF`s is checked separately for `SignedData` content,
+    /// including the `signedAttrs` which [RFC 5652 Section 5.3] requires to be DER-encoded.
+    /// Errors about these record the path of the offending field, e.g.
+    /// `SignedData.signer_infos[1].signed_attrs`.
+    ///
+    /// The `eContent` octets are not checked, as they are opaque to this crate.
+    ///
+    /// [RFC 5652 Section 5.3]: https://www.rfc-editor.org/rfc/rfc5652#section-5.3
+    pub fn from_der_strict(bytes: &[u8]) -> der::Result<Self> {
+        der::check_der(bytes)?;
+        let content_info = Self::from_der(bytes)?;
+
+        #[cfg(feature = "signed")]
+        if content_info.content_type == const_oid::db::rfc5911::ID_SIGNED_DATA {
+            content_info
+                .content
+                .decode_as::<SignedDataRef<'_>>()?
+                .check_implicit_sets()?;
+        }
+
+        Ok(content_info)
+    }
+}
+
+/// Check the DER encoding of the value of an implicitly tagged `SET OF`, as if it were
+/// tagged with the `SET OF` tag.
+#[cfg(any(feature = "signed", all(feature = "enveloped", feature = "x509")))]
+pub(crate) fn check_implicit_set(value: Option<AnyRef<'_>>) -> der::Result<()> {
+    match value {
+        Some(value) => der::check_der(&Any::new(Tag::Set, value.value())?.to_der()?),
+        None => Ok(()),
+    }
+}
+
+/// Encoding of a `ContentInfo` detected by [`ContentInfo::sniff`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ContentInfoEncoding {
+    /// DER, to be decoded with [`ContentInfo::from_der`][der::Decode::from_der].
+    Der,
+
+    /// BER using indefinite lengths or non-minimal length encodings, to be decoded with
+    /// [`ContentInfo::from_ber`].
+    Ber,
+
+    /// PEM with the `CMS` label of [RFC 7468 Section 10], or the legacy `PKCS7` label.
+    ///
+    /// [RFC 7468 Section 10]: https://www.rfc-editor.org/rfc/rfc7468#section-10
+    Pem,
+}
+
+/// Result of [`ContentInfo::sniff`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ContentInfoSniff {
+    /// Detected encoding.
+    pub encoding: ContentInfoEncoding,
+
+    /// Content type of the `ContentInfo`.
+    pub content_type: ObjectIdentifier,
+}
+
+impl ContentInfo {
+    /// Cheaply detect whether `bytes` hold a `ContentInfo`, and how it is encoded.
+    ///
+    /// Only the headers of the `ContentInfo`, its `content` and the value inside of it are
+    /// examined, without decoding the message. Data which doesn't start like a `ContentInfo`
+    /// yields `None`. As BER encodings nested deeper in the message are not detected,
+    /// [`ContentInfo::from_der_strict`] is to be used to ensure that a message is DER.
+    ///
+    /// PEM is detected without allocating, by decoding the beginning of the Base64 data.
+    pub fn sniff(bytes: &[u8]) -> Option<ContentInfoSniff> {
+        let start = bytes
+            .iter()
+            .position(|byte| !byte.is_ascii_whitespace())
+            .unwrap_or(bytes.len());
+        let pem = &bytes[start..];
+        if pem.starts_with(b"-----BEGIN ") {
+            let mut decoder = der::pem::Decoder::new(pem).ok()?;
+            if !matches!(decoder.type_label(), "CMS" | "PKCS7") {
+                return None;
+            }
+
+            let total_len = decoder.remaining_len();
+            let mut buf = [0u8; 64];
+            let prefix = decoder.decode(&mut buf[..total_len.min(64)]).ok()?;
+            let (_, content_type) = sniff_headers(prefix, total_len).ok()?;
+
+            return Some(ContentInfoSniff {
+                encoding: ContentInfoEncoding::Pem,
+                content_type,
+            });
+        }
+
+        let (is_der, content_type) = sniff_headers(bytes, bytes.len()).ok()?;
+        let encoding = if is_der {
+            ContentInfoEncoding::Der
+        } else {
+            ContentInfoEncoding::Ber
+        };
+
+        Some(ContentInfoSniff {
+            encoding,
+            content_type,
+        })
+    }
+}
+
+/// Read the headers of a `ContentInfo` from the beginning of a message of `total_len` bytes,
+/// returning whether they are DER along with the content type.
+fn sniff_headers(prefix: &[u8], total_len: usize) -> der::Result<(bool, ObjectIdentifier)> {
+    let mut reader = SliceReader::new(prefix)?;
+
+    let header = RawHeader::decode(&mut reader)?;
+    Tag::Sequence.assert_eq(header.tag)?;
+    if let Some(len) = Option::<Length>::from(header.length) {
+        let expected_len = (reader.position() + len)?;
+        if expected_len != Length::try_from(total_len)? {
+            return Err(reader.error(ErrorKind::Incomplete {
+                expected_len,
+                actual_len: total_len.try_into()?,
+            }));
+        }
+    }
+    let mut is_der = header.is_der();
+
+    let header = RawHeader::decode(&mut reader)?;
+    Tag::ObjectIdentifier.assert_eq(header.tag)?;
+    let content_type = ObjectIdentifier::from_bytes(reader.read_slice(header.length.try_into()?)?)
+        .map_err(|_| Tag::ObjectIdentifier.value_error())?;
+    is_der &= header.is_der();
+
+    let header = RawHeader::decode(&mut reader)?;
+    Tag::ContextSpecific {
+        constructed: true,
+        number: TagNumber::N0,
+    }
+    .assert_eq(header.tag)?;
+    is_der &= header.is_der();
+
+    is_der &= RawHeader::decode(&mut reader)?.is_der();
+
+    Ok((is_der, content_type))
+}
+
+/// The `EncapsulatedContentInfo` type is defined in [RFC 5652 Section 5.2].
+///
+/// ```text
+///   EncapsulatedContentInfo ::= SEQUENCE {
+///       eContentType       CONTENT-TYPE.&id({ContentSet}),
+///       eContent           [0] EXPLICIT OCTET STRING
+///               ( CONTAINING CONTENT-TYPE.
+///                   &Type({ContentSet}{@eContentType})) OPTIONAL }
+/// ```
+///
+/// [RFC 5652 Section 5.2]: https://www.rfc-editor.org/rfc/rfc5652#section-5.2
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct EncapsulatedContentInfo {
+    pub econtent_type: ObjectIdentifier,
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    pub econtent: Option<Any>,
+}
+
+impl EncapsulatedContentInfo {
+    /// Get the octets of the `eContent` which are input to the message digest, if present.
+    ///
+    /// As specified in [RFC 5652 Section 5.4], these are the octets comprising the value of the
+    /// `eContent` `OCTET STRING`, not its tag or length octets. Content of other types, as
+    /// carried in PKCS #7 messages, is digested the same way.
+    ///
+    /// When the `eContent` was BER encoded as a constructed `OCTET STRING`, the message digest
+    /// is computed over the concatenation of the contents of its segments, excluding their
+    /// framing. Such messages are to be decoded with [`ContentInfo::from_ber`], which performs
+    /// this concatenation.
+    ///
+    /// [RFC 5652 Section 5.4]: https://www.rfc-editor.org/rfc/rfc5652#section-5.4
+    pub fn econtent_digest_input(&self) -> Option<&[u8]> {
+        self.econtent.as_ref().map(Any::value)
+    }
+}
+
 /// Convert a Certificate to a certs-only SignedData message
+#[cfg(feature = "signed")]
 impl TryFrom<Certificate> for ContentInfo {
     type Error = der::Error;
 
@@ -87,6 +294,7 @@ impl TryFrom<Certificate> for ContentInfo {
 }
 
 /// Convert a vector of Certificates to a certs-only SignedData message
+#[cfg(feature = "signed")]
 impl TryFrom<PkiPath> for ContentInfo {
     type Error = der::Error;
 
@@ -118,3 +326,38 @@ impl TryFrom<PkiPath> for ContentInfo {
         })
     }
 }
+
+/// Extract the certificates from a certs-only SignedData message
+///
+/// The `certificates` field of `SignedData` is a `SET OF`, so the order of the certificates in
+/// the returned `PkiPath` follows the DER ordering of the set rather than the certification path.
+/// Certificate formats other than X.509 certificates are skipped.
+///
+/// BER-encoded messages, e.g. using indefinite lengths as produced by some PKCS #7 tools, are
+/// supported by decoding the `ContentInfo` with [`ContentInfo::from_ber`].
+#[cfg(feature = "signed")]
+impl TryFrom<ContentInfo> for PkiPath {
+    type Error = der::Error;
+
+    fn try_from(content_info: ContentInfo) -> der::Result<Self> {
+        if content_info.content_type != const_oid::db::rfc5911::ID_SIGNED_DATA {
+            return Err(ErrorKind::OidUnknown {
+                oid: content_info.content_type,
+            }
+            .into());
+        }
+
+        let sd: SignedData = content_info.content.decode_as()?;
+
+        Ok(sd
+            .certificates
+            .map(|certs| certs.0.into_vec())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|choice| match choice {
+                CertificateChoices::Certificate(cert) => Some(cert),
+                _ => None,
+            })
+            .collect())
+    }
+}
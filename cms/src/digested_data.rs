@@ -1,10 +1,9 @@
 //! DigestedData-related types
-use der::{asn1::OctetString, Sequence};
+use der::{asn1::OctetString, Any, AnyRef, Encode, Sequence};
 
 use spki::AlgorithmIdentifierOwned;
 
-use crate::content_info::CmsVersion;
-use crate::signed_data::EncapsulatedContentInfo;
+use crate::content_info::{CmsVersion, ContentInfo, EncapsulatedContentInfo};
 
 /// The `DigestedData` type is defined in [RFC 5652 Section 7].
 ///
@@ -27,6 +26,62 @@ pub struct DigestedData {
     pub digest: Digest,
 }
 
+impl DigestedData {
+    /// Create a `DigestedData` over `encap_content_info`, selecting the version as
+    /// specified in [RFC 5652 Section 7]: 0 for content of type `id-data`, 2 otherwise.
+    ///
+    /// [RFC 5652 Section 7]: https://www.rfc-editor.org/rfc/rfc5652#section-7
+    pub fn new(
+        digest_alg: AlgorithmIdentifierOwned,
+        encap_content_info: EncapsulatedContentInfo,
+        digest: Digest,
+    ) -> Self {
+        let version = if encap_content_info.econtent_type == const_oid::db::rfc5911::ID_DATA {
+            CmsVersion::V0
+        } else {
+            CmsVersion::V2
+        };
+
+        DigestedData {
+            version,
+            digest_alg,
+            encap_content_info,
+            digest,
+        }
+    }
+}
+
+/// Extract a `DigestedData` from a `ContentInfo` of type `id-digestedData`
+impl TryFrom<ContentInfo> for DigestedData {
+    type Error = der::Error;
+
+    fn try_from(content_info: ContentInfo) -> der::Result<Self> {
+        if content_info.content_type != const_oid::db::rfc5911::ID_DIGESTED_DATA {
+            return Err(der::ErrorKind::OidUnknown {
+                oid: content_info.content_type,
+            }
+            .into());
+        }
+
+        content_info.content.decode_as()
+    }
+}
+
+/// Wrap a `DigestedData` in a `ContentInfo` of type `id-digestedData`
+impl TryFrom<DigestedData> for ContentInfo {
+    type Error = der::Error;
+
+    fn try_from(digested_data: DigestedData) -> der::Result<Self> {
+        let digested_data = digested_data.to_der()?;
+        let content = AnyRef::try_from(digested_data.as_slice())?;
+
+        Ok(ContentInfo {
+            content_type: const_oid::db::rfc5911::ID_DIGESTED_DATA,
+            content: Any::from(content),
+        })
+    }
+}
+
 /// The `Digest` type is defined in [RFC 5652 Section 7].
 ///
 /// ```text
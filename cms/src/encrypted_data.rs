@@ -1,9 +1,9 @@
 //! EncryptedData-related types
-use der::Sequence;
+use der::{Any, AnyRef, Encode, Sequence};
 
 use x509_cert::attr::Attributes;
 
-use crate::content_info::CmsVersion;
+use crate::content_info::{CmsVersion, ContentInfo};
 use crate::enveloped_data::EncryptedContentInfo;
 
 /// The `EncryptedData` type is defined in [RFC 5652 Section 8].
@@ -31,3 +31,57 @@ pub struct EncryptedData {
     )]
     pub unprotected_attrs: Option<Attributes>,
 }
+
+impl EncryptedData {
+    /// Create an `EncryptedData`, selecting the version as specified in
+    /// [RFC 5652 Section 8]: 2 if unprotected attributes are present, 0 otherwise.
+    ///
+    /// [RFC 5652 Section 8]: https://www.rfc-editor.org/rfc/rfc5652#section-8
+    pub fn new(
+        enc_content_info: EncryptedContentInfo,
+        unprotected_attrs: Option<Attributes>,
+    ) -> Self {
+        let version = if unprotected_attrs.is_some() {
+            CmsVersion::V2
+        } else {
+            CmsVersion::V0
+        };
+
+        EncryptedData {
+            version,
+            enc_content_info,
+            unprotected_attrs,
+        }
+    }
+}
+
+/// Extract an `EncryptedData` from a `ContentInfo` of type `id-encryptedData`
+impl TryFrom<ContentInfo> for EncryptedData {
+    type Error = der::Error;
+
+    fn try_from(content_info: ContentInfo) -> der::Result<Self> {
+        if content_info.content_type != const_oid::db::rfc5911::ID_ENCRYPTED_DATA {
+            return Err(der::ErrorKind::OidUnknown {
+                oid: content_info.content_type,
+            }
+            .into());
+        }
+
+        content_info.content.decode_as()
+    }
+}
+
+/// Wrap an `EncryptedData` in a `ContentInfo` of type `id-encryptedData`
+impl TryFrom<EncryptedData> for ContentInfo {
+    type Error = der::Error;
+
+    fn try_from(encrypted_data: EncryptedData) -> der::Result<Self> {
+        let encrypted_data = encrypted_data.to_der()?;
+        let content = AnyRef::try_from(encrypted_data.as_slice())?;
+
+        Ok(ContentInfo {
+            content_type: const_oid::db::rfc5911::ID_ENCRYPTED_DATA,
+            content: Any::from(content),
+        })
+    }
+}
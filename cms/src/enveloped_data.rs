@@ -1,17 +1,25 @@
 //! EnvelopedData-related types
 
-use crate::cert::IssuerAndSerialNumber;
 use crate::content_info::CmsVersion;
-use crate::revocation::RevocationInfoChoices;
-use crate::signed_data::CertificateSet;
 
-use core::cmp::Ordering;
-use der::asn1::{BitString, GeneralizedTime, ObjectIdentifier, OctetString, SetOfVec};
-use der::{Any, Choice, Sequence, ValueOrd};
+use der::asn1::{BitString, ObjectIdentifier, OctetString};
+use der::{Any, Sequence};
 use spki::AlgorithmIdentifierOwned;
-use x509_cert::attr::{Attribute, Attributes};
-use x509_cert::ext::pkix::SubjectKeyIdentifier;
-use x509_cert::impl_newtype;
+
+#[cfg(feature = "x509")]
+use {
+    crate::cert::{CertificateSet, IssuerAndSerialNumber, KeyHandle},
+    crate::content_info::check_implicit_set,
+    crate::known_oids::{check_known_algorithm, check_known_attributes, check_known_oid, OidUsage},
+    crate::revocation::RevocationInfoChoices,
+    core::cmp::Ordering,
+    der::asn1::{GeneralizedTime, SetOfVec},
+    der::{AnyRef, Choice, Decode, FieldName, ValueOrd},
+    x509_cert::attr::{Attribute, Attributes},
+    x509_cert::ext::pkix::SubjectKeyIdentifier,
+    x509_cert::impl_newtype,
+    x509_cert::Certificate,
+};
 
 /// The `EnvelopedData` type is defined in [RFC 5652 Section 6.1].
 ///
@@ -27,6 +35,7 @@ use x509_cert::impl_newtype;
 /// ```
 ///
 /// [RFC 5652 Section 6.1]: https://www.rfc-editor.org/rfc/rfc5652#section-6.1
+#[cfg(feature = "x509")]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct EnvelopedData {
@@ -58,6 +67,7 @@ pub struct EnvelopedData {
 /// ```
 ///
 /// [RFC 5652 Section 6.1]: https://www.rfc-editor.org/rfc/rfc5652#section-6.1
+#[cfg(feature = "x509")]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct OriginatorInfo {
@@ -84,11 +94,13 @@ pub struct OriginatorInfo {
 /// ```
 ///
 /// [RFC 5652 Section 6.1]: https://www.rfc-editor.org/rfc/rfc5652#section-6.1
+#[cfg(feature = "x509")]
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct RecipientInfos(pub SetOfVec<RecipientInfo>);
+#[cfg(feature = "x509")]
 impl_newtype!(RecipientInfos, SetOfVec<RecipientInfo>);
 
-#[cfg(feature = "std")]
+#[cfg(all(feature = "std", feature = "x509"))]
 impl TryFrom<std::vec::Vec<RecipientInfo>> for RecipientInfos {
     type Error = der::Error;
 
@@ -129,6 +141,7 @@ pub struct EncryptedContentInfo {
 /// ```
 ///
 /// [RFC 5652 Section 6.2]: https://www.rfc-editor.org/rfc/rfc5652#section-6.2
+#[cfg(feature = "x509")]
 #[derive(Clone, Debug, Eq, PartialEq, Choice)]
 #[allow(missing_docs)]
 pub enum RecipientInfo {
@@ -143,6 +156,7 @@ pub enum RecipientInfo {
     Ori(OtherRecipientInfo),
 }
 
+#[cfg(feature = "x509")]
 impl ValueOrd for RecipientInfo {
     fn value_cmp(&self, other: &Self) -> der::Result<Ordering> {
         use der::DerOrd;
@@ -172,6 +186,7 @@ pub type EncryptedKey = OctetString;
 /// ```
 ///
 /// [RFC 5652 Section 6.2.1]: https://www.rfc-editor.org/rfc/rfc5652#section-6.2.1
+#[cfg(feature = "x509")]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct KeyTransRecipientInfo {
@@ -191,6 +206,7 @@ pub struct KeyTransRecipientInfo {
 /// ```
 ///
 /// [RFC 5652 Section 6.2.1]: https://www.rfc-editor.org/rfc/rfc5652#section-6.2.1
+#[cfg(feature = "x509")]
 #[derive(Clone, Debug, Eq, PartialEq, Choice)]
 #[allow(missing_docs)]
 pub enum RecipientIdentifier {
@@ -200,6 +216,16 @@ pub enum RecipientIdentifier {
     SubjectKeyIdentifier(SubjectKeyIdentifier),
 }
 
+#[cfg(feature = "x509")]
+impl<'a> From<&'a RecipientIdentifier> for KeyHandle<'a> {
+    fn from(rid: &'a RecipientIdentifier) -> Self {
+        match rid {
+            RecipientIdentifier::IssuerAndSerialNumber(iasn) => KeyHandle::IssuerAndSerial(iasn),
+            RecipientIdentifier::SubjectKeyIdentifier(skid) => KeyHandle::SubjectKeyId(skid),
+        }
+    }
+}
+
 /// The `KeyAgreeRecipientInfo` type is defined in [RFC 5652 Section 6.2.2].
 ///
 /// ```text
@@ -213,6 +239,7 @@ pub enum RecipientIdentifier {
 /// ```
 ///
 /// [RFC 5652 Section 6.2.2]: https://www.rfc-editor.org/rfc/rfc5652#section-6.2.2
+#[cfg(feature = "x509")]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct KeyAgreeRecipientInfo {
@@ -235,6 +262,7 @@ pub struct KeyAgreeRecipientInfo {
 /// ```
 ///
 /// [RFC 5652 Section 6.2.2]: https://www.rfc-editor.org/rfc/rfc5652#section-6.2.2
+#[cfg(feature = "x509")]
 #[derive(Clone, Debug, Eq, PartialEq, Choice)]
 #[allow(missing_docs)]
 pub enum OriginatorIdentifierOrKey {
@@ -268,6 +296,7 @@ pub struct OriginatorPublicKey {
 /// ```
 ///
 /// [RFC 5652 Section 6.2.2]: https://www.rfc-editor.org/rfc/rfc5652#section-6.2.2
+#[cfg(feature = "x509")]
 pub type RecipientEncryptedKeys = alloc::vec::Vec<RecipientEncryptedKey>;
 
 /// The `RecipientEncryptedKey` type is defined in [RFC 5652 Section 6.2.2].
@@ -279,6 +308,7 @@ pub type RecipientEncryptedKeys = alloc::vec::Vec<RecipientEncryptedKey>;
 /// ```
 ///
 /// [RFC 5652 Section 6.2.2]: https://www.rfc-editor.org/rfc/rfc5652#section-6.2.2
+#[cfg(feature = "x509")]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct RecipientEncryptedKey {
@@ -295,6 +325,7 @@ pub struct RecipientEncryptedKey {
 /// ```
 ///
 /// [RFC 5652 Section 6.2.2]: https://www.rfc-editor.org/rfc/rfc5652#section-6.2.2
+#[cfg(feature = "x509")]
 #[derive(Clone, Debug, Eq, PartialEq, Choice)]
 #[allow(missing_docs)]
 pub enum KeyAgreeRecipientIdentifier {
@@ -303,6 +334,20 @@ pub enum KeyAgreeRecipientIdentifier {
     RKeyId(RecipientKeyIdentifier),
 }
 
+#[cfg(feature = "x509")]
+impl<'a> From<&'a KeyAgreeRecipientIdentifier> for KeyHandle<'a> {
+    fn from(rid: &'a KeyAgreeRecipientIdentifier) -> Self {
+        match rid {
+            KeyAgreeRecipientIdentifier::IssuerAndSerialNumber(iasn) => {
+                KeyHandle::IssuerAndSerial(iasn)
+            }
+            KeyAgreeRecipientIdentifier::RKeyId(rkid) => {
+                KeyHandle::SubjectKeyId(&rkid.subject_key_identifier)
+            }
+        }
+    }
+}
+
 /// The `RecipientKeyIdentifier` type is defined in [RFC 5652 Section 6.2.2].
 ///
 /// ```text
@@ -313,6 +358,7 @@ pub enum KeyAgreeRecipientIdentifier {
 /// ```
 ///
 /// [RFC 5652 Section 6.2.2]: https://www.rfc-editor.org/rfc/rfc5652#section-6.2.2
+#[cfg(feature = "x509")]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct RecipientKeyIdentifier {
@@ -335,6 +381,7 @@ pub struct RecipientKeyIdentifier {
 /// ```
 ///
 /// [RFC 5652 Section 6.2.3]: https://www.rfc-editor.org/rfc/rfc5652#section-6.2.3
+#[cfg(feature = "x509")]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct KekRecipientInfo {
@@ -354,6 +401,7 @@ pub struct KekRecipientInfo {
 /// ```
 ///
 /// [RFC 5652 Section 6.2.3]: https://www.rfc-editor.org/rfc/rfc5652#section-6.2.3
+#[cfg(feature = "x509")]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct KekIdentifier {
@@ -415,3 +463,333 @@ pub struct OtherRecipientInfo {
 ///
 /// [RFC 5652 Section 10.2.5]: https://www.rfc-editor.org/rfc/rfc5652#section-10.2.5
 pub type UserKeyingMaterial = OctetString;
+
+#[cfg(feature = "x509")]
+impl EnvelopedData {
+    /// Decode a BER-encoded `EnvelopedData` without interpreting it, e.g. to pass it through.
+    ///
+    /// The message is transcoded to DER with [`der::ber_to_der`] before being decoded, as
+    /// with [`ContentInfo::from_ber`][crate::content_info::ContentInfo::from_ber].
+    /// Algorithms and attributes are not checked, so messages using ones unknown to this
+    /// crate are accepted.
+    pub fn from_ber(bytes: &[u8]) -> der::Result<Self> {
+        Self::from_der(&der::ber_to_der(bytes)?)
+    }
+
+    /// Decode a DER-encoded `EnvelopedData`, rejecting BER encodings anywhere inside the
+    /// message as well as algorithms, recipient info types and attributes unknown to this
+    /// crate.
+    ///
+    /// The message is checked with [`der::check_der`], and the ordering of the implicitly
+    /// tagged `SET OF`s of the originator info and unprotected attributes is checked
+    /// separately. The OIDs of the key-encryption, key-derivation and content-encryption
+    /// algorithms and of the attribute types must then be known to this crate for their field:
+    /// e.g. a content-encryption algorithm is rejected as a key-encryption algorithm. No type
+    /// of other recipient info is known. Otherwise [`der::ErrorKind::OidUnknown`] is returned
+    /// with the path of the offending field, e.g. `EnvelopedData.recip_infos[1].key_enc_alg.oid`.
+    pub fn from_der_strict(bytes: &[u8]) -> der::Result<Self> {
+        der::check_der(bytes)?;
+        EnvelopedDataSets::from_der(bytes)?.check()?;
+
+        let enveloped_data = Self::from_der(bytes)?;
+        enveloped_data.check_known_oids()?;
+        Ok(enveloped_data)
+    }
+
+    /// Check that the algorithms, recipient info types and attribute types of this message are
+    /// known to this crate.
+    fn check_known_oids(&self) -> der::Result<()> {
+        const RECIP_INFOS: FieldName = FieldName {
+            type_name: "EnvelopedData",
+            field: "recip_infos",
+        };
+        const KEY_DERIVATION_ALG: FieldName = FieldName {
+            type_name: "PasswordRecipientInfo",
+            field: "key_derivation_alg",
+        };
+        const KEY_ENC_ALG: FieldName = FieldName {
+            type_name: "RecipientInfo",
+            field: "key_enc_alg",
+        };
+        const ORI_TYPE: FieldName = FieldName {
+            type_name: "OtherRecipientInfo",
+            field: "ori_type",
+        };
+        const ENCRYPTED_CONTENT: FieldName = FieldName {
+            type_name: "EnvelopedData",
+            field: "encrypted_content",
+        };
+        const CONTENT_ENC_ALG: FieldName = FieldName {
+            type_name: "EncryptedContentInfo",
+            field: "content_enc_alg",
+        };
+        const UNPROTECTED_ATTRS: FieldName = FieldName {
+            type_name: "EnvelopedData",
+            field: "unprotected_attrs",
+        };
+
+        let check_key_enc_alg = |algorithm: &AlgorithmIdentifierOwned| {
+            check_known_algorithm(algorithm, OidUsage::KeyEncryptionAlgorithm)
+                .map_err(|err| err.in_field(&KEY_ENC_ALG))
+        };
+
+        for (index, info) in self.recip_infos.0.iter().enumerate() {
+            match info {
+                RecipientInfo::Ktri(ktri) => check_key_enc_alg(&ktri.key_enc_alg),
+                RecipientInfo::Kari(kari) => check_key_enc_alg(&kari.key_enc_alg),
+                RecipientInfo::Kekri(kekri) => check_key_enc_alg(&kekri.key_enc_alg),
+                RecipientInfo::Pwri(pwri) => pwri
+                    .key_derivation_alg
+                    .iter()
+                    .try_for_each(|algorithm| {
+                        check_known_algorithm(algorithm, OidUsage::KeyDerivationAlgorithm)
+                            .map_err(|err| err.in_field(&KEY_DERIVATION_ALG))
+                    })
+                    .and_then(|_| check_key_enc_alg(&pwri.key_enc_alg)),
+                RecipientInfo::Ori(ori) => {
+                    check_known_oid(ori.ori_type, OidUsage::OtherRecipientInfoType)
+                        .map_err(|err| err.in_field(&ORI_TYPE))
+                }
+            }
+            .map_err(|err| err.in_element(index).in_field(&RECIP_INFOS))?;
+        }
+
+        check_known_algorithm(
+            &self.encrypted_content.content_enc_alg,
+            OidUsage::ContentEncryptionAlgorithm,
+        )
+        .map_err(|err| err.in_field(&CONTENT_ENC_ALG).in_field(&ENCRYPTED_CONTENT))?;
+
+        check_known_attributes(self.unprotected_attrs.as_ref(), &UNPROTECTED_ATTRS)
+    }
+
+    /// Iterate over the recipients of this `EnvelopedData`.
+    ///
+    /// Each [`KeyAgreeRecipientInfo`] yields one [`Recipient`] per entry in its
+    /// `recipientEncryptedKeys`, so that the number of items is the number of keys the
+    /// content-encryption key was made available to.
+    pub fn recipients(&self) -> Recipients<'_> {
+        self.recip_infos.recipients()
+    }
+}
+
+/// Raw view of an [`EnvelopedData`], keeping its implicitly tagged `SET OF`s encoded.
+#[cfg(feature = "x509")]
+#[derive(Sequence)]
+struct EnvelopedDataSets<'a> {
+    version: CmsVersion,
+    #[asn1(
+        context_specific = "0",
+        tag_mode = "IMPLICIT",
+        constructed = "true",
+        optional = "true"
+    )]
+    originator_info: Option<OriginatorInfoSets<'a>>,
+    recip_infos: AnyRef<'a>,
+    encrypted_content: AnyRef<'a>,
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    unprotected_attrs: Option<AnyRef<'a>>,
+}
+
+/// Raw view of an [`OriginatorInfo`], keeping its implicitly tagged `SET OF`s encoded.
+#[cfg(feature = "x509")]
+#[derive(Sequence)]
+struct OriginatorInfoSets<'a> {
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", optional = "true")]
+    certs: Option<AnyRef<'a>>,
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    crls: Option<AnyRef<'a>>,
+}
+
+#[cfg(feature = "x509")]
+impl EnvelopedDataSets<'_> {
+    /// Check that the implicitly tagged `SET OF`s of the message, which are not covered by
+    /// [`der::check_der`], are DER-encoded: the `certs` and `crls` of the originator info,
+    /// and the `unprotectedAttrs`.
+    fn check(&self) -> der::Result<()> {
+        const ORIGINATOR_INFO: FieldName = FieldName {
+            type_name: "EnvelopedData",
+            field: "originator_info",
+        };
+        const CERTS: FieldName = FieldName {
+            type_name: "OriginatorInfo",
+            field: "certs",
+        };
+        const CRLS: FieldName = FieldName {
+            type_name: "OriginatorInfo",
+            field: "crls",
+        };
+        const UNPROTECTED_ATTRS: FieldName = FieldName {
+            type_name: "EnvelopedData",
+            field: "unprotected_attrs",
+        };
+
+        if let Some(originator_info) = &self.originator_info {
+            check_implicit_set(originator_info.certs)
+                .map_err(|err| err.in_field(&CERTS))
+                .and_then(|_| {
+                    check_implicit_set(originator_info.crls).map_err(|err| err.in_field(&CRLS))
+                })
+                .map_err(|err| err.in_field(&ORIGINATOR_INFO))?;
+        }
+
+        check_implicit_set(self.unprotected_attrs).map_err(|err| err.in_field(&UNPROTECTED_ATTRS))
+    }
+}
+
+#[cfg(feature = "x509")]
+impl RecipientInfos {
+    /// Iterate over the recipients described by these `RecipientInfos`.
+    ///
+    /// See [`EnvelopedData::recipients`].
+    pub fn recipients(&self) -> Recipients<'_> {
+        Recipients {
+            infos: self.0.iter(),
+            kari: None,
+        }
+    }
+}
+
+/// Kind of [`RecipientInfo`] a [`Recipient`] was taken from.
+#[cfg(feature = "x509")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecipientKind {
+    /// Key transport (`ktri`).
+    KeyTransport,
+
+    /// Key agreement (`kari`).
+    KeyAgreement,
+
+    /// Previously distributed symmetric key-encryption key (`kekri`).
+    Kek,
+
+    /// Password (`pwri`).
+    Password,
+
+    /// Other recipient info type (`ori`).
+    Other,
+}
+
+/// Identifier of the key of a [`Recipient`].
+#[cfg(feature = "x509")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecipientKeyId<'a> {
+    /// Certificate of the recipient, identified by its issuer and serial number or by its
+    /// subject key identifier.
+    Certificate(KeyHandle<'a>),
+
+    /// Identifier of a previously distributed key-encryption key.
+    KekIdentifier(&'a OctetString),
+}
+
+/// Normalized view of a recipient of an [`EnvelopedData`].
+#[cfg(feature = "x509")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Recipient<'a> {
+    /// Kind of `RecipientInfo` this recipient was taken from.
+    pub kind: RecipientKind,
+
+    /// Identifier of the recipient's key, absent for password and other recipients.
+    pub key_id: Option<RecipientKeyId<'a>>,
+
+    /// Key-encryption algorithm, absent for other recipients.
+    pub key_enc_alg: Option<&'a AlgorithmIdentifierOwned>,
+
+    /// Encrypted content-encryption key, absent for other recipients.
+    pub enc_key: Option<&'a EncryptedKey>,
+
+    /// `RecipientInfo` this recipient was taken from.
+    pub info: &'a RecipientInfo,
+}
+
+#[cfg(feature = "x509")]
+impl<'a> Recipient<'a> {
+    fn new(info: &'a RecipientInfo) -> Self {
+        let (kind, key_id, key_enc_alg, enc_key) = match info {
+            RecipientInfo::Ktri(ktri) => (
+                RecipientKind::KeyTransport,
+                Some(RecipientKeyId::Certificate(KeyHandle::from(&ktri.rid))),
+                Some(&ktri.key_enc_alg),
+                Some(&ktri.enc_key),
+            ),
+            RecipientInfo::Kari(kari) => (
+                RecipientKind::KeyAgreement,
+                None,
+                Some(&kari.key_enc_alg),
+                None,
+            ),
+            RecipientInfo::Kekri(kekri) => (
+                RecipientKind::Kek,
+                Some(RecipientKeyId::KekIdentifier(&kekri.kek_id.kek_identifier)),
+                Some(&kekri.key_enc_alg),
+                Some(&kekri.encrypted_key),
+            ),
+            RecipientInfo::Pwri(pwri) => (
+                RecipientKind::Password,
+                None,
+                Some(&pwri.key_enc_alg),
+                Some(&pwri.enc_key),
+            ),
+            RecipientInfo::Ori(_) => (RecipientKind::Other, None, None, None),
+        };
+
+        Self {
+            kind,
+            key_id,
+            key_enc_alg,
+            enc_key,
+            info,
+        }
+    }
+
+    /// Is `cert` the certificate of this recipient?
+    ///
+    /// Certificates are matched with [`KeyHandle::matches_certificate`]. Recipients which
+    /// aren't identified by a certificate never match.
+    pub fn matches_certificate(&self, cert: &Certificate) -> bool {
+        match self.key_id {
+            Some(RecipientKeyId::Certificate(key_handle)) => key_handle.matches_certificate(cert),
+            _ => false,
+        }
+    }
+
+    fn with_encrypted_key(mut self, rek: &'a RecipientEncryptedKey) -> Self {
+        self.key_id = Some(RecipientKeyId::Certificate(KeyHandle::from(&rek.rid)));
+        self.enc_key = Some(&rek.enc_key);
+        self
+    }
+}
+
+/// Iterator over the [`Recipient`]s of an [`EnvelopedData`].
+#[cfg(feature = "x509")]
+#[derive(Clone, Debug)]
+pub struct Recipients<'a> {
+    infos: core::slice::Iter<'a, RecipientInfo>,
+    kari: Option<(Recipient<'a>, core::slice::Iter<'a, RecipientEncryptedKey>)>,
+}
+
+#[cfg(feature = "x509")]
+impl<'a> Iterator for Recipients<'a> {
+    type Item = Recipient<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((recipient, keys)) = &mut self.kari {
+                if let Some(rek) = keys.next() {
+                    return Some(recipient.with_encrypted_key(rek));
+                }
+                self.kari = None;
+            }
+
+            let info = self.infos.next()?;
+            let recipient = Recipient::new(info);
+
+            match info {
+                RecipientInfo::Kari(kari) => {
+                    self.kari = Some((recipient, kari.recipient_enc_keys.iter()));
+                }
+                _ => return Some(recipient),
+            }
+        }
+    }
+}
@@ -0,0 +1,299 @@
+//! OIDs known to this crate, checked by the `from_der_strict` decoders.
+
+use const_oid::ObjectIdentifier;
+use der::{ErrorKind, FieldName};
+use spki::AlgorithmIdentifierOwned;
+use x509_cert::attr::Attributes;
+
+/// Usage of an OID checked by the `from_der_strict` decoders, each usage having its own set
+/// of OIDs known to this crate.
+///
+/// OIDs are only accepted where they are meaningful: e.g. a digest algorithm can't be used as
+/// a signature algorithm, even though both are registered in [`const_oid::db::DB`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum OidUsage {
+    /// `DigestAlgorithmIdentifier`
+    #[cfg(feature = "signed")]
+    DigestAlgorithm,
+
+    /// `SignatureAlgorithmIdentifier`
+    #[cfg(feature = "signed")]
+    SignatureAlgorithm,
+
+    /// `KeyEncryptionAlgorithmIdentifier`
+    #[cfg(all(feature = "enveloped", feature = "x509"))]
+    KeyEncryptionAlgorithm,
+
+    /// `KeyDerivationAlgorithmIdentifier`
+    #[cfg(all(feature = "enveloped", feature = "x509"))]
+    KeyDerivationAlgorithm,
+
+    /// `ContentEncryptionAlgorithmIdentifier`
+    #[cfg(all(feature = "enveloped", feature = "x509"))]
+    ContentEncryptionAlgorithm,
+
+    /// Type of an `Attribute`
+    AttributeType,
+
+    /// Type of an `OtherRecipientInfo`
+    #[cfg(all(feature = "enveloped", feature = "x509"))]
+    OtherRecipientInfoType,
+}
+
+impl OidUsage {
+    /// OIDs known to this crate for this usage.
+    fn known_oids(self) -> &'static [ObjectIdentifier] {
+        use const_oid::db::{rfc5911, rfc5912, rfc6268, rfc8894};
+
+        match self {
+            #[cfg(feature = "signed")]
+            OidUsage::DigestAlgorithm => &[
+                rfc5912::ID_MD_5,
+                rfc5912::ID_SHA_1,
+                rfc5912::ID_SHA_224,
+                rfc5912::ID_SHA_256,
+                rfc5912::ID_SHA_384,
+                rfc5912::ID_SHA_512,
+                ID_SHA_3_224,
+                ID_SHA_3_256,
+                ID_SHA_3_384,
+                ID_SHA_3_512,
+            ],
+            #[cfg(feature = "signed")]
+            OidUsage::SignatureAlgorithm => &[
+                rfc5912::RSA_ENCRYPTION,
+                rfc5912::MD_5_WITH_RSA_ENCRYPTION,
+                rfc5912::SHA_1_WITH_RSA_ENCRYPTION,
+                rfc5912::SHA_224_WITH_RSA_ENCRYPTION,
+                rfc5912::SHA_256_WITH_RSA_ENCRYPTION,
+                rfc5912::SHA_384_WITH_RSA_ENCRYPTION,
+                rfc5912::SHA_512_WITH_RSA_ENCRYPTION,
+                rfc5912::ID_RSASSA_PSS,
+                rfc5912::ID_DSA,
+                rfc5912::DSA_WITH_SHA_1,
+                rfc5912::DSA_WITH_SHA_224,
+                rfc5912::DSA_WITH_SHA_256,
+                ECDSA_WITH_SHA_1,
+                rfc5912::ECDSA_WITH_SHA_224,
+                rfc5912::ECDSA_WITH_SHA_256,
+                rfc5912::ECDSA_WITH_SHA_384,
+                rfc5912::ECDSA_WITH_SHA_512,
+                // Emitted by some producers instead of an `ecdsa-with-*` algorithm
+                rfc5912::ID_EC_PUBLIC_KEY,
+                ID_ECDSA_WITH_SHA_3_224,
+                ID_ECDSA_WITH_SHA_3_256,
+                ID_ECDSA_WITH_SHA_3_384,
+                ID_ECDSA_WITH_SHA_3_512,
+                ID_RSASSA_PKCS_1_V_1_5_WITH_SHA_3_224,
+                ID_RSASSA_PKCS_1_V_1_5_WITH_SHA_3_256,
+                ID_RSASSA_PKCS_1_V_1_5_WITH_SHA_3_384,
+                ID_RSASSA_PKCS_1_V_1_5_WITH_SHA_3_512,
+                ID_ED_25519,
+                ID_ED_448,
+            ],
+            #[cfg(all(feature = "enveloped", feature = "x509"))]
+            OidUsage::KeyEncryptionAlgorithm => &[
+                rfc5912::RSA_ENCRYPTION,
+                rfc5912::ID_RSAES_OAEP,
+                rfc5911::ID_ALG_ESDH,
+                rfc5911::ID_ALG_SSDH,
+                rfc5911::ID_ALG_CMS_3_DE_SWRAP,
+                rfc5911::ID_ALG_CMSRC_2_WRAP,
+                rfc5911::ID_AES_128_WRAP,
+                rfc5911::ID_AES_192_WRAP,
+                rfc5911::ID_AES_256_WRAP,
+                ID_ALG_PWRI_KEK,
+                DH_SINGLE_PASS_STD_DH_SHA_1_KDF_SCHEME,
+                DH_SINGLE_PASS_STD_DH_SHA_224_KDF_SCHEME,
+                DH_SINGLE_PASS_STD_DH_SHA_256_KDF_SCHEME,
+                DH_SINGLE_PASS_STD_DH_SHA_384_KDF_SCHEME,
+                DH_SINGLE_PASS_STD_DH_SHA_512_KDF_SCHEME,
+                DH_SINGLE_PASS_COFACTOR_DH_SHA_1_KDF_SCHEME,
+                DH_SINGLE_PASS_COFACTOR_DH_SHA_224_KDF_SCHEME,
+                DH_SINGLE_PASS_COFACTOR_DH_SHA_256_KDF_SCHEME,
+                DH_SINGLE_PASS_COFACTOR_DH_SHA_384_KDF_SCHEME,
+                DH_SINGLE_PASS_COFACTOR_DH_SHA_512_KDF_SCHEME,
+            ],
+            #[cfg(all(feature = "enveloped", feature = "x509"))]
+            OidUsage::KeyDerivationAlgorithm => &[rfc5911::ID_PBKDF_2],
+            #[cfg(all(feature = "enveloped", feature = "x509"))]
+            OidUsage::ContentEncryptionAlgorithm => &[
+                rfc5911::DES_EDE_3_CBC,
+                rfc5911::RC_2_CBC,
+                rfc5911::ID_AES_128_CBC,
+                rfc5911::ID_AES_192_CBC,
+                rfc5911::ID_AES_256_CBC,
+                rfc5911::ID_AES_128_GCM,
+                rfc5911::ID_AES_192_GCM,
+                rfc5911::ID_AES_256_GCM,
+                rfc5911::ID_AES_128_CCM,
+                rfc5911::ID_AES_192_CCM,
+                rfc5911::ID_AES_256_CCM,
+            ],
+            OidUsage::AttributeType => &[
+                rfc5911::ID_CONTENT_TYPE,
+                rfc5911::ID_MESSAGE_DIGEST,
+                rfc5911::ID_SIGNING_TIME,
+                rfc5911::ID_COUNTERSIGNATURE,
+                rfc5911::SMIME_CAPABILITIES,
+                rfc5911::ID_AA_RECEIPT_REQUEST,
+                rfc5911::ID_AA_CONTENT_REFERENCE,
+                rfc5911::ID_AA_ENCRYP_KEY_PREF,
+                rfc5911::ID_AA_SIGNING_CERTIFICATE,
+                rfc5911::ID_AA_SIGNING_CERTIFICATE_V_2,
+                rfc5911::ID_AA_SECURITY_LABEL,
+                rfc5911::ID_AA_EQUIVALENT_LABELS,
+                rfc5911::ID_AA_ML_EXPAND_HISTORY,
+                rfc5911::ID_AA_CONTENT_HINT,
+                rfc5911::ID_AA_CONTENT_IDENTIFIER,
+                rfc5911::ID_AA_MSG_SIG_DIGEST,
+                rfc6268::ID_AA_BINARY_SIGNING_TIME,
+                rfc6268::ID_AA_MULTIPLE_SIGNATURES,
+                #[cfg(feature = "signed")]
+                crate::attr::ID_AA_CMS_ALGORITHM_PROTECTION,
+                ID_AA_SIGNATURE_TIME_STAMP_TOKEN,
+                rfc8894::ID_MESSAGE_TYPE,
+                rfc8894::ID_PKI_STATUS,
+                rfc8894::ID_FAIL_INFO,
+                rfc8894::ID_SENDER_NONCE,
+                rfc8894::ID_RECIPIENT_NONCE,
+                rfc8894::ID_TRANSACTION_ID,
+            ],
+            #[cfg(all(feature = "enveloped", feature = "x509"))]
+            OidUsage::OtherRecipientInfoType => &[],
+        }
+    }
+}
+
+// The following OIDs are missing from `const_oid::db`.
+
+// FIPS 202
+#[cfg(feature = "signed")]
+const ID_SHA_3_224: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.7");
+#[cfg(feature = "signed")]
+const ID_SHA_3_256: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.8");
+#[cfg(feature = "signed")]
+const ID_SHA_3_384: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.9");
+#[cfg(feature = "signed")]
+const ID_SHA_3_512: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.10");
+
+// NIST CSOR signature algorithms
+#[cfg(feature = "signed")]
+const ID_ECDSA_WITH_SHA_3_224: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.9");
+#[cfg(feature = "signed")]
+const ID_ECDSA_WITH_SHA_3_256: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.10");
+#[cfg(feature = "signed")]
+const ID_ECDSA_WITH_SHA_3_384: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.11");
+#[cfg(feature = "signed")]
+const ID_ECDSA_WITH_SHA_3_512: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.12");
+#[cfg(feature = "signed")]
+const ID_RSASSA_PKCS_1_V_1_5_WITH_SHA_3_224: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.13");
+#[cfg(feature = "signed")]
+const ID_RSASSA_PKCS_1_V_1_5_WITH_SHA_3_256: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.14");
+#[cfg(feature = "signed")]
+const ID_RSASSA_PKCS_1_V_1_5_WITH_SHA_3_384: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.15");
+#[cfg(feature = "signed")]
+const ID_RSASSA_PKCS_1_V_1_5_WITH_SHA_3_512: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.16");
+
+// RFC 3279
+#[cfg(feature = "signed")]
+const ECDSA_WITH_SHA_1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.1");
+
+// RFC 8410
+#[cfg(feature = "signed")]
+const ID_ED_25519: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+#[cfg(feature = "signed")]
+const ID_ED_448: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.113");
+
+// RFC 3211
+#[cfg(all(feature = "enveloped", feature = "x509"))]
+const ID_ALG_PWRI_KEK: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.16.3.9");
+
+// RFC 5753
+#[cfg(all(feature = "enveloped", feature = "x509"))]
+const DH_SINGLE_PASS_STD_DH_SHA_1_KDF_SCHEME: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.133.16.840.63.0.2");
+#[cfg(all(feature = "enveloped", feature = "x509"))]
+const DH_SINGLE_PASS_STD_DH_SHA_224_KDF_SCHEME: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.132.1.11.0");
+#[cfg(all(feature = "enveloped", feature = "x509"))]
+const DH_SINGLE_PASS_STD_DH_SHA_256_KDF_SCHEME: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.132.1.11.1");
+#[cfg(all(feature = "enveloped", feature = "x509"))]
+const DH_SINGLE_PASS_STD_DH_SHA_384_KDF_SCHEME: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.132.1.11.2");
+#[cfg(all(feature = "enveloped", feature = "x509"))]
+const DH_SINGLE_PASS_STD_DH_SHA_512_KDF_SCHEME: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.132.1.11.3");
+#[cfg(all(feature = "enveloped", feature = "x509"))]
+const DH_SINGLE_PASS_COFACTOR_DH_SHA_1_KDF_SCHEME: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.133.16.840.63.0.3");
+#[cfg(all(feature = "enveloped", feature = "x509"))]
+const DH_SINGLE_PASS_COFACTOR_DH_SHA_224_KDF_SCHEME: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.132.1.14.0");
+#[cfg(all(feature = "enveloped", feature = "x509"))]
+const DH_SINGLE_PASS_COFACTOR_DH_SHA_256_KDF_SCHEME: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.132.1.14.1");
+#[cfg(all(feature = "enveloped", feature = "x509"))]
+const DH_SINGLE_PASS_COFACTOR_DH_SHA_384_KDF_SCHEME: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.132.1.14.2");
+#[cfg(all(feature = "enveloped", feature = "x509"))]
+const DH_SINGLE_PASS_COFACTOR_DH_SHA_512_KDF_SCHEME: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.132.1.14.3");
+
+// RFC 3161
+const ID_AA_SIGNATURE_TIME_STAMP_TOKEN: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.16.2.14");
+
+/// Check that `oid` is known to this crate for `usage`.
+pub(crate) fn check_known_oid(oid: ObjectIdentifier, usage: OidUsage) -> der::Result<()> {
+    if usage.known_oids().contains(&oid) {
+        Ok(())
+    } else {
+        Err(ErrorKind::OidUnknown { oid }.into())
+    }
+}
+
+/// Check that `algorithm` is known to this crate for `usage`, as with [`check_known_oid`].
+pub(crate) fn check_known_algorithm(
+    algorithm: &AlgorithmIdentifierOwned,
+    usage: OidUsage,
+) -> der::Result<()> {
+    const ALGORITHM: FieldName = FieldName {
+        type_name: "AlgorithmIdentifier",
+        field: "oid",
+    };
+
+    check_known_oid(algorithm.oid, usage).map_err(|err| err.in_field(&ALGORITHM))
+}
+
+/// Check that the types of `attributes`, the value of `field`, are known to this crate, as
+/// with [`check_known_oid`].
+pub(crate) fn check_known_attributes(
+    attributes: Option<&Attributes>,
+    field: &'static FieldName,
+) -> der::Result<()> {
+    const ATTR_TYPE: FieldName = FieldName {
+        type_name: "Attribute",
+        field: "oid",
+    };
+
+    for (index, attribute) in attributes
+        .into_iter()
+        .flat_map(|attrs| attrs.iter())
+        .enumerate()
+    {
+        check_known_oid(attribute.oid, OidUsage::AttributeType)
+            .map_err(|err| err.in_field(&ATTR_TYPE).in_element(index).in_field(field))?;
+    }
+
+    Ok(())
+}
@@ -21,20 +21,60 @@
 //! Use a [`TryFrom`] conversion between [`cert::x509::Certificate`] and
 //! [`content_info::ContentInfo`] to generate the data structures, then use
 //! `to_der` to serialize it.
+//!
+//! # Features
+//!
+//! Content types are selected with crate features, all of which are enabled by default:
+//!
+//! - `signed`: `SignedData` and its certificate and revocation information types.
+//! - `enveloped`: `EnvelopedData` and `EncryptedData`.
+//! - `authenticated`: `AuthenticatedData`.
+//! - `compressed`: `CompressedData`, with `zlib` enabling (de)compression.
+//! - `x509`: the types referencing certificates, names or attributes, which are defined by the
+//!   `x509-cert` dependency.
+//!
+//! `DigestedData` and `ContentInfo` are always available. The `signed` and `authenticated`
+//! features enable `x509`, and the `builder` feature enables `signed`.
+//!
+//! Without `x509`, the `enveloped` feature only provides `EncryptedContentInfo`,
+//! `PasswordRecipientInfo`, `OtherRecipientInfo` and `OriginatorPublicKey`. `EnvelopedData`,
+//! `EncryptedData` and the key transport, key agreement and KEK recipient types also need `x509`.
+//!
+//! Signatures of `SignedData` messages are verified with the `verify` feature, which the
+//! `builder` feature enables, and concurrently on a thread pool with the `rayon` feature.
 
+#[cfg(any(
+    feature = "signed",
+    all(feature = "enveloped", feature = "x509"),
+    feature = "zlib"
+))]
 extern crate alloc;
 
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "signed")]
 pub mod attr;
+#[cfg(feature = "authenticated")]
 pub mod authenticated_data;
 pub mod builder;
+#[cfg(feature = "x509")]
 pub mod cert;
+#[cfg(feature = "compressed")]
 pub mod compressed_data;
 pub mod content_info;
 pub mod digested_data;
+#[cfg(all(feature = "enveloped", feature = "x509"))]
 pub mod encrypted_data;
+#[cfg(feature = "enveloped")]
 pub mod enveloped_data;
+#[cfg(any(feature = "signed", all(feature = "enveloped", feature = "x509")))]
+mod known_oids;
+#[cfg(feature = "x509")]
 pub mod revocation;
+#[cfg(feature = "signed")]
+pub mod scep;
+#[cfg(feature = "signed")]
 pub mod signed_data;
+#[cfg(feature = "verify")]
+pub mod verify;
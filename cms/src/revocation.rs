@@ -1,13 +1,25 @@
 //! Revocation-related types
-use core::cmp::Ordering;
 
-use der::asn1::SetOfVec;
+use der::asn1::{ObjectIdentifier, SetOfVec};
 use der::{Any, Choice, Sequence, ValueOrd};
 use spki::AlgorithmIdentifierOwned;
 
 use x509_cert::crl::CertificateList;
 use x509_cert::impl_newtype;
 
+#[cfg(feature = "ocsp")]
+pub use x509_ocsp as ocsp;
+
+#[cfg(feature = "ocsp")]
+use x509_ocsp::OcspResponse;
+
+/// `id-ri-ocsp-response` revocation information format (`1.3.6.1.5.5.7.16.2`) as defined in
+/// [RFC 5940 Section 4.1].
+///
+/// [RFC 5940 Section 4.1]: https://www.rfc-editor.org/rfc/rfc5940#section-4.1
+pub const ID_RI_OCSP_RESPONSE: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.16.2");
+
 /// The `RevocationInfoChoices` type is defined in [RFC 5652 Section 10.2.1].
 ///
 /// ```text
@@ -29,7 +41,7 @@ impl_newtype!(RevocationInfoChoices, SetOfVec<RevocationInfoChoice>);
 /// ```
 ///
 /// [RFC 5652 Section 10.2.1]: https://www.rfc-editor.org/rfc/rfc5652#section-10.2.1
-#[derive(Clone, Debug, Eq, PartialEq, Choice)]
+#[derive(Clone, Debug, Eq, PartialEq, Choice, ValueOrd)]
 #[allow(missing_docs)]
 #[allow(clippy::large_enum_variant)]
 pub enum RevocationInfoChoice {
@@ -38,15 +50,6 @@ pub enum RevocationInfoChoice {
     Other(OtherRevocationInfoFormat),
 }
 
-// TODO DEFER ValueOrd is not supported for CHOICE types (see new_enum in value_ord.rs)
-impl ValueOrd for RevocationInfoChoice {
-    fn value_cmp(&self, other: &Self) -> der::Result<Ordering> {
-        use der::DerOrd;
-        use der::Encode;
-        self.to_der()?.der_cmp(&other.to_der()?)
-    }
-}
-
 #[cfg(feature = "std")]
 impl TryFrom<std::vec::Vec<RevocationInfoChoice>> for RevocationInfoChoices {
     type Error = der::Error;
@@ -56,7 +59,7 @@ impl TryFrom<std::vec::Vec<RevocationInfoChoice>> for RevocationInfoChoices {
     }
 }
 
-/// The `RevocationInfoChoices` type is defined in [RFC 5652 Section 10.2.1].
+/// The `OtherRevocationInfoFormat` type is defined in [RFC 5652 Section 10.2.1].
 ///
 /// ```text
 ///   OtherRevocationInfoFormat ::= SEQUENCE {
@@ -66,10 +69,74 @@ impl TryFrom<std::vec::Vec<RevocationInfoChoice>> for RevocationInfoChoices {
 ///               &Type({SupportedOtherRevokInfo}{@otherRevInfoFormat})}
 /// ```
 ///
+/// `other_format` is an `AlgorithmIdentifier` rather than the bare `OBJECT IDENTIFIER` of
+/// RFC 5652 for compatibility with previous releases: use [`OtherRevocationInfoFormat::format`]
+/// to get the format identifier.
+///
 /// [RFC 5652 Section 10.2.1]: https://www.rfc-editor.org/rfc/rfc5652#section-10.2.1
-#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
 #[allow(missing_docs)]
 pub struct OtherRevocationInfoFormat {
     pub other_format: AlgorithmIdentifierOwned,
     pub other: Any,
 }
+
+impl OtherRevocationInfoFormat {
+    /// Create revocation information of the format identified by `format`.
+    pub fn new(format: ObjectIdentifier, other: Any) -> Self {
+        Self {
+            other_format: AlgorithmIdentifierOwned {
+                oid: format,
+                parameters: None,
+            },
+            other,
+        }
+    }
+
+    /// Get the identifier of the format of this revocation information.
+    pub fn format(&self) -> ObjectIdentifier {
+        self.other_format.oid
+    }
+}
+
+#[cfg(feature = "ocsp")]
+impl RevocationInfoChoices {
+    /// Iterate over the OCSP responses ([RFC 5940 Section 4.1]) carried in this set.
+    ///
+    /// [RFC 5940 Section 4.1]: https://www.rfc-editor.org/rfc/rfc5940#section-4.1
+    pub fn ocsp_responses(&self) -> impl Iterator<Item = der::Result<OcspResponse<'_>>> {
+        self.0.iter().filter_map(|choice| match choice {
+            RevocationInfoChoice::Other(other) => other.ocsp_response().transpose(),
+            RevocationInfoChoice::Crl(_) => None,
+        })
+    }
+}
+
+#[cfg(feature = "ocsp")]
+impl OtherRevocationInfoFormat {
+    /// Decode the OCSP response ([RFC 5940 Section 4.1]) carried by this revocation information.
+    ///
+    /// Returns `None` if the format is not `id-ri-ocsp-response`.
+    ///
+    /// [RFC 5940 Section 4.1]: https://www.rfc-editor.org/rfc/rfc5940#section-4.1
+    pub fn ocsp_response(&self) -> der::Result<Option<OcspResponse<'_>>> {
+        if self.format() != ID_RI_OCSP_RESPONSE {
+            return Ok(None);
+        }
+
+        self.other.decode_as().map(Some)
+    }
+}
+
+/// Wrap an OCSP response as `id-ri-ocsp-response` revocation information
+#[cfg(feature = "ocsp")]
+impl TryFrom<&OcspResponse<'_>> for OtherRevocationInfoFormat {
+    type Error = der::Error;
+
+    fn try_from(response: &OcspResponse<'_>) -> der::Result<Self> {
+        Ok(OtherRevocationInfoFormat::new(
+            ID_RI_OCSP_RESPONSE,
+            Any::encode_from(response)?,
+        ))
+    }
+}
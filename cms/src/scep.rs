@@ -0,0 +1,215 @@
+//! Helpers for the CA responses of the Simple Certificate Enrolment Protocol (SCEP)
+//! defined in [RFC 8894].
+//!
+//! [RFC 8894]: https://www.rfc-editor.org/rfc/rfc8894
+
+use crate::content_info::ContentInfo;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::{fmt, str::FromStr};
+use der::{Decode, Encode};
+use x509_cert::{Certificate, PkiPath};
+
+/// Media type of a `GetCACert` response carrying a single CA certificate.
+pub const CA_CERT_CONTENT_TYPE: &str = "application/x-x509-ca-cert";
+
+/// Media type of a `GetCACert` response carrying the CA certificate along with RA
+/// certificates, as a degenerate certificates-only `SignedData`.
+pub const CA_RA_CERT_CONTENT_TYPE: &str = "application/x-x509-ca-ra-cert";
+
+/// Media type of a `GetCACaps` response.
+pub const CA_CAPS_CONTENT_TYPE: &str = "text/plain";
+
+/// Response to a `GetCACert` message, as defined in [RFC 8894 Section 4.2.1].
+///
+/// [RFC 8894 Section 4.2.1]: https://www.rfc-editor.org/rfc/rfc8894#section-4.2.1
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum CaCertResponse {
+    /// The CA certificate alone, sent as a DER-encoded certificate.
+    Ca(Certificate),
+
+    /// The CA certificate along with RA or intermediate CA certificates, sent as a
+    /// degenerate certificates-only `SignedData`.
+    CaRa(PkiPath),
+}
+
+impl CaCertResponse {
+    /// Build the response for the given certificates: a single certificate is sent as
+    /// is, several certificates as a certificates-only `SignedData`.
+    pub fn new(mut certs: PkiPath) -> Self {
+        match certs.len() {
+            1 => Self::Ca(certs.remove(0)),
+            _ => Self::CaRa(certs),
+        }
+    }
+
+    /// Get the media type of the response body.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Ca(_) => CA_CERT_CONTENT_TYPE,
+            Self::CaRa(_) => CA_RA_CERT_CONTENT_TYPE,
+        }
+    }
+
+    /// Parse the body of a response with the given media type, as found in the
+    /// `Content-Type` header: parameters such as `charset` are ignored.
+    ///
+    /// The `SignedData` of a [`CA_RA_CERT_CONTENT_TYPE`] response may be BER-encoded.
+    /// Media types other than [`CA_CERT_CONTENT_TYPE`] and [`CA_RA_CERT_CONTENT_TYPE`] are
+    /// rejected with [`der::ErrorKind::Failed`].
+    pub fn from_der(content_type: &str, body: &[u8]) -> der::Result<Self> {
+        let media_type = content_type.split(';').next().unwrap_or_default().trim();
+
+        if media_type.eq_ignore_ascii_case(CA_CERT_CONTENT_TYPE) {
+            Ok(Self::Ca(Certificate::from_der(body)?))
+        } else if media_type.eq_ignore_ascii_case(CA_RA_CERT_CONTENT_TYPE) {
+            Ok(Self::CaRa(PkiPath::try_from(ContentInfo::from_ber(body)?)?))
+        } else {
+            Err(der::ErrorKind::Failed.into())
+        }
+    }
+
+    /// Encode the body of the response.
+    pub fn to_der(&self) -> der::Result<Vec<u8>> {
+        match self {
+            Self::Ca(cert) => cert.to_der(),
+            Self::CaRa(certs) => ContentInfo::try_from(certs.clone())?.to_der(),
+        }
+    }
+
+    /// Get the certificates carried by the response.
+    ///
+    /// The certificates of a certificates-only `SignedData` are ordered following the DER
+    /// ordering of the `SET OF`, not the certification path.
+    pub fn certificates(&self) -> &[Certificate] {
+        match self {
+            Self::Ca(cert) => core::slice::from_ref(cert),
+            Self::CaRa(certs) => certs,
+        }
+    }
+}
+
+/// Capability advertised in response to a `GetCACaps` message, as defined in
+/// [RFC 8894 Section 3.5.2].
+///
+/// [RFC 8894 Section 3.5.2]: https://www.rfc-editor.org/rfc/rfc8894#section-3.5.2
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CaCapability {
+    /// CA supports the AES128-CBC encryption algorithm.
+    Aes,
+
+    /// CA supports the triple DES-CBC encryption algorithm.
+    Des3,
+
+    /// CA supports the `GetNextCACert` message.
+    GetNextCaCert,
+
+    /// CA supports `PKCSReq` messages sent with HTTP POST.
+    PostPkiOperation,
+
+    /// CA supports the `RenewalReq` message.
+    Renewal,
+
+    /// CA supports the SHA-1 hashing algorithm.
+    Sha1,
+
+    /// CA supports the SHA-256 hashing algorithm.
+    Sha256,
+
+    /// CA supports the SHA-512 hashing algorithm.
+    Sha512,
+
+    /// CA supports all capabilities mandated by RFC 8894.
+    ScepStandard,
+
+    /// Capability not known to this library.
+    Other(String),
+}
+
+impl CaCapability {
+    /// Get the keyword of this capability.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Aes => "AES",
+            Self::Des3 => "DES3",
+            Self::GetNextCaCert => "GetNextCACert",
+            Self::PostPkiOperation => "POSTPKIOperation",
+            Self::Renewal => "Renewal",
+            Self::Sha1 => "SHA-1",
+            Self::Sha256 => "SHA-256",
+            Self::Sha512 => "SHA-512",
+            Self::ScepStandard => "SCEPStandard",
+            Self::Other(keyword) => keyword,
+        }
+    }
+}
+
+/// Parse a capability keyword. Keywords are case insensitive.
+impl From<&str> for CaCapability {
+    fn from(keyword: &str) -> Self {
+        [
+            Self::Aes,
+            Self::Des3,
+            Self::GetNextCaCert,
+            Self::PostPkiOperation,
+            Self::Renewal,
+            Self::Sha1,
+            Self::Sha256,
+            Self::Sha512,
+            Self::ScepStandard,
+        ]
+        .into_iter()
+        .find(|capability| capability.as_str().eq_ignore_ascii_case(keyword))
+        .unwrap_or_else(|| Self::Other(keyword.into()))
+    }
+}
+
+impl fmt::Display for CaCapability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Response to a `GetCACaps` message: a list of capabilities, one per line.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CaCaps(pub Vec<CaCapability>);
+
+impl CaCaps {
+    /// Whether the given capability is advertised.
+    ///
+    /// Advertising `SCEPStandard` implies `AES`, `POSTPKIOperation` and `SHA-256`.
+    pub fn supports(&self, capability: &CaCapability) -> bool {
+        self.0.contains(capability)
+            || (matches!(
+                capability,
+                CaCapability::Aes | CaCapability::PostPkiOperation | CaCapability::Sha256
+            ) && self.0.contains(&CaCapability::ScepStandard))
+    }
+}
+
+/// Parse the body of a `GetCACaps` response. Lines are separated by LF or CRLF, and
+/// blank lines are ignored.
+impl FromStr for CaCaps {
+    type Err = core::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(
+            s.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(CaCapability::from)
+                .collect(),
+        ))
+    }
+}
+
+/// Format the body of a `GetCACaps` response, with each capability on its own line.
+impl fmt::Display for CaCaps {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for capability in &self.0 {
+            writeln!(f, "{}", capability)?;
+        }
+        Ok(())
+    }
+}
@@ -1,16 +1,23 @@
 //! SignedData-related types
 
-use crate::cert::{CertificateChoices, IssuerAndSerialNumber};
-use crate::content_info::CmsVersion;
+use crate::cert::{CertificateChoices, IssuerAndSerialNumber, KeyHandle};
+use crate::content_info::{check_implicit_set, CmsVersion};
+use crate::known_oids::{check_known_algorithm, check_known_attributes, OidUsage};
 use crate::revocation::RevocationInfoChoices;
 
-use core::cmp::Ordering;
-use der::asn1::{ObjectIdentifier, OctetString, SetOfVec};
-use der::{Any, Choice, DerOrd, Sequence, ValueOrd};
-use spki::AlgorithmIdentifierOwned;
+use der::asn1::{IntRef, ObjectIdentifier, OctetString, OctetStringRef, SetOfVec};
+use der::{
+    AnyRef, Choice, Decode, DecodeValue, Encode, EncodeValue, FieldName, FixedTag, Header, Length,
+    Reader, Sequence, SliceReader, Tag, ValueOrd, Writer,
+};
+use spki::{AlgorithmIdentifierOwned, AlgorithmIdentifierRef};
 use x509_cert::attr::Attributes;
 use x509_cert::ext::pkix::SubjectKeyIdentifier;
 use x509_cert::impl_newtype;
+use x509_cert::Certificate;
+
+pub use crate::cert::CertificateSet;
+pub use crate::content_info::EncapsulatedContentInfo;
 
 /// The `SignedData` type is defined in [RFC 5652 Section 5.1].
 ///
@@ -48,26 +55,6 @@ pub struct SignedData {
 /// [RFC 5652 Section 5.1]: https://datatracker.ietf.org/doc/html/rfc5652#section-5.1
 pub type DigestAlgorithmIdentifiers = SetOfVec<AlgorithmIdentifierOwned>;
 
-/// CertificateSet structure as defined in [RFC 5652 Section 10.2.3].
-///
-/// ```text
-///   CertificateSet ::= SET OF CertificateChoices
-/// ```
-///
-/// [RFC 5652 Section 10.2.3]: https://datatracker.ietf.org/doc/html/rfc5652#section-10.2.3
-#[derive(Clone, Eq, PartialEq, Debug)]
-pub struct CertificateSet(pub SetOfVec<CertificateChoices>);
-impl_newtype!(CertificateSet, SetOfVec<CertificateChoices>);
-
-#[cfg(feature = "std")]
-impl TryFrom<std::vec::Vec<CertificateChoices>> for CertificateSet {
-    type Error = der::Error;
-
-    fn try_from(vec: std::vec::Vec<CertificateChoices>) -> der::Result<CertificateSet> {
-        Ok(CertificateSet(SetOfVec::try_from(vec)?))
-    }
-}
-
 /// The `SignerInfos` type is defined in [RFC 5652 Section 5.1].
 ///
 /// ```text
@@ -88,25 +75,6 @@ impl TryFrom<std::vec::Vec<SignerInfo>> for SignerInfos {
     }
 }
 
-/// The `EncapsulatedContentInfo` type is defined in [RFC 5652 Section 5.2].
-///
-/// ```text
-///   EncapsulatedContentInfo ::= SEQUENCE {
-///       eContentType       CONTENT-TYPE.&id({ContentSet}),
-///       eContent           [0] EXPLICIT OCTET STRING
-///               ( CONTAINING CONTENT-TYPE.
-///                   &Type({ContentSet}{@eContentType})) OPTIONAL }
-/// ```
-///
-/// [RFC 5652 Section 5.2]: https://www.rfc-editor.org/rfc/rfc5652#section-5.2
-#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
-#[allow(missing_docs)]
-pub struct EncapsulatedContentInfo {
-    pub econtent_type: ObjectIdentifier,
-    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
-    pub econtent: Option<Any>,
-}
-
 /// The `SignerInfo` type is defined in [RFC 5652 Section 5.3].
 ///
 /// ```text
@@ -146,6 +114,13 @@ pub struct SignerInfo {
     pub unsigned_attrs: Option<UnsignedAttributes>,
 }
 
+impl SignerInfo {
+    /// Identifier of the signer's certificate and key.
+    pub fn key_handle(&self) -> KeyHandle<'_> {
+        KeyHandle::from(&self.sid)
+    }
+}
+
 /// The `SignerInfo` type is defined in [RFC 5652 Section 5.3].
 ///
 /// ```text
@@ -164,7 +139,7 @@ pub type SignedAttributes = Attributes;
 /// ```
 ///
 /// [RFC 5652 Section 5.3]: https://datatracker.ietf.org/doc/html/rfc5652#section-5.3
-#[derive(Clone, Debug, Eq, PartialEq, Choice)]
+#[derive(Clone, Debug, Eq, PartialEq, Choice, ValueOrd)]
 #[allow(missing_docs)]
 pub enum SignerIdentifier {
     IssuerAndSerialNumber(IssuerAndSerialNumber),
@@ -173,11 +148,12 @@ pub enum SignerIdentifier {
     SubjectKeyIdentifier(SubjectKeyIdentifier),
 }
 
-// TODO DEFER ValueOrd is not supported for CHOICE types (see new_enum in value_ord.rs)
-impl ValueOrd for SignerIdentifier {
-    fn value_cmp(&self, other: &Self) -> der::Result<Ordering> {
-        use der::Encode;
-        self.to_der()?.der_cmp(&other.to_der()?)
+impl<'a> From<&'a SignerIdentifier> for KeyHandle<'a> {
+    fn from(sid: &'a SignerIdentifier) -> Self {
+        match sid {
+            SignerIdentifier::IssuerAndSerialNumber(iasn) => KeyHandle::IssuerAndSerial(iasn),
+            SignerIdentifier::SubjectKeyIdentifier(skid) => KeyHandle::SubjectKeyId(skid),
+        }
     }
 }
 
@@ -198,3 +174,327 @@ pub type UnsignedAttributes = Attributes;
 ///
 /// [RFC 5652 Section 5.3]: https://datatracker.ietf.org/doc/html/rfc5652#section-5.3
 pub type SignatureValue = OctetString;
+
+impl SignedData {
+    /// Decode a BER-encoded `SignedData` without interpreting it, e.g. to pass it through.
+    ///
+    /// The message is transcoded to DER with [`der::ber_to_der`] before being decoded, as
+    /// with [`ContentInfo::from_ber`][crate::content_info::ContentInfo::from_ber].
+    /// Algorithms and attributes are not checked, so messages using ones unknown to this
+    /// crate are accepted.
+    pub fn from_ber(bytes: &[u8]) -> der::Result<Self> {
+        Self::from_der(&der::ber_to_der(bytes)?)
+    }
+
+    /// Decode a DER-encoded `SignedData`, rejecting BER encodings anywhere inside the message
+    /// as well as algorithms and attributes unknown to this crate.
+    ///
+    /// The encoding is checked as with
+    /// [`ContentInfo::from_der_strict`][crate::content_info::ContentInfo::from_der_strict].
+    /// The OIDs of the digest algorithms, signature algorithms and attribute types must then
+    /// be known to this crate for their field: e.g. a digest algorithm is rejected as a
+    /// signature algorithm. Otherwise [`der::ErrorKind::OidUnknown`] is returned with the path
+    /// of the offending field, e.g. `SignedData.signer_infos[0].signed_attrs[2].oid`.
+    pub fn from_der_strict(bytes: &[u8]) -> der::Result<Self> {
+        der::check_der(bytes)?;
+        SignedDataRef::from_der(bytes)?.check_implicit_sets()?;
+
+        let signed_data = Self::from_der(bytes)?;
+        signed_data.check_known_oids()?;
+        Ok(signed_data)
+    }
+
+    /// Find the certificate of `signer_info` among the certificates carried by this message.
+    ///
+    /// Certificates are matched with [`KeyHandle::matches_certificate`]. The certificate is
+    /// not validated.
+    pub fn signer_certificate(&self, signer_info: &SignerInfo) -> Option<&Certificate> {
+        let key_handle = signer_info.key_handle();
+
+        self.certificates
+            .iter()
+            .flat_map(|certificates| certificates.0.iter())
+            .find_map(|choice| match choice {
+                CertificateChoices::Certificate(cert) if key_handle.matches_certificate(cert) => {
+                    Some(cert)
+                }
+                _ => None,
+            })
+    }
+
+    /// Check that the algorithms and attribute types of this message are known to this crate.
+    fn check_known_oids(&self) -> der::Result<()> {
+        const DIGEST_ALGORITHMS: FieldName = FieldName {
+            type_name: "SignedData",
+            field: "digest_algorithms",
+        };
+        const SIGNER_INFOS: FieldName = FieldName {
+            type_name: "SignedData",
+            field: "signer_infos",
+        };
+        const DIGEST_ALG: FieldName = FieldName {
+            type_name: "SignerInfo",
+            field: "digest_alg",
+        };
+        const SIGNED_ATTRS: FieldName = FieldName {
+            type_name: "SignerInfo",
+            field: "signed_attrs",
+        };
+        const SIGNATURE_ALGORITHM: FieldName = FieldName {
+            type_name: "SignerInfo",
+            field: "signature_algorithm",
+        };
+        const UNSIGNED_ATTRS: FieldName = FieldName {
+            type_name: "SignerInfo",
+            field: "unsigned_attrs",
+        };
+
+        for (index, algorithm) in self.digest_algorithms.iter().enumerate() {
+            check_known_algorithm(algorithm, OidUsage::DigestAlgorithm)
+                .map_err(|err| err.in_element(index).in_field(&DIGEST_ALGORITHMS))?;
+        }
+
+        for (index, signer_info) in self.signer_infos.0.iter().enumerate() {
+            check_known_algorithm(&signer_info.digest_alg, OidUsage::DigestAlgorithm)
+                .map_err(|err| err.in_field(&DIGEST_ALG))
+                .and_then(|_| {
+                    check_known_attributes(signer_info.signed_attrs.as_ref(), &SIGNED_ATTRS)
+                })
+                .and_then(|_| {
+                    check_known_algorithm(
+                        &signer_info.signature_algorithm,
+                        OidUsage::SignatureAlgorithm,
+                    )
+                    .map_err(|err| err.in_field(&SIGNATURE_ALGORITHM))
+                })
+                .and_then(|_| {
+                    check_known_attributes(signer_info.unsigned_attrs.as_ref(), &UNSIGNED_ATTRS)
+                })
+                .map_err(|err| err.in_element(index).in_field(&SIGNER_INFOS))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Borrowed variant of [`SignedData`] which references the input buffer.
+///
+/// Only the fields needed to route a message are decoded: the
+/// [`EncapsulatedContentInfoRef`] and, lazily, the [`SignerInfoRef`]s. The digest algorithms,
+/// certificates and CRLs are kept as their raw encodings, so no allocation takes place.
+///
+/// The content of a [`ContentInfo`][crate::content_info::ContentInfo] can be decoded as a
+/// `SignedDataRef` with [`Any::decode_as`][der::Any::decode_as].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct SignedDataRef<'a> {
+    pub version: CmsVersion,
+    pub digest_algorithms: AnyRef<'a>,
+    pub encap_content_info: EncapsulatedContentInfoRef<'a>,
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", optional = "true")]
+    pub certificates: Option<AnyRef<'a>>,
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    pub crls: Option<AnyRef<'a>>,
+    pub signer_infos: SignerInfosRef<'a>,
+}
+
+impl SignedDataRef<'_> {
+    /// Check that the implicitly tagged `SET OF`s of the message, which are not covered by
+    /// [`der::check_der`], are DER-encoded: the `certificates`, the `crls`, and the
+    /// `signedAttrs` and `unsignedAttrs` of each `SignerInfo`.
+    ///
+    /// The position of errors is relative to the start of the offending `SET OF`.
+    pub(crate) fn check_implicit_sets(&self) -> der::Result<()> {
+        const CERTIFICATES: FieldName = FieldName {
+            type_name: "SignedData",
+            field: "certificates",
+        };
+        const CRLS: FieldName = FieldName {
+            type_name: "SignedData",
+            field: "crls",
+        };
+        const SIGNER_INFOS: FieldName = FieldName {
+            type_name: "SignedData",
+            field: "signer_infos",
+        };
+        const SIGNED_ATTRS: FieldName = FieldName {
+            type_name: "SignerInfo",
+            field: "signed_attrs",
+        };
+        const UNSIGNED_ATTRS: FieldName = FieldName {
+            type_name: "SignerInfo",
+            field: "unsigned_attrs",
+        };
+
+        check_implicit_set(self.certificates).map_err(|err| err.in_field(&CERTIFICATES))?;
+        check_implicit_set(self.crls).map_err(|err| err.in_field(&CRLS))?;
+
+        for (index, signer_info) in self.signer_infos.iter().enumerate() {
+            let signer_info = signer_info?;
+            check_implicit_set(signer_info.signed_attrs)
+                .map_err(|err| err.in_field(&SIGNED_ATTRS))
+                .and_then(|_| {
+                    check_implicit_set(signer_info.unsigned_attrs)
+                        .map_err(|err| err.in_field(&UNSIGNED_ATTRS))
+                })
+                .map_err(|err| err.in_element(index).in_field(&SIGNER_INFOS))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<SignedDataRef<'a>> for SignedData {
+    type Error = der::Error;
+
+    fn try_from(signed_data: SignedDataRef<'a>) -> der::Result<SignedData> {
+        SignedData::from_der(&signed_data.to_der()?)
+    }
+}
+
+/// Borrowed variant of [`EncapsulatedContentInfo`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct EncapsulatedContentInfoRef<'a> {
+    pub econtent_type: ObjectIdentifier,
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    pub econtent: Option<AnyRef<'a>>,
+}
+
+/// Borrowed variant of [`SignerInfos`].
+///
+/// The `SignerInfo`s are decoded on demand by [`SignerInfosRef::iter`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SignerInfosRef<'a> {
+    /// Contents of the `SET OF SignerInfo`.
+    contents: &'a [u8],
+}
+
+impl<'a> SignerInfosRef<'a> {
+    /// Iterate over the [`SignerInfoRef`]s, decoding each of them in turn.
+    pub fn iter(&self) -> SignerInfosRefIter<'a> {
+        SignerInfosRefIter {
+            remaining: self.contents,
+        }
+    }
+}
+
+impl<'a> DecodeValue<'a> for SignerInfosRef<'a> {
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        Ok(Self {
+            contents: reader.read_slice(header.length)?,
+        })
+    }
+}
+
+impl EncodeValue for SignerInfosRef<'_> {
+    fn value_len(&self) -> der::Result<Length> {
+        self.contents.len().try_into()
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        writer.write(self.contents)
+    }
+}
+
+impl FixedTag for SignerInfosRef<'_> {
+    const TAG: Tag = Tag::Set;
+}
+
+impl<'a> IntoIterator for SignerInfosRef<'a> {
+    type Item = der::Result<SignerInfoRef<'a>>;
+    type IntoIter = SignerInfosRefIter<'a>;
+
+    fn into_iter(self) -> SignerInfosRefIter<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator over the [`SignerInfoRef`]s of a [`SignerInfosRef`].
+///
+/// Iteration stops after the first decoding error.
+#[derive(Clone, Debug)]
+pub struct SignerInfosRefIter<'a> {
+    remaining: &'a [u8],
+}
+
+impl<'a> Iterator for SignerInfosRefIter<'a> {
+    type Item = der::Result<SignerInfoRef<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+
+        let result = SliceReader::new(self.remaining).and_then(|mut reader| {
+            let signer_info = SignerInfoRef::decode(&mut reader)?;
+            Ok((signer_info, reader.remaining_len()))
+        });
+
+        match result {
+            Ok((signer_info, remaining_len)) => {
+                let offset = self.remaining.len() - usize::try_from(remaining_len).ok()?;
+                self.remaining = &self.remaining[offset..];
+                Some(Ok(signer_info))
+            }
+            Err(err) => {
+                self.remaining = &[];
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// Borrowed variant of [`SignerInfo`].
+///
+/// The signed and unsigned attributes are kept as their raw encodings. Note that, as with
+/// [`SignerInfo`], the `signedAttrs` are tagged `[0] IMPLICIT`: the octets covered by the
+/// signature are obtained by re-encoding them with the `SET OF` tag.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct SignerInfoRef<'a> {
+    pub version: CmsVersion,
+    pub sid: SignerIdentifierRef<'a>,
+    pub digest_alg: AlgorithmIdentifierRef<'a>,
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", optional = "true")]
+    pub signed_attrs: Option<AnyRef<'a>>,
+    pub signature_algorithm: AlgorithmIdentifierRef<'a>,
+    pub signature: OctetStringRef<'a>,
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    pub unsigned_attrs: Option<AnyRef<'a>>,
+}
+
+impl<'a> TryFrom<SignerInfoRef<'a>> for SignerInfo {
+    type Error = der::Error;
+
+    fn try_from(signer_info: SignerInfoRef<'a>) -> der::Result<SignerInfo> {
+        SignerInfo::from_der(&signer_info.to_der()?)
+    }
+}
+
+/// Borrowed variant of [`SignerIdentifier`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Choice)]
+#[allow(missing_docs)]
+pub enum SignerIdentifierRef<'a> {
+    IssuerAndSerialNumber(IssuerAndSerialNumberRef<'a>),
+
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT")]
+    SubjectKeyIdentifier(OctetStringRef<'a>),
+}
+
+impl<'a> TryFrom<SignerIdentifierRef<'a>> for SignerIdentifier {
+    type Error = der::Error;
+
+    fn try_from(sid: SignerIdentifierRef<'a>) -> der::Result<SignerIdentifier> {
+        SignerIdentifier::from_der(&sid.to_der()?)
+    }
+}
+
+/// Borrowed variant of [`IssuerAndSerialNumber`], with the issuer `Name` kept as its raw
+/// encoding.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct IssuerAndSerialNumberRef<'a> {
+    pub issuer: AnyRef<'a>,
+    pub serial_number: IntRef<'a>,
+}
@@ -0,0 +1,350 @@
+//! Signature verification of `SignedData`
+
+use crate::attr::{SignedAttributesExt, SigningTimePolicy, SigningTimeViolation};
+use crate::signed_data::{SignedData, SignerInfo};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use const_oid::ObjectIdentifier;
+use core::fmt;
+use der::oid::db::DB;
+use der::DateTime;
+use sha2::digest::Digest;
+use signature::digest::DynDigest;
+use signature::{SignatureEncoding, Verifier};
+use spki::AlgorithmIdentifierOwned;
+
+/// Error type
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors.
+    Asn1(der::Error),
+
+    /// Verification error propagated for the [`signature::Verifier`] type.
+    Signature(signature::Error),
+
+    /// The digest algorithm of the signer is not supported.
+    UnsupportedDigestAlgorithm(ObjectIdentifier),
+
+    /// The message is signed over detached content which was not provided.
+    MissingContent,
+
+    /// The content-type attribute is missing or doesn't match the `eContentType`.
+    ContentTypeMismatch,
+
+    /// The message-digest attribute is missing or doesn't match the digest of the content.
+    MessageDigestMismatch,
+
+    /// No verifying key was found for the signer.
+    UnknownSigner,
+
+    /// The signing-time attribute violates the [`SigningTimePolicy`].
+    SigningTime(Vec<SigningTimeViolation>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::Signature(err) => write!(f, "signature error: {}", err),
+            Error::UnsupportedDigestAlgorithm(oid) => {
+                write!(f, "unsupported digest algorithm: {}", oid)
+            }
+            Error::MissingContent => write!(f, "detached content is missing"),
+            Error::ContentTypeMismatch => write!(f, "content-type attribute mismatch"),
+            Error::MessageDigestMismatch => write!(f, "message-digest attribute mismatch"),
+            Error::UnknownSigner => write!(f, "no verifying key found for the signer"),
+            Error::SigningTime(violations) => {
+                write!(f, "signing-time attribute violates the policy: ")?;
+                for (i, violation) in violations.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", violation)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(err: der::Error) -> Error {
+        Error::Asn1(err)
+    }
+}
+
+impl From<signature::Error> for Error {
+    fn from(err: signature::Error) -> Error {
+        Error::Signature(err)
+    }
+}
+
+type Result<T> = core::result::Result<T, Error>;
+
+impl SignedData {
+    /// Verify the signature of `signer_info` over this message, as described in
+    /// [RFC 5652 Section 5.6].
+    ///
+    /// If the signer info carries signed attributes, the content-type and message-digest
+    /// attributes are checked against the content, and the signature is verified over the
+    /// DER encoding of the signed attributes. Otherwise the signature is verified over the
+    /// content itself.
+    ///
+    /// `detached_content` is only used if the message doesn't encapsulate its content.
+    ///
+    /// The signing-time attribute of the signer, if present, is checked against
+    /// `signing_time_policy`, or the default policy if `None`, and violations are returned as
+    /// [`Error::SigningTime`]. In particular, the signing time must not follow `time`, the
+    /// verification time. The system time is never consulted. The certificate of the signer
+    /// given to the policy is found among the certificates carried by the message with
+    /// [`SignedData::signer_certificate`], i.e. by matching the signer identifier with a
+    /// [`KeyHandle`][`crate::cert::KeyHandle`].
+    ///
+    /// Matching the key with the signer identifier and the signature algorithm, as well as
+    /// validating the signer's certification path, including the validity of its certificates
+    /// at `time`, are left to the caller.
+    ///
+    /// [RFC 5652 Section 5.6]: https://www.rfc-editor.org/rfc/rfc5652#section-5.6
+    pub fn verify_signer_info<V, Sig>(
+        &self,
+        signer_info: &SignerInfo,
+        verifying_key: &V,
+        detached_content: Option<&[u8]>,
+        time: DateTime,
+        signing_time_policy: Option<&SigningTimePolicy>,
+    ) -> Result<()>
+    where
+        V: Verifier<Sig>,
+        Sig: SignatureEncoding,
+    {
+        let default_policy = SigningTimePolicy::default();
+        let violations = signing_time_policy.unwrap_or(&default_policy).check(
+            signer_info,
+            self.signer_certificate(signer_info),
+            time,
+        )?;
+        if !violations.is_empty() {
+            return Err(Error::SigningTime(violations));
+        }
+
+        let content = self
+            .encap_content_info
+            .econtent_digest_input()
+            .or(detached_content)
+            .ok_or(Error::MissingContent)?;
+
+        let signature =
+            Sig::try_from(signer_info.signature.as_bytes()).map_err(|_| signature::Error::new())?;
+
+        match &signer_info.signed_attrs {
+            Some(signed_attrs) => {
+                if signed_attrs.content_type()? != Some(self.encap_content_info.econtent_type) {
+                    return Err(Error::ContentTypeMismatch);
+                }
+
+                let mut hasher = get_hasher(&signer_info.digest_alg).ok_or(
+                    Error::UnsupportedDigestAlgorithm(signer_info.digest_alg.oid),
+                )?;
+                hasher.update(content);
+                let message_digest = signed_attrs
+                    .message_digest()?
+                    .ok_or(Error::MessageDigestMismatch)?;
+                if message_digest.as_bytes() != &*hasher.finalize() {
+                    return Err(Error::MessageDigestMismatch);
+                }
+
+                Ok(verifying_key.verify(&signed_attrs.to_der_for_signing()?, &signature)?)
+            }
+            None => Ok(verifying_key.verify(content, &signature)?),
+        }
+    }
+
+    /// Verify the signatures of all signer infos of this message, with the verifying key
+    /// returned by `find_key` for each of them.
+    ///
+    /// The results are in the order of the signer infos. Signers for which `find_key`
+    /// returns `None` fail with [`Error::UnknownSigner`]. See
+    /// [`SignedData::verify_signer_info`] for the use of `time` and `signing_time_policy`.
+    pub fn verify_signer_infos<V, Sig, F>(
+        &self,
+        detached_content: Option<&[u8]>,
+        time: DateTime,
+        signing_time_policy: Option<&SigningTimePolicy>,
+        mut find_key: F,
+    ) -> Vec<Result<()>>
+    where
+        V: Verifier<Sig>,
+        Sig: SignatureEncoding,
+        F: FnMut(&SignerInfo) -> Option<V>,
+    {
+        self.signer_infos
+            .0
+            .iter()
+            .map(|signer_info| {
+                let verifying_key = find_key(signer_info).ok_or(Error::UnknownSigner)?;
+                self.verify_signer_info(
+                    signer_info,
+                    &verifying_key,
+                    detached_content,
+                    time,
+                    signing_time_policy,
+                )
+            })
+            .collect()
+    }
+
+    /// Verify the signature of `signer_info` over this message at the current system time.
+    ///
+    /// Behaves like [`SignedData::verify_signer_info`] with the time read from
+    /// [`std::time::SystemTime::now`].
+    #[cfg(all(
+        feature = "std",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    pub fn verify_signer_info_now<V, Sig>(
+        &self,
+        signer_info: &SignerInfo,
+        verifying_key: &V,
+        detached_content: Option<&[u8]>,
+        signing_time_policy: Option<&SigningTimePolicy>,
+    ) -> Result<()>
+    where
+        V: Verifier<Sig>,
+        Sig: SignatureEncoding,
+    {
+        self.verify_signer_info(
+            signer_info,
+            verifying_key,
+            detached_content,
+            now()?,
+            signing_time_policy,
+        )
+    }
+
+    /// Verify the signatures of all signer infos of this message at the current system time.
+    ///
+    /// Behaves like [`SignedData::verify_signer_infos`] with the time read from
+    /// [`std::time::SystemTime::now`].
+    #[cfg(all(
+        feature = "std",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    pub fn verify_signer_infos_now<V, Sig, F>(
+        &self,
+        detached_content: Option<&[u8]>,
+        signing_time_policy: Option<&SigningTimePolicy>,
+        find_key: F,
+    ) -> Vec<Result<()>>
+    where
+        V: Verifier<Sig>,
+        Sig: SignatureEncoding,
+        F: FnMut(&SignerInfo) -> Option<V>,
+    {
+        match now() {
+            Ok(time) => {
+                self.verify_signer_infos(detached_content, time, signing_time_policy, find_key)
+            }
+            Err(err) => self
+                .signer_infos
+                .0
+                .iter()
+                .map(|_| Err(err.into()))
+                .collect(),
+        }
+    }
+
+    /// Verify the signatures of all signer infos of this message concurrently.
+    ///
+    /// Behaves like [`SignedData::verify_signer_infos`], with `find_key` called from the
+    /// threads of the [`rayon`] global thread pool.
+    #[cfg(feature = "rayon")]
+    pub fn par_verify_signer_infos<V, Sig, F>(
+        &self,
+        detached_content: Option<&[u8]>,
+        time: DateTime,
+        signing_time_policy: Option<&SigningTimePolicy>,
+        find_key: F,
+    ) -> Vec<Result<()>>
+    where
+        V: Verifier<Sig>,
+        Sig: SignatureEncoding,
+        F: Fn(&SignerInfo) -> Option<V> + Sync,
+    {
+        use rayon::prelude::*;
+
+        self.signer_infos
+            .0
+            .as_slice()
+            .par_iter()
+            .map(|signer_info| {
+                let verifying_key = find_key(signer_info).ok_or(Error::UnknownSigner)?;
+                self.verify_signer_info(
+                    signer_info,
+                    &verifying_key,
+                    detached_content,
+                    time,
+                    signing_time_policy,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Verify the signatures of a batch of messages concurrently, with the verifying key returned
+/// by `find_key` for each signer info.
+///
+/// All the signer infos of all the messages are verified on the [`rayon`] global thread pool.
+/// The results are grouped by message, in the order of `messages`, and then in the order of
+/// their signer infos. Messages must encapsulate their content.
+#[cfg(feature = "rayon")]
+pub fn par_verify_all<V, Sig, F>(
+    messages: &[SignedData],
+    time: DateTime,
+    signing_time_policy: Option<&SigningTimePolicy>,
+    find_key: F,
+) -> Vec<Vec<Result<()>>>
+where
+    V: Verifier<Sig>,
+    Sig: SignatureEncoding,
+    F: Fn(&SignedData, &SignerInfo) -> Option<V> + Sync,
+{
+    use rayon::prelude::*;
+
+    messages
+        .par_iter()
+        .map(|message| {
+            message.par_verify_signer_infos(None, time, signing_time_policy, |si| {
+                find_key(message, si)
+            })
+        })
+        .collect()
+}
+
+/// Current system time, for the verification functions which don't take it explicitly.
+#[cfg(all(
+    feature = "std",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+fn now() -> der::Result<DateTime> {
+    DateTime::from_system_time(std::time::SystemTime::now())
+}
+
+/// Get a hasher for a given digest algorithm
+pub(crate) fn get_hasher(
+    digest_algorithm_identifier: &AlgorithmIdentifierOwned,
+) -> Option<Box<dyn DynDigest>> {
+    let digest_name = DB.by_oid(&digest_algorithm_identifier.oid)?;
+    match digest_name {
+        "id-sha1" => Some(Box::new(sha1::Sha1::new())),
+        "id-sha256" => Some(Box::new(sha2::Sha256::new())),
+        "id-sha384" => Some(Box::new(sha2::Sha384::new())),
+        "id-sha512" => Some(Box::new(sha2::Sha512::new())),
+        "id-sha224" => Some(Box::new(sha2::Sha224::new())),
+        "id-sha-3-224" => Some(Box::new(sha3::Sha3_224::new())),
+        "id-sha-3-256" => Some(Box::new(sha3::Sha3_256::new())),
+        "id-sha-3-384" => Some(Box::new(sha3::Sha3_384::new())),
+        "id-sha-3-512" => Some(Box::new(sha3::Sha3_512::new())),
+        _ => None,
+    }
+}
@@ -0,0 +1,306 @@
+//! Signed and unsigned attribute tests
+
+#![cfg(feature = "signed")]
+
+use core::time::Duration;
+
+use cms::attr::{
+    signing_time_from_date_time, SignedAttributesExt, SigningTimePolicy, SigningTimeViolation,
+    UnsignedAttributesExt,
+};
+use cms::cert::CertificateChoices;
+use cms::content_info::ContentInfo;
+use cms::signed_data::{SignedAttributes, SignedData, SignerInfo, UnsignedAttributes};
+use const_oid::db::rfc5911::{ID_CONTENT_TYPE, ID_DATA, ID_SIGNED_DATA, ID_SIGNING_TIME};
+use der::asn1::{GeneralizedTime, SetOfVec, UtcTime};
+use der::{Any, DateTime, Decode, Encode};
+use hex_literal::hex;
+use x509_cert::attr::Attribute;
+use x509_cert::time::Time;
+use x509_cert::Certificate;
+
+fn signer_info() -> SignerInfo {
+    let ci = ContentInfo::from_der(include_bytes!("examples/sd.cms")).unwrap();
+    let sd = SignedData::from_der(&ci.content.to_der().unwrap()).unwrap();
+    sd.signer_infos.0.get(0).unwrap().clone()
+}
+
+fn signed_attrs() -> SignedAttributes {
+    signer_info().signed_attrs.unwrap()
+}
+
+#[test]
+fn read_signed_attributes() {
+    let attrs = signed_attrs();
+
+    assert_eq!(attrs.content_type().unwrap(), Some(ID_DATA));
+    assert_eq!(
+        attrs.message_digest().unwrap().unwrap().as_bytes(),
+        hex!("7202ffa8978c95f731c6d2e7b1b6a25e706561c05be24894ec7c1e358a469ff1")
+    );
+
+    let signing_time = attrs.signing_time().unwrap().unwrap();
+    assert!(matches!(signing_time, Time::UtcTime(_)));
+    assert_eq!(signing_time.to_unix_duration().as_secs(), 1666110050);
+}
+
+#[test]
+fn signed_attributes_for_signing() {
+    let signer_info = signer_info();
+    let signer_info_der = signer_info.to_der().unwrap();
+    let signed_attrs_der = signer_info
+        .signed_attrs
+        .unwrap()
+        .to_der_for_signing()
+        .unwrap();
+
+    // Same encoding as the `signedAttrs` of the `SignerInfo`, except for the tag
+    assert_eq!(signed_attrs_der[0], 0x31);
+    let offset = signer_info_der
+        .windows(signed_attrs_der.len() - 1)
+        .position(|window| window == &signed_attrs_der[1..])
+        .unwrap();
+    assert_eq!(signer_info_der[offset - 1], 0xA0);
+    assert_eq!(
+        SignedAttributes::from_der(&signed_attrs_der).unwrap(),
+        signed_attrs()
+    );
+}
+
+#[test]
+fn absent_signed_attributes() {
+    let attrs = SignedAttributes::new();
+
+    assert_eq!(attrs.content_type().unwrap(), None);
+    assert_eq!(attrs.message_digest().unwrap(), None);
+    assert_eq!(attrs.signing_time().unwrap(), None);
+}
+
+#[test]
+fn multi_valued_content_type_rejected() {
+    let attr = Attribute {
+        oid: ID_CONTENT_TYPE,
+        values: SetOfVec::try_from([
+            Any::encode_from(&ID_DATA).unwrap(),
+            Any::encode_from(&ID_SIGNED_DATA).unwrap(),
+        ])
+        .unwrap(),
+    };
+    let attrs = SignedAttributes::try_from([attr]).unwrap();
+
+    assert!(attrs.content_type().is_err());
+}
+
+#[test]
+fn set_signed_attributes() {
+    let mut attrs = SignedAttributes::new();
+    let signing_time =
+        Time::UtcTime(UtcTime::from_unix_duration(Duration::from_secs(1666110050)).unwrap());
+
+    attrs.set_message_digest(&[0x42; 32]).unwrap();
+    attrs.set_signing_time(signing_time).unwrap();
+    attrs.set_content_type(ID_DATA).unwrap();
+
+    assert_eq!(attrs.len(), 3);
+    assert_eq!(attrs.content_type().unwrap(), Some(ID_DATA));
+    assert_eq!(
+        attrs.message_digest().unwrap().unwrap().as_bytes(),
+        &[0x42; 32]
+    );
+    assert_eq!(attrs.signing_time().unwrap(), Some(signing_time));
+
+    // the set must remain DER-ordered so that it round-trips
+    let encoded = attrs.to_der().unwrap();
+    assert_eq!(SignedAttributes::from_der(&encoded).unwrap(), attrs);
+}
+
+#[test]
+fn signing_time_encoding() {
+    let mut attrs = SignedAttributes::new();
+
+    // 2049-12-31T23:59:59Z is the last instant encoded as UTCTime
+    let last_utc = DateTime::new(2049, 12, 31, 23, 59, 59).unwrap();
+    let time = Time::GeneralTime(GeneralizedTime::from_date_time(last_utc));
+    attrs.set_signing_time(time).unwrap();
+    let signing_time = attrs.signing_time().unwrap().unwrap();
+    assert!(matches!(signing_time, Time::UtcTime(_)));
+    assert_eq!(signing_time.to_date_time(), last_utc);
+
+    let first_generalized = DateTime::new(2050, 1, 1, 0, 0, 0).unwrap();
+    assert!(matches!(
+        signing_time_from_date_time(first_generalized).unwrap(),
+        Time::GeneralTime(_)
+    ));
+
+    // GeneralizedTime values are accepted on decode, even when UTCTime would be required
+    let attr = Attribute {
+        oid: ID_SIGNING_TIME,
+        values: SetOfVec::try_from([Any::encode_from(&time).unwrap()]).unwrap(),
+    };
+    let attrs = SignedAttributes::try_from([attr]).unwrap();
+    assert_eq!(attrs.signing_time().unwrap(), Some(time));
+}
+
+#[test]
+fn replace_signed_attributes() {
+    let mut attrs = signed_attrs();
+    let len = attrs.len();
+
+    attrs.set_content_type(ID_SIGNED_DATA).unwrap();
+    attrs.set_message_digest(&[0; 32]).unwrap();
+
+    assert_eq!(attrs.len(), len);
+    assert_eq!(attrs.content_type().unwrap(), Some(ID_SIGNED_DATA));
+    assert_eq!(
+        attrs.message_digest().unwrap().unwrap().as_bytes(),
+        &[0; 32]
+    );
+
+    // untouched attributes are retained
+    assert_eq!(
+        attrs.signing_time().unwrap(),
+        signed_attrs().signing_time().unwrap()
+    );
+}
+
+#[test]
+fn countersignatures() {
+    let countersignature = signer_info();
+    let mut attrs = UnsignedAttributes::new();
+    assert!(attrs.countersignatures().unwrap().is_empty());
+
+    attrs.add_countersignature(&countersignature).unwrap();
+    assert_eq!(attrs.len(), 1);
+    assert_eq!(attrs.countersignatures().unwrap().len(), 1);
+    assert_eq!(attrs.countersignatures().unwrap()[0], countersignature);
+
+    // a second, distinct countersignature is added to the existing attribute
+    let mut other = countersignature.clone();
+    other.signed_attrs = None;
+    attrs.add_countersignature(&other).unwrap();
+    assert_eq!(attrs.len(), 1);
+
+    let countersignatures = attrs.countersignatures().unwrap();
+    assert_eq!(countersignatures.len(), 2);
+    assert!(countersignatures.contains(&countersignature));
+    assert!(countersignatures.contains(&other));
+
+    let encoded = attrs.to_der().unwrap();
+    assert_eq!(UnsignedAttributes::from_der(&encoded).unwrap(), attrs);
+}
+
+fn signer_cert() -> Certificate {
+    let ci = ContentInfo::from_der(include_bytes!("examples/sd.cms")).unwrap();
+    let sd = SignedData::from_der(&ci.content.to_der().unwrap()).unwrap();
+    match sd.certificates.unwrap().0.get(0).unwrap() {
+        CertificateChoices::Certificate(cert) => cert.clone(),
+        _ => panic!("expected a certificate"),
+    }
+}
+
+fn utc_time(secs: u64) -> Time {
+    Time::UtcTime(UtcTime::from_unix_duration(Duration::from_secs(secs)).unwrap())
+}
+
+/// Verification time following all the signing times of these tests.
+fn verification_time() -> DateTime {
+    DateTime::from_unix_duration(Duration::from_secs(1800000000)).unwrap()
+}
+
+#[test]
+fn signing_time_policy_accepts() {
+    let policy = SigningTimePolicy {
+        within_signer_validity: true,
+        not_before: Some(utc_time(1666110000)),
+        not_after: Some(utc_time(1666110100)),
+    };
+
+    let violations = policy
+        .check(&signer_info(), Some(&signer_cert()), verification_time())
+        .unwrap();
+    assert!(violations.is_empty());
+}
+
+#[test]
+fn signing_time_policy_bounds() {
+    let policy = SigningTimePolicy {
+        within_signer_validity: false,
+        not_before: Some(utc_time(1666110100)),
+        not_after: Some(utc_time(1666110000)),
+    };
+
+    let violations = policy
+        .check(&signer_info(), Some(&signer_cert()), verification_time())
+        .unwrap();
+    assert_eq!(
+        violations,
+        [
+            SigningTimeViolation::BeforeLowerBound,
+            SigningTimeViolation::AfterUpperBound
+        ]
+    );
+}
+
+#[test]
+fn signing_time_policy_signer_validity() {
+    let policy = SigningTimePolicy {
+        within_signer_validity: true,
+        ..Default::default()
+    };
+
+    // signing time after the signer certificate expired
+    let mut signer_info = signer_info();
+    let mut attrs = signer_info.signed_attrs.take().unwrap();
+    attrs.set_signing_time(utc_time(1700000000)).unwrap();
+    signer_info.signed_attrs = Some(attrs);
+
+    let violations = policy
+        .check(&signer_info, Some(&signer_cert()), verification_time())
+        .unwrap();
+    assert_eq!(violations, [SigningTimeViolation::AfterSignerValidity]);
+}
+
+#[test]
+fn signing_time_policy_verification_time() {
+    let policy = SigningTimePolicy::default();
+    let time = DateTime::from_unix_duration(Duration::from_secs(1666110000)).unwrap();
+
+    let violations = policy.check(&signer_info(), None, time).unwrap();
+    assert_eq!(violations, [SigningTimeViolation::AfterVerificationTime]);
+}
+
+#[test]
+fn signing_time_policy_absent() {
+    let policy = SigningTimePolicy {
+        within_signer_validity: true,
+        not_before: Some(utc_time(1700000000)),
+        not_after: None,
+    };
+
+    let mut signer_info = signer_info();
+    signer_info.signed_attrs = None;
+
+    let violations = policy
+        .check(&signer_info, Some(&signer_cert()), verification_time())
+        .unwrap();
+    assert!(violations.is_empty());
+}
+
+#[test]
+fn signing_time_policy_unknown_signer() {
+    let policy = SigningTimePolicy {
+        within_signer_validity: true,
+        ..Default::default()
+    };
+
+    let violations = policy
+        .check(&signer_info(), None, verification_time())
+        .unwrap();
+    assert_eq!(violations, [SigningTimeViolation::UnknownSignerValidity]);
+
+    let policy = SigningTimePolicy::default();
+    assert!(policy
+        .check(&signer_info(), None, verification_time())
+        .unwrap()
+        .is_empty());
+}
@@ -1,16 +1,18 @@
 #![cfg(feature = "builder")]
 
+use cms::attr::{CmsAlgorithmProtection, ID_AA_CMS_ALGORITHM_PROTECTION};
 use cms::builder::{create_signing_time_attribute, SignedDataBuilder, SignerInfoBuilder};
 use cms::cert::{CertificateChoices, IssuerAndSerialNumber};
-use cms::signed_data::{EncapsulatedContentInfo, SignerIdentifier};
+use cms::signed_data::{EncapsulatedContentInfo, SignedData, SignerIdentifier};
 use der::asn1::{OctetString, SetOfVec, Utf8StringRef};
-use der::{Any, DecodePem, Encode, Tag, Tagged};
+use der::{Any, Decode, DecodePem, Encode, Tag, Tagged};
 use p256::{pkcs8::DecodePrivateKey, NistP256};
 use pem_rfc7468::LineEnding;
 use rsa::pkcs1::DecodeRsaPrivateKey;
 use rsa::pkcs1v15::SigningKey;
 use sha2::Sha256;
-use spki::AlgorithmIdentifierOwned;
+use signature::Verifier;
+use spki::{AlgorithmIdentifierOwned, DynSignatureAlgorithmIdentifier};
 use x509_cert::attr::{Attribute, AttributeTypeAndValue};
 use x509_cert::name::{RdnSequence, RelativeDistinguishedName};
 use x509_cert::serial_number::SerialNumber;
@@ -118,6 +120,287 @@ fn test_build_signed_data() {
     );
 }
 
+#[test]
+fn test_migrate_signer_info() {
+    let content = EncapsulatedContentInfo {
+        econtent_type: const_oid::db::rfc5911::ID_DATA,
+        econtent: Some(
+            Any::new(
+                Tag::OctetString,
+                OctetString::new(vec![48]).unwrap().to_der().unwrap(),
+            )
+            .unwrap(),
+        ),
+    };
+    let sha256 = AlgorithmIdentifierOwned {
+        oid: const_oid::db::rfc5912::ID_SHA_256,
+        parameters: None,
+    };
+    let sha512 = AlgorithmIdentifierOwned {
+        oid: const_oid::db::rfc5912::ID_SHA_512,
+        parameters: None,
+    };
+    let custom_attribute = Attribute {
+        oid: const_oid::db::rfc5911::ID_AA_CONTENT_HINT,
+        values: SetOfVec::try_from(vec![Any::from(Utf8StringRef::new("archive").unwrap())])
+            .unwrap(),
+    };
+
+    let signer = rsa_signer();
+    let mut signer_info_builder = SignerInfoBuilder::new(
+        &signer,
+        signer_identifier(1),
+        sha256.clone(),
+        &content,
+        None,
+    )
+    .unwrap();
+    signer_info_builder
+        .add_signed_attribute(custom_attribute.clone())
+        .unwrap();
+    let signed_data = SignedDataBuilder::new(&content)
+        .add_digest_algorithm(sha256.clone())
+        .unwrap()
+        .add_signer_info::<SigningKey<Sha256>, rsa::pkcs1v15::Signature>(signer_info_builder)
+        .unwrap()
+        .build()
+        .unwrap()
+        .content
+        .decode_as::<SignedData>()
+        .unwrap();
+    let previous = signed_data.signer_infos.0.get(0).unwrap();
+
+    let signer_2 = ecdsa_signer();
+    let signer_info_builder_2 = SignerInfoBuilder::new(
+        &signer_2,
+        signer_identifier(2),
+        sha512.clone(),
+        &signed_data.encap_content_info,
+        None,
+    )
+    .unwrap();
+    let migrated = SignedDataBuilder::from_signed_data(&signed_data)
+        .add_migrated_signer_info::<ecdsa::SigningKey<NistP256>, p256::ecdsa::DerSignature>(
+            previous,
+            signer_info_builder_2,
+            &|_| true,
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .content
+        .decode_as::<SignedData>()
+        .unwrap();
+
+    assert_eq!(migrated.encap_content_info, signed_data.encap_content_info);
+    assert_eq!(migrated.digest_algorithms.len(), 2);
+    assert!(migrated.digest_algorithms.iter().any(|alg| alg == &sha512));
+    assert_eq!(migrated.signer_infos.0.len(), 2);
+    assert!(migrated.signer_infos.0.iter().any(|si| si == previous));
+
+    let signer_info = migrated
+        .signer_infos
+        .0
+        .iter()
+        .find(|si| si.sid == signer_identifier(2))
+        .unwrap();
+    assert_eq!(signer_info.digest_alg, sha512);
+    let signed_attrs = signer_info.signed_attrs.as_ref().unwrap();
+    assert!(signed_attrs.iter().any(|attr| attr == &custom_attribute));
+    let message_digest = signed_attrs
+        .iter()
+        .find(|attr| attr.oid == const_oid::db::rfc5911::ID_MESSAGE_DIGEST)
+        .unwrap();
+    assert_eq!(message_digest.values.len(), 1);
+    assert_eq!(message_digest.values.get(0).unwrap().value().len(), 64);
+
+    let signature = p256::ecdsa::DerSignature::try_from(signer_info.signature.as_bytes()).unwrap();
+    ecdsa::VerifyingKey::from(&signer_2)
+        .verify(&signed_attrs.to_der().unwrap(), &signature)
+        .unwrap();
+}
+
+#[test]
+fn test_migrate_signer_info_bound_attributes() {
+    let content = EncapsulatedContentInfo {
+        econtent_type: const_oid::db::rfc5911::ID_DATA,
+        econtent: Some(Any::new(Tag::OctetString, vec![0x04, 0x01, 0x30]).unwrap()),
+    };
+    let sha256 = AlgorithmIdentifierOwned {
+        oid: const_oid::db::rfc5912::ID_SHA_256,
+        parameters: None,
+    };
+    let sha512 = AlgorithmIdentifierOwned {
+        oid: const_oid::db::rfc5912::ID_SHA_512,
+        parameters: None,
+    };
+    let content_hint = Attribute {
+        oid: const_oid::db::rfc5911::ID_AA_CONTENT_HINT,
+        values: SetOfVec::try_from(vec![Any::from(Utf8StringRef::new("archive").unwrap())])
+            .unwrap(),
+    };
+    let content_identifier = Attribute {
+        oid: const_oid::db::rfc5911::ID_AA_CONTENT_IDENTIFIER,
+        values: SetOfVec::try_from(vec![Any::new(Tag::OctetString, vec![1, 2, 3]).unwrap()])
+            .unwrap(),
+    };
+    // SigningCertificateV2 with a single ESSCertIDv2 holding a SHA-256 certificate hash
+    let signing_certificate = Attribute {
+        oid: const_oid::db::rfc5911::ID_AA_SIGNING_CERTIFICATE_V_2,
+        values: SetOfVec::try_from(vec![Any::from_der(
+            &[
+                &[0x30, 0x26, 0x30, 0x24, 0x30, 0x22, 0x04, 0x20][..],
+                &[0xab; 32],
+            ]
+            .concat(),
+        )
+        .unwrap()])
+        .unwrap(),
+    };
+    let algorithm_protection = |protection: CmsAlgorithmProtection| Attribute {
+        oid: ID_AA_CMS_ALGORITHM_PROTECTION,
+        values: SetOfVec::try_from(vec![Any::encode_from(&protection).unwrap()]).unwrap(),
+    };
+
+    let signer = rsa_signer();
+    let mut signer_info_builder = SignerInfoBuilder::new(
+        &signer,
+        signer_identifier(1),
+        sha256.clone(),
+        &content,
+        None,
+    )
+    .unwrap();
+    signer_info_builder
+        .add_signed_attribute(content_hint.clone())
+        .unwrap()
+        .add_signed_attribute(content_identifier.clone())
+        .unwrap()
+        .add_signed_attribute(signing_certificate.clone())
+        .unwrap()
+        .add_signed_attribute(algorithm_protection(CmsAlgorithmProtection {
+            digest_algorithm: sha256.clone(),
+            signature_algorithm: Some(signer.signature_algorithm_identifier().unwrap()),
+            mac_algorithm: None,
+        }))
+        .unwrap();
+    let signed_data = SignedDataBuilder::new(&content)
+        .add_digest_algorithm(sha256)
+        .unwrap()
+        .add_signer_info::<SigningKey<Sha256>, rsa::pkcs1v15::Signature>(signer_info_builder)
+        .unwrap()
+        .build()
+        .unwrap()
+        .content
+        .decode_as::<SignedData>()
+        .unwrap();
+
+    let signer_2 = ecdsa_signer();
+    let signer_info_builder_2 = SignerInfoBuilder::new(
+        &signer_2,
+        signer_identifier(2),
+        sha512.clone(),
+        &signed_data.encap_content_info,
+        None,
+    )
+    .unwrap();
+    let migrated = SignedDataBuilder::from_signed_data(&signed_data)
+        .add_migrated_signer_info::<ecdsa::SigningKey<NistP256>, p256::ecdsa::DerSignature>(
+            signed_data.signer_infos.0.get(0).unwrap(),
+            signer_info_builder_2,
+            &|attr| attr.oid != const_oid::db::rfc5911::ID_AA_CONTENT_IDENTIFIER,
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .content
+        .decode_as::<SignedData>()
+        .unwrap();
+
+    let signer_info = migrated
+        .signer_infos
+        .0
+        .iter()
+        .find(|si| si.sid == signer_identifier(2))
+        .unwrap();
+    let signed_attrs = signer_info.signed_attrs.as_ref().unwrap();
+    assert!(signed_attrs.iter().any(|attr| attr == &content_hint));
+    assert!(!signed_attrs.iter().any(|attr| attr == &content_identifier));
+    assert!(!signed_attrs
+        .iter()
+        .any(|attr| attr.oid == signing_certificate.oid));
+
+    let protection = signed_attrs
+        .iter()
+        .filter(|attr| attr.oid == ID_AA_CMS_ALGORITHM_PROTECTION)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        protection,
+        [&algorithm_protection(CmsAlgorithmProtection {
+            digest_algorithm: sha512,
+            signature_algorithm: Some(signer_info.signature_algorithm.clone()),
+            mac_algorithm: None,
+        })]
+    );
+    assert_eq!(
+        signer_info.signature_algorithm,
+        signer_2.signature_algorithm_identifier().unwrap()
+    );
+}
+
+#[test]
+fn test_migrate_signer_info_other_content() {
+    let content = EncapsulatedContentInfo {
+        econtent_type: const_oid::db::rfc5911::ID_DATA,
+        econtent: Some(Any::new(Tag::OctetString, vec![0x04, 0x01, 0x30]).unwrap()),
+    };
+    let other_content = EncapsulatedContentInfo {
+        econtent_type: const_oid::db::rfc5911::ID_DATA,
+        econtent: Some(Any::new(Tag::OctetString, vec![0x04, 0x01, 0x31]).unwrap()),
+    };
+    let sha256 = AlgorithmIdentifierOwned {
+        oid: const_oid::db::rfc5912::ID_SHA_256,
+        parameters: None,
+    };
+
+    let signer = rsa_signer();
+    let signer_info_builder = SignerInfoBuilder::new(
+        &signer,
+        signer_identifier(1),
+        sha256.clone(),
+        &content,
+        None,
+    )
+    .unwrap();
+    let signed_data = SignedDataBuilder::new(&content)
+        .add_digest_algorithm(sha256.clone())
+        .unwrap()
+        .add_signer_info::<SigningKey<Sha256>, rsa::pkcs1v15::Signature>(signer_info_builder)
+        .unwrap()
+        .build()
+        .unwrap()
+        .content
+        .decode_as::<SignedData>()
+        .unwrap();
+
+    let signer_2 = ecdsa_signer();
+    let signer_info_builder_2 = SignerInfoBuilder::new(
+        &signer_2,
+        signer_identifier(2),
+        sha256,
+        &other_content,
+        None,
+    )
+    .unwrap();
+    assert!(SignedDataBuilder::from_signed_data(&signed_data)
+        .add_migrated_signer_info::<ecdsa::SigningKey<NistP256>, p256::ecdsa::DerSignature>(
+            signed_data.signer_infos.0.get(0).unwrap(),
+            signer_info_builder_2,
+            &|_| true,
+        )
+        .is_err());
+}
+
 // TODO more tests:
 // - external message
 // - PKCS #7 message:
@@ -1,3 +1,5 @@
+#![cfg(feature = "compressed")]
+
 extern crate core;
 
 use cms::compressed_data::CompressedData;
@@ -50,3 +52,103 @@ fn reencode_compressed_data_test() {
     // should match the original
     assert_eq!(reencoded_data_inci, der_ci)
 }
+
+#[test]
+fn compressed_data_content_info_round_trip_test() {
+    let der_ci = include_bytes!("examples/compressed_data.bin");
+    let ci = ContentInfo::from_der(der_ci).unwrap();
+
+    let data = CompressedData::try_from(ci).unwrap();
+    let ci2 = ContentInfo::try_from(data).unwrap();
+    assert_eq!(ci2.to_der().unwrap(), der_ci);
+}
+
+#[test]
+fn compressed_data_wrong_content_type_test() {
+    let der_ci = include_bytes!("examples/digested_data.bin");
+    let ci = ContentInfo::from_der(der_ci).unwrap();
+    assert!(CompressedData::try_from(ci).is_err());
+}
+
+#[cfg(feature = "zlib")]
+#[test]
+fn decompress_openssl_compressed_data_test() {
+    let der_ci = include_bytes!("examples/compressed_data.bin");
+    let ci = ContentInfo::from_der(der_ci).unwrap();
+    let data = CompressedData::try_from(ci).unwrap();
+
+    let content = data.decompress().unwrap();
+    assert_eq!(content, include_bytes!("examples/data.txt"));
+}
+
+#[cfg(feature = "zlib")]
+#[test]
+fn compress_decompress_round_trip_test() {
+    let content = include_bytes!("examples/data.txt");
+    let data = CompressedData::compress(const_oid::db::rfc5911::ID_DATA, content).unwrap();
+    assert_eq!(CmsVersion::V0, data.version);
+    assert_eq!(
+        data.compression_alg.oid,
+        const_oid::db::rfc6268::ID_ALG_ZLIB_COMPRESS
+    );
+
+    // survive a trip through ContentInfo encoding
+    let ci = ContentInfo::try_from(data).unwrap();
+    let ci = ContentInfo::from_der(&ci.to_der().unwrap()).unwrap();
+    let data = CompressedData::try_from(ci).unwrap();
+    assert_eq!(data.decompress().unwrap(), content);
+}
+
+#[cfg(feature = "zlib")]
+#[test]
+fn decompress_unsupported_algorithm_test() {
+    let mut data = CompressedData::compress(const_oid::db::rfc5911::ID_DATA, b"hello").unwrap();
+    data.compression_alg.oid = const_oid::db::rfc5911::ID_DATA;
+    assert!(matches!(
+        data.decompress(),
+        Err(cms::compressed_data::Error::UnsupportedAlgorithm(_))
+    ));
+}
+
+#[cfg(feature = "zlib")]
+#[test]
+fn decompress_length_limit_test() {
+    let content = [0u8; 4096];
+    let data = CompressedData::compress(const_oid::db::rfc5911::ID_DATA, &content).unwrap();
+
+    assert_eq!(data.decompress_with_limit(4096).unwrap(), content);
+    assert!(matches!(
+        data.decompress_with_limit(4095),
+        Err(cms::compressed_data::Error::LengthLimitExceeded)
+    ));
+}
+
+#[cfg(feature = "zlib")]
+#[test]
+fn decompress_constructed_econtent_test() {
+    let content = b"streamed content";
+    let data = CompressedData::compress(const_oid::db::rfc5911::ID_DATA, content).unwrap();
+    let compressed = data
+        .encap_content_info
+        .econtent
+        .as_ref()
+        .unwrap()
+        .value()
+        .to_vec();
+    let (first, second) = compressed.split_at(compressed.len() / 2);
+
+    // CompressedData with an indefinite-length constructed eContent in two segments
+    let mut ber = hex_literal::hex!(
+        "3080 020100 300d060b2a864886f70d0109100308 3080 06092a864886f70d010701 a080 2480"
+    )
+    .to_vec();
+    for segment in [first, second] {
+        ber.extend_from_slice(&[0x04, segment.len() as u8]);
+        ber.extend_from_slice(segment);
+    }
+    ber.extend_from_slice(&[0; 8]);
+
+    assert!(CompressedData::from_der(&ber).is_err());
+    let data = CompressedData::from_ber(&ber).unwrap();
+    assert_eq!(data.decompress().unwrap(), content);
+}
@@ -0,0 +1,87 @@
+//! ContentInfo tests
+
+use cms::content_info::{ContentInfo, ContentInfoEncoding, ContentInfoSniff};
+use der::pem::LineEnding;
+use hex_literal::hex;
+
+const SIGNED_DATA_DER: &[u8] = include_bytes!("examples/cms_der.bin");
+const SIGNED_DATA_BER: &[u8] = include_bytes!("examples/cms_ber.bin");
+
+#[test]
+fn sniff_der_and_ber() {
+    let signed_data = ContentInfoSniff {
+        encoding: ContentInfoEncoding::Der,
+        content_type: const_oid::db::rfc5911::ID_SIGNED_DATA,
+    };
+    assert_eq!(ContentInfo::sniff(SIGNED_DATA_DER), Some(signed_data));
+    assert_eq!(
+        ContentInfo::sniff(include_bytes!("examples/digested_data.bin")),
+        Some(ContentInfoSniff {
+            encoding: ContentInfoEncoding::Der,
+            content_type: const_oid::db::rfc5911::ID_DIGESTED_DATA,
+        })
+    );
+
+    // indefinite lengths
+    assert_eq!(
+        ContentInfo::sniff(SIGNED_DATA_BER),
+        Some(ContentInfoSniff {
+            encoding: ContentInfoEncoding::Ber,
+            ..signed_data
+        })
+    );
+
+    // id-data content, with a minimal and a non-minimal length
+    let data = ContentInfoSniff {
+        encoding: ContentInfoEncoding::Der,
+        content_type: const_oid::db::rfc5911::ID_DATA,
+    };
+    assert_eq!(
+        ContentInfo::sniff(&hex!("300f06092a864886f70d010701a0020400")),
+        Some(data)
+    );
+    assert_eq!(
+        ContentInfo::sniff(&hex!("30810f06092a864886f70d010701a0020400")),
+        Some(ContentInfoSniff {
+            encoding: ContentInfoEncoding::Ber,
+            ..data
+        })
+    );
+}
+
+#[test]
+fn sniff_pem() {
+    for label in ["CMS", "PKCS7"] {
+        let pem = der::pem::encode_string(label, LineEnding::LF, SIGNED_DATA_DER).unwrap();
+        assert_eq!(
+            ContentInfo::sniff(pem.as_bytes()),
+            Some(ContentInfoSniff {
+                encoding: ContentInfoEncoding::Pem,
+                content_type: const_oid::db::rfc5911::ID_SIGNED_DATA,
+            })
+        );
+    }
+}
+
+#[test]
+fn sniff_not_cms() {
+    let cert = include_bytes!("examples/GoodCACert.crt");
+    assert_eq!(ContentInfo::sniff(cert), None);
+    assert_eq!(
+        ContentInfo::sniff(include_bytes!("examples/GoodCACert.pem")),
+        None
+    );
+    assert_eq!(ContentInfo::sniff(b"hello world"), None);
+    assert_eq!(ContentInfo::sniff(&[]), None);
+
+    // truncated and trailing data
+    assert_eq!(
+        ContentInfo::sniff(&SIGNED_DATA_DER[..SIGNED_DATA_DER.len() - 1]),
+        None
+    );
+    assert_eq!(ContentInfo::sniff(&[SIGNED_DATA_DER, &[0]].concat()), None);
+
+    // certificate in a PEM labeled as CMS
+    let pem = der::pem::encode_string("CMS", LineEnding::LF, cert).unwrap();
+    assert_eq!(ContentInfo::sniff(pem.as_bytes()), None);
+}
@@ -54,3 +54,38 @@ fn reencode_digested_data_test() {
     // should match the original
     assert_eq!(reencoded_data_inci, der_ci)
 }
+
+#[test]
+fn digested_data_content_info_round_trip_test() {
+    let der_ci = include_bytes!("examples/digested_data.bin");
+    let ci = ContentInfo::from_der(der_ci).unwrap();
+
+    let data = DigestedData::try_from(ci).unwrap();
+    let rebuilt = DigestedData::new(
+        data.digest_alg.clone(),
+        data.encap_content_info.clone(),
+        data.digest.clone(),
+    );
+    assert_eq!(rebuilt, data);
+
+    let ci2 = ContentInfo::try_from(rebuilt).unwrap();
+    assert_eq!(ci2.to_der().unwrap(), der_ci);
+}
+
+#[test]
+fn digested_data_version_test() {
+    let der_ci = include_bytes!("examples/digested_data.bin");
+    let data = DigestedData::try_from(ContentInfo::from_der(der_ci).unwrap()).unwrap();
+
+    let mut encap_content_info = data.encap_content_info;
+    encap_content_info.econtent_type = const_oid::db::rfc5911::ID_SIGNED_DATA;
+    let data = DigestedData::new(data.digest_alg, encap_content_info, data.digest);
+    assert_eq!(CmsVersion::V2, data.version);
+}
+
+#[test]
+fn digested_data_wrong_content_type_test() {
+    let der_ci = include_bytes!("examples/compressed_data.bin");
+    let ci = ContentInfo::from_der(der_ci).unwrap();
+    assert!(DigestedData::try_from(ci).is_err());
+}
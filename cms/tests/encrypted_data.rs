@@ -1,3 +1,5 @@
+#![cfg(all(feature = "enveloped", feature = "x509"))]
+
 extern crate core;
 
 use cms::content_info::{CmsVersion, ContentInfo};
@@ -56,3 +58,32 @@ fn reencode_encrypted_data_test() {
     // should match the original
     assert_eq!(reencoded_data_inci, der_ci)
 }
+
+#[test]
+fn encrypted_data_content_info_round_trip_test() {
+    let der_ci = include_bytes!("examples/encrypted_data.bin");
+    let ci = ContentInfo::from_der(der_ci).unwrap();
+
+    let data = EncryptedData::try_from(ci).unwrap();
+    let rebuilt = EncryptedData::new(data.enc_content_info.clone(), None);
+    assert_eq!(rebuilt, data);
+
+    let ci2 = ContentInfo::try_from(rebuilt).unwrap();
+    assert_eq!(ci2.to_der().unwrap(), der_ci);
+}
+
+#[test]
+fn encrypted_data_version_test() {
+    let der_ci = include_bytes!("examples/encrypted_data.bin");
+    let data = EncryptedData::try_from(ContentInfo::from_der(der_ci).unwrap()).unwrap();
+
+    let data = EncryptedData::new(data.enc_content_info, Some(Default::default()));
+    assert_eq!(CmsVersion::V2, data.version);
+}
+
+#[test]
+fn encrypted_data_wrong_content_type_test() {
+    let der_ci = include_bytes!("examples/digested_data.bin");
+    let ci = ContentInfo::from_der(der_ci).unwrap();
+    assert!(EncryptedData::try_from(ci).is_err());
+}
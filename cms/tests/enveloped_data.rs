@@ -1,9 +1,12 @@
+#![cfg(all(feature = "enveloped", feature = "x509"))]
+
 extern crate core;
 
+use cms::cert::KeyHandle;
 use cms::content_info::{CmsVersion, ContentInfo};
 use cms::enveloped_data::{
     EnvelopedData, KeyAgreeRecipientIdentifier, OriginatorIdentifierOrKey, RecipientIdentifier,
-    RecipientInfo,
+    RecipientInfo, RecipientInfos, RecipientKeyId, RecipientKind,
 };
 use const_oid::ObjectIdentifier;
 use der::{AnyRef, Decode, Encode, Tag, Tagged};
@@ -508,3 +511,121 @@ fn reencode_enveloped_data_multi_test() {
     // should match the original
     assert_eq!(reencoded_data_in_ci, der_ci)
 }
+
+#[test]
+fn enveloped_data_recipients() {
+    let der_ci = include_bytes!("examples/enveloped_data_multi.bin");
+    let ci = ContentInfo::from_der(der_ci).unwrap();
+    let data = EnvelopedData::from_der(&ci.content.to_der().unwrap()).unwrap();
+
+    let rsa_cert = Certificate::from_der(include_bytes!("examples/rsa_cert.der")).unwrap();
+    let ec_cert = Certificate::from_der(include_bytes!("examples/ec384-ee-key.der")).unwrap();
+
+    let recipients: Vec<_> = data.recipients().collect();
+    assert_eq!(4, recipients.len());
+
+    let kinds: Vec<_> = recipients.iter().map(|r| r.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            RecipientKind::KeyTransport,
+            RecipientKind::KeyAgreement,
+            RecipientKind::Kek,
+            RecipientKind::Password,
+        ]
+    );
+
+    for (recipient, cert) in recipients[..2].iter().zip([&rsa_cert, &ec_cert]) {
+        match recipient.key_id {
+            Some(RecipientKeyId::Certificate(KeyHandle::IssuerAndSerial(iasn))) => {
+                assert_eq!(iasn.issuer, cert.tbs_certificate.issuer);
+                assert_eq!(iasn.serial_number, cert.tbs_certificate.serial_number);
+            }
+            other => panic!("unexpected key identifier {:?}", other),
+        }
+        assert!(recipient.matches_certificate(cert));
+        assert!(recipient.enc_key.is_some());
+    }
+
+    assert_eq!(
+        ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1"),
+        recipients[0].key_enc_alg.unwrap().oid
+    );
+    assert_eq!(
+        ObjectIdentifier::new_unwrap("1.3.133.16.840.63.0.2"),
+        recipients[1].key_enc_alg.unwrap().oid
+    );
+
+    match recipients[2].key_id {
+        Some(RecipientKeyId::KekIdentifier(kek_id)) => {
+            assert_eq!(kek_id.as_bytes(), hex!("DEADBEEF"))
+        }
+        other => panic!("unexpected key identifier {:?}", other),
+    }
+    assert!(!recipients[2].matches_certificate(&rsa_cert));
+    assert_eq!(None, recipients[3].key_id);
+}
+
+#[test]
+fn lenient_and_strict_enveloped_data() {
+    for bytes in [
+        &include_bytes!("examples/enveloped_data_ktri.bin")[..],
+        &include_bytes!("examples/enveloped_data_kari.bin")[..],
+        &include_bytes!("examples/enveloped_data_pwri.bin")[..],
+        &include_bytes!("examples/enveloped_data_kekri.bin")[..],
+        &include_bytes!("examples/enveloped_data_multi.bin")[..],
+    ] {
+        let der = ContentInfo::from_der(bytes)
+            .unwrap()
+            .content
+            .to_der()
+            .unwrap();
+        let data = EnvelopedData::from_der(&der).unwrap();
+        assert_eq!(EnvelopedData::from_der_strict(&der).unwrap(), data);
+        assert_eq!(EnvelopedData::from_ber(&der).unwrap(), data);
+    }
+}
+
+#[test]
+fn strict_enveloped_data_unknown_oids() {
+    let unknown = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.55555.1");
+    let der_ci = include_bytes!("examples/enveloped_data_multi.bin");
+    let ci = ContentInfo::from_der(der_ci).unwrap();
+    let data: EnvelopedData = ci.content.decode_as().unwrap();
+
+    let mut unknown_content_enc_alg = data.clone();
+    unknown_content_enc_alg
+        .encrypted_content
+        .content_enc_alg
+        .oid = unknown;
+
+    let mut unknown_key_enc_alg = data;
+    let mut recip_infos = unknown_key_enc_alg.recip_infos.0.into_vec();
+    let index = recip_infos
+        .iter()
+        .position(|info| matches!(info, RecipientInfo::Kekri(_)))
+        .unwrap();
+    match &mut recip_infos[index] {
+        RecipientInfo::Kekri(kekri) => kekri.key_enc_alg.oid = unknown,
+        _ => unreachable!(),
+    }
+    unknown_key_enc_alg.recip_infos = RecipientInfos(recip_infos.try_into().unwrap());
+
+    for (data, path) in [
+        (
+            unknown_content_enc_alg,
+            "EnvelopedData.encrypted_content.content_enc_alg.oid".to_string(),
+        ),
+        (
+            unknown_key_enc_alg,
+            format!("EnvelopedData.recip_infos[{}].key_enc_alg.oid", index),
+        ),
+    ] {
+        let der = data.to_der().unwrap();
+        assert_eq!(EnvelopedData::from_ber(&der).unwrap(), data);
+
+        let err = EnvelopedData::from_der_strict(&der).unwrap_err();
+        assert_eq!(err.kind(), der::ErrorKind::OidUnknown { oid: unknown });
+        assert_eq!(err.path().to_string(), path);
+    }
+}
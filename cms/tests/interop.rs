@@ -0,0 +1,268 @@
+//! Interop fixture tests
+//!
+//! Each [`Fixture`] holds a CMS message produced by another implementation along with the
+//! values it is expected to decode to. Fixtures with [`Encoding::Ber`] are included for
+//! regression testing of BER support: they are not accepted by the DER decoder used by
+//! [`Fixture::decode`].
+
+#![cfg(feature = "signed")]
+
+use const_oid::db::rfc5911::{ID_DATA, ID_SIGNED_DATA};
+use const_oid::db::rfc5912::{ID_SHA_1, ID_SHA_256};
+use const_oid::db::rfc6268::ID_CT_COMPRESSED_DATA;
+use der::{asn1::ObjectIdentifier, Decode};
+
+use cms::content_info::{CmsVersion, ContentInfo};
+use cms::signed_data::SignedData;
+
+/// `szOID_CTL`: Microsoft certificate trust list.
+const SZ_OID_CTL: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.311.10.1");
+
+/// Implementation which produced a [`Fixture`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Origin {
+    /// OpenSSL command line tools.
+    OpenSsl,
+
+    /// EJBCA SCEP responder.
+    Ejbca,
+
+    /// MicroMDM SCEP server, as used for Apple MDM enrollment.
+    MicroMdm,
+
+    /// Microsoft Windows.
+    Windows,
+}
+
+/// Encoding rules used by a [`Fixture`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Encoding {
+    /// Distinguished Encoding Rules.
+    Der,
+
+    /// Basic Encoding Rules, e.g. with indefinite lengths.
+    Ber,
+}
+
+/// CMS message with its expected decoded values.
+#[derive(Clone, Copy, Debug)]
+struct Fixture {
+    /// Short name of the fixture.
+    name: &'static str,
+
+    /// Implementation which produced the message.
+    origin: Origin,
+
+    /// Encoding rules used by the message.
+    encoding: Encoding,
+
+    /// Encoded `ContentInfo`.
+    bytes: &'static [u8],
+
+    /// Expected `contentType` of the `ContentInfo`.
+    content_type: ObjectIdentifier,
+
+    /// Expected values of `SignedData` content.
+    signed_data: Option<SignedDataGolden>,
+}
+
+/// Expected decoded values of a `SignedData` [`Fixture`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct SignedDataGolden {
+    /// Expected `version`.
+    version: CmsVersion,
+
+    /// Expected algorithms in `digestAlgorithms`.
+    digest_algorithms: &'static [ObjectIdentifier],
+
+    /// Expected `eContentType`.
+    econtent_type: ObjectIdentifier,
+
+    /// Expected length of the `eContent` value, if present.
+    econtent_len: Option<usize>,
+
+    /// Expected number of `certificates`, if present.
+    certificates: Option<usize>,
+
+    /// Expected number of `crls`, if present.
+    crls: Option<usize>,
+
+    /// Expected number of `signerInfos`.
+    signer_infos: usize,
+}
+
+impl Fixture {
+    /// Decode the `ContentInfo` of this fixture.
+    fn decode(&self) -> der::Result<ContentInfo> {
+        ContentInfo::from_der(self.bytes)
+    }
+
+    /// Decode the fixture and check it against its expected values.
+    ///
+    /// Returns `Ok(false)` if the message decodes but does not match the expected values.
+    fn check(&self) -> der::Result<bool> {
+        let content_info = self.decode()?;
+        if content_info.content_type != self.content_type {
+            return Ok(false);
+        }
+
+        match &self.signed_data {
+            Some(golden) => Ok(golden.matches(&content_info.content.decode_as()?)),
+            None => Ok(true),
+        }
+    }
+}
+
+impl SignedDataGolden {
+    /// Check whether `signed_data` matches these values.
+    fn matches(&self, signed_data: &SignedData) -> bool {
+        let eci = &signed_data.encap_content_info;
+
+        signed_data.version == self.version
+            && signed_data
+                .digest_algorithms
+                .iter()
+                .map(|alg| alg.oid)
+                .eq(self.digest_algorithms.iter().copied())
+            && eci.econtent_type == self.econtent_type
+            && eci.econtent.as_ref().map(|c| c.value().len()) == self.econtent_len
+            && signed_data.certificates.as_ref().map(|c| c.0.len()) == self.certificates
+            && signed_data.crls.as_ref().map(|c| c.0.len()) == self.crls
+            && signed_data.signer_infos.0.len() == self.signer_infos
+    }
+}
+
+/// Signature over 10034 bytes of encapsulated data.
+const OPENSSL_SIGNED_DATA: SignedDataGolden = SignedDataGolden {
+    version: CmsVersion::V1,
+    digest_algorithms: &[ID_SHA_256],
+    econtent_type: ID_DATA,
+    econtent_len: Some(10034),
+    certificates: Some(1),
+    crls: None,
+    signer_infos: 1,
+};
+
+/// All fixtures of the corpus.
+const FIXTURES: &[Fixture] = &[
+    Fixture {
+        name: "openssl-signed-data",
+        origin: Origin::OpenSsl,
+        encoding: Encoding::Der,
+        bytes: include_bytes!("examples/sd.cms"),
+        content_type: ID_SIGNED_DATA,
+        signed_data: Some(SignedDataGolden {
+            version: CmsVersion::V1,
+            digest_algorithms: &[ID_SHA_256],
+            econtent_type: ID_DATA,
+            econtent_len: Some(447),
+            certificates: Some(1),
+            crls: None,
+            signer_infos: 1,
+        }),
+    },
+    Fixture {
+        name: "openssl-signed-data-der",
+        origin: Origin::OpenSsl,
+        encoding: Encoding::Der,
+        bytes: include_bytes!("examples/cms_der.bin"),
+        content_type: ID_SIGNED_DATA,
+        signed_data: Some(OPENSSL_SIGNED_DATA),
+    },
+    Fixture {
+        name: "openssl-signed-data-ber",
+        origin: Origin::OpenSsl,
+        encoding: Encoding::Ber,
+        bytes: include_bytes!("examples/cms_ber.bin"),
+        content_type: ID_SIGNED_DATA,
+        signed_data: Some(OPENSSL_SIGNED_DATA),
+    },
+    Fixture {
+        name: "openssl-compressed-data",
+        origin: Origin::OpenSsl,
+        encoding: Encoding::Der,
+        bytes: include_bytes!("examples/compressed_data.bin"),
+        content_type: ID_CT_COMPRESSED_DATA,
+        signed_data: None,
+    },
+    Fixture {
+        name: "ejbca-scep-pki-message",
+        origin: Origin::Ejbca,
+        encoding: Encoding::Der,
+        bytes: include_bytes!("examples/scep_der.bin"),
+        content_type: ID_SIGNED_DATA,
+        signed_data: Some(SignedDataGolden {
+            version: CmsVersion::V1,
+            digest_algorithms: &[ID_SHA_256],
+            econtent_type: ID_DATA,
+            econtent_len: Some(1060),
+            certificates: Some(1),
+            crls: None,
+            signer_infos: 1,
+        }),
+    },
+    Fixture {
+        name: "micromdm-scep-signature",
+        origin: Origin::MicroMdm,
+        encoding: Encoding::Der,
+        bytes: include_bytes!("examples/apple_mdm_signature_der.bin"),
+        content_type: ID_SIGNED_DATA,
+        signed_data: Some(SignedDataGolden {
+            version: CmsVersion::V1,
+            digest_algorithms: &[ID_SHA_1],
+            econtent_type: ID_DATA,
+            econtent_len: Some(1093),
+            certificates: None,
+            crls: None,
+            signer_infos: 1,
+        }),
+    },
+    Fixture {
+        name: "windows-authroot-stl",
+        origin: Origin::Windows,
+        encoding: Encoding::Der,
+        bytes: include_bytes!("examples/authroot.stl"),
+        content_type: ID_SIGNED_DATA,
+        signed_data: Some(SignedDataGolden {
+            version: CmsVersion::V1,
+            digest_algorithms: &[ID_SHA_256],
+            econtent_type: SZ_OID_CTL,
+            econtent_len: Some(161238),
+            certificates: Some(2),
+            crls: None,
+            signer_infos: 1,
+        }),
+    },
+];
+
+#[test]
+fn der_fixtures_match_golden_values() {
+    for fixture in FIXTURES.iter().filter(|f| f.encoding == Encoding::Der) {
+        assert!(
+            fixture.check().unwrap(),
+            "{} from {:?} does not match",
+            fixture.name,
+            fixture.origin
+        );
+    }
+}
+
+#[test]
+fn ber_fixtures_are_rejected() {
+    for fixture in FIXTURES.iter().filter(|f| f.encoding == Encoding::Ber) {
+        assert!(fixture.decode().is_err(), "{} decoded as DER", fixture.name);
+    }
+}
+
+#[test]
+fn ber_fixtures_transcoded_to_der() {
+    for fixture in FIXTURES.iter().filter(|f| f.encoding == Encoding::Ber) {
+        let der = der::ber_to_der(fixture.bytes).unwrap();
+        let content_info = ContentInfo::from_der(&der).unwrap();
+        assert_eq!(content_info.content_type, fixture.content_type);
+
+        if let Some(golden) = &fixture.signed_data {
+            assert!(golden.matches(&content_info.content.decode_as().unwrap()));
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Revocation information tests
+
+#![cfg(all(feature = "ocsp", feature = "signed"))]
+
+use cms::revocation::ocsp::{OcspResponse, OcspResponseStatus};
+use cms::revocation::{
+    OtherRevocationInfoFormat, RevocationInfoChoice, RevocationInfoChoices, ID_RI_OCSP_RESPONSE,
+};
+use der::asn1::SetOfVec;
+use der::{Any, Decode, Encode};
+use hex_literal::hex;
+
+/// OCSP response from the x509-ocsp tests, as returned by `ocsp.digicert.com`
+const OCSP_RESPONSE: &[u8] = &hex!("308201D30A0100A08201CC308201C806092B0601050507300101048201B9308201B530819EA2160414246E2B2DD06A925151256901AA9A47A689E74020180F32303232303230323034313232355A307330713049300906052B0E03021A05000414A87E303106E4E88565CFE952598FA6DA7C00532F0414246E2B2DD06A925151256901AA9A47A689E7402002100E4239AB85E2E6A27C52C6DE9B9078D98000180F32303232303230323033353730315AA011180F32303232303230393033313230315A300D06092A864886F70D01010B0500038201010089DC0913BD91DB172C314AE8F78E6574950353618FFBBF2F12B1F134F21EC20063E66C60AC463BDB926A9D5B699EB39689032659D43C441863BAC9D8B7CC84137B558503D2631348EDA79AEC9FD4B69AE988F68A752470D3D4BA46F41B9FD41A974A5311BAA191AFBA370F0B051F035297FA76A2133B98FF471BDA4BFBFDA275B4FA1CF8377EEB2B2141F9C998ACE27C60F11E221D6732A5D538FEDF9A01D776E1877BCF70A8C953630B2C66752A66DF087C075794F5A2C0023C964CD73476EA2556F091F6C191B30B74E2523F668F32CE10AFB3D51CE053D4D41E62214B60171093AB915DBBA497F09B3C279127E1D25C7AA6A7048BBEA41A23F4F827BD108F");
+
+#[test]
+fn ocsp_response_round_trip() {
+    let response = OcspResponse::from_der(OCSP_RESPONSE).unwrap();
+    let other = OtherRevocationInfoFormat::try_from(&response).unwrap();
+    assert_eq!(other.format(), ID_RI_OCSP_RESPONSE);
+
+    let choices =
+        RevocationInfoChoices(SetOfVec::try_from([RevocationInfoChoice::Other(other)]).unwrap());
+    let encoded = choices.to_der().unwrap();
+
+    // SET { [1] IMPLICIT SEQUENCE { AlgorithmIdentifier, OCSPResponse } }
+    assert_eq!(
+        &encoded[..24],
+        hex!("318201E7 A18201E3 300A06082B06010505071002 308201D3")
+    );
+    assert_eq!(&encoded[20..], OCSP_RESPONSE);
+
+    let decoded = RevocationInfoChoices::from_der(&encoded).unwrap();
+    let responses = decoded
+        .ocsp_responses()
+        .collect::<der::Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(responses, [response]);
+
+    assert_eq!(responses[0].response_status, OcspResponseStatus::Successful);
+    let basic = responses[0].basic_response().unwrap().unwrap();
+    assert_eq!(basic.tbs_response_data.responses.len(), 1);
+}
+
+#[test]
+fn other_format_is_not_ocsp() {
+    let other = OtherRevocationInfoFormat::new(
+        const_oid::db::rfc5911::ID_DATA,
+        Any::from_der(OCSP_RESPONSE).unwrap(),
+    );
+
+    assert_eq!(other.ocsp_response().unwrap(), None);
+}
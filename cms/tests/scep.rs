@@ -0,0 +1,99 @@
+//! SCEP CA response tests
+
+#![cfg(feature = "signed")]
+
+use cms::scep::{
+    CaCapability, CaCaps, CaCertResponse, CA_CERT_CONTENT_TYPE, CA_RA_CERT_CONTENT_TYPE,
+};
+use der::{Decode, Encode};
+use x509_cert::Certificate;
+
+#[test]
+fn get_ca_cert_single() {
+    let der = include_bytes!("examples/GoodCACert.crt");
+    let ca = Certificate::from_der(der).unwrap();
+
+    let response = CaCertResponse::new(vec![ca.clone()]);
+    assert_eq!(response.content_type(), CA_CERT_CONTENT_TYPE);
+    assert_eq!(response.to_der().unwrap(), der);
+
+    let parsed = CaCertResponse::from_der(CA_CERT_CONTENT_TYPE, der).unwrap();
+    assert_eq!(parsed, CaCertResponse::Ca(ca));
+}
+
+#[test]
+fn get_ca_cert_with_ra() {
+    let ca = Certificate::from_der(include_bytes!("examples/GoodCACert.crt")).unwrap();
+    let ra =
+        Certificate::from_der(include_bytes!("examples/ValidCertificatePathTest1EE.crt")).unwrap();
+
+    let response = CaCertResponse::new(vec![ca.clone(), ra.clone()]);
+    assert_eq!(response.content_type(), CA_RA_CERT_CONTENT_TYPE);
+
+    let body = response.to_der().unwrap();
+    let parsed = CaCertResponse::from_der("Application/X-X509-CA-RA-Cert", &body).unwrap();
+    let certs = parsed.certificates();
+    assert_eq!(certs.len(), 2);
+    assert!(certs.contains(&ca));
+    assert!(certs.contains(&ra));
+
+    let parsed =
+        CaCertResponse::from_der("application/x-x509-ca-ra-cert; charset=binary", &body).unwrap();
+    assert_eq!(parsed.certificates().len(), 2);
+
+    // A certs-only SignedData is not a certificate
+    assert!(CaCertResponse::from_der(CA_CERT_CONTENT_TYPE, &body).is_err());
+    assert!(CaCertResponse::from_der("text/plain", &body).is_err());
+}
+
+#[test]
+fn get_ca_cert_with_ra_ber() {
+    let ber = include_bytes!("examples/pkits_ber.p7b");
+    let der = include_bytes!("examples/pkits.p7b");
+
+    let parsed = CaCertResponse::from_der(CA_RA_CERT_CONTENT_TYPE, ber).unwrap();
+    assert_eq!(
+        parsed,
+        CaCertResponse::from_der(CA_RA_CERT_CONTENT_TYPE, der).unwrap()
+    );
+    assert!(!parsed.certificates().is_empty());
+}
+
+#[test]
+fn get_ca_caps() {
+    let caps: CaCaps = "POSTPKIOperation\r\nsha-256\r\nAES\r\n\r\nRenewal\r\nX-Vendor\r\n"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        caps.0,
+        [
+            CaCapability::PostPkiOperation,
+            CaCapability::Sha256,
+            CaCapability::Aes,
+            CaCapability::Renewal,
+            CaCapability::Other("X-Vendor".into()),
+        ]
+    );
+    assert!(caps.supports(&CaCapability::Renewal));
+    assert!(!caps.supports(&CaCapability::GetNextCaCert));
+    assert_eq!(
+        caps.to_string(),
+        "POSTPKIOperation\nSHA-256\nAES\nRenewal\nX-Vendor\n"
+    );
+
+    let caps = CaCaps(vec![CaCapability::ScepStandard]);
+    assert!(caps.supports(&CaCapability::Sha256));
+    assert!(!caps.supports(&CaCapability::Sha512));
+}
+
+#[test]
+fn certs_only_round_trip() {
+    let ca = Certificate::from_der(include_bytes!("examples/GoodCACert.crt")).unwrap();
+    let response = CaCertResponse::CaRa(vec![ca.clone()]);
+    let body = response.to_der().unwrap();
+    assert_eq!(
+        CaCertResponse::from_der(CA_RA_CERT_CONTENT_TYPE, &body).unwrap(),
+        response
+    );
+    assert_ne!(body, ca.to_der().unwrap());
+}
@@ -1,8 +1,18 @@
 //! SignedData tests
 
+#![cfg(feature = "signed")]
+
+use cms::attr::SignedAttributesExt;
+use cms::cert::{CertificateChoices, KeyHandle};
 use cms::content_info::ContentInfo;
-use cms::signed_data::{SignedData, SignerInfos};
-use der::{AnyRef, Decode, DecodePem, Encode, ErrorKind, Tag};
+use cms::signed_data::{SignedData, SignedDataRef, SignerIdentifier, SignerInfo, SignerInfos};
+use const_oid::ObjectIdentifier;
+use der::asn1::SetOfVec;
+use der::{Any, AnyRef, Decode, DecodePem, Encode, ErrorKind, Reader, SliceReader, Tag, TagNumber};
+use sha2::{Digest, Sha256};
+use x509_cert::attr::Attribute;
+use x509_cert::attr_cert::AttributeCertificate;
+use x509_cert::ext::pkix::SubjectKeyIdentifier;
 use x509_cert::Certificate;
 
 #[test]
@@ -145,3 +155,399 @@ fn certs_to_p7b() {
     let p7b_buf2 = p7b_ee.to_der().unwrap();
     assert_eq!(p7b_buf, p7b_buf2.as_slice());
 }
+
+#[test]
+fn p7b_to_certs() {
+    let p7b_buf = include_bytes!("examples/pkits.p7b");
+    let ee_cert =
+        Certificate::from_der(include_bytes!("examples/ValidCertificatePathTest1EE.crt")).unwrap();
+    let ca_cert = Certificate::from_der(include_bytes!("examples/GoodCACert.crt")).unwrap();
+
+    let ci = ContentInfo::from_der(p7b_buf).unwrap();
+    let certs = Vec::<Certificate>::try_from(ci).unwrap();
+    assert_eq!(certs.len(), 2);
+    assert!(certs.contains(&ee_cert));
+    assert!(certs.contains(&ca_cert));
+
+    // round trip through a certs-only SignedData
+    let ci = ContentInfo::try_from(certs.clone()).unwrap();
+    assert_eq!(ci.to_der().unwrap(), p7b_buf);
+    assert_eq!(Vec::<Certificate>::try_from(ci).unwrap(), certs);
+}
+
+#[test]
+fn ber_p7b_to_certs() {
+    // pkits.p7b with indefinite lengths for the ContentInfo, its content, the SignedData and
+    // its certificates
+    let p7b_ber_buf = include_bytes!("examples/pkits_ber.p7b");
+    assert!(ContentInfo::from_der(p7b_ber_buf).is_err());
+
+    let ci = ContentInfo::from_ber(p7b_ber_buf).unwrap();
+    assert_eq!(ci.to_der().unwrap(), include_bytes!("examples/pkits.p7b"));
+
+    let certs = Vec::<Certificate>::try_from(ci).unwrap();
+    assert_eq!(certs.len(), 2);
+    assert!(
+        certs.contains(&Certificate::from_der(include_bytes!("examples/GoodCACert.crt")).unwrap())
+    );
+}
+
+#[test]
+fn non_signed_data_to_certs() {
+    let ci = ContentInfo::from_der(include_bytes!("examples/digested_data.bin")).unwrap();
+    let err = Vec::<Certificate>::try_from(ci).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::OidUnknown { .. }));
+}
+
+#[test]
+fn v2_attr_cert_in_certificate_set() {
+    let ac_buf = include_bytes!("examples/attr_cert.der");
+    let ac = AttributeCertificate::from_der(ac_buf).unwrap();
+
+    let ci = ContentInfo::from_der(include_bytes!("examples/sd.cms")).unwrap();
+    let mut sd = ci.content.decode_as::<SignedData>().unwrap();
+    let certificates = sd.certificates.as_mut().unwrap();
+    certificates
+        .0
+        .insert(CertificateChoices::V2AttrCert(ac.clone()))
+        .unwrap();
+
+    // v2AttrCert is [2] IMPLICIT AttributeCertificateV2
+    let choice = CertificateChoices::V2AttrCert(ac.clone());
+    let choice_der = choice.to_der().unwrap();
+    assert_eq!(choice_der[0], 0xA2);
+    assert_eq!(&choice_der[1..], &ac_buf[1..]);
+
+    let sd2 = SignedData::from_der(&sd.to_der().unwrap()).unwrap();
+    let acs: Vec<_> = sd2
+        .certificates
+        .unwrap()
+        .0
+        .into_vec()
+        .into_iter()
+        .filter_map(|choice| match choice {
+            CertificateChoices::V2AttrCert(ac) => Some(ac),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(acs, [ac]);
+}
+
+/// Check that the digest of the encapsulated content matches the message-digest signed attribute
+/// of each signer.
+fn check_econtent_digest(content_info: &ContentInfo) {
+    let sd: SignedData = content_info.content.decode_as().unwrap();
+    let digest = Sha256::digest(sd.encap_content_info.econtent_digest_input().unwrap());
+
+    for signer_info in sd.signer_infos.0.iter() {
+        let signed_attrs = signer_info.signed_attrs.as_ref().unwrap();
+        let message_digest = signed_attrs.message_digest().unwrap().unwrap();
+        assert_eq!(message_digest.as_bytes(), digest.as_slice());
+    }
+}
+
+#[test]
+fn econtent_digest_der() {
+    let ci = ContentInfo::from_der(include_bytes!("examples/cms_der.bin")).unwrap();
+    check_econtent_digest(&ci);
+
+    // EJBCA SCEP response
+    let ci = ContentInfo::from_der(include_bytes!("examples/scep_der.bin")).unwrap();
+    check_econtent_digest(&ci);
+}
+
+#[test]
+fn econtent_digest_ber_constructed_octet_string() {
+    let ber = include_bytes!("examples/cms_ber.bin");
+    assert!(ContentInfo::from_der(ber).is_err());
+
+    // The eContent is a constructed OCTET STRING of 1000 byte segments
+    let ci = ContentInfo::from_ber(ber).unwrap();
+    check_econtent_digest(&ci);
+
+    let sd: SignedData = ci.content.decode_as().unwrap();
+    let econtent = sd.encap_content_info.econtent_digest_input().unwrap();
+    assert_eq!(econtent.len(), 10034);
+
+    // Same content as the DER encoded message
+    let der_ci = ContentInfo::from_der(include_bytes!("examples/cms_der.bin")).unwrap();
+    let der_sd: SignedData = der_ci.content.decode_as().unwrap();
+    assert_eq!(
+        der_sd.encap_content_info.econtent_digest_input().unwrap(),
+        econtent
+    );
+}
+
+#[test]
+fn borrowed_signed_data() {
+    for bytes in [
+        &include_bytes!("examples/sd.cms")[..],
+        &include_bytes!("examples/scep_der.bin")[..],
+        &include_bytes!("examples/cms_der.bin")[..],
+    ] {
+        let ci = ContentInfo::from_der(bytes).unwrap();
+        let sd: SignedData = ci.content.decode_as().unwrap();
+        let sd_ref: SignedDataRef<'_> = ci.content.decode_as().unwrap();
+
+        assert_eq!(sd_ref.version, sd.version);
+        assert_eq!(
+            sd_ref.encap_content_info.econtent_type,
+            sd.encap_content_info.econtent_type
+        );
+        assert_eq!(sd_ref.to_der().unwrap(), ci.content.to_der().unwrap());
+        assert_eq!(SignedData::try_from(sd_ref).unwrap(), sd);
+
+        let signer_infos = sd_ref
+            .signer_infos
+            .iter()
+            .collect::<der::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(signer_infos.len(), sd.signer_infos.0.len());
+
+        for (signer_info_ref, signer_info) in signer_infos.into_iter().zip(sd.signer_infos.0.iter())
+        {
+            assert_eq!(
+                SignerIdentifier::try_from(signer_info_ref.sid).unwrap(),
+                signer_info.sid
+            );
+            assert_eq!(
+                signer_info_ref.signature.as_bytes(),
+                signer_info.signature.as_bytes()
+            );
+            assert_eq!(&SignerInfo::try_from(signer_info_ref).unwrap(), signer_info);
+        }
+    }
+}
+
+#[test]
+fn strict_der() {
+    for bytes in [
+        &include_bytes!("examples/sd.cms")[..],
+        &include_bytes!("examples/scep_der.bin")[..],
+        &include_bytes!("examples/cms_der.bin")[..],
+        &include_bytes!("examples/pkits.p7b")[..],
+    ] {
+        assert_eq!(
+            ContentInfo::from_der_strict(bytes).unwrap(),
+            ContentInfo::from_der(bytes).unwrap()
+        );
+    }
+
+    let ber = include_bytes!("examples/cms_ber.bin");
+    let err = ContentInfo::from_der_strict(ber).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::IndefiniteLength);
+    assert_eq!(err.position(), Some(0u8.into()));
+    assert!(ContentInfo::from_ber(ber).is_ok());
+}
+
+#[test]
+fn strict_der_signed_attrs_ordering() {
+    let mut bytes = include_bytes!("examples/sd.cms").to_vec();
+
+    // Reverse the order of the signed attributes, which is not checked by `from_der`
+    let ci = ContentInfo::from_der(&bytes).unwrap();
+    let sd: SignedDataRef<'_> = ci.content.decode_as().unwrap();
+    let signer_info = sd.signer_infos.iter().next().unwrap().unwrap();
+    let signed_attrs = signer_info.signed_attrs.unwrap().value();
+    let offset = bytes
+        .windows(signed_attrs.len())
+        .position(|window| window == signed_attrs)
+        .unwrap();
+
+    let mut reader = SliceReader::new(signed_attrs).unwrap();
+    let mut attrs = Vec::new();
+    while !reader.is_finished() {
+        attrs.push(AnyRef::decode(&mut reader).unwrap().to_der().unwrap());
+    }
+    assert!(attrs.len() > 1);
+    let reversed = attrs.into_iter().rev().flatten().collect::<Vec<u8>>();
+    bytes[offset..offset + reversed.len()].copy_from_slice(&reversed);
+
+    assert!(ContentInfo::from_der(&bytes).is_ok());
+    let err = ContentInfo::from_der_strict(&bytes).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::SetOrdering);
+    assert_eq!(
+        err.path().to_string(),
+        "SignedData.signer_infos[0].signed_attrs"
+    );
+}
+
+#[test]
+fn lenient_and_strict_signed_data() {
+    for bytes in [
+        &include_bytes!("examples/sd.cms")[..],
+        &include_bytes!("examples/scep_der.bin")[..],
+        &include_bytes!("examples/cms_der.bin")[..],
+        &include_bytes!("examples/pkits.p7b")[..],
+    ] {
+        let der = ContentInfo::from_der(bytes)
+            .unwrap()
+            .content
+            .to_der()
+            .unwrap();
+        let sd = SignedData::from_der(&der).unwrap();
+        assert_eq!(SignedData::from_der_strict(&der).unwrap(), sd);
+        assert_eq!(SignedData::from_ber(&der).unwrap(), sd);
+    }
+
+    // SignedData inside of the indefinite length ContentInfo and its [0] content
+    let ber = include_bytes!("examples/cms_ber.bin");
+    let ber = &ber[15..ber.len() - 4];
+    let err = SignedData::from_der_strict(ber).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::IndefiniteLength);
+    assert_eq!(
+        SignedData::from_ber(ber).unwrap(),
+        ContentInfo::from_ber(include_bytes!("examples/cms_ber.bin"))
+            .unwrap()
+            .content
+            .decode_as()
+            .unwrap()
+    );
+}
+
+#[test]
+fn strict_signed_data_ecdsa_sha1() {
+    // `openssl cms -sign -md sha1` with a P-256 key
+    let ci = ContentInfo::from_der(include_bytes!("examples/ecdsa_sha1.cms")).unwrap();
+    let der = ci.content.to_der().unwrap();
+    let mut sd = SignedData::from_der_strict(&der).unwrap();
+    assert_eq!(sd, SignedData::from_der(&der).unwrap());
+
+    let signer_info = sd.signer_infos.0.get(0).unwrap();
+    assert_eq!(
+        signer_info.signature_algorithm.oid,
+        ObjectIdentifier::new_unwrap("1.2.840.10045.4.1")
+    );
+
+    // Some producers identify ECDSA signatures by the public key algorithm
+    let mut signer_infos = sd.signer_infos.0.into_vec();
+    signer_infos[0].signature_algorithm.oid = const_oid::db::rfc5912::ID_EC_PUBLIC_KEY;
+    sd.signer_infos = SignerInfos(signer_infos.try_into().unwrap());
+    let der = sd.to_der().unwrap();
+    assert_eq!(SignedData::from_der_strict(&der).unwrap(), sd);
+}
+
+#[test]
+fn strict_signed_data_unknown_oids() {
+    let unknown = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.55555.1");
+    let ci = ContentInfo::from_der(include_bytes!("examples/sd.cms")).unwrap();
+    let sd: SignedData = ci.content.decode_as().unwrap();
+
+    let with_signature_algorithm = |oid| {
+        let mut sd = sd.clone();
+        let mut signer_infos = sd.signer_infos.0.into_vec();
+        signer_infos[0].signature_algorithm.oid = oid;
+        sd.signer_infos = SignerInfos(signer_infos.try_into().unwrap());
+        sd
+    };
+
+    let mut unknown_attr = sd.clone();
+    let mut signer_infos = unknown_attr.signer_infos.0.into_vec();
+    let mut values = SetOfVec::new();
+    values.insert(Any::from(AnyRef::NULL)).unwrap();
+    signer_infos[0].unsigned_attrs = Some(
+        [Attribute {
+            oid: unknown,
+            values,
+        }]
+        .try_into()
+        .unwrap(),
+    );
+    unknown_attr.signer_infos = SignerInfos(signer_infos.try_into().unwrap());
+
+    // A digest algorithm is not a signature algorithm
+    let sha256 = const_oid::db::rfc5912::ID_SHA_256;
+
+    for (sd, oid, path) in [
+        (
+            with_signature_algorithm(unknown),
+            unknown,
+            "SignedData.signer_infos[0].signature_algorithm.oid",
+        ),
+        (
+            with_signature_algorithm(sha256),
+            sha256,
+            "SignedData.signer_infos[0].signature_algorithm.oid",
+        ),
+        (
+            unknown_attr,
+            unknown,
+            "SignedData.signer_infos[0].unsigned_attrs[0].oid",
+        ),
+    ] {
+        let der = sd.to_der().unwrap();
+        assert_eq!(SignedData::from_ber(&der).unwrap(), sd);
+
+        let err = SignedData::from_der_strict(&der).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::OidUnknown { oid });
+        assert_eq!(err.path().to_string(), path);
+    }
+}
+
+#[test]
+fn strict_der_high_tag_number() {
+    // Unsigned attribute whose value is a `[31]` tag, in the high tag number form
+    let ci = ContentInfo::from_der(include_bytes!("examples/sd.cms")).unwrap();
+    let mut sd: SignedData = ci.content.decode_as().unwrap();
+    let mut signer_infos = sd.signer_infos.0.into_vec();
+    let mut values = SetOfVec::new();
+    values
+        .insert(Any::new(TagNumber::new(31).context_specific(false), [0x2A]).unwrap())
+        .unwrap();
+    signer_infos[0].unsigned_attrs = Some(
+        [Attribute {
+            oid: const_oid::db::rfc5911::ID_AA_CONTENT_HINT,
+            values,
+        }]
+        .try_into()
+        .unwrap(),
+    );
+    sd.signer_infos = SignerInfos(signer_infos.try_into().unwrap());
+
+    let der = sd.to_der().unwrap();
+    assert!(der.windows(3).any(|window| window == [0x9F, 0x1F, 0x01]));
+    assert_eq!(SignedData::from_der_strict(&der).unwrap(), sd);
+
+    let ci = ContentInfo {
+        content_type: ci.content_type,
+        content: Any::from_der(&der).unwrap(),
+    };
+    let der = ci.to_der().unwrap();
+    assert_eq!(ContentInfo::from_der_strict(&der).unwrap(), ci);
+}
+
+#[test]
+fn signer_certificate() {
+    let ci = ContentInfo::from_der(include_bytes!("examples/sd.cms")).unwrap();
+    let sd = ci.content.decode_as::<SignedData>().unwrap();
+    let signer_info = sd.signer_infos.0.get(0).unwrap();
+
+    let cert = sd.signer_certificate(signer_info).unwrap();
+    assert!(matches!(
+        signer_info.key_handle(),
+        KeyHandle::IssuerAndSerial(iasn) if iasn.serial_number == cert.tbs_certificate.serial_number
+    ));
+    assert!(!signer_info
+        .key_handle()
+        .matches_key_id(cert.tbs_certificate.serial_number.as_bytes()));
+
+    // Certificates of other subjects don't match
+    let ca_cert = Certificate::from_der(include_bytes!("examples/GoodCACert.crt")).unwrap();
+    assert!(!signer_info.key_handle().matches_certificate(&ca_cert));
+
+    // Signer identified by its subject key identifier
+    let (_, skid) = ca_cert
+        .tbs_certificate
+        .get::<SubjectKeyIdentifier>()
+        .unwrap()
+        .unwrap();
+    let sid = SignerIdentifier::SubjectKeyIdentifier(skid.clone());
+    let key_handle = KeyHandle::from(&sid);
+    assert!(key_handle.matches_certificate(&ca_cert));
+    assert!(key_handle.matches_key_id(skid.0.as_bytes()));
+    assert!(!key_handle.matches_key_id(&[0u8; 20]));
+
+    let ee_cert =
+        Certificate::from_der(include_bytes!("examples/ValidCertificatePathTest1EE.crt")).unwrap();
+    assert!(!key_handle.matches_certificate(&ee_cert));
+}
@@ -1,3 +1,5 @@
+#![cfg(all(feature = "signed", feature = "enveloped"))]
+
 use cms::content_info::{CmsVersion, ContentInfo};
 use cms::encrypted_data::EncryptedData;
 use cms::signed_data::SignedData;
@@ -0,0 +1,279 @@
+#![cfg(feature = "builder")]
+
+use cms::attr::{SigningTimePolicy, SigningTimeViolation};
+use cms::builder::{SignedDataBuilder, SignerInfoBuilder};
+use cms::cert::{CertificateChoices, IssuerAndSerialNumber};
+use cms::signed_data::{EncapsulatedContentInfo, SignedData, SignerIdentifier};
+use cms::verify::Error;
+use der::asn1::{GeneralizedTime, OctetString, SetOfVec, Utf8StringRef};
+use der::{Any, DateTime, Encode, Tag};
+use p256::{
+    ecdsa::DerSignature,
+    pkcs8::{DecodePrivateKey, EncodePublicKey},
+    NistP256,
+};
+use spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned};
+use std::time::{Duration, SystemTime};
+use x509_cert::attr::AttributeTypeAndValue;
+use x509_cert::builder::{Builder, CertificateBuilder, Profile};
+use x509_cert::name::{RdnSequence, RelativeDistinguishedName};
+use x509_cert::serial_number::SerialNumber;
+use x509_cert::time::{Time, Validity};
+
+const PKCS8_PRIVATE_KEY_DER: &[u8] = include_bytes!("examples/p256-priv.der");
+
+fn ecdsa_signer() -> ecdsa::SigningKey<NistP256> {
+    let secret_key = p256::SecretKey::from_pkcs8_der(PKCS8_PRIVATE_KEY_DER).unwrap();
+    ecdsa::SigningKey::from(secret_key)
+}
+
+fn name(id: i32) -> RdnSequence {
+    let mut rdn_sequence = RdnSequence::default();
+    let rdn = &[AttributeTypeAndValue {
+        oid: const_oid::db::rfc4519::CN,
+        value: Any::from(Utf8StringRef::new(&format!("test client {id}")).unwrap()),
+    }];
+    let set_of_vector = SetOfVec::try_from(rdn.to_vec()).unwrap();
+    rdn_sequence
+        .0
+        .push(RelativeDistinguishedName::from(set_of_vector));
+    rdn_sequence
+}
+
+fn signer_identifier(id: i32) -> SignerIdentifier {
+    SignerIdentifier::IssuerAndSerialNumber(IssuerAndSerialNumber {
+        issuer: name(id),
+        serial_number: SerialNumber::from(1u32),
+    })
+}
+
+fn content(data: &[u8]) -> EncapsulatedContentInfo {
+    EncapsulatedContentInfo {
+        econtent_type: const_oid::db::rfc5911::ID_DATA,
+        econtent: Some(
+            Any::new(
+                Tag::OctetString,
+                OctetString::new(data).unwrap().to_der().unwrap(),
+            )
+            .unwrap(),
+        ),
+    }
+}
+
+/// Self-signed certificate of signer 1, valid during 2020.
+fn signer_certificate() -> x509_cert::Certificate {
+    let signer = ecdsa_signer();
+    let spki = signer.verifying_key().to_public_key_der().unwrap();
+
+    CertificateBuilder::new(
+        Profile::Root,
+        SerialNumber::from(1u32),
+        Validity::from_unix_duration(
+            Duration::from_secs(1_577_836_800),
+            Duration::from_secs(366 * 24 * 60 * 60 - 1),
+        )
+        .unwrap(),
+        name(1),
+        SubjectPublicKeyInfoOwned::try_from(spki.as_bytes()).unwrap(),
+        &signer,
+    )
+    .unwrap()
+    .build::<DerSignature>()
+    .unwrap()
+}
+
+fn date_time(year: u16, month: u8, day: u8) -> DateTime {
+    DateTime::new(year, month, day, 0, 0, 0).unwrap()
+}
+
+fn now() -> DateTime {
+    DateTime::from_system_time(SystemTime::now()).unwrap()
+}
+
+/// Build a message signed by two signers, with SHA-256 and SHA-512.
+fn signed_data(data: &[u8]) -> SignedData {
+    signed_data_with_certificate(data, None)
+}
+
+fn signed_data_with_certificate(
+    data: &[u8],
+    certificate: Option<x509_cert::Certificate>,
+) -> SignedData {
+    let content = content(data);
+    let signer = ecdsa_signer();
+    let mut builder = SignedDataBuilder::new(&content);
+    if let Some(certificate) = certificate {
+        builder
+            .add_certificate(CertificateChoices::Certificate(certificate))
+            .unwrap();
+    }
+
+    for (id, oid) in [
+        (1, const_oid::db::rfc5912::ID_SHA_256),
+        (2, const_oid::db::rfc5912::ID_SHA_512),
+    ] {
+        let digest_algorithm = AlgorithmIdentifierOwned {
+            oid,
+            parameters: None,
+        };
+        let signer_info_builder = SignerInfoBuilder::new(
+            &signer,
+            signer_identifier(id),
+            digest_algorithm.clone(),
+            &content,
+            None,
+        )
+        .unwrap();
+        builder
+            .add_digest_algorithm(digest_algorithm)
+            .unwrap()
+            .add_signer_info::<_, DerSignature>(signer_info_builder)
+            .unwrap();
+    }
+
+    builder.build().unwrap().content.decode_as().unwrap()
+}
+
+fn find_key(_: &cms::signed_data::SignerInfo) -> Option<ecdsa::VerifyingKey<NistP256>> {
+    Some(*ecdsa_signer().verifying_key())
+}
+
+#[test]
+fn verify_signer_infos() {
+    let signed_data = signed_data(b"content");
+    let results = signed_data.verify_signer_infos_now::<_, DerSignature, _>(None, None, find_key);
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(Result::is_ok));
+}
+
+#[test]
+fn verify_tampered_content() {
+    let mut signed_data = signed_data(b"content");
+    signed_data.encap_content_info = content(b"tampered");
+
+    let results = signed_data.verify_signer_infos_now::<_, DerSignature, _>(None, None, find_key);
+    assert!(results
+        .iter()
+        .all(|result| matches!(result, Err(Error::MessageDigestMismatch))));
+}
+
+#[test]
+fn verify_unknown_signer() {
+    let signed_data = signed_data(b"content");
+    let results = signed_data
+        .verify_signer_infos_now::<ecdsa::VerifyingKey<NistP256>, DerSignature, _>(
+            None,
+            None,
+            |_| None,
+        );
+    assert!(results
+        .iter()
+        .all(|result| matches!(result, Err(Error::UnknownSigner))));
+}
+
+#[test]
+fn verify_detached_content() {
+    let mut signed_data = signed_data(b"content");
+    let econtent = signed_data.encap_content_info.econtent.take().unwrap();
+
+    let results = signed_data.verify_signer_infos_now::<_, DerSignature, _>(None, None, find_key);
+    assert!(results
+        .iter()
+        .all(|result| matches!(result, Err(Error::MissingContent))));
+
+    let results = signed_data.verify_signer_infos_now::<_, DerSignature, _>(
+        Some(econtent.value()),
+        None,
+        find_key,
+    );
+    assert!(results.iter().all(Result::is_ok));
+}
+
+#[test]
+fn verify_at_time() {
+    let signed_data = signed_data_with_certificate(b"content", Some(signer_certificate()));
+    let signer_info = signed_data.signer_infos.0.get(0).unwrap();
+    let key = find_key(signer_info).unwrap();
+
+    // Only the first signer is identified by the certificate
+    assert!(signed_data.signer_certificate(signer_info).is_some());
+
+    // The validity of the signer certificate, which has long expired, is left to the caller
+    signed_data
+        .verify_signer_info::<_, DerSignature>(signer_info, &key, None, now(), None)
+        .unwrap();
+
+    // The signing time is the time the message was built, which follows the verification time
+    let results = signed_data.verify_signer_infos::<_, DerSignature, _>(
+        None,
+        date_time(2020, 7, 14),
+        None,
+        find_key,
+    );
+    assert!(results.iter().all(|result| matches!(
+        result,
+        Err(Error::SigningTime(violations)) if violations == &[SigningTimeViolation::AfterVerificationTime]
+    )));
+}
+
+#[test]
+fn verify_signing_time_policy() {
+    let signed_data = signed_data_with_certificate(b"content", Some(signer_certificate()));
+    let policy = SigningTimePolicy {
+        within_signer_validity: true,
+        ..Default::default()
+    };
+
+    // The signing time is the time the message was built, after the certificate expired, and
+    // the second signer's certificate is not carried by the message.
+    let results =
+        signed_data.verify_signer_infos_now::<_, DerSignature, _>(None, Some(&policy), find_key);
+    assert!(matches!(
+        &results[0],
+        Err(Error::SigningTime(violations)) if violations == &[SigningTimeViolation::AfterSignerValidity]
+    ));
+    assert!(matches!(
+        &results[1],
+        Err(Error::SigningTime(violations)) if violations == &[SigningTimeViolation::UnknownSignerValidity]
+    ));
+
+    let policy = SigningTimePolicy {
+        not_after: Some(Time::from(GeneralizedTime::from_date_time(date_time(
+            2020, 1, 1,
+        )))),
+        ..Default::default()
+    };
+    let results =
+        signed_data.verify_signer_infos_now::<_, DerSignature, _>(None, Some(&policy), find_key);
+    assert!(results.iter().all(|result| matches!(
+        result,
+        Err(Error::SigningTime(violations)) if violations == &[SigningTimeViolation::AfterUpperBound]
+    )));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_verify_all() {
+    let mut messages: Vec<_> = (0..8u8).map(|i| signed_data(&[i; 32])).collect();
+    messages[5].encap_content_info = content(b"tampered");
+
+    let time = now();
+    let results =
+        cms::verify::par_verify_all::<_, DerSignature, _>(&messages, time, None, |_, si| {
+            find_key(si)
+        });
+    assert_eq!(results.len(), messages.len());
+    for (i, (message, results)) in messages.iter().zip(&results).enumerate() {
+        assert_eq!(results.len(), 2);
+        assert_eq!(results.iter().all(Result::is_ok), i != 5);
+
+        let sequential =
+            message.verify_signer_infos::<_, DerSignature, _>(None, time, None, find_key);
+        let parallel =
+            message.par_verify_signer_infos::<_, DerSignature, _>(None, time, None, find_key);
+        assert_eq!(
+            sequential.iter().map(Result::is_ok).collect::<Vec<_>>(),
+            parallel.iter().map(Result::is_ok).collect::<Vec<_>>()
+        );
+    }
+}
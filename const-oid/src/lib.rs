@@ -241,6 +241,34 @@ where
     pub fn len(&self) -> usize {
         self.arcs().count()
     }
+
+    /// Write the dot-delimited string form of this [`ObjectIdentifier`] to
+    /// the given [`fmt::Write`] without allocating.
+    pub fn write_str(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        for (i, arc) in self.arcs().enumerate() {
+            if i != 0 {
+                w.write_char('.')?;
+            }
+
+            write!(w, "{}", arc)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the dot-delimited string form of this [`ObjectIdentifier`] into
+    /// the given buffer, returning the written string.
+    ///
+    /// Returns [`Error::Length`] if the buffer is too small.
+    pub fn to_str_buf<'b>(&self, buf: &'b mut [u8]) -> Result<&'b str> {
+        let mut writer = SliceWriter { buf, pos: 0 };
+        self.write_str(&mut writer).map_err(|_| Error::Length)?;
+
+        let SliceWriter { buf, pos } = writer;
+        buf.get(..pos)
+            .and_then(|bytes| core::str::from_utf8(bytes).ok())
+            .ok_or(Error::Length)
+    }
 }
 
 impl<B> AsRef<[u8]> for ObjectIdentifier<B>
@@ -276,18 +304,24 @@ impl fmt::Debug for ObjectIdentifier {
 
 impl fmt::Display for ObjectIdentifier {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let len = self.arcs().count();
-
-        for (i, arc) in self.arcs().enumerate() {
-            write!(f, "{}", arc)?;
+        self.write_str(f)
+    }
+}
 
-            if let Some(j) = i.checked_add(1) {
-                if j < len {
-                    write!(f, ".")?;
-                }
-            }
-        }
+/// [`fmt::Write`] adapter for writing into a byte slice.
+struct SliceWriter<'b> {
+    buf: &'b mut [u8],
+    pos: usize,
+}
 
+impl fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.pos.checked_add(s.len()).ok_or(fmt::Error)?;
+        self.buf
+            .get_mut(self.pos..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.pos = end;
         Ok(())
     }
 }
@@ -133,6 +133,35 @@ fn display() {
     assert_eq!(EXAMPLE_OID_LARGE_ARC.to_string(), EXAMPLE_OID_LARGE_ARC_STR);
 }
 
+#[test]
+fn write_str() {
+    let mut s = String::new();
+    EXAMPLE_OID_1.write_str(&mut s).unwrap();
+    assert_eq!(s, EXAMPLE_OID_1_STR);
+}
+
+#[test]
+fn to_str_buf() {
+    let mut buf = [0u8; 64];
+    assert_eq!(
+        EXAMPLE_OID_0.to_str_buf(&mut buf).unwrap(),
+        EXAMPLE_OID_0_STR
+    );
+    assert_eq!(
+        EXAMPLE_OID_LARGE_ARC.to_str_buf(&mut buf).unwrap(),
+        EXAMPLE_OID_LARGE_ARC_STR
+    );
+
+    let mut buf = [0u8; EXAMPLE_OID_1_STR.len()];
+    assert_eq!(
+        EXAMPLE_OID_1.to_str_buf(&mut buf).unwrap(),
+        EXAMPLE_OID_1_STR
+    );
+
+    let mut buf = [0u8; EXAMPLE_OID_1_STR.len() - 1];
+    assert_eq!(EXAMPLE_OID_1.to_str_buf(&mut buf), Err(Error::Length));
+}
+
 #[test]
 fn try_from_u32_slice() {
     let oid1 = ObjectIdentifier::from_arcs([1, 2, 840, 10045, 2, 1]).unwrap();
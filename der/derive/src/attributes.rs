@@ -60,6 +60,10 @@ pub(crate) struct FieldAttrs {
     /// Value of the `#[asn1(context_specific = "...")] attribute if provided.
     pub context_specific: Option<TagNumber>,
 
+    /// Name of the sibling field which defines the type of this `ANY DEFINED BY`
+    /// field, supplied as `#[asn1(defined_by = "...")]`.
+    pub defined_by: Option<Ident>,
+
     /// Indicates name of function that supplies the default value, which will be used in cases
     /// where encoding is omitted per DER and to omit the encoding per DER
     pub default: Option<Path>,
@@ -85,7 +89,7 @@ impl FieldAttrs {
     /// Return true when either an optional or default ASN.1 attribute is associated
     /// with a field. Default signifies optionality due to omission of default values in
     /// DER encodings.
-    fn is_optional(&self) -> bool {
+    pub fn is_optional(&self) -> bool {
         self.optional || self.default.is_some()
     }
 
@@ -94,6 +98,7 @@ impl FieldAttrs {
         let mut asn1_type = None;
         let mut context_specific = None;
         let mut default = None;
+        let mut defined_by = None;
         let mut extensible = None;
         let mut optional = None;
         let mut tag_mode = None;
@@ -119,6 +124,19 @@ impl FieldAttrs {
                 default = Some(attr.value.parse().unwrap_or_else(|e| {
                     abort!(attr.value, "error parsing ASN.1 `default` attribute: {}", e)
                 }));
+            // `defined_by` attribute
+            } else if attr.parse_value::<String>("defined_by").is_some() {
+                if defined_by.is_some() {
+                    abort!(attr.name, "duplicate ASN.1 `defined_by` attribute");
+                }
+
+                defined_by = Some(attr.value.parse().unwrap_or_else(|e| {
+                    abort!(
+                        attr.value,
+                        "error parsing ASN.1 `defined_by` attribute: {}",
+                        e
+                    )
+                }));
             // `extensible` attribute
             } else if let Some(ext) = attr.parse_value("extensible") {
                 if extensible.is_some() {
@@ -167,6 +185,7 @@ impl FieldAttrs {
             asn1_type,
             context_specific,
             default,
+            defined_by,
             extensible: extensible.unwrap_or_default(),
             optional: optional.unwrap_or_default(),
             tag_mode: tag_mode.unwrap_or(type_attrs.tag_mode),
@@ -189,9 +208,23 @@ impl FieldAttrs {
         }
     }
 
+    /// Get the [`Tag`] given by the `context_specific` or `type` attributes, if any.
+    pub fn tag_if_known(&self) -> Option<Tag> {
+        match (self.context_specific, self.asn1_type) {
+            (Some(number), _) => Some(Tag::ContextSpecific {
+                constructed: self.constructed,
+                number,
+            }),
+            (None, Some(asn1_type)) => Some(Tag::Universal(asn1_type)),
+            (None, None) => None,
+        }
+    }
+
     /// Get a `der::Decoder` object which respects these field attributes.
     pub fn decoder(&self) -> TokenStream {
-        if let Some(tag_number) = self.context_specific {
+        if let Some(defined_by) = &self.defined_by {
+            quote!(::der::DecodeDefinedBy::decode_defined_by(&#defined_by, reader)?)
+        } else if let Some(tag_number) = self.context_specific {
             let type_params = self.asn1_type.map(|ty| ty.type_path()).unwrap_or_default();
             let tag_number = tag_number.to_tokens();
 
@@ -245,7 +278,7 @@ impl FieldAttrs {
             let type_params = self.asn1_type.map(|ty| ty.type_path()).unwrap_or_default();
             self.asn1_type.map(|ty| ty.decoder()).unwrap_or_else(|| {
                 quote! {
-                    Option::<#type_params>::decode(reader)?.unwrap_or_else(#default),
+                    Option::<#type_params>::decode(reader)?.unwrap_or_else(#default)
                 }
             })
         } else {
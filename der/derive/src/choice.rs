@@ -82,17 +82,34 @@ impl DeriveChoice {
         let mut tagged_body = Vec::new();
 
         for variant in &self.variants {
-            can_decode_body.push(variant.tag.to_tokens());
+            can_decode_body.push(variant.tag.to_pattern_tokens());
             decode_body.push(variant.to_decode_tokens());
             encode_body.push(variant.to_encode_value_tokens());
             value_len_body.push(variant.to_value_len_tokens());
             tagged_body.push(variant.to_tagged_tokens());
         }
 
+        let const_assertions = if cfg!(feature = "const-assertions") {
+            let tags = self
+                .variants
+                .iter()
+                .map(|variant| variant.tag.to_const_tokens())
+                .map(|tag| quote!(Some(#tag)));
+            let message = format!("`{}` has CHOICE alternatives with the same tag", ident);
+
+            quote! {
+                const _: () = ::der::Tag::assert_distinct(&[#(#tags),*], #message);
+            }
+        } else {
+            TokenStream::new()
+        };
+
         quote! {
+            #const_assertions
+
             impl<#lifetime> ::der::Choice<#lifetime> for #ident<#lt_params> {
                 fn can_decode(tag: ::der::Tag) -> bool {
-                    matches!(tag, #(#can_decode_body)|*)
+                    #(matches!(tag, #can_decode_body))||*
                 }
             }
 
@@ -1,10 +1,10 @@
 //! Choice variant IR and lowerings
 
-use crate::{FieldAttrs, Tag, TypeAttrs};
+use crate::{FieldAttrs, StaticLifetimes, Tag, TypeAttrs};
 use proc_macro2::TokenStream;
 use proc_macro_error::abort;
 use quote::quote;
-use syn::{Fields, Ident, Path, Type, Variant};
+use syn::{visit_mut::VisitMut, Fields, Ident, Path, Type, Variant};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(super) enum TagOrPath {
@@ -57,6 +57,26 @@ impl TagOrPath {
             Self::Path(path) => quote! { <#path as ::der::FixedTag>::TAG },
         }
     }
+
+    pub fn to_pattern_tokens(&self) -> TokenStream {
+        match self {
+            Self::Tag(tag) => tag.to_pattern_tokens(),
+            Self::Path(_) => self.to_tokens(),
+        }
+    }
+
+    /// Lower to tokens usable in a `const` item, where the lifetimes of the
+    /// type aren't in scope: they are replaced with `'static`.
+    pub fn to_const_tokens(&self) -> TokenStream {
+        match self {
+            Self::Tag(tag) => tag.to_tokens(),
+            Self::Path(path) => {
+                let mut path = path.clone();
+                StaticLifetimes.visit_path_mut(&mut path);
+                quote! { <#path as ::der::FixedTag>::TAG }
+            }
+        }
+    }
 }
 
 /// "IR" for a variant of a derived `Choice`.
@@ -81,6 +101,10 @@ impl ChoiceVariant {
             abort!(&ident, "`extensible` is not allowed on CHOICE");
         }
 
+        if attrs.defined_by.is_some() {
+            abort!(&ident, "`defined_by` is not allowed on CHOICE");
+        }
+
         // Validate that variant is a 1-element tuple struct
         match &input.fields {
             // TODO(tarcieri): handle 0 bindings for ASN.1 NULL
@@ -98,7 +122,7 @@ impl ChoiceVariant {
 
     /// Derive a match arm of the impl body for `TryFrom<der::asn1::Any<'_>>`.
     pub(super) fn to_decode_tokens(&self) -> TokenStream {
-        let tag = self.tag.to_tokens();
+        let tag = self.tag.to_pattern_tokens();
         let ident = &self.ident;
         let decoder = self.attrs.decoder();
 
@@ -62,6 +62,22 @@
 //! This behaves like `serde_derive`'s `default` attribute, allowing you to
 //! specify the path to a function which returns a default value.
 //!
+//! As required by DER, the field is omitted when encoding if it's equal to the
+//! default value, and the default value is used when decoding if the field is
+//! absent. It can be combined with the `context_specific` and `type` attributes.
+//!
+//! ### `#[asn1(defined_by = "...")]` attribute: `ANY DEFINED BY` support
+//!
+//! This attribute can be applied to the fields of `struct` types whose type is
+//! determined by the value of a preceding field, typically an `OBJECT IDENTIFIER`,
+//! e.g. the `parameters` of an `AlgorithmIdentifier`.
+//!
+//! The value must be quoted and contain the name of the preceding field, e.g.
+//! `#[asn1(defined_by = "algorithm")]`. The field is decoded using the
+//! [`der::DecodeDefinedBy`] trait, which receives the value of the named field,
+//! and encoded like any other field. It can't be combined with the
+//! `context_specific`, `default` and `type` attributes.
+//!
 //! ### `#[asn1(extensible = "true")]` attribute: support for `...` extensibility operator
 //!
 //! This attribute can be applied to the fields of `struct` types, and will
@@ -104,6 +120,7 @@
 //! [`der`]: https://docs.rs/der/
 //! [`Choice`]: derive@Choice
 //! [`Sequence`]: derive@Sequence
+//! [`der::DecodeDefinedBy`]: https://docs.rs/der/latest/der/trait.DecodeDefinedBy.html
 //! [`der::asn1::BitString`]: https://docs.rs/der/latest/der/asn1/struct.BitString.html
 //! [`der::asn1::Ia5String`]: https://docs.rs/der/latest/der/asn1/struct.Ia5String.html
 //! [`der::asn1::GeneralizedTime`]: https://docs.rs/der/latest/der/asn1/struct.GeneralizedTime.html
@@ -141,13 +158,23 @@ use crate::{
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use proc_macro_error::proc_macro_error;
-use syn::{parse_macro_input, DeriveInput, Lifetime};
+use syn::{parse_macro_input, visit_mut::VisitMut, DeriveInput, Lifetime};
 
 /// Get the default lifetime.
 fn default_lifetime() -> Lifetime {
     Lifetime::new("'__der_lifetime", Span::call_site())
 }
 
+/// Replaces all lifetimes with `'static`, for use in `const` items where the
+/// lifetimes of a type aren't in scope.
+struct StaticLifetimes;
+
+impl VisitMut for StaticLifetimes {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        *lifetime = Lifetime::new("'static", lifetime.apostrophe);
+    }
+}
+
 /// Derive the [`Choice`][1] trait on an `enum`.
 ///
 /// This custom derive macro can be used to automatically impl the
@@ -268,12 +295,16 @@ pub fn derive_sequence(input: TokenStream) -> TokenStream {
     DeriveSequence::new(input).to_tokens().into()
 }
 
-/// Derive the [`ValueOrd`][1] trait on a `struct`.
+/// Derive the [`ValueOrd`][1] trait on a `struct` or a `CHOICE` enum.
 ///
 /// This trait is used in conjunction with ASN.1 `SET OF` types to determine
 /// the lexicographical order of their DER encodings.
 ///
+/// When deriving on an `enum`, it must also derive [`Choice`][2]: values of
+/// different variants are ordered by their tags.
+///
 /// [1]: https://docs.rs/der/latest/der/trait.ValueOrd.html
+/// [2]: derive@Choice
 #[proc_macro_derive(ValueOrd, attributes(asn1))]
 #[proc_macro_error]
 pub fn derive_value_ord(input: TokenStream) -> TokenStream {
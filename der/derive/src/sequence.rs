@@ -35,12 +35,24 @@ impl DeriveSequence {
 
         let type_attrs = TypeAttrs::parse(&input.attrs);
 
-        let fields = data
+        let fields: Vec<SequenceField> = data
             .fields
             .iter()
             .map(|field| SequenceField::new(field, &type_attrs))
             .collect();
 
+        for (i, field) in fields.iter().enumerate() {
+            if let Some(defined_by) = &field.attrs.defined_by {
+                if !fields[..i].iter().any(|f| &f.ident == defined_by) {
+                    abort!(
+                        defined_by,
+                        "`defined_by` must name a field preceding `{}`",
+                        field.ident
+                    );
+                }
+            }
+        }
+
         Self {
             ident: input.ident,
             generics: input.generics.clone(),
@@ -77,7 +89,7 @@ impl DeriveSequence {
         let mut encode_fields = Vec::new();
 
         for field in &self.fields {
-            decode_body.push(field.to_decode_tokens());
+            decode_body.push(field.to_decode_tokens(ident));
             decode_result.push(&field.ident);
 
             let field = field.to_encode_tokens();
@@ -85,7 +97,15 @@ impl DeriveSequence {
             encode_fields.push(quote!(#field.encode(writer)?;));
         }
 
+        let const_assertions = if cfg!(feature = "const-assertions") {
+            self.to_const_assertion_tokens()
+        } else {
+            TokenStream::new()
+        };
+
         quote! {
+            #const_assertions
+
             impl #impl_generics ::der::DecodeValue<#lifetime> for #ident #ty_generics #where_clause {
                 fn decode_value<R: ::der::Reader<#lifetime>>(
                     reader: &mut R,
@@ -124,6 +144,48 @@ impl DeriveSequence {
             impl #impl_generics ::der::Sequence<#lifetime> for #ident #ty_generics #where_clause {}
         }
     }
+
+    /// Assert that each run of `OPTIONAL` and `DEFAULT` fields and the field
+    /// following it have distinct tags, as decoding is ambiguous otherwise.
+    ///
+    /// Fields whose tag isn't known at compile time, see
+    /// [`SequenceField::to_const_tag_tokens`], aren't checked.
+    fn to_const_assertion_tokens(&self) -> TokenStream {
+        let message = format!(
+            "`{}` has an OPTIONAL or DEFAULT field with the same tag as a following field",
+            self.ident
+        );
+        let generic = self
+            .generics
+            .params
+            .iter()
+            .any(|param| !matches!(param, GenericParam::Lifetime(_)));
+
+        let mut assertions = Vec::new();
+        let mut tags = Vec::new();
+
+        for field in &self.fields {
+            tags.push(field.to_const_tag_tokens(generic));
+
+            if !field.attrs.is_optional() {
+                if tags.len() > 1 {
+                    assertions.push(quote! {
+                        const _: () = ::der::Tag::assert_distinct(&[#(#tags),*], #message);
+                    });
+                }
+
+                tags.clear();
+            }
+        }
+
+        if tags.len() > 1 {
+            assertions.push(quote! {
+                const _: () = ::der::Tag::assert_distinct(&[#(#tags),*], #message);
+            });
+        }
+
+        quote! { #(#assertions)* }
+    }
 }
 
 #[cfg(test)]
@@ -1,10 +1,12 @@
 //! Sequence field IR and lowerings
 
-use crate::{Asn1Type, FieldAttrs, TagMode, TagNumber, TypeAttrs};
+use crate::{Asn1Type, FieldAttrs, StaticLifetimes, TagMode, TagNumber, TypeAttrs};
 use proc_macro2::TokenStream;
 use proc_macro_error::abort;
 use quote::quote;
-use syn::{Field, Ident, Path, Type};
+use syn::{
+    ext::IdentExt, visit_mut::VisitMut, Field, GenericArgument, Ident, Path, PathArguments, Type,
+};
 
 /// "IR" for a field of a derived `Sequence`.
 pub(super) struct SequenceField {
@@ -30,17 +32,21 @@ impl SequenceField {
 
         let attrs = FieldAttrs::parse(&field.attrs, type_attrs);
 
-        if attrs.asn1_type.is_some() && attrs.default.is_some() {
+        if attrs.default.is_some() && attrs.optional {
             abort!(
                 ident,
-                "ASN.1 `type` and `default` options cannot be combined"
+                "`optional` and `default` field qualifiers are mutually exclusive"
             );
         }
 
-        if attrs.default.is_some() && attrs.optional {
+        if attrs.defined_by.is_some()
+            && (attrs.asn1_type.is_some()
+                || attrs.context_specific.is_some()
+                || attrs.default.is_some())
+        {
             abort!(
                 ident,
-                "`optional` and `default` field qualifiers are mutually exclusive"
+                "`defined_by` can't be combined with `context_specific`, `default` or `type`"
             );
         }
 
@@ -52,27 +58,24 @@ impl SequenceField {
     }
 
     /// Derive code for decoding a field of a sequence.
-    pub(super) fn to_decode_tokens(&self) -> TokenStream {
+    ///
+    /// Errors are annotated with the name of the field and of the type it belongs to.
+    pub(super) fn to_decode_tokens(&self, type_ident: &Ident) -> TokenStream {
         let mut lowerer = LowerFieldDecoder::new(&self.attrs);
 
-        if self.attrs.asn1_type.is_some() {
-            lowerer.apply_asn1_type(self.attrs.optional);
-        }
-
-        if let Some(default) = &self.attrs.default {
-            // TODO(tarcieri): default in conjunction with ASN.1 types?
-            debug_assert!(
-                self.attrs.asn1_type.is_none(),
-                "`type` and `default` are mutually exclusive"
-            );
-
-            // TODO(tarcieri): support for context-specific fields with defaults?
-            if self.attrs.context_specific.is_none() {
+        match (self.attrs.asn1_type, &self.attrs.default) {
+            (Some(asn1_type), Some(default)) => {
+                lowerer.apply_asn1_type_default(asn1_type, default, &self.attrs);
+            }
+            (Some(_), None) => lowerer.apply_asn1_type(self.attrs.optional),
+            // Context-specific fields are defaulted by `FieldAttrs::decoder`
+            (None, Some(default)) if self.attrs.context_specific.is_none() => {
                 lowerer.apply_default(default, &self.field_type);
             }
+            _ => (),
         }
 
-        lowerer.into_tokens(&self.ident)
+        lowerer.into_tokens(&self.ident, type_ident)
     }
 
     /// Derive code for encoding a field of a sequence.
@@ -81,12 +84,19 @@ impl SequenceField {
         let attrs = &self.attrs;
 
         if let Some(ty) = &attrs.asn1_type {
-            // TODO(tarcieri): default in conjunction with ASN.1 types?
-            debug_assert!(
-                attrs.default.is_none(),
-                "`type` and `default` are mutually exclusive"
-            );
             lowerer.apply_asn1_type(ty, attrs.optional);
+
+            // The converted value is a temporary: omit it before tagging, so the tagged field
+            // borrows it as an optional value
+            if let Some(default) = &attrs.default {
+                lowerer.apply_default(&self.ident, default);
+
+                if let Some(tag_number) = &attrs.context_specific {
+                    lowerer.apply_context_specific(tag_number, &attrs.tag_mode, true);
+                }
+
+                return lowerer.into_tokens();
+            }
         }
 
         if let Some(tag_number) = &attrs.context_specific {
@@ -103,6 +113,63 @@ impl SequenceField {
 
         lowerer.into_tokens()
     }
+
+    /// Derive an `Option<Tag>` expression usable in a `const` item, giving the
+    /// tag of this field if known at compile time.
+    ///
+    /// The tag is given by the `context_specific` or `type` attributes, or
+    /// otherwise by the `der::FixedTag` impl of the field type, or of `T` for
+    /// `OPTIONAL` fields of type `Option<T>`. Without such an impl, or if the
+    /// type may depend on the `generic` parameters of the struct, it's `None`.
+    pub(super) fn to_const_tag_tokens(&self, generic: bool) -> TokenStream {
+        let attrs = &self.attrs;
+
+        if let Some(tag) = attrs.tag_if_known() {
+            let tag = tag.to_tokens();
+            return quote!(Some(#tag));
+        }
+
+        if generic || attrs.defined_by.is_some() {
+            return quote!(None);
+        }
+
+        let mut ty = self.field_type.clone();
+
+        if attrs.optional {
+            if let Some(inner) = option_inner_type(&ty) {
+                ty = inner.clone();
+            }
+        }
+
+        StaticLifetimes.visit_type_mut(&mut ty);
+
+        quote! {
+            {
+                use ::der::NoFixedTag as _;
+                ::der::FixedTagOf::<#ty>::TAG
+            }
+        }
+    }
+}
+
+/// Get `T` if `ty` is written as `Option<T>`.
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+
+    if path.qself.is_some() || segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
 }
 
 /// AST lowerer for field decoders.
@@ -120,11 +187,20 @@ impl LowerFieldDecoder {
     }
 
     ///  the field decoder to tokens.
-    fn into_tokens(self, ident: &Ident) -> TokenStream {
+    fn into_tokens(self, ident: &Ident, type_ident: &Ident) -> TokenStream {
         let decoder = self.decoder;
+        let type_name = type_ident.unraw().to_string();
+        let field_name = ident.unraw().to_string();
 
         quote! {
-            let #ident = #decoder;
+            #[allow(clippy::needless_question_mark, clippy::redundant_closure_call)]
+            let #ident = (|| -> ::der::Result<_> { Ok(#decoder) })().map_err(|err| {
+                const FIELD: ::der::FieldName = ::der::FieldName {
+                    type_name: #type_name,
+                    field: #field_name,
+                };
+                err.in_field(&FIELD)
+            })?;
         }
     }
 
@@ -146,7 +222,26 @@ impl LowerFieldDecoder {
     /// Handle default value for a type.
     fn apply_default(&mut self, default: &Path, field_type: &Type) {
         self.decoder = quote! {
-            Option::<#field_type>::decode(reader)?.unwrap_or_else(#default);
+            Option::<#field_type>::decode(reader)?.unwrap_or_else(#default)
+        };
+    }
+
+    /// Handle default value for a field with an ASN.1 type.
+    fn apply_asn1_type_default(&mut self, asn1_type: Asn1Type, default: &Path, attrs: &FieldAttrs) {
+        let decoder = if attrs.context_specific.is_some() {
+            FieldAttrs {
+                default: None,
+                optional: true,
+                ..attrs.clone()
+            }
+            .decoder()
+        } else {
+            let type_path = asn1_type.type_path();
+            quote!(Option::<#type_path>::decode(reader)?)
+        };
+
+        self.decoder = quote! {
+            #decoder.map(TryInto::try_into).transpose()?.unwrap_or_else(#default)
         };
     }
 }
@@ -194,11 +289,11 @@ impl LowerFieldEncoder {
         let encoder = &self.encoder;
 
         self.encoder = quote! {
-            if &self.#ident == &#default() {
+            (if &self.#ident == &#default() {
                 None
             } else {
                 Some(#encoder)
-            }
+            })
         };
     }
 
@@ -264,11 +359,13 @@ mod tests {
     fn simple() {
         let span = Span::call_site();
         let ident = Ident::new("example_field", span);
+        let type_ident = Ident::new("Example", span);
 
         let attrs = FieldAttrs {
             asn1_type: None,
             context_specific: None,
             default: None,
+            defined_by: None,
             extensible: false,
             optional: false,
             tag_mode: TagMode::Explicit,
@@ -284,9 +381,17 @@ mod tests {
         };
 
         assert_eq!(
-            field.to_decode_tokens().to_string(),
+            field.to_decode_tokens(&type_ident).to_string(),
             quote! {
-                let example_field = reader.decode()?;
+                #[allow(clippy::needless_question_mark, clippy::redundant_closure_call)]
+                let example_field = (|| -> ::der::Result<_> { Ok(reader.decode()?) })()
+                    .map_err(|err| {
+                        const FIELD: ::der::FieldName = ::der::FieldName {
+                            type_name: "Example",
+                            field: "example_field",
+                        };
+                        err.in_field(&FIELD)
+                    })?;
             }
             .to_string()
         );
@@ -304,11 +409,13 @@ mod tests {
     fn implicit() {
         let span = Span::call_site();
         let ident = Ident::new("implicit_field", span);
+        let type_ident = Ident::new("Example", span);
 
         let attrs = FieldAttrs {
             asn1_type: None,
             context_specific: Some(TagNumber(0)),
             default: None,
+            defined_by: None,
             extensible: false,
             optional: false,
             tag_mode: TagMode::Implicit,
@@ -324,20 +431,30 @@ mod tests {
         };
 
         assert_eq!(
-            field.to_decode_tokens().to_string(),
+            field.to_decode_tokens(&type_ident).to_string(),
             quote! {
-                let implicit_field = ::der::asn1::ContextSpecific::<>::decode_implicit(
-                        reader,
-                        ::der::TagNumber::N0
-                    )?
-                    .ok_or_else(|| {
-                        der::Tag::ContextSpecific {
-                            number: ::der::TagNumber::N0,
-                            constructed: false
-                        }
-                        .value_error()
-                    })?
-                    .value;
+                #[allow(clippy::needless_question_mark, clippy::redundant_closure_call)]
+                let implicit_field = (|| -> ::der::Result<_> {
+                    Ok(::der::asn1::ContextSpecific::<>::decode_implicit(
+                            reader,
+                            ::der::TagNumber::N0
+                        )?
+                        .ok_or_else(|| {
+                            der::Tag::ContextSpecific {
+                                number: ::der::TagNumber::N0,
+                                constructed: false
+                            }
+                            .value_error()
+                        })?
+                        .value)
+                })()
+                .map_err(|err| {
+                    const FIELD: ::der::FieldName = ::der::FieldName {
+                        type_name: "Example",
+                        field: "implicit_field",
+                    };
+                    err.in_field(&FIELD)
+                })?;
             }
             .to_string()
         );
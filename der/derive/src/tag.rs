@@ -50,6 +50,29 @@ impl Tag {
             }
         }
     }
+
+    /// Lower this [`Tag`] to a pattern matching it.
+    ///
+    /// Tag numbers above 30 have no associated constant, so they are matched
+    /// with a guard.
+    pub fn to_pattern_tokens(self) -> TokenStream {
+        match self {
+            Tag::ContextSpecific {
+                constructed,
+                number,
+            } if number.is_high() => {
+                let number = number.to_tokens();
+
+                quote! {
+                    ::der::Tag::ContextSpecific {
+                        constructed: #constructed,
+                        number: tag_number,
+                    } if tag_number == #number
+                }
+            }
+            _ => self.to_tokens(),
+        }
+    }
 }
 
 /// Tagging modes: `EXPLICIT` versus `IMPLICIT`.
@@ -99,13 +122,15 @@ impl Display for TagMode {
     }
 }
 
-/// ASN.1 tag numbers (i.e. lower 5 bits of a [`Tag`]).
+/// ASN.1 tag numbers.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
-pub(crate) struct TagNumber(pub u8);
+pub(crate) struct TagNumber(pub u32);
 
 impl TagNumber {
-    /// Maximum tag number supported (inclusive).
-    pub const MAX: u8 = 30;
+    /// Is this tag number above 30, i.e. without a `der::TagNumber::N*` constant?
+    pub fn is_high(self) -> bool {
+        self.0 > 30
+    }
 
     /// Get tokens describing this tag.
     pub fn to_tokens(self) -> TokenStream {
@@ -141,7 +166,7 @@ impl TagNumber {
             28 => quote!(::der::TagNumber::N28),
             29 => quote!(::der::TagNumber::N29),
             30 => quote!(::der::TagNumber::N30),
-            _ => unreachable!("tag number out of range: {}", self),
+            n => quote!(::der::TagNumber::new_u32(#n)),
         }
     }
 }
@@ -150,13 +175,7 @@ impl FromStr for TagNumber {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, ParseError> {
-        let n = s.parse::<u8>().map_err(|_| ParseError)?;
-
-        if n <= Self::MAX {
-            Ok(Self(n))
-        } else {
-            Err(ParseError)
-        }
+        s.parse::<u32>().map(Self).map_err(|_| ParseError)
     }
 }
 
@@ -2,10 +2,12 @@
 //!
 //! This trait is used in conjunction with ASN.1 `SET OF` types to determine
 //! the lexicographical order of their DER encodings.
+//!
+//! Enums are expected to be `CHOICE` types, i.e. to also derive `Choice`:
+//! values of the same variant are compared by their values, while values of
+//! different variants are ordered by their tags.
 
-// TODO(tarcieri): enum support
-
-use crate::{FieldAttrs, TypeAttrs};
+use crate::{FieldAttrs, TagMode, TypeAttrs};
 use proc_macro2::TokenStream;
 use proc_macro_error::abort;
 use quote::quote;
@@ -91,10 +93,11 @@ impl DeriveValueOrd {
             InputType::Enum => {
                 quote! {
                     #[allow(unused_imports)]
-                    use ::der::ValueOrd;
+                    use ::der::{DerOrd, ValueOrd};
                     match (self, other) {
                         #(#body)*
-                        _ => unreachable!(),
+                        #[allow(unreachable_patterns)]
+                        _ => ::der::Tagged::tag(self).der_cmp(&::der::Tagged::tag(other)),
                     }
                 }
             }
@@ -176,8 +179,22 @@ impl ValueField {
         if self.is_enum {
             let binding1 = quote!(Self::#ident(this));
             let binding2 = quote!(Self::#ident(other));
-            quote! {
-                (#binding1, #binding2) => this.value_cmp(other),
+
+            let (mut this, mut other) = (quote!(this), quote!(other));
+            if let Some(ty) = &self.attrs.asn1_type {
+                this = ty.encoder(&this);
+                other = ty.encoder(&other);
+            }
+
+            // The value of an `EXPLICIT` variant is the complete encoding of the inner type
+            if self.attrs.context_specific.is_some() && self.attrs.tag_mode == TagMode::Explicit {
+                quote! {
+                    (#binding1, #binding2) => #this.der_cmp(&#other),
+                }
+            } else {
+                quote! {
+                    (#binding1, #binding2) => #this.value_cmp(&#other),
+                }
             }
         } else {
             let mut binding1 = quote!(self.#ident);
@@ -0,0 +1,49 @@
+//! Decodes arbitrary input with the decoders of this crate, which must return errors instead
+//! of panicking on malformed input.
+
+#![no_main]
+
+use der::{
+    asn1::{
+        Any, BitString, BmpString, GeneralizedTime, Ia5String, Int, Null, ObjectIdentifier,
+        OctetString, PrintableString, SequenceOf, SetOfVec, TeletexString, Uint, UtcTime,
+        Utf8StringRef, VideotexStringRef,
+    },
+    Decode, PemReader, RawHeader,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &[u8]| {
+    let _ = Any::from_der(input);
+    let _ = RawHeader::from_der(input);
+    let _ = BitString::from_der(input);
+    let _ = BmpString::from_der(input);
+    let _ = GeneralizedTime::from_der(input);
+    let _ = Ia5String::from_der(input);
+    let _ = Int::from_der(input);
+    let _ = Null::from_der(input);
+    let _ = ObjectIdentifier::from_der(input);
+    let _ = OctetString::from_der(input);
+    let _ = PrintableString::from_der(input);
+    let _ = SequenceOf::<Any, 8>::from_der(input);
+    let _ = SetOfVec::<Any>::from_der(input);
+    let _ = TeletexString::from_der(input);
+    let _ = Uint::from_der(input);
+    let _ = UtcTime::from_der(input);
+    let _ = Utf8StringRef::from_der(input);
+    let _ = VideotexStringRef::from_der(input);
+    let _ = bool::from_der(input);
+    let _ = f64::from_der(input);
+    let _ = i64::from_der(input);
+    let _ = u64::from_der(input);
+
+    // Transcoded BER must be accepted as DER
+    if let Ok(der) = der::ber_to_der(input) {
+        der::check_der(&der).expect("ber_to_der output is not DER");
+        assert_eq!(der::ber_to_der(&der).as_deref(), Ok(der.as_slice()));
+    }
+
+    if let Ok(mut reader) = PemReader::new(input) {
+        let _ = Any::decode(&mut reader);
+    }
+});
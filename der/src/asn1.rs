@@ -6,6 +6,8 @@ mod internal_macros;
 
 mod any;
 mod bit_string;
+#[cfg(feature = "alloc")]
+mod bmp_string;
 mod boolean;
 mod choice;
 mod context_specific;
@@ -24,9 +26,12 @@ mod sequence;
 mod sequence_of;
 mod set_of;
 mod teletex_string;
+#[cfg(feature = "alloc")]
+mod universal_string;
 mod utc_time;
 mod utf8_string;
 mod videotex_string;
+mod visible_string;
 
 pub use self::{
     any::AnyRef,
@@ -46,18 +51,22 @@ pub use self::{
     utc_time::UtcTime,
     utf8_string::Utf8StringRef,
     videotex_string::VideotexStringRef,
+    visible_string::VisibleStringRef,
 };
 
 #[cfg(feature = "alloc")]
 pub use self::{
     any::Any,
     bit_string::BitString,
+    bmp_string::BmpString,
     ia5_string::Ia5String,
     integer::{int::Int, uint::Uint},
     octet_string::OctetString,
     printable_string::PrintableString,
     set_of::SetOfVec,
     teletex_string::TeletexString,
+    universal_string::UniversalString,
+    visible_string::VisibleString,
 };
 
 #[cfg(feature = "oid")]
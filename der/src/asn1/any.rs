@@ -130,8 +130,19 @@ impl Tagged for AnyRef<'_> {
     }
 }
 
+/// With the `alloc` feature, values are compared after canonicalization of their contents, so
+/// BER-sourced values are ordered consistently with their DER equivalents. Values which can't be
+/// transcoded to DER are compared bytewise.
 impl ValueOrd for AnyRef<'_> {
     fn value_cmp(&self, other: &Self) -> Result<Ordering> {
+        #[cfg(feature = "alloc")]
+        if self.value != other.value {
+            if let (Ok(value), Ok(other_value)) = (self.canonical_value(), other.canonical_value())
+            {
+                return BytesRef::new(&value)?.der_cmp(&BytesRef::new(&other_value)?);
+            }
+        }
+
         self.value.der_cmp(&other.value)
     }
 }
@@ -156,8 +167,8 @@ pub use self::allocating::Any;
 #[cfg(feature = "alloc")]
 mod allocating {
     use super::*;
-    use crate::{referenced::*, BytesOwned};
-    use alloc::boxed::Box;
+    use crate::{ber::check_der_value, ber_to_der, referenced::*, BytesOwned, Encode};
+    use alloc::{borrow::Cow, boxed::Box};
 
     /// ASN.1 `ANY`: represents any explicitly tagged ASN.1 value.
     ///
@@ -270,9 +281,11 @@ mod allocating {
         }
     }
 
+    /// Values are compared like [`AnyRef`]s: after canonicalization of their contents, or bytewise
+    /// if they can't be transcoded to DER. See [`Any::content_eq`].
     impl ValueOrd for Any {
         fn value_cmp(&self, other: &Self) -> Result<Ordering> {
-            self.value.der_cmp(&other.value)
+            AnyRef::from(self).value_cmp(&other.into())
         }
     }
 
@@ -306,10 +319,155 @@ mod allocating {
         }
     }
 
+    impl<'a> AnyRef<'a> {
+        /// Get the value of this [`AnyRef`], re-encoded as DER if necessary.
+        pub(crate) fn canonical_value(self) -> Result<Cow<'a, [u8]>> {
+            // only values which actually use BER productions need to be transcoded
+            if check_der_value(self.tag, self.value()).is_ok() {
+                return Ok(Cow::Borrowed(self.value()));
+            }
+
+            let der = ber_to_der(&self.to_der()?)?;
+
+            let mut reader = SliceReader::new(&der)?;
+            let header = Header::decode(&mut reader)?;
+            Ok(Cow::Owned(reader.read_slice(header.length)?.to_vec()))
+        }
+    }
+
     impl Any {
+        /// Compare the contents of this value with `other` after canonicalizing both as DER.
+        ///
+        /// Values decoded from BER sources may contain non-DER productions such as
+        /// indefinite lengths or constructed strings nested within them. Such values are
+        /// equal to their DER equivalents according to this method, but not according to
+        /// [`PartialEq`]. See [`ber_to_der`] for the canonicalization performed.
+        pub fn content_eq(&self, other: &Any) -> Result<bool> {
+            if self.tag != other.tag {
+                return Ok(false);
+            }
+
+            if self.value == other.value {
+                return Ok(true);
+            }
+
+            Ok(self.canonical_value()? == other.canonical_value()?)
+        }
+
+        /// Convert this value into its canonical DER form.
+        ///
+        /// Unlike [`ValueOrd`], [`DerOrd`] also compares the length of values, so values of a
+        /// `SET OF` should be canonicalized for BER-sourced elements to sort consistently.
+        pub fn to_canonical(&self) -> Result<Any> {
+            match self.canonical_value()? {
+                Cow::Borrowed(_) => Ok(self.clone()),
+                Cow::Owned(value) => Any::new(self.tag, value),
+            }
+        }
+
+        /// Get the value of this [`Any`], re-encoded as DER if necessary.
+        pub(crate) fn canonical_value(&self) -> Result<Cow<'_, [u8]>> {
+            AnyRef::from(self).canonical_value()
+        }
+
         /// Is this value an ASN.1 `NULL` value?
         pub fn is_null(&self) -> bool {
             self.owned_to_ref() == AnyRef::NULL
         }
     }
 }
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::{Any, AnyRef};
+    use crate::{asn1::SetOfVec, Decode, DerOrd, ValueOrd};
+    use alloc::borrow::Cow;
+    use core::cmp::Ordering;
+    use hex_literal::hex;
+
+    /// `SEQUENCE { BOOLEAN TRUE, OCTET STRING }` in DER.
+    const DER: &[u8] = &hex!("3009 0101FF 0404 AABBCCDD");
+
+    /// Same value with a BER `BOOLEAN` and constructed `OCTET STRING`.
+    const BER_SAME_LENGTH: &[u8] = &hex!("300D 010101 2408 0402AABB 0402CCDD");
+
+    #[test]
+    fn content_eq() {
+        let der = Any::from_der(DER).unwrap();
+        let ber = Any::from_der(BER_SAME_LENGTH).unwrap();
+
+        assert_ne!(der, ber);
+        assert!(der.content_eq(&ber).unwrap());
+        assert!(ber.content_eq(&der).unwrap());
+
+        let other = Any::from_der(&hex!("3009 0101FF 0404 AABBCCEE")).unwrap();
+        assert!(!der.content_eq(&other).unwrap());
+        assert!(!ber.content_eq(&other).unwrap());
+    }
+
+    #[test]
+    fn value_cmp() {
+        // elements of a BER `SET` are sorted before comparison
+        let der = Any::from_der(&hex!("3106 040101 040102")).unwrap();
+        let ber = Any::from_der(&hex!("3106 040102 040101")).unwrap();
+        assert_eq!(der.value_cmp(&ber).unwrap(), Ordering::Equal);
+
+        // BER `BOOLEAN` true is canonicalized before comparison
+        let der = Any::from_der(&hex!("3003 0101FF")).unwrap();
+        let ber = Any::from_der(&hex!("3003 010101")).unwrap();
+        assert_eq!(der.value_cmp(&ber).unwrap(), Ordering::Equal);
+        assert_eq!(ber.value_cmp(&der).unwrap(), Ordering::Equal);
+
+        // values in the high tag number form are compared too
+        let der = Any::from_der(&hex!("7F1F05 3003 0101FF")).unwrap();
+        let ber = Any::from_der(&hex!("7F1F05 3003 010101")).unwrap();
+        assert_eq!(der.value_cmp(&ber).unwrap(), Ordering::Equal);
+    }
+
+    #[test]
+    fn value_cmp_untranscodable() {
+        // the first element's `OCTET STRING` is truncated, so it can't be transcoded to DER
+        let der = hex!("310a 3003 040501 3003 050005");
+        let set = SetOfVec::<Any>::from_der(&der).unwrap();
+        assert_eq!(set.len(), 2);
+
+        let set = SetOfVec::<AnyRef<'_>>::from_der(&der).unwrap();
+        assert_eq!(set.len(), 2);
+
+        let (a, b) = (set.as_slice()[0], set.as_slice()[1]);
+        assert_eq!(a.value_cmp(&b).unwrap(), Ordering::Less);
+        assert_eq!(
+            Any::from(a).value_cmp(&Any::from(b)).unwrap(),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn canonical_value() {
+        // DER values are borrowed rather than transcoded
+        let der = Any::from_der(DER).unwrap();
+        assert!(matches!(der.canonical_value().unwrap(), Cow::Borrowed(_)));
+
+        let ber = Any::from_der(BER_SAME_LENGTH).unwrap();
+        assert!(matches!(ber.canonical_value().unwrap(), Cow::Owned(_)));
+    }
+
+    #[test]
+    fn to_canonical() {
+        let der = Any::from_der(DER).unwrap();
+        let ber = Any::from_der(BER_SAME_LENGTH).unwrap();
+        assert_eq!(ber.to_canonical().unwrap(), der);
+        assert_eq!(der.to_canonical().unwrap(), der);
+
+        // BER-sourced elements of a `SET OF` sort consistently once canonicalized
+        let smaller = Any::from_der(&hex!("3009 0101FF 0404 AABBCCCC")).unwrap();
+        assert_eq!(ber.der_cmp(&smaller).unwrap(), Ordering::Greater);
+        assert_eq!(
+            ber.to_canonical().unwrap().der_cmp(&der).unwrap(),
+            Ordering::Equal
+        );
+
+        let set = SetOfVec::try_from([ber.to_canonical().unwrap(), smaller.clone()]).unwrap();
+        assert_eq!(set.as_slice(), [smaller, der]);
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
     BytesRef, DecodeValue, DerOrd, EncodeValue, Error, ErrorKind, FixedTag, Header, Length, Reader,
     Result, Tag, ValueOrd, Writer,
 };
-use core::{cmp::Ordering, iter::FusedIterator};
+use core::{cmp::Ordering, iter::FusedIterator, ops::Range};
 
 /// ASN.1 `BIT STRING` type.
 ///
@@ -108,13 +108,46 @@ impl<'a> BitStringRef<'a> {
         self.inner.as_slice()
     }
 
+    /// Get the bit at the given index, where index `0` is the most
+    /// significant bit of the first octet.
+    ///
+    /// Returns `None` if the index is out of range, i.e. refers to one of
+    /// the unused bits or lies past the end of the `BIT STRING`.
+    #[allow(clippy::integer_arithmetic)]
+    pub fn bit(&self, index: usize) -> Option<bool> {
+        if index >= self.bit_len() {
+            return None;
+        }
+
+        let byte = self.raw_bytes().get(index / 8)?;
+        let bit = 1u8 << (7 - (index % 8));
+        Some(byte & bit != 0)
+    }
+
     /// Iterator over the bits of this `BIT STRING`.
     pub fn bits(self) -> BitStringIter<'a> {
         BitStringIter {
             bit_string: self,
             position: 0,
+            end: self.bit_len(),
         }
     }
+
+    /// Iterator over the given range of bits of this `BIT STRING`.
+    ///
+    /// Returns `None` if the range is out of bounds. Unused bits are never
+    /// considered in range.
+    pub fn bits_range(self, range: Range<usize>) -> Option<BitStringIter<'a>> {
+        if range.start > range.end || range.end > self.bit_len() {
+            return None;
+        }
+
+        Some(BitStringIter {
+            bit_string: self,
+            position: range.start,
+            end: range.end,
+        })
+    }
 }
 
 impl_any_conversions!(BitStringRef<'a>, 'a);
@@ -300,10 +333,53 @@ mod allocating {
             self.inner.as_slice()
         }
 
+        /// Get the bit at the given index, where index `0` is the most
+        /// significant bit of the first octet.
+        ///
+        /// Returns `None` if the index is out of range.
+        pub fn bit(&self, index: usize) -> Option<bool> {
+            BitStringRef::from(self).bit(index)
+        }
+
         /// Iterator over the bits of this `BIT STRING`.
         pub fn bits(&self) -> BitStringIter<'_> {
             BitStringRef::from(self).bits()
         }
+
+        /// Iterator over the given range of bits of this `BIT STRING`.
+        ///
+        /// Returns `None` if the range is out of bounds.
+        pub fn bits_range(&self, range: Range<usize>) -> Option<BitStringIter<'_>> {
+            BitStringRef::from(self).bits_range(range)
+        }
+
+        /// Create a new ASN.1 `BIT STRING` from an iterator over its bits.
+        ///
+        /// The unused bits of the final octet are set to zero, as required by
+        /// DER. Combined with [`BitString::bits_range`] this can be used to
+        /// extract an arbitrary (not necessarily octet-aligned) run of bits.
+        #[allow(clippy::integer_arithmetic)]
+        pub fn from_bits(bits: impl IntoIterator<Item = bool>) -> Result<Self> {
+            let mut inner = Vec::new();
+            let mut bit_length = 0usize;
+
+            for bit in bits {
+                let offset = bit_length % 8;
+
+                if offset == 0 {
+                    inner.push(0);
+                }
+
+                if let (true, Some(byte)) = (bit, inner.last_mut()) {
+                    *byte |= 1u8 << (7 - offset);
+                }
+
+                bit_length = bit_length.checked_add(1).ok_or(ErrorKind::Overflow)?;
+            }
+
+            let unused_bits = u8::try_from((8 - bit_length % 8) % 8)?;
+            Self::new(unused_bits, inner)
+        }
     }
 
     impl_any_conversions!(BitString);
@@ -392,27 +468,44 @@ pub struct BitStringIter<'a> {
 
     /// Current bit position within the iterator.
     position: usize,
+
+    /// Bit position at which iteration ends (exclusive).
+    end: usize,
 }
 
 impl<'a> Iterator for BitStringIter<'a> {
     type Item = bool;
 
-    #[allow(clippy::integer_arithmetic)]
     fn next(&mut self) -> Option<bool> {
-        if self.position >= self.bit_string.bit_len() {
+        if self.position >= self.end {
             return None;
         }
 
-        let byte = self.bit_string.raw_bytes().get(self.position / 8)?;
-        let bit = 1u8 << (7 - (self.position % 8));
+        let bit = self.bit_string.bit(self.position)?;
         self.position = self.position.checked_add(1)?;
-        Some(byte & bit != 0)
+        Some(bit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a> DoubleEndedIterator for BitStringIter<'a> {
+    fn next_back(&mut self) -> Option<bool> {
+        if self.position >= self.end {
+            return None;
+        }
+
+        self.end = self.end.checked_sub(1)?;
+        self.bit_string.bit(self.end)
     }
 }
 
 impl<'a> ExactSizeIterator for BitStringIter<'a> {
     fn len(&self) -> usize {
-        self.bit_string.bit_len()
+        self.end.saturating_sub(self.position)
     }
 }
 
@@ -542,6 +635,70 @@ mod tests {
         assert_eq!(bits.next(), None);
     }
 
+    #[test]
+    fn bit_string_iter_len_tracks_remaining() {
+        let bs = parse_bitstring(&hex!("066e5dc0")).unwrap();
+        let mut bits = bs.bits();
+        assert_eq!(bits.len(), 18);
+        bits.next();
+        assert_eq!(bits.len(), 17);
+        bits.next_back();
+        assert_eq!(bits.len(), 16);
+        assert_eq!(bits.count(), 16);
+    }
+
+    #[test]
+    fn bit_by_index() {
+        let bs = parse_bitstring(&hex!("066e5dc0")).unwrap();
+        assert_eq!(bs.bit(0), Some(false));
+        assert_eq!(bs.bit(1), Some(true));
+        assert_eq!(bs.bit(17), Some(true));
+
+        // unused bits are out of range
+        assert_eq!(bs.bit(18), None);
+        assert_eq!(bs.bit(24), None);
+    }
+
+    #[test]
+    fn bits_range() {
+        let bs = parse_bitstring(&hex!("066e5dc0")).unwrap();
+
+        // Expected: 011011100101110111
+        let bits: [bool; 5] = [false, false, true, false, true];
+        assert!(bs.bits_range(7..12).unwrap().eq(bits));
+        assert!(bs
+            .bits_range(7..12)
+            .unwrap()
+            .rev()
+            .eq(bits.into_iter().rev()));
+        assert_eq!(bs.bits_range(18..18).unwrap().len(), 0);
+        assert!(bs.bits_range(10..19).is_none());
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = bs.bits_range(5..4);
+        assert!(reversed.is_none());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn bit_string_from_bits_range() {
+        use super::BitString;
+
+        let bs = parse_bitstring(&hex!("066e5dc0")).unwrap();
+
+        // Extract the misaligned run `1100101110` (bits 5..15)
+        let sub = BitString::from_bits(bs.bits_range(5..15).unwrap()).unwrap();
+        assert_eq!(sub.bit_len(), 10);
+        assert_eq!(sub.unused_bits(), 6);
+        assert_eq!(sub.raw_bytes(), &hex!("cb80"));
+
+        let aligned = BitString::from_bits(bs.bits_range(0..8).unwrap()).unwrap();
+        assert_eq!(aligned.as_bytes().unwrap(), &hex!("6e"));
+
+        let empty = BitString::from_bits(core::iter::empty()).unwrap();
+        assert_eq!(empty.bit_len(), 0);
+        assert_eq!(empty.unused_bits(), 0);
+    }
+
     #[test]
     fn reject_unused_bits_in_empty_string() {
         assert_eq!(
@@ -10,6 +10,7 @@ use core::cmp::Ordering;
 ///
 /// This type decodes/encodes a field which is specific to a particular context
 /// and is identified by a [`TagNumber`].
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub struct ContextSpecific<T> {
     /// Context-specific tag number sans the leading `0b10000000` class
@@ -88,8 +89,8 @@ impl<T> ContextSpecific<T> {
     where
         F: FnOnce(&mut R) -> Result<Self>,
     {
-        while let Some(octet) = reader.peek_byte() {
-            let tag = Tag::try_from(octet)?;
+        while reader.peek_byte().is_some() {
+            let tag = reader.peek_tag()?;
 
             if !tag.is_context_specific() || (tag.number() > tag_number) {
                 break;
@@ -256,6 +257,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::ContextSpecific;
+    use super::ContextSpecificRef;
     use crate::{asn1::BitStringRef, Decode, Encode, SliceReader, TagMode, TagNumber};
     use hex_literal::hex;
 
@@ -266,7 +268,7 @@ mod tests {
     #[test]
     fn round_trip() {
         let field = ContextSpecific::<BitStringRef<'_>>::from_der(EXAMPLE_BYTES).unwrap();
-        assert_eq!(field.tag_number.value(), 1);
+        assert_eq!(field.tag_number.value(), Some(1));
         assert_eq!(
             field.value,
             BitStringRef::from_bytes(&EXAMPLE_BYTES[5..]).unwrap()
@@ -351,4 +353,37 @@ mod tests {
             None
         );
     }
+
+    #[test]
+    fn context_specific_with_high_tag_number() {
+        let tag = TagNumber::new(33);
+
+        // [0] skipped, then [33] EXPLICIT INTEGER
+        let bytes = hex!("A003020100BF2103020101");
+        let mut reader = SliceReader::new(&bytes).unwrap();
+        let field = ContextSpecific::<u8>::decode_explicit(&mut reader, tag)
+            .unwrap()
+            .unwrap();
+        assert_eq!(field.tag_number, tag);
+        assert_eq!(field.value, 1);
+        let mut buf = [0u8; 16];
+        assert_eq!(field.encode_to_slice(&mut buf).unwrap(), &bytes[5..]);
+
+        let field_ref = ContextSpecificRef {
+            tag_number: tag,
+            tag_mode: TagMode::Implicit,
+            value: &1u8,
+        };
+        assert_eq!(
+            field_ref.encode_to_slice(&mut buf).unwrap(),
+            hex!("9F210101")
+        );
+
+        // [33] is greater than [31]
+        let mut reader = SliceReader::new(&bytes[5..]).unwrap();
+        assert_eq!(
+            ContextSpecific::<u8>::decode_implicit(&mut reader, TagNumber::new(31)).unwrap(),
+            None
+        );
+    }
 }
@@ -60,6 +60,10 @@ impl GeneralizedTime {
     }
 
     /// Instantiate from [`SystemTime`].
+    ///
+    /// On targets without a system clock, such as `wasm32-unknown-unknown` where
+    /// [`SystemTime::now`] panics, the current time is to be obtained from the environment
+    /// (e.g. `Date.now()` in a browser) and passed to [`GeneralizedTime::from_unix_duration`].
     #[cfg(feature = "std")]
     pub fn from_system_time(time: SystemTime) -> Result<Self> {
         DateTime::try_from(time)
@@ -95,6 +95,9 @@ mod allocation {
     use alloc::string::String;
     use core::{fmt, ops::Deref};
 
+    #[cfg(feature = "arbitrary")]
+    use alloc::vec::Vec;
+
     /// ASN.1 `IA5String` type.
     ///
     /// Supports the [International Alphabet No. 5 (IA5)] character encoding, i.e.
@@ -128,6 +131,23 @@ mod allocation {
 
     impl_ia5_string!(Ia5String);
 
+    #[cfg(feature = "arbitrary")]
+    impl<'a> arbitrary::Arbitrary<'a> for Ia5String {
+        fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+            // Clear the high bit of each byte to map the input onto the IA5 alphabet
+            let bytes = Vec::<u8>::arbitrary(u)?
+                .into_iter()
+                .map(|byte| byte & 0x7F)
+                .collect::<Vec<_>>();
+
+            Self::new(&bytes).map_err(|_| arbitrary::Error::IncorrectFormat)
+        }
+
+        fn size_hint(depth: usize) -> (usize, Option<usize>) {
+            Vec::<u8>::size_hint(depth)
+        }
+    }
+
     impl Deref for Ia5String {
         type Target = StrOwned;
 
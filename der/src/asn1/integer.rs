@@ -3,9 +3,19 @@
 pub(super) mod int;
 pub(super) mod uint;
 
-use core::{cmp::Ordering, mem};
+use core::{
+    cmp::Ordering,
+    mem,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
+        NonZeroU32, NonZeroU64, NonZeroU8,
+    },
+};
 
-use crate::{EncodeValue, Result, SliceWriter};
+use crate::{
+    AnyRef, DecodeValue, EncodeValue, Error, FixedTag, Header, Length, Reader, Result, SliceWriter,
+    Tag, ValueOrd, Writer,
+};
 
 /// Is the highest bit of the first byte in the slice set to `1`? (if present)
 #[inline]
@@ -24,20 +34,82 @@ where
     const MAX_INT_SIZE: usize = 16;
     debug_assert!(mem::size_of::<T>() <= MAX_INT_SIZE);
 
-    let mut buf1 = [0u8; MAX_INT_SIZE];
+    // Unsigned integers with the highest bit set are encoded with a leading
+    // `0x00`, which needs an extra byte.
+    const MAX_ENCODED_SIZE: usize = MAX_INT_SIZE + 1;
+
+    let mut buf1 = [0u8; MAX_ENCODED_SIZE];
     let mut encoder1 = SliceWriter::new(&mut buf1);
     a.encode_value(&mut encoder1)?;
 
-    let mut buf2 = [0u8; MAX_INT_SIZE];
+    let mut buf2 = [0u8; MAX_ENCODED_SIZE];
     let mut encoder2 = SliceWriter::new(&mut buf2);
     b.encode_value(&mut encoder2)?;
 
     Ok(encoder1.finish()?.cmp(encoder2.finish()?))
 }
 
+macro_rules! impl_non_zero {
+    ($($non_zero:ty => $int:ty),+) => {
+        $(
+            impl<'a> DecodeValue<'a> for $non_zero {
+                fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+                    <$non_zero>::new(<$int>::decode_value(reader, header)?)
+                        .ok_or_else(|| Self::TAG.value_error())
+                }
+            }
+
+            impl EncodeValue for $non_zero {
+                fn value_len(&self) -> Result<Length> {
+                    self.get().value_len()
+                }
+
+                fn encode_value(&self, writer: &mut impl Writer) -> Result<()> {
+                    self.get().encode_value(writer)
+                }
+            }
+
+            impl FixedTag for $non_zero {
+                const TAG: Tag = Tag::Integer;
+            }
+
+            impl ValueOrd for $non_zero {
+                fn value_cmp(&self, other: &Self) -> Result<Ordering> {
+                    value_cmp(self.get(), other.get())
+                }
+            }
+
+            impl TryFrom<AnyRef<'_>> for $non_zero {
+                type Error = Error;
+
+                fn try_from(any: AnyRef<'_>) -> Result<Self> {
+                    any.decode_as()
+                }
+            }
+        )+
+    };
+}
+
+impl_non_zero!(
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroI128 => i128,
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128
+);
+
 #[cfg(test)]
 pub(crate) mod tests {
-    use crate::{Decode, Encode};
+    use crate::{Decode, Encode, ErrorKind, Tag, ValueOrd};
+    use core::{
+        cmp::Ordering,
+        num::{NonZeroI128, NonZeroI32, NonZeroU128, NonZeroU64, NonZeroU8},
+    };
 
     // Vectors from Section 5.7 of:
     // https://luca.ntop.org/Teaching/Appunti/asn1.html
@@ -150,6 +222,69 @@ pub(crate) mod tests {
         assert_eq!(I65535_BYTES, 65535u16.encode_to_slice(&mut buffer).unwrap());
     }
 
+    #[test]
+    fn round_trip_u128() {
+        let mut buffer = [0u8; 19];
+        let der = u128::MAX.encode_to_slice(&mut buffer).unwrap();
+        assert_eq!(der[..3], [0x02, 0x11, 0x00]);
+        assert!(der[3..].iter().all(|&byte| byte == 0xFF));
+        assert_eq!(u128::MAX, u128::from_der(der).unwrap());
+
+        assert_eq!(I256_BYTES, 256u128.encode_to_slice(&mut buffer).unwrap());
+        assert_eq!(256, u128::from_der(I256_BYTES).unwrap());
+    }
+
+    #[test]
+    fn round_trip_i128() {
+        let mut buffer = [0u8; 18];
+        let der = i128::MIN.encode_to_slice(&mut buffer).unwrap();
+        assert_eq!(der[..3], [0x02, 0x10, 0x80]);
+        assert!(der[3..].iter().all(|&byte| byte == 0x00));
+        assert_eq!(i128::MIN, i128::from_der(der).unwrap());
+
+        let der = i128::MAX.encode_to_slice(&mut buffer).unwrap();
+        assert_eq!(der[..3], [0x02, 0x10, 0x7F]);
+        assert_eq!(i128::MAX, i128::from_der(der).unwrap());
+
+        assert_eq!(
+            INEG129_BYTES,
+            (-129i128).encode_to_slice(&mut buffer).unwrap()
+        );
+        assert_eq!(-129, i128::from_der(INEG129_BYTES).unwrap());
+    }
+
+    #[test]
+    fn value_cmp_u128() {
+        assert_eq!(
+            u128::MAX.value_cmp(&(u128::MAX - 1)).unwrap(),
+            Ordering::Greater
+        );
+        assert_eq!(u128::MAX.value_cmp(&u128::MAX).unwrap(), Ordering::Equal);
+    }
+
+    #[test]
+    fn non_zero() {
+        let mut buffer = [0u8; 19];
+
+        let value = NonZeroU64::new(65535).unwrap();
+        assert_eq!(I65535_BYTES, value.encode_to_slice(&mut buffer).unwrap());
+        assert_eq!(value, NonZeroU64::from_der(I65535_BYTES).unwrap());
+
+        let value = NonZeroI32::new(-129).unwrap();
+        assert_eq!(INEG129_BYTES, value.encode_to_slice(&mut buffer).unwrap());
+        assert_eq!(value, NonZeroI32::from_der(INEG129_BYTES).unwrap());
+
+        let value = NonZeroU128::new(u128::MAX).unwrap();
+        let der = value.encode_to_slice(&mut buffer).unwrap();
+        assert_eq!(value, NonZeroU128::from_der(der).unwrap());
+
+        assert_eq!(
+            NonZeroU8::from_der(I0_BYTES).unwrap_err().kind(),
+            ErrorKind::Value { tag: Tag::Integer }
+        );
+        assert!(NonZeroI128::from_der(I0_BYTES).is_err());
+    }
+
     /// Integers must be encoded with a minimum number of octets
     #[test]
     fn reject_non_canonical() {
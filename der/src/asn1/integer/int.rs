@@ -213,6 +213,19 @@ mod allocating {
 
     impl_any_conversions!(Int);
 
+    #[cfg(feature = "arbitrary")]
+    impl<'a> arbitrary::Arbitrary<'a> for Int {
+        fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+            let bytes = Vec::<u8>::arbitrary(u)?;
+            validate_canonical(&bytes).map_err(|_| arbitrary::Error::IncorrectFormat)?;
+            Self::new(&bytes).map_err(|_| arbitrary::Error::IncorrectFormat)
+        }
+
+        fn size_hint(depth: usize) -> (usize, Option<usize>) {
+            Vec::<u8>::size_hint(depth)
+        }
+    }
+
     impl<'a> DecodeValue<'a> for Int {
         fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
             let bytes = BytesOwned::decode_value(reader, header)?;
@@ -164,6 +164,9 @@ mod allocating {
         Tag, Writer,
     };
 
+    #[cfg(feature = "arbitrary")]
+    use alloc::vec::Vec;
+
     /// Unsigned arbitrary precision ASN.1 `INTEGER` type.
     ///
     /// Provides heap-allocated storage for big endian bytes which comprise an
@@ -205,6 +208,21 @@ mod allocating {
 
     impl_any_conversions!(Uint);
 
+    #[cfg(feature = "arbitrary")]
+    impl<'a> arbitrary::Arbitrary<'a> for Uint {
+        fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+            let bytes = Vec::<u8>::arbitrary(u)?;
+            if bytes.is_empty() {
+                return Err(arbitrary::Error::IncorrectFormat);
+            }
+            Self::new(&bytes).map_err(|_| arbitrary::Error::IncorrectFormat)
+        }
+
+        fn size_hint(depth: usize) -> (usize, Option<usize>) {
+            Vec::<u8>::size_hint(depth)
+        }
+    }
+
     impl<'a> DecodeValue<'a> for Uint {
         fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
             let bytes = BytesOwned::decode_value(reader, header)?;
@@ -1,6 +1,6 @@
 //! ASN.1 `OPTIONAL` as mapped to Rust's `Option` type
 
-use crate::{Choice, Decode, DerOrd, Encode, Length, Reader, Result, Tag, Writer};
+use crate::{Choice, Decode, DerOrd, Encode, Length, Reader, Result, Writer};
 use core::cmp::Ordering;
 
 impl<'a, T> Decode<'a> for Option<T>
@@ -8,13 +8,7 @@ where
     T: Choice<'a>, // NOTE: all `Decode + Tagged` types receive a blanket `Choice` impl
 {
     fn decode<R: Reader<'a>>(reader: &mut R) -> Result<Option<T>> {
-        if let Some(byte) = reader.peek_byte() {
-            if T::can_decode(Tag::try_from(byte)?) {
-                return T::decode(reader).map(Some);
-            }
-        }
-
-        Ok(None)
+        reader.decode_optional()
     }
 }
 
@@ -64,3 +58,96 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{asn1::Null, Decode, Reader, SliceReader};
+
+    #[test]
+    fn decode_present() {
+        let mut reader = SliceReader::new(&[0x05, 0x00]).unwrap();
+        assert_eq!(Option::<Null>::decode(&mut reader).unwrap(), Some(Null));
+        assert!(reader.is_finished());
+    }
+
+    #[test]
+    fn decode_absent() {
+        let mut reader = SliceReader::new(&[]).unwrap();
+        assert_eq!(Option::<Null>::decode(&mut reader).unwrap(), None);
+
+        // a value of another type follows
+        let mut reader = SliceReader::new(&[0x02, 0x01, 0x00]).unwrap();
+        assert_eq!(Option::<Null>::decode(&mut reader).unwrap(), None);
+        assert_eq!(u8::decode(&mut reader).unwrap(), 0);
+    }
+
+    #[test]
+    fn decode_eoc() {
+        // SEQUENCE (indefinite length) { NULL }
+        let mut reader = SliceReader::new(&[0x30, 0x80, 0x05, 0x00, 0x00, 0x00]).unwrap();
+        reader.read_slice(2u8.into()).unwrap();
+        let values = reader
+            .read_indefinite(|contents| {
+                assert!(contents.in_indefinite_contents());
+                let first = Option::<Null>::decode(contents)?;
+                assert!(contents.peek_eoc());
+                let second = Option::<Null>::decode(contents)?;
+                assert_eq!(contents.remaining_len(), 2u8.into());
+                Ok((first, second))
+            })
+            .unwrap();
+        assert_eq!(values, (Some(Null), None));
+        assert!(reader.is_finished());
+    }
+
+    #[test]
+    fn decode_eoc_missing() {
+        let mut reader = SliceReader::new(&[0x05, 0x00]).unwrap();
+        assert!(reader.read_indefinite(Option::<Null>::decode).is_err());
+
+        let mut reader = SliceReader::new(&[0x05, 0x00, 0x05, 0x00]).unwrap();
+        assert!(reader.read_indefinite(Option::<Null>::decode).is_err());
+    }
+
+    #[test]
+    fn decode_lone_zero_octet() {
+        // `0x00` is not end-of-contents unless followed by a zero length
+        for bytes in [&[0x00][..], &[0x00, 0x01, 0xFF]] {
+            let mut reader = SliceReader::new(bytes).unwrap();
+            assert!(!reader.peek_eoc());
+            assert!(Option::<Null>::decode(&mut reader).is_err());
+        }
+    }
+
+    #[test]
+    fn decode_zero_octets_outside_indefinite_contents() {
+        // `0x00 0x00` is not end-of-contents at the top level
+        let mut reader = SliceReader::new(&[0x00, 0x00]).unwrap();
+        assert!(reader.peek_eoc());
+        assert!(!reader.in_indefinite_contents());
+        assert!(Option::<Null>::decode(&mut reader).is_err());
+
+        // nor within a value with a definite length, even when that value is
+        // itself within indefinite-length contents
+        let mut reader = SliceReader::new(&[0x00, 0x00, 0x00, 0x00]).unwrap();
+        let result = reader.read_indefinite(|contents| {
+            contents.read_nested(2u8.into(), |nested| {
+                assert!(nested.peek_eoc());
+                assert!(!nested.in_indefinite_contents());
+                Option::<Null>::decode(nested)
+            })
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn decode_eoc_past_nested_value() {
+        // The second `0x00` octet is past the end of the nested value
+        let mut reader = SliceReader::new(&[0x00, 0x00]).unwrap();
+        let result = reader.read_nested(1u8.into(), |nested| {
+            assert!(!nested.peek_eoc());
+            Option::<Null>::decode(nested)
+        });
+        assert!(result.is_err());
+    }
+}
@@ -1,5 +1,7 @@
 //! ASN.1 `PrintableString` support.
 
+#![cfg_attr(feature = "arbitrary", allow(clippy::integer_arithmetic))]
+
 use crate::{asn1::AnyRef, FixedTag, Result, StrRef, Tag};
 use core::{fmt, ops::Deref};
 
@@ -129,6 +131,9 @@ mod allocation {
     use alloc::string::String;
     use core::{fmt, ops::Deref};
 
+    #[cfg(feature = "arbitrary")]
+    use alloc::vec::Vec;
+
     /// ASN.1 `PrintableString` type.
     ///
     /// Supports a subset the ASCII character set (described below).
@@ -179,6 +184,25 @@ mod allocation {
 
     impl_printable_string!(PrintableString);
 
+    #[cfg(feature = "arbitrary")]
+    impl<'a> arbitrary::Arbitrary<'a> for PrintableString {
+        fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+            const CHARSET: &[u8] =
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789 '()+,-./:=?";
+
+            let bytes = Vec::<u8>::arbitrary(u)?
+                .into_iter()
+                .map(|byte| CHARSET[usize::from(byte) % CHARSET.len()])
+                .collect::<Vec<_>>();
+
+            Self::new(&bytes).map_err(|_| arbitrary::Error::IncorrectFormat)
+        }
+
+        fn size_hint(depth: usize) -> (usize, Option<usize>) {
+            Vec::<u8>::size_hint(depth)
+        }
+    }
+
     impl Deref for PrintableString {
         type Target = StrOwned;
 
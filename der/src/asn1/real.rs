@@ -40,12 +40,17 @@ impl<'a> DecodeValue<'a> for f64 {
             let exponent = match mnth_bits_to_u8::<1, 0>(bytes) {
                 0 => {
                     mantissa_start = 2;
-                    let ebytes = (i16::from_be_bytes([0x0, bytes[1]])).to_be_bytes();
+                    let exponent = bytes.get(1).ok_or_else(|| Tag::Real.length_error())?;
+                    let ebytes = (i16::from_be_bytes([0x0, *exponent])).to_be_bytes();
                     u64::from_be_bytes([0x0, 0x0, 0x0, 0x0, 0x0, 0x0, ebytes[0], ebytes[1]])
                 }
                 1 => {
                     mantissa_start = 3;
-                    let ebytes = (i16::from_be_bytes([bytes[1], bytes[2]])).to_be_bytes();
+                    let exponent = match bytes.get(1..3) {
+                        Some(&[e0, e1]) => [e0, e1],
+                        _ => return Err(Tag::Real.length_error()),
+                    };
+                    let ebytes = (i16::from_be_bytes(exponent)).to_be_bytes();
                     u64::from_be_bytes([0x0, 0x0, 0x0, 0x0, 0x0, 0x0, ebytes[0], ebytes[1]])
                 }
                 _ => {
@@ -54,13 +59,24 @@ impl<'a> DecodeValue<'a> for f64 {
                 }
             };
             // Section 8.5.7.5: Read the remaining bytes for the mantissa
+            let mantissa_bytes = bytes
+                .get(mantissa_start..)
+                .ok_or_else(|| Tag::Real.length_error())?;
             let mut n_bytes = [0x0; 8];
-            for (pos, byte) in bytes[mantissa_start..].iter().rev().enumerate() {
-                n_bytes[7 - pos] = *byte;
-            }
+            let offset = n_bytes
+                .len()
+                .checked_sub(mantissa_bytes.len())
+                // Real related error: mantissa cannot be represented on an IEEE-754 double
+                .ok_or_else(|| Tag::Real.value_error())?;
+            n_bytes[offset..].copy_from_slice(mantissa_bytes);
             let n = u64::from_be_bytes(n_bytes);
             // Multiply byt 2^F corresponds to just a left shift
             let mantissa = n << scaling_factor;
+            // Real related error: value cannot be represented on an IEEE-754 double
+            let ebytes = exponent.to_be_bytes();
+            if mantissa == 0 || i16::from_be_bytes([ebytes[6], ebytes[7]]) > 1023 {
+                return Err(Tag::Real.value_error());
+            }
             // Create the f64
             Ok(encode_f64(sign, exponent, mantissa))
         } else if is_nth_bit_one::<6>(bytes) {
@@ -262,6 +278,20 @@ mod tests {
         assert!(neg_zero.is_sign_negative() && neg_zero.abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn decode_malformed_binary() {
+        // Missing exponent octets
+        assert!(f64::from_der(&[0x09, 0x01, 0x80]).is_err());
+        assert!(f64::from_der(&[0x09, 0x02, 0x81, 0x00]).is_err());
+
+        // Mantissa longer than 8 octets
+        assert!(f64::from_der(&[0x09, 0x0B, 0x80, 0x00, 1, 2, 3, 4, 5, 6, 7, 8, 9]).is_err());
+
+        // Zero mantissa and out of range exponent
+        assert!(f64::from_der(&[0x09, 0x02, 0x80, 0x00]).is_err());
+        assert!(f64::from_der(&[0x09, 0x04, 0x81, 0x7F, 0xFF, 0x01]).is_err());
+    }
+
     #[test]
     fn encode_subnormal() {
         // All subnormal fit in three bytes
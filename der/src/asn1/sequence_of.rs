@@ -56,6 +56,28 @@ impl<T, const N: usize> SequenceOf<T, N> {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a, T, const N: usize> arbitrary::Arbitrary<'a> for SequenceOf<T, N>
+where
+    T: arbitrary::Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut sequence_of = Self::new();
+
+        for _ in 0..u.int_in_range(0..=N)? {
+            sequence_of
+                .add(T::arbitrary(u)?)
+                .map_err(|_| arbitrary::Error::IncorrectFormat)?;
+        }
+
+        Ok(sequence_of)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
 impl<T, const N: usize> Default for SequenceOf<T, N> {
     fn default() -> Self {
         Self::new()
@@ -71,7 +93,8 @@ where
             let mut sequence_of = Self::new();
 
             while !reader.is_finished() {
-                sequence_of.add(T::decode(reader)?)?;
+                let index = sequence_of.len();
+                sequence_of.add(T::decode(reader).map_err(|e| e.in_element(index))?)?;
             }
 
             Ok(sequence_of)
@@ -187,7 +210,8 @@ where
             let mut sequence_of = Self::new();
 
             while !reader.is_finished() {
-                sequence_of.push(T::decode(reader)?);
+                let index = sequence_of.len();
+                sequence_of.push(T::decode(reader).map_err(|e| e.in_element(index))?);
             }
 
             Ok(sequence_of)
@@ -113,7 +113,10 @@ where
             let mut result = Self::new();
 
             while !reader.is_finished() {
-                result.inner.push(T::decode(reader)?)?;
+                let index = result.inner.len();
+                result
+                    .inner
+                    .push(T::decode(reader).map_err(|e| e.in_element(index))?)?;
             }
 
             der_sort(result.inner.as_mut())?;
@@ -167,6 +170,30 @@ where
     }
 }
 
+// Implement by hand because the derive would create invalid values.
+// Use `insert` to keep the elements in DER order.
+#[cfg(feature = "arbitrary")]
+impl<'a, T, const N: usize> arbitrary::Arbitrary<'a> for SetOf<T, N>
+where
+    T: DerOrd + arbitrary::Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut set_of = Self::new();
+
+        for _ in 0..u.int_in_range(0..=N)? {
+            set_of
+                .insert(T::arbitrary(u)?)
+                .map_err(|_| arbitrary::Error::IncorrectFormat)?;
+        }
+
+        Ok(set_of)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
 impl<T, const N: usize> ValueOrd for SetOf<T, N>
 where
     T: DerOrd,
@@ -257,7 +284,7 @@ where
         I: IntoIterator<Item = T>,
     {
         self.inner.extend(iter);
-        der_sort(&mut self.inner)
+        der_sort_vec(&mut self.inner)
     }
 
     /// Insert an item into this [`SetOfVec`]. Must be unique.
@@ -331,11 +358,11 @@ where
             let mut inner = Vec::new();
 
             while !reader.is_finished() {
-                inner.push(T::decode(reader)?);
+                let index = inner.len();
+                inner.push(T::decode(reader).map_err(|e| e.in_element(index))?);
             }
 
-            der_sort(inner.as_mut())?;
-            validate(inner.as_ref())?;
+            der_sort_vec(&mut inner)?;
             Ok(Self { inner })
         })
     }
@@ -386,7 +413,7 @@ where
     type Error = Error;
 
     fn try_from(mut vec: Vec<T>) -> Result<SetOfVec<T>> {
-        der_sort(vec.as_mut_slice())?;
+        der_sort_vec(&mut vec)?;
         Ok(SetOfVec { inner: vec })
     }
 }
@@ -471,6 +498,80 @@ fn der_sort<T: DerOrd>(slice: &mut [T]) -> Result<()> {
     Ok(())
 }
 
+/// Sort a [`Vec`] according to its [`DerOrd`], returning any errors which
+/// might occur during the comparison.
+///
+/// Unlike [`der_sort`], this is a merge sort which performs `O(n log n)`
+/// comparisons, and only `O(n)` when the input is already sorted as is the
+/// case for DER-encoded sets. It is used for bulk construction of a
+/// [`SetOfVec`], e.g. when decoding sets with many elements.
+#[cfg(feature = "alloc")]
+#[allow(clippy::integer_arithmetic)]
+fn der_sort_vec<T: DerOrd>(vec: &mut Vec<T>) -> Result<()> {
+    let mut sorted = true;
+
+    for pair in vec.windows(2) {
+        if let [a, b] = pair {
+            match a.der_cmp(b)? {
+                Ordering::Less => (),
+                Ordering::Equal => return Err(ErrorKind::SetDuplicate.into()),
+                Ordering::Greater => {
+                    sorted = false;
+                    break;
+                }
+            }
+        }
+    }
+
+    if sorted {
+        return Ok(());
+    }
+
+    // Bottom-up merge sort of the indices of the elements. Equal elements are
+    // always compared with each other when merging, so duplicates are detected.
+    let mut indices: Vec<usize> = (0..vec.len()).collect();
+    let mut merged = Vec::with_capacity(vec.len());
+    let mut width = 1;
+
+    while width < indices.len() {
+        merged.clear();
+
+        for chunk in indices.chunks(2 * width) {
+            let (left, right) = chunk.split_at(width.min(chunk.len()));
+            let (mut i, mut j) = (0, 0);
+
+            while let (Some(&a), Some(&b)) = (left.get(i), right.get(j)) {
+                match vec[a].der_cmp(&vec[b])? {
+                    Ordering::Less => {
+                        merged.push(a);
+                        i += 1;
+                    }
+                    Ordering::Equal => return Err(ErrorKind::SetDuplicate.into()),
+                    Ordering::Greater => {
+                        merged.push(b);
+                        j += 1;
+                    }
+                }
+            }
+
+            merged.extend_from_slice(&left[i..]);
+            merged.extend_from_slice(&right[j..]);
+        }
+
+        core::mem::swap(&mut indices, &mut merged);
+        width *= 2;
+    }
+
+    let mut elements: Vec<Option<T>> = vec.drain(..).map(Some).collect();
+    vec.extend(
+        indices
+            .into_iter()
+            .filter_map(|i| elements.get_mut(i).and_then(Option::take)),
+    );
+
+    Ok(())
+}
+
 /// Validate the elements of a `SET OF`, ensuring that they are all in order
 /// and that there are no duplicates.
 fn validate<T: DerOrd>(slice: &[T]) -> Result<()> {
@@ -535,5 +636,23 @@ mod tests {
         let vec = vec![1u16, 1];
         let err = SetOfVec::try_from(vec).err().unwrap();
         assert_eq!(err.kind(), ErrorKind::SetDuplicate);
+
+        let vec = vec![5u16, 3, 8, 1, 3, 9];
+        let err = SetOfVec::try_from(vec).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::SetDuplicate);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn setofvec_large() {
+        let set = SetOfVec::from_iter((0..1000u16).rev()).unwrap();
+        assert!(set.iter().copied().eq(0..1000));
+
+        let mut set = SetOfVec::from_iter((0..1000u16).step_by(2)).unwrap();
+        set.extend((0..1000u16).skip(1).step_by(2).rev()).unwrap();
+        assert!(set.iter().copied().eq(0..1000));
+
+        let err = set.extend([1000u16, 500]).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::SetDuplicate);
     }
 }
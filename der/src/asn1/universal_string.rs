@@ -0,0 +1,196 @@
+//! ASN.1 `UniversalString` support.
+
+use crate::{
+    ord::OrdIsValueOrd, BytesOwned, DecodeValue, EncodeValue, Error, FixedTag, Header, Length,
+    Reader, Result, Tag, Writer,
+};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{fmt, str::FromStr};
+
+/// ASN.1 `UniversalString` type.
+///
+/// Encodes Unicode (ISO 10646) as fixed-width 32-bit code points, a.k.a. UCS-4.
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord)]
+pub struct UniversalString {
+    bytes: BytesOwned,
+}
+
+impl UniversalString {
+    /// Create a new [`UniversalString`] from its UCS-4 encoding.
+    pub fn from_ucs4(bytes: impl Into<Box<[u8]>>) -> Result<Self> {
+        let bytes = bytes.into();
+
+        if bytes.len() % 4 != 0 {
+            return Err(Tag::UniversalString.length_error());
+        }
+
+        let ret = Self {
+            bytes: bytes.try_into()?,
+        };
+
+        // Surrogates and values above U+10FFFF aren't characters
+        if ret.codepoints().any(|c| char::from_u32(c).is_none()) {
+            return Err(Tag::UniversalString.value_error());
+        }
+
+        Ok(ret)
+    }
+
+    /// Create a new [`UniversalString`] from a UTF-8 string.
+    pub fn from_utf8(utf8: &str) -> Result<Self> {
+        let capacity = utf8
+            .len()
+            .checked_mul(4)
+            .ok_or_else(|| Tag::UniversalString.length_error())?;
+
+        let mut bytes = Vec::with_capacity(capacity);
+
+        for c in utf8.chars() {
+            bytes.extend(u32::from(c).to_be_bytes());
+        }
+
+        Self::from_ucs4(bytes)
+    }
+
+    /// Borrow the encoded UCS-4 as bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_ref()
+    }
+
+    /// Obtain the inner bytes.
+    #[inline]
+    pub fn into_bytes(self) -> Box<[u8]> {
+        self.bytes.as_slice().into()
+    }
+
+    /// Get an iterator over characters in the string.
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        // invalid code points are rejected by the constructor
+        self.codepoints()
+            .map(|c| char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    /// Get an iterator over the `u32` code points.
+    pub fn codepoints(&self) -> impl Iterator<Item = u32> + '_ {
+        self.as_bytes()
+            .chunks_exact(4)
+            .map(|chunk| u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for UniversalString {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Self::from_utf8(&String::arbitrary(u)?).map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        String::size_hint(depth)
+    }
+}
+
+impl AsRef<[u8]> for UniversalString {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<'a> DecodeValue<'a> for UniversalString {
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+        Self::from_ucs4(reader.read_vec(header.length)?)
+    }
+}
+
+impl EncodeValue for UniversalString {
+    fn value_len(&self) -> Result<Length> {
+        Ok(self.bytes.len())
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> Result<()> {
+        writer.write(self.as_bytes())
+    }
+}
+
+impl FixedTag for UniversalString {
+    const TAG: Tag = Tag::UniversalString;
+}
+
+impl OrdIsValueOrd for UniversalString {}
+
+impl FromStr for UniversalString {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_utf8(s)
+    }
+}
+
+impl TryFrom<&str> for UniversalString {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self> {
+        Self::from_utf8(s)
+    }
+}
+
+impl From<&UniversalString> for String {
+    fn from(universal_string: &UniversalString) -> String {
+        universal_string.chars().collect()
+    }
+}
+
+impl fmt::Debug for UniversalString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "UniversalString(\"{}\")", self)
+    }
+}
+
+impl fmt::Display for UniversalString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.chars() {
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+impl_any_conversions!(UniversalString);
+
+#[cfg(test)]
+mod tests {
+    use super::UniversalString;
+    use crate::{Decode, Encode};
+    use alloc::string::ToString;
+    use hex_literal::hex;
+
+    const EXAMPLE_BYTES: &[u8] = &hex!(
+        "1c 14 00 00 00 43 00 00 00 65 00 00 00 72 00 00"
+        "   00 74 00 01 f6 00"
+    );
+
+    const EXAMPLE_UTF8: &str = "Cert\u{1F600}";
+
+    #[test]
+    fn decode() {
+        let universal_string = UniversalString::from_der(EXAMPLE_BYTES).unwrap();
+        assert_eq!(universal_string.to_string(), EXAMPLE_UTF8);
+    }
+
+    #[test]
+    fn encode() {
+        let universal_string = UniversalString::from_utf8(EXAMPLE_UTF8).unwrap();
+        let encoded = universal_string.to_der().unwrap();
+        assert_eq!(encoded, EXAMPLE_BYTES);
+    }
+
+    #[test]
+    fn reject_truncated_code_point() {
+        assert!(UniversalString::from_ucs4(&[0x00, 0x00, 0x41][..]).is_err());
+    }
+
+    #[test]
+    fn reject_invalid_code_points() {
+        assert!(UniversalString::from_ucs4(&[0x00, 0x00, 0xd8, 0x00][..]).is_err());
+        assert!(UniversalString::from_ucs4(&[0x00, 0x11, 0x00, 0x00][..]).is_err());
+    }
+}
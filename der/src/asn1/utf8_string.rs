@@ -44,6 +44,17 @@ impl<'a> Utf8StringRef<'a> {
 
 impl_string_type!(Utf8StringRef<'a>, 'a);
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Utf8StringRef<'a> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Self::new(<&'a str>::arbitrary(u)?).map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <&'a str>::size_hint(depth)
+    }
+}
+
 impl<'a> Deref for Utf8StringRef<'a> {
     type Target = StrRef<'a>;
 
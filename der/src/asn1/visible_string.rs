@@ -0,0 +1,215 @@
+//! ASN.1 `VisibleString` support.
+
+#![cfg_attr(feature = "arbitrary", allow(clippy::integer_arithmetic))]
+
+use crate::{asn1::AnyRef, FixedTag, Result, StrRef, Tag};
+use core::{fmt, ops::Deref};
+
+macro_rules! impl_visible_string {
+    ($type: ty) => {
+        impl_visible_string!($type,);
+    };
+    ($type: ty, $($li: lifetime)?) => {
+        impl_string_type!($type, $($li),*);
+
+        impl<$($li),*> FixedTag for $type {
+            const TAG: Tag = Tag::VisibleString;
+        }
+
+        impl<$($li),*> fmt::Debug for $type {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "VisibleString({:?})", self.as_str())
+            }
+        }
+    };
+}
+
+/// ASN.1 `VisibleString` type.
+///
+/// Supports the printable characters of the ASCII alphabet, i.e. `0x20`
+/// (space) through `0x7E` (`~`), excluding control characters.
+///
+/// For UTF-8, use [`Utf8StringRef`][`crate::asn1::Utf8StringRef`].
+///
+/// This is a zero-copy reference type which borrows from the input data.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord)]
+pub struct VisibleStringRef<'a> {
+    /// Inner value
+    inner: StrRef<'a>,
+}
+
+impl<'a> VisibleStringRef<'a> {
+    /// Create a new `VisibleString`.
+    pub fn new<T>(input: &'a T) -> Result<Self>
+    where
+        T: AsRef<[u8]> + ?Sized,
+    {
+        let input = input.as_ref();
+
+        // Validate all characters are within VisibleString's allowed set
+        if input.iter().any(|&c| !(0x20..=0x7E).contains(&c)) {
+            return Err(Self::TAG.value_error());
+        }
+
+        StrRef::from_bytes(input)
+            .map(|inner| Self { inner })
+            .map_err(|_| Self::TAG.value_error())
+    }
+}
+
+impl_visible_string!(VisibleStringRef<'a>, 'a);
+
+impl<'a> Deref for VisibleStringRef<'a> {
+    type Target = StrRef<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<'a> From<&VisibleStringRef<'a>> for VisibleStringRef<'a> {
+    fn from(value: &VisibleStringRef<'a>) -> VisibleStringRef<'a> {
+        *value
+    }
+}
+
+impl<'a> From<VisibleStringRef<'a>> for AnyRef<'a> {
+    fn from(visible_string: VisibleStringRef<'a>) -> AnyRef<'a> {
+        AnyRef::from_tag_and_value(Tag::VisibleString, visible_string.inner.into())
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub use self::allocation::VisibleString;
+
+#[cfg(feature = "alloc")]
+mod allocation {
+    use super::VisibleStringRef;
+    use crate::{
+        asn1::AnyRef,
+        referenced::{OwnedToRef, RefToOwned},
+        Error, FixedTag, Result, StrOwned, Tag,
+    };
+    use alloc::string::String;
+    use core::{fmt, ops::Deref};
+
+    #[cfg(feature = "arbitrary")]
+    use alloc::vec::Vec;
+
+    /// ASN.1 `VisibleString` type.
+    ///
+    /// Supports the printable characters of the ASCII alphabet, i.e. `0x20`
+    /// (space) through `0x7E` (`~`), excluding control characters.
+    ///
+    /// For UTF-8, use [`String`][`alloc::string::String`].
+    #[derive(Clone, Eq, PartialEq, PartialOrd, Ord)]
+    pub struct VisibleString {
+        /// Inner value
+        inner: StrOwned,
+    }
+
+    impl VisibleString {
+        /// Create a new `VisibleString`.
+        pub fn new<T>(input: &T) -> Result<Self>
+        where
+            T: AsRef<[u8]> + ?Sized,
+        {
+            let input = input.as_ref();
+            VisibleStringRef::new(input)?;
+
+            StrOwned::from_bytes(input)
+                .map(|inner| Self { inner })
+                .map_err(|_| Self::TAG.value_error())
+        }
+    }
+
+    impl_visible_string!(VisibleString);
+
+    #[cfg(feature = "arbitrary")]
+    impl<'a> arbitrary::Arbitrary<'a> for VisibleString {
+        fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+            // Map each byte onto the 95 visible characters
+            let bytes = Vec::<u8>::arbitrary(u)?
+                .into_iter()
+                .map(|byte| 0x20 + byte % 95)
+                .collect::<Vec<_>>();
+
+            Self::new(&bytes).map_err(|_| arbitrary::Error::IncorrectFormat)
+        }
+
+        fn size_hint(depth: usize) -> (usize, Option<usize>) {
+            Vec::<u8>::size_hint(depth)
+        }
+    }
+
+    impl Deref for VisibleString {
+        type Target = StrOwned;
+
+        fn deref(&self) -> &Self::Target {
+            &self.inner
+        }
+    }
+
+    impl<'a> From<VisibleStringRef<'a>> for VisibleString {
+        fn from(visible_string: VisibleStringRef<'a>) -> VisibleString {
+            let inner = visible_string.inner.into();
+            Self { inner }
+        }
+    }
+
+    impl<'a> From<&'a VisibleString> for AnyRef<'a> {
+        fn from(visible_string: &'a VisibleString) -> AnyRef<'a> {
+            AnyRef::from_tag_and_value(Tag::VisibleString, (&visible_string.inner).into())
+        }
+    }
+
+    impl<'a> RefToOwned<'a> for VisibleStringRef<'a> {
+        type Owned = VisibleString;
+        fn ref_to_owned(&self) -> Self::Owned {
+            VisibleString {
+                inner: self.inner.ref_to_owned(),
+            }
+        }
+    }
+
+    impl OwnedToRef for VisibleString {
+        type Borrowed<'a> = VisibleStringRef<'a>;
+        fn owned_to_ref(&self) -> Self::Borrowed<'_> {
+            VisibleStringRef {
+                inner: self.inner.owned_to_ref(),
+            }
+        }
+    }
+
+    impl TryFrom<String> for VisibleString {
+        type Error = Error;
+
+        fn try_from(input: String) -> Result<Self> {
+            VisibleStringRef::new(&input)?;
+
+            StrOwned::new(input)
+                .map(|inner| Self { inner })
+                .map_err(|_| Self::TAG.value_error())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VisibleStringRef;
+    use crate::Decode;
+    use hex_literal::hex;
+
+    #[test]
+    fn parse_bytes() {
+        let example_bytes = hex!("1a 0d 74 65 73 74 31 40 72 73 61 2e 63 6f 6d");
+        let visible_string = VisibleStringRef::from_der(&example_bytes).unwrap();
+        assert_eq!(visible_string.as_str(), "test1@rsa.com");
+    }
+
+    #[test]
+    fn reject_control_characters() {
+        assert!(VisibleStringRef::new("test\n").is_err());
+        assert!(VisibleStringRef::from_der(&hex!("1a 02 74 00")).is_err());
+    }
+}
@@ -0,0 +1,725 @@
+//! Transcoding of BER-encoded data to DER.
+
+use crate::{
+    reader::END_OF_CONTENTS, Encode, Error, ErrorKind, Length, Result, Tag, DEFAULT_MAX_DEPTH,
+};
+use alloc::vec::Vec;
+
+/// Bit set in the identifier octet of constructed values.
+const CONSTRUCTED_FLAG: u8 = 0b100000;
+
+/// Re-encode a single BER-encoded value as DER, without knowledge of its ASN.1 schema.
+///
+/// The following BER productions are converted to their DER form:
+///
+/// - indefinite and non-minimal lengths are replaced by minimal definite lengths;
+/// - constructed encodings of string types (e.g. `OCTET STRING` and `BIT STRING`) are
+///   concatenated into primitive encodings;
+/// - `BOOLEAN` values other than `0x00` are encoded as `0xFF`;
+/// - unused bits of `BIT STRING`s are cleared;
+/// - elements of `SET` and `SET OF` values are sorted by their encoding.
+///
+/// As the schema of the input is unknown, canonicalization which depends on it is not
+/// performed. In particular implicitly tagged strings and `SET`s, `DEFAULT` values and the
+/// representation of `UTCTime` and `GeneralizedTime` values are left as-is.
+///
+/// Constructed values may be nested up to [`DEFAULT_MAX_DEPTH`] levels deep: use
+/// [`ber_to_der_with_max_depth`] to choose another limit.
+pub fn ber_to_der(ber: &[u8]) -> Result<Vec<u8>> {
+    ber_to_der_with_max_depth(ber, DEFAULT_MAX_DEPTH)
+}
+
+/// Re-encode a single BER-encoded value as DER like [`ber_to_der`], failing with
+/// [`ErrorKind::DepthExceeded`] if constructed values are nested more than `max_depth` levels
+/// deep.
+///
+/// This matches the limit enforced by a [`Reader`][`crate::Reader`] with the same
+/// [`Reader::max_depth`][`crate::Reader::max_depth`].
+pub fn ber_to_der_with_max_depth(ber: &[u8], max_depth: usize) -> Result<Vec<u8>> {
+    let mut der = Vec::with_capacity(ber.len());
+    Transcoder::new(ber, max_depth).run(Some(&mut der))?;
+    Ok(der)
+}
+
+/// Check that a single value is DER-encoded, without knowledge of its ASN.1 schema.
+///
+/// This rejects the BER productions which [`ber_to_der`] converts, with an error
+/// located at the first offending value:
+///
+/// - [`ErrorKind::IndefiniteLength`] for indefinite lengths;
+/// - [`ErrorKind::Overlength`] for lengths not encoded with a minimum number of octets;
+/// - [`ErrorKind::Noncanonical`] for constructed encodings of string types, `BOOLEAN`
+///   values other than `0x00` and `0xFF`, and `BIT STRING`s with unused bits set;
+/// - [`ErrorKind::SetOrdering`] for `SET` and `SET OF` values whose elements are not
+///   sorted.
+///
+/// The same limitations apply: in particular the elements of implicitly tagged `SET`s
+/// are not checked for ordering. Unlike [`ber_to_der`], this doesn't allocate.
+/// Constructed values may be nested up to
+/// [`DEFAULT_MAX_DEPTH`] levels deep: use [`check_der_with_max_depth`] to choose another
+/// limit.
+pub fn check_der(der: &[u8]) -> Result<()> {
+    check_der_with_max_depth(der, DEFAULT_MAX_DEPTH)
+}
+
+/// Check that a single value is DER-encoded like [`check_der`], failing with
+/// [`ErrorKind::DepthExceeded`] if constructed values are nested more than `max_depth` levels
+/// deep.
+pub fn check_der_with_max_depth(der: &[u8], max_depth: usize) -> Result<()> {
+    Transcoder::new(der, max_depth).run(None)
+}
+
+/// Check that the contents of a value with the given `tag` are DER-encoded, like
+/// [`check_der`] does for a whole encoding.
+pub(crate) fn check_der_value(tag: Tag, value: &[u8]) -> Result<()> {
+    let header = BerHeader {
+        tag: tag.octet(),
+        identifier: &[],
+        length: Some(value.len()),
+    };
+
+    let mut transcoder = Transcoder::new(value, DEFAULT_MAX_DEPTH);
+    transcoder.transcode_contents(Length::ZERO, &header, None, 0)
+}
+
+/// Identifier and length octets of a BER-encoded value.
+struct BerHeader<'a> {
+    /// Leading identifier octet.
+    tag: u8,
+
+    /// All identifier octets, including the tag number octets of the high tag number form.
+    identifier: &'a [u8],
+
+    /// Length of the contents, or `None` for indefinite lengths.
+    length: Option<usize>,
+}
+
+impl BerHeader<'_> {
+    fn is_constructed(&self) -> bool {
+        self.tag & CONSTRUCTED_FLAG != 0
+    }
+
+    /// Universal string type whose constructed encoding must be converted to a
+    /// primitive one in DER.
+    ///
+    /// See X.690 Section 10.2. `CHARACTER STRING` (`0x1D`) is excluded: it is encoded as a
+    /// `SEQUENCE`, see X.690 Section 8.23.
+    fn is_string(&self) -> bool {
+        matches!(
+            self.tag & !CONSTRUCTED_FLAG,
+            0x03 | 0x04 | 0x07 | 0x0C | 0x12..=0x1C | 0x1E
+        ) && self.tag & 0x1F != 0x1F
+    }
+}
+
+/// Cursor over BER-encoded input.
+///
+/// The DER encoding of the input is appended to an output buffer if one is given. Otherwise
+/// the input is only checked, and BER productions are rejected instead of being converted.
+struct Transcoder<'a> {
+    input: &'a [u8],
+    pos: usize,
+
+    /// Maximum nesting depth of constructed values.
+    max_depth: usize,
+}
+
+// Positions are bounded by the length of the input, and depths by `max_depth`.
+#[allow(clippy::integer_arithmetic)]
+impl<'a> Transcoder<'a> {
+    fn new(input: &'a [u8], max_depth: usize) -> Self {
+        Self {
+            input,
+            pos: 0,
+            max_depth,
+        }
+    }
+
+    /// Transcode the whole input, which must consist of a single value.
+    fn run(mut self, der: Option<&mut Vec<u8>>) -> Result<()> {
+        self.transcode(der, 0)?;
+
+        if self.pos < self.input.len() {
+            let decoded = self.position()?;
+            let remaining = Length::try_from(self.input.len() - self.pos)?;
+            return Err(ErrorKind::TrailingData { decoded, remaining }.at(decoded));
+        }
+
+        Ok(())
+    }
+
+    /// Transcode the next value of the input, appending its DER encoding to `der`.
+    fn transcode(&mut self, der: Option<&mut Vec<u8>>, depth: usize) -> Result<()> {
+        let start = self.position()?;
+        let header = self.read_header(der.is_none())?;
+        self.transcode_contents(start, &header, der, depth)
+    }
+
+    /// Transcode the contents of a value starting at `start`, whose header has been read.
+    fn transcode_contents(
+        &mut self,
+        start: Length,
+        header: &BerHeader<'_>,
+        der: Option<&mut Vec<u8>>,
+        depth: usize,
+    ) -> Result<()> {
+        if !header.is_constructed() {
+            let contents = self.read_primitive(header)?;
+
+            return match der {
+                Some(der) => write_primitive(der, header.tag, header.identifier, contents),
+                None if is_canonical_primitive(header.tag, contents)? => Ok(()),
+                None => Err(ErrorKind::Noncanonical {
+                    tag: Tag::try_from(header.tag)?,
+                }
+                .at(start)),
+            };
+        }
+
+        if depth >= self.max_depth {
+            return Err(ErrorKind::DepthExceeded {
+                max_depth: self.max_depth,
+            }
+            .at(self.position()?));
+        }
+
+        let end = header.length.map(|len| self.pos + len);
+
+        if header.is_string() {
+            let primitive_tag = header.tag & !CONSTRUCTED_FLAG;
+
+            let der = der.ok_or_else(|| {
+                Tag::try_from(primitive_tag)
+                    .map(|tag| ErrorKind::Noncanonical { tag }.at(start))
+                    .unwrap_or_else(|err| err)
+            })?;
+
+            let mut contents = Vec::new();
+            self.read_string_segments(header, primitive_tag, &mut contents, depth + 1)?;
+            return write_primitive(der, primitive_tag, &[primitive_tag], &contents);
+        }
+
+        // X.690 Section 11.6: the elements of a SET OF are ordered by their encoding, which
+        // for the distinct tags of a SET is the ascending order of X.690 Section 10.3.
+        let is_set = header.tag == 0x31;
+
+        let der = match der {
+            Some(der) => der,
+            None => {
+                let mut previous = None;
+
+                while !self.at_end_of_contents(end)? {
+                    let element_start = self.pos;
+                    self.transcode(None, depth + 1)?;
+                    let element = self.input.get(element_start..self.pos);
+
+                    if is_set && previous > element {
+                        return Err(ErrorKind::SetOrdering.at(start));
+                    }
+
+                    previous = element;
+                }
+
+                return Ok(());
+            }
+        };
+
+        let mut elements = Vec::new();
+
+        while !self.at_end_of_contents(end)? {
+            let mut element = Vec::new();
+            self.transcode(Some(&mut element), depth + 1)?;
+            elements.push(element);
+        }
+
+        if is_set {
+            elements.sort();
+        }
+
+        let len = elements.iter().map(Vec::len).sum::<usize>();
+        write_header(der, header.identifier, len)?;
+        for element in elements {
+            der.extend_from_slice(&element);
+        }
+
+        Ok(())
+    }
+
+    /// Concatenate the contents of the primitive segments of a constructed string.
+    fn read_string_segments(
+        &mut self,
+        header: &BerHeader<'_>,
+        primitive_tag: u8,
+        contents: &mut Vec<u8>,
+        depth: usize,
+    ) -> Result<()> {
+        let end = header.length.map(|len| self.pos + len);
+
+        while !self.at_end_of_contents(end)? {
+            let segment = self.read_header(false)?;
+
+            // X.690 Section 8.23.3: segments are encoded with the universal tag of the string
+            if segment.tag & !CONSTRUCTED_FLAG != primitive_tag {
+                return Err(ErrorKind::TagUnexpected {
+                    expected: None,
+                    actual: Tag::try_from(primitive_tag)?,
+                }
+                .at(self.position()?));
+            }
+
+            if segment.is_constructed() {
+                if depth >= self.max_depth {
+                    return Err(ErrorKind::DepthExceeded {
+                        max_depth: self.max_depth,
+                    }
+                    .at(self.position()?));
+                }
+
+                self.read_string_segments(&segment, primitive_tag, contents, depth + 1)?;
+            } else if primitive_tag == 0x03 {
+                // X.690 Section 8.6.4: only the last segment may have unused bits
+                if contents
+                    .first()
+                    .map_or(false, |&unused_bits| unused_bits != 0)
+                {
+                    return Err(Tag::BitString.value_error());
+                }
+
+                let segment = self.read_primitive(&segment)?;
+                let (&unused_bits, bits) = segment
+                    .split_first()
+                    .ok_or_else(|| Tag::BitString.length_error())?;
+
+                match contents.first_mut() {
+                    Some(first) => *first = unused_bits,
+                    None => contents.push(unused_bits),
+                }
+                contents.extend_from_slice(bits);
+            } else {
+                contents.extend_from_slice(self.read_primitive(&segment)?);
+            }
+        }
+
+        // empty constructed BIT STRING
+        if primitive_tag == 0x03 && contents.is_empty() {
+            contents.push(0);
+        }
+
+        Ok(())
+    }
+
+    /// Read the identifier and length octets of the next value, rejecting BER lengths if
+    /// `strict`.
+    fn read_header(&mut self, strict: bool) -> Result<BerHeader<'a>> {
+        let start = self.position()?;
+        let tag_start = self.pos;
+        let tag = self.read_byte()?;
+
+        if tag & 0x1F == 0x1F {
+            self.read_high_tag_number()?;
+        }
+
+        let identifier = self
+            .input
+            .get(tag_start..self.pos)
+            .ok_or_else(|| self.incomplete(0))?;
+
+        let length = match self.read_byte()? {
+            len @ 0..=0x7F => Some(usize::from(len)),
+            0x80 if strict => return Err(ErrorKind::IndefiniteLength.at(start)),
+            0x80 if tag & CONSTRUCTED_FLAG != 0 => None,
+            0x80 => return Err(ErrorKind::IndefiniteLength.at(self.position()?)),
+            0xFF => return Err(ErrorKind::Overlength.at(self.position()?)),
+            initial => {
+                let position = self.position()?;
+                let mut len = 0u32;
+                for _ in 0..(initial & 0x7F) {
+                    len = len
+                        .checked_mul(0x100)
+                        .ok_or_else(|| ErrorKind::Overflow.at(position))?
+                        | u32::from(self.read_byte()?);
+                }
+
+                let len = Length::try_from(len)?;
+
+                // X.690 Section 10.1
+                if strict && len.initial_octet() != Some(initial) {
+                    return Err(ErrorKind::Overlength.at(start));
+                }
+
+                Some(usize::try_from(len)?)
+            }
+        };
+
+        if let Some(len) = length {
+            if self.input.len() - self.pos < len {
+                return Err(self.incomplete(len));
+            }
+        }
+
+        Ok(BerHeader {
+            tag,
+            identifier,
+            length,
+        })
+    }
+
+    /// Read the tag number octets of the high tag number form (X.690 Section 8.1.2.4).
+    ///
+    /// The same restrictions as [`Tag`][`crate::Tag`] apply: the tag number must not fit in
+    /// the leading identifier octet, must be encoded in the minimum number of octets and fit
+    /// in a `u32`.
+    fn read_high_tag_number(&mut self) -> Result<()> {
+        let position = self.position()?;
+        let mut number = 0u32;
+
+        for i in 0..5 {
+            let octet = self.read_byte()?;
+
+            if i == 0 && octet == 0x80 {
+                break;
+            }
+
+            number = match number.checked_mul(0x80) {
+                Some(number) => number | u32::from(octet & 0x7F),
+                None => break,
+            };
+
+            if octet & 0x80 == 0 {
+                if number > 30 {
+                    return Ok(());
+                }
+
+                break;
+            }
+        }
+
+        Err(ErrorKind::TagNumberInvalid.at(position))
+    }
+
+    /// Read the contents of a primitive value.
+    fn read_primitive(&mut self, header: &BerHeader<'_>) -> Result<&'a [u8]> {
+        let len = match header.length {
+            Some(len) => len,
+            None => return Err(ErrorKind::IndefiniteLength.at(self.position()?)),
+        };
+        let contents = self
+            .input
+            .get(self.pos..)
+            .and_then(|remaining| remaining.get(..len))
+            .ok_or_else(|| self.incomplete(len))?;
+        self.pos += len;
+        Ok(contents)
+    }
+
+    /// Check whether the contents of a constructed value have been fully read, consuming the
+    /// end-of-contents octets of indefinite lengths.
+    fn at_end_of_contents(&mut self, end: Option<usize>) -> Result<bool> {
+        match end {
+            Some(end) if self.pos > end => {
+                Err(ErrorKind::Length { tag: Tag::Sequence }.at(self.position()?))
+            }
+            Some(end) => Ok(self.pos == end),
+            None if self
+                .input
+                .get(self.pos..)
+                .map_or(false, |remaining| remaining.starts_with(&END_OF_CONTENTS)) =>
+            {
+                self.pos += END_OF_CONTENTS.len();
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Error for `len` bytes of contents extending past the end of the input.
+    fn incomplete(&self, len: usize) -> Error {
+        let actual_len = Length::try_from(self.input.len()).unwrap_or(Length::MAX);
+        let expected_len = self
+            .pos
+            .checked_add(len)
+            .and_then(|end| Length::try_from(end).ok())
+            .unwrap_or(Length::MAX);
+
+        ErrorKind::Incomplete {
+            expected_len,
+            actual_len,
+        }
+        .at(Length::try_from(self.pos).unwrap_or(Length::MAX))
+    }
+
+    fn read_byte(&mut self) -> Result<u8> {
+        let byte = *self.input.get(self.pos).ok_or_else(|| {
+            let actual_len = Length::try_from(self.input.len()).unwrap_or(Length::MAX);
+            ErrorKind::Incomplete {
+                expected_len: actual_len.saturating_add(Length::ONE),
+                actual_len,
+            }
+            .at(actual_len)
+        })?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn position(&self) -> Result<Length> {
+        Length::try_from(self.pos)
+    }
+}
+
+/// Check the contents of a primitive value whose leading identifier octet is `tag`,
+/// returning whether they are in their DER form.
+fn is_canonical_primitive(tag: u8, contents: &[u8]) -> Result<bool> {
+    match tag {
+        // X.690 Section 11.1
+        0x01 => match contents {
+            [0] | [0xFF] => Ok(true),
+            [_] => Ok(false),
+            _ => Err(Tag::Boolean.length_error()),
+        },
+        // X.690 Section 11.2.1
+        0x03 => match contents.split_first() {
+            Some((&unused_bits, bits)) if unused_bits < 8 => {
+                if bits.is_empty() && unused_bits != 0 {
+                    return Err(Tag::BitString.value_error());
+                }
+
+                Ok(bits
+                    .last()
+                    .map_or(true, |last| last & !(0xFF << unused_bits) == 0))
+            }
+            _ => Err(Tag::BitString.value_error()),
+        },
+        _ => Ok(true),
+    }
+}
+
+/// Write the DER encoding of a primitive value, whose leading identifier octet is `tag`.
+fn write_primitive(der: &mut Vec<u8>, tag: u8, identifier: &[u8], contents: &[u8]) -> Result<()> {
+    let canonical = is_canonical_primitive(tag, contents)?;
+
+    write_header(der, identifier, contents.len())?;
+    der.extend_from_slice(contents);
+
+    if !canonical {
+        if let Some(last) = der.last_mut() {
+            *last = match (tag, contents.first()) {
+                (0x01, _) => 0xFF,
+                (0x03, Some(&unused_bits)) => *last & (0xFF << unused_bits),
+                _ => *last,
+            };
+        }
+    }
+
+    Ok(())
+}
+
+/// Write DER identifier and length octets.
+fn write_header(der: &mut Vec<u8>, identifier: &[u8], len: usize) -> Result<()> {
+    let mut buf = [0u8; 5];
+    der.extend_from_slice(identifier);
+    der.extend_from_slice(Length::try_from(len)?.encode_to_slice(&mut buf)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ber_to_der, ber_to_der_with_max_depth, check_der, check_der_with_max_depth};
+    use crate::{ErrorKind, Length, Tag};
+    use hex_literal::hex;
+
+    #[test]
+    fn der_is_unchanged() {
+        let der = hex!("3009 0201 01 0101 FF 0401 AA");
+        assert_eq!(ber_to_der(&der).unwrap(), der);
+    }
+
+    #[test]
+    fn indefinite_length() {
+        let ber = hex!("3080 0201 01 3080 0500 0000 0000");
+        assert_eq!(ber_to_der(&ber).unwrap(), hex!("3007 0201 01 3002 0500"));
+    }
+
+    #[test]
+    fn non_minimal_length() {
+        let ber = hex!("0482 0002 AABB");
+        assert_eq!(ber_to_der(&ber).unwrap(), hex!("0402 AABB"));
+    }
+
+    #[test]
+    fn constructed_octet_string() {
+        let ber = hex!("2480 0402 AABB 2406 0401 CC 0401 DD 0000");
+        assert_eq!(ber_to_der(&ber).unwrap(), hex!("0404 AABBCCDD"));
+    }
+
+    #[test]
+    fn constructed_bit_string() {
+        let ber = hex!("2309 0303 00 AAAA 0302 04 FF");
+        assert_eq!(ber_to_der(&ber).unwrap(), hex!("0304 04 AAAAF0"));
+
+        // only the last segment may have unused bits
+        let ber = hex!("2309 0303 04 AAA0 0302 00 FF");
+        assert!(ber_to_der(&ber).is_err());
+    }
+
+    #[test]
+    fn character_string_is_constructed() {
+        // `CHARACTER STRING` is a `SEQUENCE`, whose elements aren't concatenated
+        let ber = hex!("3D80 3003 0601 00 0402 AABB 0000");
+        assert_eq!(
+            ber_to_der(&ber).unwrap(),
+            hex!("3D09 3003 0601 00 0402 AABB")
+        );
+    }
+
+    #[test]
+    fn boolean_and_bit_string_values() {
+        assert_eq!(ber_to_der(&hex!("0101 01")).unwrap(), hex!("0101 FF"));
+        assert_eq!(ber_to_der(&hex!("0302 03 FF")).unwrap(), hex!("0302 03 F8"));
+    }
+
+    #[test]
+    fn set_is_sorted() {
+        let ber = hex!("3180 0401 02 0201 05 0401 01 0000");
+        assert_eq!(
+            ber_to_der(&ber).unwrap(),
+            hex!("3109 0201 05 0401 01 0401 02")
+        );
+    }
+
+    #[test]
+    fn high_tag_numbers() {
+        // [APPLICATION 31] { [PRIVATE 200] IMPLICIT OCTET STRING, [31] IMPLICIT NULL }
+        let der = hex!("7F1F 09 DF8148 02 AABB 9F1F 00");
+        assert_eq!(ber_to_der(&der).unwrap(), der);
+        assert!(check_der(&der).is_ok());
+
+        let ber = hex!("7F1F 80 DF8148 81 02 AABB 9F1F 00 0000");
+        assert_eq!(ber_to_der(&ber).unwrap(), der);
+        assert_eq!(
+            check_der(&ber).unwrap_err().kind(),
+            ErrorKind::IndefiniteLength
+        );
+
+        // SET of high tags sorted by encoding
+        let ber = hex!("3180 9F8101 00 9F1F 00 0000");
+        let der = hex!("3107 9F1F 00 9F8101 00");
+        assert_eq!(ber_to_der(&ber).unwrap(), der);
+        assert!(check_der(&der).is_ok());
+        assert_eq!(
+            check_der(&hex!("3107 9F8101 00 9F1F 00"))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::SetOrdering
+        );
+
+        // tag numbers below 31, with a leading `0x80` octet, or above `u32::MAX`
+        for ber in [
+            &hex!("9F1E 00")[..],
+            &hex!("9F801F 00"),
+            &hex!("9F9080808000 00"),
+        ] {
+            let err = ber_to_der(ber).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::TagNumberInvalid, "{:02X?}", ber);
+            assert_eq!(err.position(), Some(Length::ONE));
+            assert_eq!(
+                check_der(ber).unwrap_err().kind(),
+                ErrorKind::TagNumberInvalid
+            );
+        }
+    }
+
+    #[test]
+    fn malformed() {
+        // indefinite length of a primitive value
+        assert_eq!(
+            ber_to_der(&hex!("0480 AA 0000")).unwrap_err().kind(),
+            ErrorKind::IndefiniteLength
+        );
+
+        // missing end-of-contents
+        assert!(ber_to_der(&hex!("3080 0500")).is_err());
+
+        // trailing data
+        assert!(matches!(
+            ber_to_der(&hex!("0500 00")).unwrap_err().kind(),
+            ErrorKind::TrailingData { .. }
+        ));
+
+        // unbounded nesting
+        let mut ber = [0x30u8, 0x80].repeat(100);
+        ber.extend_from_slice(&[0; 200]);
+        assert!(ber_to_der(&ber).is_err());
+    }
+
+    #[test]
+    fn max_depth() {
+        let ber = hex!("3080 3080 3080 0500 0000 0000 0000");
+        let der = hex!("3006 3004 3002 0500");
+
+        assert_eq!(ber_to_der_with_max_depth(&ber, 3).unwrap(), der);
+        assert!(check_der_with_max_depth(&der, 3).is_ok());
+
+        let err = ber_to_der_with_max_depth(&ber, 2).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DepthExceeded { max_depth: 2 });
+        assert_eq!(err.position(), Some(Length::new(6)));
+        assert_eq!(
+            check_der_with_max_depth(&der, 2).unwrap_err().kind(),
+            ErrorKind::DepthExceeded { max_depth: 2 }
+        );
+
+        // constructed string segments count towards the depth
+        let ber = hex!("2480 2480 0401 AA 0000 0000");
+        assert_eq!(ber_to_der_with_max_depth(&ber, 2).unwrap(), hex!("0401 AA"));
+        assert!(ber_to_der_with_max_depth(&ber, 1).is_err());
+    }
+
+    #[test]
+    fn strict() {
+        assert!(check_der(&hex!("3109 0201 05 0401 01 0401 02")).is_ok());
+        assert!(check_der(&hex!("3009 0201 01 0101 FF 0401 AA")).is_ok());
+
+        let check = |ber: &[u8]| {
+            let err = check_der(ber).unwrap_err();
+            (err.kind(), err.position().map(u32::from))
+        };
+
+        assert_eq!(
+            check(&hex!("3007 0201 01 3080 0500 0000")),
+            (ErrorKind::IndefiniteLength, Some(5))
+        );
+        assert_eq!(
+            check(&hex!("3006 0500 0482 0000")),
+            (ErrorKind::Overlength, Some(4))
+        );
+        assert_eq!(
+            check(&hex!("3005 2403 0401 AA")),
+            (
+                ErrorKind::Noncanonical {
+                    tag: Tag::OctetString
+                },
+                Some(2)
+            )
+        );
+        assert_eq!(
+            check(&hex!("3005 0500 0101 01")),
+            (ErrorKind::Noncanonical { tag: Tag::Boolean }, Some(4))
+        );
+        assert_eq!(
+            check(&hex!("0302 03 FF")),
+            (
+                ErrorKind::Noncanonical {
+                    tag: Tag::BitString
+                },
+                Some(0)
+            )
+        );
+        assert_eq!(
+            check(&hex!("3008 3106 0401 02 0401 01")),
+            (ErrorKind::SetOrdering, Some(2))
+        );
+        assert_eq!(
+            check_der(&hex!("0500 00")).unwrap_err().position(),
+            Some(Length::new(2))
+        );
+    }
+}
@@ -143,14 +143,11 @@ impl TryFrom<Vec<u8>> for BytesOwned {
 #[cfg(feature = "arbitrary")]
 impl<'a> arbitrary::Arbitrary<'a> for BytesOwned {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        let length = u.arbitrary()?;
-        Ok(Self {
-            length,
-            inner: Box::from(u.bytes(u32::from(length) as usize)?),
-        })
+        // Let `arbitrary` pick a length which fits the remaining input
+        Self::new(<&'a [u8]>::arbitrary(u)?).map_err(|_| arbitrary::Error::IncorrectFormat)
     }
 
     fn size_hint(depth: usize) -> (usize, Option<usize>) {
-        arbitrary::size_hint::and(Length::size_hint(depth), (0, None))
+        <&'a [u8]>::size_hint(depth)
     }
 }
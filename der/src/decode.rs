@@ -4,7 +4,10 @@ use crate::{FixedTag, Header, Reader, Result, SliceReader};
 use core::marker::PhantomData;
 
 #[cfg(feature = "pem")]
-use crate::{pem::PemLabel, PemReader};
+use crate::{
+    pem::{LabelRegistry, PemLabel},
+    PemReader,
+};
 
 #[cfg(doc)]
 use crate::{Length, Tag};
@@ -50,6 +53,36 @@ where
     }
 }
 
+/// Decoding trait for `ANY DEFINED BY` values, i.e. values whose type is
+/// determined by another value, typically an `OBJECT IDENTIFIER`.
+///
+/// This trait is used by the `#[asn1(defined_by = "...")]` attribute of the
+/// `Sequence` derive macro, where `K` is the type of the field which defines
+/// the type of the value.
+pub trait DecodeDefinedBy<'a, K: ?Sized>: Sized {
+    /// Attempt to decode this message using the provided decoder, selecting
+    /// its type based on the value of `key`.
+    fn decode_defined_by<R: Reader<'a>>(key: &K, reader: &mut R) -> Result<Self>;
+}
+
+/// Absent values are decoded as `None`, when the end of the enclosing message
+/// has been reached, e.g. for an `OPTIONAL` value at the end of a `SEQUENCE`,
+/// or when the next value is the end-of-contents marker of the contents of a
+/// value with an indefinite length.
+impl<'a, K, T> DecodeDefinedBy<'a, K> for Option<T>
+where
+    K: ?Sized,
+    T: DecodeDefinedBy<'a, K>,
+{
+    fn decode_defined_by<R: Reader<'a>>(key: &K, reader: &mut R) -> Result<Self> {
+        if reader.is_finished() || (reader.in_indefinite_contents() && reader.peek_eoc()) {
+            Ok(None)
+        } else {
+            T::decode_defined_by(key, reader).map(Some)
+        }
+    }
+}
+
 /// Marker trait for data structures that can be decoded from DER without
 /// borrowing any data from the decoder.
 ///
@@ -70,6 +103,10 @@ impl<T> DecodeOwned for T where T: for<'a> Decode<'a> {}
 pub trait DecodePem: DecodeOwned + PemLabel {
     /// Try to decode this type from PEM.
     fn from_pem(pem: impl AsRef<[u8]>) -> Result<Self>;
+
+    /// Try to decode this type from PEM, also accepting the type labels
+    /// registered as aliases of its label in `labels`.
+    fn from_pem_with_labels(pem: impl AsRef<[u8]>, labels: &LabelRegistry<'_>) -> Result<Self>;
 }
 
 #[cfg(feature = "pem")]
@@ -79,6 +116,12 @@ impl<T: DecodeOwned + PemLabel> DecodePem for T {
         Self::validate_pem_label(reader.type_label())?;
         T::decode(&mut reader)
     }
+
+    fn from_pem_with_labels(pem: impl AsRef<[u8]>, labels: &LabelRegistry<'_>) -> Result<Self> {
+        let mut reader = PemReader::new(pem.as_ref())?;
+        labels.validate_label::<Self>(reader.type_label())?;
+        T::decode(&mut reader)
+    }
 }
 
 /// Decode the value part of a Tag-Length-Value encoded field, sans the [`Tag`]
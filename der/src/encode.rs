@@ -6,6 +6,9 @@ use core::marker::PhantomData;
 #[cfg(feature = "alloc")]
 use {alloc::boxed::Box, alloc::vec::Vec, core::iter};
 
+#[cfg(feature = "std")]
+use std::io;
+
 #[cfg(feature = "pem")]
 use {
     crate::PemWriter,
@@ -13,7 +16,7 @@ use {
     pem_rfc7468::{self as pem, LineEnding, PemLabel},
 };
 
-#[cfg(any(feature = "alloc", feature = "pem"))]
+#[cfg(feature = "alloc")]
 use crate::ErrorKind;
 
 #[cfg(doc)]
@@ -65,6 +68,21 @@ pub trait Encode {
         self.encode_to_vec(&mut buf)?;
         Ok(buf)
     }
+
+    /// Encode this message as ASN.1 DER, streaming it to the provided
+    /// [`io::Write`] implementation, e.g. a file or socket.
+    ///
+    /// Unlike [`Encode::to_der`], this doesn't buffer the encoded message.
+    /// Writers which perform a system call per write, such as [`std::fs::File`],
+    /// should be wrapped in a [`std::io::BufWriter`].
+    ///
+    /// Returns the length of the encoded message.
+    #[cfg(feature = "std")]
+    fn encode_to_writer(&self, writer: &mut impl io::Write) -> Result<Length> {
+        let len = self.encoded_len()?;
+        self.encode(writer)?;
+        Ok(len)
+    }
 }
 
 impl<T> Encode for T
@@ -73,7 +91,8 @@ where
 {
     /// Compute the length of this value in bytes when encoded as ASN.1 DER.
     fn encoded_len(&self) -> Result<Length> {
-        self.value_len().and_then(|len| len.for_tlv())
+        let len = self.value_len()?;
+        Header::new(self.tag(), len)?.encoded_len()? + len
     }
 
     /// Encode this value as ASN.1 DER using the provided [`Writer`].
@@ -98,6 +117,70 @@ where
     }
 }
 
+/// Object-safe counterpart of [`Encode`].
+///
+/// This trait is automatically impl'd for any type which impls [`Encode`], and
+/// `dyn EncodeDyn` impls [`Encode`] in turn, which allows encoding heterogeneous
+/// collections of values, e.g. `Vec<Box<dyn EncodeDyn>>` as a `SEQUENCE OF`,
+/// without wrapping them in an enum.
+pub trait EncodeDyn {
+    /// Compute the length of this value in bytes when encoded as ASN.1 DER.
+    fn encoded_len_dyn(&self) -> Result<Length>;
+
+    /// Encode this value as ASN.1 DER using the provided [`Writer`], returning
+    /// the length of the encoded value.
+    fn encode_dyn(&self, writer: &mut dyn Writer) -> Result<Length>;
+}
+
+impl<T> EncodeDyn for T
+where
+    T: Encode,
+{
+    fn encoded_len_dyn(&self) -> Result<Length> {
+        self.encoded_len()
+    }
+
+    fn encode_dyn(&self, writer: &mut dyn Writer) -> Result<Length> {
+        let len = self.encoded_len()?;
+        self.encode(&mut DynWriter(writer))?;
+        Ok(len)
+    }
+}
+
+impl Encode for dyn EncodeDyn + '_ {
+    fn encoded_len(&self) -> Result<Length> {
+        self.encoded_len_dyn()
+    }
+
+    fn encode(&self, writer: &mut impl Writer) -> Result<()> {
+        self.encode_dyn(writer).map(|_| ())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Encode for Box<dyn EncodeDyn + '_> {
+    fn encoded_len(&self) -> Result<Length> {
+        self.as_ref().encoded_len_dyn()
+    }
+
+    fn encode(&self, writer: &mut impl Writer) -> Result<()> {
+        self.as_ref().encode_dyn(writer).map(|_| ())
+    }
+}
+
+/// Adapter passing a `&mut dyn Writer` where an `impl Writer` is expected.
+struct DynWriter<'a>(&'a mut dyn Writer);
+
+impl Writer for DynWriter<'_> {
+    fn write(&mut self, slice: &[u8]) -> Result<()> {
+        self.0.write(slice)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> Result<()> {
+        self.0.write_byte(byte)
+    }
+}
+
 /// PEM encoding trait.
 ///
 /// This trait is automatically impl'd for any type which impls both
@@ -156,3 +239,39 @@ where
         T::encode_value(self, writer)
     }
 }
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::EncodeDyn;
+    use crate::{
+        asn1::{BitStringRef, OctetStringRef},
+        Encode, Length, SliceWriter,
+    };
+    use alloc::{boxed::Box, vec, vec::Vec};
+    use hex_literal::hex;
+
+    #[test]
+    fn encode_dyn() {
+        let value: &dyn EncodeDyn = &OctetStringRef::new(&[1, 2]).unwrap();
+        assert_eq!(value.encoded_len().unwrap(), Length::new(4));
+        assert_eq!(value.to_der().unwrap(), hex!("04020102"));
+
+        let mut buf = [0u8; 4];
+        let mut writer = SliceWriter::new(&mut buf);
+        assert_eq!(value.encode_dyn(&mut writer).unwrap(), Length::new(4));
+        assert_eq!(writer.finish().unwrap(), hex!("04020102"));
+    }
+
+    #[test]
+    fn encode_heterogeneous_sequence() {
+        let values: Vec<Box<dyn EncodeDyn>> = vec![
+            Box::new(true),
+            Box::new(42u8),
+            Box::new(BitStringRef::from_bytes(&[0x80]).unwrap()),
+        ];
+        assert_eq!(
+            values.to_der().unwrap(),
+            hex!("300a 0101ff 02012a 03020080")
+        );
+    }
+}
@@ -14,14 +14,25 @@ use crate::pem;
 /// Result type.
 pub type Result<T> = core::result::Result<T, Error>;
 
+/// Maximum number of fields recorded in an [`ErrorPath`].
+///
+/// Kept small since the path is stored inline in each [`Error`].
+const MAX_PATH_LEN: usize = 3;
+
 /// Error type.
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+///
+/// Errors are compared by their [`ErrorKind`] and position: their
+/// [`ErrorPath`] is ignored.
+#[derive(Copy, Clone, Debug, Eq)]
 pub struct Error {
     /// Kind of error.
     kind: ErrorKind,
 
     /// Position inside of message where error occurred.
     position: Option<Length>,
+
+    /// Path to the value being decoded when the error occurred.
+    path: ErrorPath,
 }
 
 impl Error {
@@ -30,6 +41,7 @@ impl Error {
         Error {
             kind,
             position: Some(position),
+            path: ErrorPath::default(),
         }
     }
 
@@ -57,6 +69,28 @@ impl Error {
         self.position
     }
 
+    /// Get the path to the value being decoded when the error occurred.
+    pub fn path(self) -> ErrorPath {
+        self.path
+    }
+
+    /// Record that the error occurred while decoding the given field.
+    ///
+    /// Errors are annotated as they propagate, i.e. from the innermost field
+    /// to the outermost one. This is performed for each field by the
+    /// `Sequence` derive macro.
+    pub fn in_field(mut self, field: &'static FieldName) -> Self {
+        self.path.push_field(field);
+        self
+    }
+
+    /// Record that the error occurred while decoding the element with the
+    /// given index of a `SEQUENCE OF` or `SET OF`.
+    pub fn in_element(mut self, index: usize) -> Self {
+        self.path.push_index(index);
+        self
+    }
+
     /// For errors occurring inside of a nested message, extend the position
     /// count by the location where the nested message occurs.
     pub(crate) fn nested(self, nested_position: Length) -> Self {
@@ -66,15 +100,26 @@ impl Error {
         Self {
             kind: self.kind,
             position,
+            path: self.path,
         }
     }
 }
 
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.position == other.position
+    }
+}
+
 #[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path)?;
+        }
+
         write!(f, "{}", self.kind)?;
 
         if let Some(pos) = self.position {
@@ -85,11 +130,127 @@ impl fmt::Display for Error {
     }
 }
 
+/// Name of a field of a type, recorded in the [`ErrorPath`] of errors which
+/// occurred while decoding it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct FieldName {
+    /// Name of the type.
+    pub type_name: &'static str,
+
+    /// Name of the field.
+    pub field: &'static str,
+}
+
+/// Path to the value being decoded when an [`Error`] occurred, e.g.
+/// `SignedData.certificates[3].tbs_certificate.validity`.
+///
+/// Only the innermost fields are recorded when the value is nested deeper
+/// than the capacity of the path, in which case [`ErrorPath::is_truncated`]
+/// returns `true`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ErrorPath {
+    /// Fields, innermost first.
+    fields: [Option<&'static FieldName>; MAX_PATH_LEN],
+
+    /// Index plus one of the value of the corresponding field within a
+    /// `SEQUENCE OF` or `SET OF`, or zero, including for indices which don't
+    /// fit in a `u16`.
+    ///
+    /// The entry following the last field holds the index of the outermost
+    /// value within a `SEQUENCE OF` or `SET OF` which isn't a field.
+    indices: [u16; MAX_PATH_LEN],
+
+    /// Number of fields which have been pushed.
+    len: u8,
+}
+
+impl ErrorPath {
+    /// Is this path empty?
+    pub fn is_empty(&self) -> bool {
+        self.len == 0 && self.indices[0] == 0
+    }
+
+    /// Have outer fields been omitted from this path?
+    pub fn is_truncated(&self) -> bool {
+        usize::from(self.len) > MAX_PATH_LEN
+    }
+
+    /// Iterate over the recorded fields, from the outermost to the innermost,
+    /// along with the index of their value within a `SEQUENCE OF` or `SET OF`.
+    pub fn fields(&self) -> impl Iterator<Item = (&'static FieldName, Option<usize>)> + '_ {
+        self.fields
+            .iter()
+            .zip(self.indices.iter())
+            .rev()
+            .filter_map(|(field, &index)| Some(((*field)?, index_of(index))))
+    }
+
+    /// Record an outer field.
+    fn push_field(&mut self, field: &'static FieldName) {
+        if let Some(slot) = self.fields.get_mut(usize::from(self.len)) {
+            *slot = Some(field);
+        }
+
+        self.len = self.len.saturating_add(1);
+    }
+
+    /// Record the index of the outer value within a `SEQUENCE OF` or `SET OF`.
+    fn push_index(&mut self, index: usize) {
+        if let Some(slot) = self.indices.get_mut(usize::from(self.len)) {
+            *slot = u16::try_from(index)
+                .ok()
+                .and_then(|index| index.checked_add(1))
+                .unwrap_or(0);
+        }
+    }
+
+    /// Get the index of the outermost value, if it isn't a field.
+    fn root_index(&self) -> Option<usize> {
+        self.indices
+            .get(usize::from(self.len))
+            .and_then(|&index| index_of(index))
+    }
+}
+
+impl fmt::Display for ErrorPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_truncated() {
+            f.write_str("..")?;
+        } else if let Some(index) = self.root_index() {
+            write!(f, "[{}]", index)?;
+        }
+
+        for (i, (field, index)) in self.fields().enumerate() {
+            if i == 0 && !self.is_truncated() {
+                if self.root_index().is_some() {
+                    f.write_str(".")?;
+                }
+
+                f.write_str(field.type_name)?;
+            }
+
+            write!(f, ".{}", field.field)?;
+
+            if let Some(index) = index {
+                write!(f, "[{}]", index)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Convert an index stored in an [`ErrorPath`].
+fn index_of(index: u16) -> Option<usize> {
+    index.checked_sub(1).map(usize::from)
+}
+
 impl From<ErrorKind> for Error {
     fn from(kind: ErrorKind) -> Error {
         Error {
             kind,
             position: None,
+            path: ErrorPath::default(),
         }
     }
 }
@@ -105,6 +266,7 @@ impl From<TryFromIntError> for Error {
         Error {
             kind: ErrorKind::Overflow,
             position: None,
+            path: ErrorPath::default(),
         }
     }
 }
@@ -114,6 +276,7 @@ impl From<Utf8Error> for Error {
         Error {
             kind: ErrorKind::Utf8(err),
             position: None,
+            path: ErrorPath::default(),
         }
     }
 }
@@ -165,6 +328,12 @@ pub enum ErrorKind {
     /// Date-and-time related errors.
     DateTime,
 
+    /// Nesting depth of the message exceeds the maximum allowed by the reader.
+    DepthExceeded {
+        /// Maximum nesting depth.
+        max_depth: usize,
+    },
+
     /// This error indicates a previous DER parsing operation resulted in
     /// an error and tainted the state of a `Decoder` or `Encoder`.
     ///
@@ -309,6 +478,9 @@ impl fmt::Display for ErrorKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ErrorKind::DateTime => write!(f, "date/time error"),
+            ErrorKind::DepthExceeded { max_depth } => {
+                write!(f, "nesting depth exceeds maximum of {}", max_depth)
+            }
             ErrorKind::Failed => write!(f, "operation failed"),
             #[cfg(feature = "std")]
             ErrorKind::FileNotFound => write!(f, "file not found"),
@@ -0,0 +1,315 @@
+//! Values which preserve their original encoding.
+
+use crate::{
+    Decode, DerOrd, Encode, Error, ErrorKind, FixedTag, Header, Length, Reader, Result, Tag, Writer,
+};
+use alloc::vec::Vec;
+use core::{cmp::Ordering, ops::Deref};
+
+/// Decoded value along with the exact bytes it was decoded from.
+///
+/// Decoding a value and encoding it again usually yields the same bytes, but this is not
+/// guaranteed for all types and inputs: some decoders accept inputs which aren't in their
+/// canonical form, and the encoding of a value may change between versions of this crate or of
+/// the crates defining it. Applications which must relay signed structures unchanged, such as
+/// Certificate Transparency log mirrors or CMS relays, can't rely on that.
+///
+/// `Exact<T>` guarantees byte-identical round trips: the [`Encode`] implementation always
+/// outputs the bytes the value was decoded from (or encoded to, for [`Exact::new`]), regardless
+/// of how `T` encodes itself. This guarantee is part of the stable API of this crate.
+///
+/// The value can't be modified, since the change wouldn't be reflected in the encoding: use
+/// [`Exact::into_inner`] and [`Exact::new`] to re-encode a modified value instead.
+#[derive(Clone, Debug)]
+pub struct Exact<T> {
+    /// Decoded value.
+    value: T,
+
+    /// DER-encoded bytes of the value, including its tag and length.
+    der_bytes: Vec<u8>,
+}
+
+impl<T> Exact<T> {
+    /// Encode `value`, fixing its encoding from now on.
+    pub fn new(value: T) -> Result<Self>
+    where
+        T: Encode,
+    {
+        let der_bytes = value.to_der()?;
+        Ok(Self { value, der_bytes })
+    }
+
+    /// Borrow the decoded value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Borrow the original bytes of the value, including its tag and length.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.der_bytes
+    }
+
+    /// Convert into the decoded value, dropping the original bytes.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Convert into the original bytes of the value.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.der_bytes
+    }
+
+    /// Whether encoding the value with `T`'s own [`Encode`] implementation yields the original
+    /// bytes, i.e. whether they were in canonical form.
+    pub fn is_canonical(&self) -> Result<bool>
+    where
+        T: Encode,
+    {
+        Ok(self.value.to_der()? == self.der_bytes)
+    }
+}
+
+impl<T> AsRef<T> for Exact<T> {
+    fn as_ref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> Deref for Exact<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T: Decode<'a>> Decode<'a> for Exact<T> {
+    fn decode<R: Reader<'a>>(reader: &mut R) -> Result<Self> {
+        let offset = reader.offset();
+        let header = reader.peek_header()?;
+        let len = (header.encoded_len()? + header.length)?;
+
+        // The nested reader checks that the input holds `len` bytes, so they can be allocated
+        reader.read_nested(len, |reader| {
+            let mut reader = RecordingReader {
+                inner: reader,
+                bytes: Vec::with_capacity(usize::try_from(len)?),
+            };
+            // Errors without a position are located at the start of the value
+            let value = T::decode(&mut reader).map_err(|err| match err.position() {
+                Some(_) => err,
+                None => err.nested(offset),
+            })?;
+
+            Ok(Self {
+                value,
+                der_bytes: reader.bytes,
+            })
+        })
+    }
+}
+
+/// Reader keeping a copy of the bytes read from the inner reader.
+struct RecordingReader<'i, R> {
+    /// Inner reader type.
+    inner: &'i mut R,
+
+    /// Bytes read so far.
+    bytes: Vec<u8>,
+}
+
+impl<'i, 'r, R: Reader<'r>> Reader<'r> for RecordingReader<'i, R> {
+    fn input_len(&self) -> Length {
+        self.inner.input_len()
+    }
+
+    fn peek_byte(&self) -> Option<u8> {
+        self.inner.peek_byte()
+    }
+
+    fn peek_eoc(&self) -> bool {
+        self.inner.peek_eoc()
+    }
+
+    fn peek_header(&self) -> Result<Header> {
+        self.inner.peek_header()
+    }
+
+    fn position(&self) -> Length {
+        self.inner.position()
+    }
+
+    fn read_slice(&mut self, len: Length) -> Result<&'r [u8]> {
+        let slice = self.inner.read_slice(len)?;
+        self.bytes.extend_from_slice(slice);
+        Ok(slice)
+    }
+
+    fn error(&mut self, kind: ErrorKind) -> Error {
+        self.inner.error(kind)
+    }
+
+    fn offset(&self) -> Length {
+        self.inner.offset()
+    }
+
+    fn depth(&self) -> usize {
+        self.inner.depth()
+    }
+
+    fn in_indefinite_contents(&self) -> bool {
+        self.inner.in_indefinite_contents()
+    }
+
+    fn max_depth(&self) -> usize {
+        self.inner.max_depth()
+    }
+
+    fn read_into<'o>(&mut self, out: &'o mut [u8]) -> Result<&'o [u8]> {
+        let out = self.inner.read_into(out)?;
+        self.bytes.extend_from_slice(out);
+        Ok(out)
+    }
+}
+
+impl<T> Encode for Exact<T> {
+    fn encoded_len(&self) -> Result<Length> {
+        self.der_bytes.len().try_into()
+    }
+
+    fn encode(&self, writer: &mut impl Writer) -> Result<()> {
+        writer.write(&self.der_bytes)
+    }
+}
+
+impl<T: FixedTag> FixedTag for Exact<T> {
+    const TAG: Tag = T::TAG;
+}
+
+impl<T> DerOrd for Exact<T> {
+    fn der_cmp(&self, other: &Self) -> Result<Ordering> {
+        Ok(self.der_bytes.cmp(&other.der_bytes))
+    }
+}
+
+impl<T> Eq for Exact<T> {}
+
+/// Values are equal when their original bytes are, even if `T` considers the decoded values
+/// equal.
+impl<T> PartialEq for Exact<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.der_bytes == other.der_bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Exact;
+    use crate::{
+        asn1::{OctetString, OctetStringRef, SequenceOf},
+        Decode, DecodeValue, Encode, EncodeValue, ErrorKind, FixedTag, Header, Length, Reader,
+        Result, SliceReader, Tag, Writer,
+    };
+    use hex_literal::hex;
+
+    /// OCTET STRING decoded leniently: only its first octet is kept.
+    #[derive(Debug, PartialEq)]
+    struct Lenient(u8);
+
+    impl FixedTag for Lenient {
+        const TAG: Tag = Tag::OctetString;
+    }
+
+    impl<'a> DecodeValue<'a> for Lenient {
+        fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+            let octets = OctetString::decode_value(reader, header)?;
+            Ok(Self(octets.as_bytes().first().copied().unwrap_or_default()))
+        }
+    }
+
+    impl EncodeValue for Lenient {
+        fn value_len(&self) -> Result<Length> {
+            Ok(Length::ONE)
+        }
+
+        fn encode_value(&self, writer: &mut impl Writer) -> Result<()> {
+            writer.write_byte(self.0)
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let der = hex!("0403010203");
+        let exact = Exact::<Lenient>::from_der(&der).unwrap();
+
+        assert_eq!(exact.value(), &Lenient(1));
+        assert_eq!(exact.as_bytes(), der);
+        assert_eq!(exact.to_der().unwrap(), der);
+        assert!(!exact.is_canonical().unwrap());
+        assert_eq!(exact.into_inner().to_der().unwrap(), hex!("040101"));
+    }
+
+    #[test]
+    fn new() {
+        let exact = Exact::new(Lenient(1)).unwrap();
+        assert_eq!(exact.as_bytes(), hex!("040101"));
+        assert!(exact.is_canonical().unwrap());
+        assert_ne!(exact, Exact::from_der(&hex!("0403010203")).unwrap());
+    }
+
+    #[test]
+    fn nested() {
+        let der = hex!("300b 0403010203 040101 040102");
+        let values = SequenceOf::<Exact<Lenient>, 3>::from_der(&der).unwrap();
+
+        assert_eq!(values.get(0).unwrap().as_bytes(), hex!("0403010203"));
+        assert_eq!(values.get(2).unwrap().value(), &Lenient(2));
+        assert_eq!(values.to_der().unwrap(), der);
+    }
+
+    #[test]
+    fn error_position() {
+        let der = hex!("3007 0403010203 0500");
+        let err = SequenceOf::<Exact<Lenient>, 3>::from_der(&der).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::TagUnexpected {
+                expected: Some(Tag::OctetString),
+                actual: Tag::Null
+            }
+        );
+        assert_eq!(err.position(), Some(Length::new(7)));
+    }
+
+    #[test]
+    fn borrowed() {
+        let der = hex!("0403010203");
+        let exact = Exact::<OctetStringRef<'_>>::from_der(&der).unwrap();
+
+        assert_eq!(exact.as_bytes(), der);
+        assert_eq!(exact.value().as_bytes().as_ptr(), der[2..].as_ptr());
+    }
+
+    #[test]
+    fn truncated() {
+        // The header claims about 256 MiB
+        let der = hex!("04 84 0f ff ff f0 00");
+        let err = Exact::<Lenient>::from_der(&der).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::Incomplete { .. }));
+    }
+
+    #[test]
+    fn max_depth() {
+        let der = hex!("3005 0403010203");
+
+        let mut reader = SliceReader::new(&der).unwrap().with_max_depth(1);
+        let err = reader
+            .decode::<Exact<SequenceOf<Lenient, 1>>>()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DepthExceeded { max_depth: 1 });
+
+        let mut reader = SliceReader::new(&der).unwrap().with_max_depth(2);
+        let values = reader.decode::<Exact<SequenceOf<Lenient, 1>>>().unwrap();
+        assert_eq!(values.get(0), Some(&Lenient(1)));
+    }
+}
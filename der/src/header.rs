@@ -1,9 +1,12 @@
 //! ASN.1 DER headers.
 
-use crate::{Decode, DerOrd, Encode, ErrorKind, Length, Reader, Result, Tag, Writer};
+use crate::{
+    Decode, DerOrd, Encode, Error, ErrorKind, IndefiniteLength, Length, Reader, Result, Tag, Writer,
+};
 use core::cmp::Ordering;
 
 /// ASN.1 DER headers: tag + length component of TLV-encoded values
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub struct Header {
     /// Tag representing the type of the encoded value
@@ -14,6 +17,10 @@ pub struct Header {
 }
 
 impl Header {
+    /// Maximum number of octets in an encoded [`Header`].
+    #[cfg(all(feature = "pem", feature = "std"))]
+    pub(crate) const MAX_SIZE: usize = Tag::MAX_SIZE + Length::MAX_SIZE;
+
     /// Create a new [`Header`] from a [`Tag`] and a specified length.
     ///
     /// Returns an error if the length exceeds the limits of [`Length`].
@@ -58,3 +65,278 @@ impl DerOrd for Header {
         }
     }
 }
+
+/// Form of the length octets of a header, as described in X.690 Section 8.1.3.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LengthForm {
+    /// Definite form using a single octet, for lengths up to 127.
+    Short,
+
+    /// Definite form using an initial octet followed by the given number of octets, from 1
+    /// to 126.
+    Long(u8),
+
+    /// Indefinite form, with the contents terminated by end-of-contents octets.
+    Indefinite,
+}
+
+/// ASN.1 BER header which retains the form its length was encoded with.
+///
+/// Unlike [`Header`], which only decodes DER lengths, this decodes lengths in the long form
+/// with superfluous octets and in the indefinite form, which are allowed in BER. Encoding a
+/// `RawHeader` reproduces the original length octets, so that transcoders and linters can
+/// preserve or report the framing of their input.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct RawHeader {
+    /// Tag representing the type of the encoded value
+    pub tag: Tag,
+
+    /// Length of the encoded value, indefinite if encoded in the indefinite form
+    pub length: IndefiniteLength,
+
+    /// Form the length was encoded with
+    pub length_form: LengthForm,
+}
+
+impl RawHeader {
+    /// Is the length encoded as required by DER, i.e. in the definite form using the
+    /// minimum number of octets?
+    pub fn is_der(&self) -> bool {
+        let length = match Option::<Length>::from(self.length) {
+            Some(length) => length,
+            None => return false,
+        };
+
+        match self.length_form {
+            LengthForm::Short => length < Length::from(0x80u8),
+            LengthForm::Long(octets) => {
+                length >= Length::from(0x80u8) && usize::from(octets) == significant_octets(length)
+            }
+            LengthForm::Indefinite => false,
+        }
+    }
+}
+
+impl<'a> Decode<'a> for RawHeader {
+    fn decode<R: Reader<'a>>(reader: &mut R) -> Result<RawHeader> {
+        let tag = Tag::decode(reader)?;
+
+        let (length, length_form) = match reader.read_byte()? {
+            0x80 => (IndefiniteLength::INDEFINITE, LengthForm::Indefinite),
+            len @ 0x00..=0x7F => (Length::from(len).into(), LengthForm::Short),
+            // X.690 Section 8.1.3.5: the value 0xFF is reserved
+            0xFF => return Err(ErrorKind::Length { tag }.into()),
+            initial => {
+                let octets = initial & 0x7F;
+                let mut len = 0u32;
+
+                for _ in 0..octets {
+                    if len.leading_zeros() < 8 {
+                        return Err(ErrorKind::Overflow.into());
+                    }
+
+                    len = (len << 8) | u32::from(reader.read_byte()?);
+                }
+
+                (Length::try_from(len)?.into(), LengthForm::Long(octets))
+            }
+        };
+
+        Ok(Self {
+            tag,
+            length,
+            length_form,
+        })
+    }
+}
+
+impl Encode for RawHeader {
+    fn encoded_len(&self) -> Result<Length> {
+        let length_len = match self.length_form {
+            LengthForm::Short | LengthForm::Indefinite => Length::ONE,
+            LengthForm::Long(octets) => (Length::from(octets) + Length::ONE)?,
+        };
+
+        self.tag.encoded_len()? + length_len
+    }
+
+    fn encode(&self, writer: &mut impl Writer) -> Result<()> {
+        self.tag.encode(writer)?;
+
+        let length = Option::<Length>::from(self.length);
+        match (self.length_form, length) {
+            (LengthForm::Indefinite, None) => writer.write_byte(0x80),
+            (LengthForm::Short, Some(length)) => {
+                let len = u8::try_from(u32::from(length)).map_err(|_| ErrorKind::Overflow)?;
+
+                if len > 0x7F {
+                    return Err(ErrorKind::Overflow.into());
+                }
+
+                writer.write_byte(len)
+            }
+            (LengthForm::Long(octets), Some(length)) if (1..0x7F).contains(&octets) => {
+                let significant = significant_octets(length);
+
+                if significant > usize::from(octets) {
+                    return Err(ErrorKind::Overflow.into());
+                }
+
+                writer.write_byte(0x80 | octets)?;
+
+                for _ in significant..usize::from(octets) {
+                    writer.write_byte(0)?;
+                }
+
+                let len = u32::from(length).to_be_bytes();
+                writer.write(&len[len.len().saturating_sub(significant)..])
+            }
+            _ => Err(ErrorKind::Length { tag: self.tag }.into()),
+        }
+    }
+}
+
+/// Number of octets of `length` without leading zeros.
+fn significant_octets(length: Length) -> usize {
+    u32::from(length)
+        .to_be_bytes()
+        .iter()
+        .skip_while(|&&byte| byte == 0)
+        .count()
+}
+
+impl From<Header> for RawHeader {
+    fn from(header: Header) -> RawHeader {
+        let length_form = match significant_octets(header.length) {
+            octets if header.length >= Length::from(0x80u8) => {
+                LengthForm::Long(u8::try_from(octets).unwrap_or(4))
+            }
+            _ => LengthForm::Short,
+        };
+
+        Self {
+            tag: header.tag,
+            length: header.length.into(),
+            length_form,
+        }
+    }
+}
+
+impl TryFrom<RawHeader> for Header {
+    type Error = Error;
+
+    fn try_from(header: RawHeader) -> Result<Header> {
+        Ok(Self {
+            tag: header.tag,
+            length: header.length.try_into()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Header, LengthForm, RawHeader};
+    use crate::{Decode, Encode, ErrorKind, IndefiniteLength, Length, Tag};
+
+    fn round_trip(bytes: &[u8]) -> RawHeader {
+        let header = RawHeader::from_der(bytes).unwrap();
+        let mut buf = [0u8; 16];
+        assert_eq!(header.encode_to_slice(&mut buf).unwrap(), bytes);
+        assert_eq!(
+            header.encoded_len().unwrap(),
+            Length::try_from(bytes.len()).unwrap()
+        );
+        header
+    }
+
+    #[test]
+    fn short_form() {
+        let header = round_trip(&[0x04, 0x7F]);
+        assert_eq!(header.tag, Tag::OctetString);
+        assert_eq!(header.length, IndefiniteLength::new(0x7Fu8));
+        assert_eq!(header.length_form, LengthForm::Short);
+        assert!(header.is_der());
+    }
+
+    #[test]
+    fn long_form() {
+        let header = round_trip(&[0x30, 0x82, 0x01, 0x00]);
+        assert_eq!(header.length, IndefiniteLength::new(0x100u16));
+        assert_eq!(header.length_form, LengthForm::Long(2));
+        assert!(header.is_der());
+        assert_eq!(
+            Header::try_from(header).unwrap(),
+            Header::new(Tag::Sequence, 0x100u16).unwrap()
+        );
+        assert_eq!(RawHeader::from(Header::try_from(header).unwrap()), header);
+    }
+
+    #[test]
+    fn non_minimal() {
+        for bytes in [
+            &[0x04, 0x81, 0x05][..],
+            &[0x04, 0x82, 0x00, 0x05][..],
+            &[0x04, 0x86, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05][..],
+        ] {
+            let header = round_trip(bytes);
+            assert_eq!(header.length, IndefiniteLength::new(5u8));
+            assert!(!header.is_der());
+            assert_eq!(
+                Header::from_der(bytes).unwrap_err().kind(),
+                ErrorKind::Length {
+                    tag: Tag::OctetString
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn indefinite() {
+        let header = round_trip(&[0x30, 0x80]);
+        assert_eq!(header.length, IndefiniteLength::INDEFINITE);
+        assert_eq!(header.length_form, LengthForm::Indefinite);
+        assert!(!header.is_der());
+        assert_eq!(
+            Header::try_from(header).unwrap_err().kind(),
+            ErrorKind::IndefiniteLength
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            RawHeader::from_der(&[0x04, 0xFF]).unwrap_err().kind(),
+            ErrorKind::Length {
+                tag: Tag::OctetString
+            }
+        );
+        assert_eq!(
+            RawHeader::from_der(&[0x04, 0x85, 0x01, 0x00, 0x00, 0x00, 0x00])
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Overflow
+        );
+
+        let header = RawHeader {
+            tag: Tag::OctetString,
+            length: IndefiniteLength::new(0x100u16),
+            length_form: LengthForm::Long(1),
+        };
+        let mut buf = [0u8; 4];
+        assert_eq!(
+            header.encode_to_slice(&mut buf).unwrap_err().kind(),
+            ErrorKind::Overflow
+        );
+
+        // `0x80` is the indefinite length marker, not a long form length without octets
+        let header = RawHeader {
+            tag: Tag::Null,
+            length: IndefiniteLength::new(0u8),
+            length_form: LengthForm::Long(0),
+        };
+        assert_eq!(
+            header.encode_to_slice(&mut buf).unwrap_err().kind(),
+            ErrorKind::Length { tag: Tag::Null }
+        );
+    }
+}
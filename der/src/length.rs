@@ -37,6 +37,10 @@ impl Length {
     /// Maximum length currently supported: 256 MiB
     pub const MAX: Self = Self(MAX_U32);
 
+    /// Maximum number of octets in the encoding of a [`Length`].
+    #[cfg(all(feature = "pem", feature = "std"))]
+    pub(crate) const MAX_SIZE: usize = MAX_DER_OCTETS;
+
     /// Create a new [`Length`] for any value which fits inside of a [`u16`].
     ///
     /// This function is const-safe and therefore useful for [`Length`] constants.
@@ -51,6 +55,9 @@ impl Length {
 
     /// Get the length of DER Tag-Length-Value (TLV) encoded data if `self`
     /// is the length of the inner "value" portion of the message.
+    ///
+    /// Assumes the tag is encoded in a single identifier octet, i.e. has a
+    /// number of 30 or less.
     pub fn for_tlv(self) -> Result<Self> {
         Self::ONE + self.encoded_len()? + self
     }
@@ -77,7 +84,7 @@ impl Length {
     /// >    length octets, as an unsigned binary integer with bit 7 as the
     /// >    most significant bit;
     /// > c) the value 11111111₂ shall not be used.
-    fn initial_octet(self) -> Option<u8> {
+    pub(crate) fn initial_octet(self) -> Option<u8> {
         match self.0 {
             0x80..=0xFF => Some(0x81),
             0x100..=0xFFFF => Some(0x82),
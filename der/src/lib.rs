@@ -47,6 +47,7 @@
 //! The following ASN.1 types provided by this crate also impl these traits:
 //! - [`Any`], [`AnyRef`]: ASN.1 `ANY`.
 //! - [`BitString`], [`BitStringRef`]: ASN.1 `BIT STRING`
+//! - [`BmpString`]: ASN.1 `BMPString`.
 //! - [`GeneralizedTime`]: ASN.1 `GeneralizedTime`.
 //! - [`Ia5StringRef`]: ASN.1 `IA5String`.
 //! - [`Null`]: ASN.1 `NULL`.
@@ -55,9 +56,11 @@
 //! - [`PrintableStringRef`]: ASN.1 `PrintableString` (ASCII subset).
 //! - [`TeletexStringRef`]: ASN.1 `TeletexString`.
 //! - [`VideotexStringRef`]: ASN.1 `VideotexString`.
+//! - [`VisibleStringRef`]: ASN.1 `VisibleString` (printable ASCII).
 //! - [`SequenceOf`]: ASN.1 `SEQUENCE OF`.
 //! - [`SetOf`], [`SetOfVec`]: ASN.1 `SET OF`.
 //! - [`UintRef`]: ASN.1 unsigned `INTEGER` with raw access to encoded bytes.
+//! - [`UniversalString`]: ASN.1 `UniversalString`.
 //! - [`UtcTime`]: ASN.1 `UTCTime`.
 //! - [`Utf8StringRef`]: ASN.1 `UTF8String`.
 //!
@@ -288,6 +291,39 @@
 //! # }
 //! ```
 //!
+//! ### Compile-time checks
+//! When the `const-assertions` feature of this crate is enabled, the custom
+//! derive macros also emit `const` assertions rejecting definitions which
+//! can't be decoded unambiguously, e.g. when transcribing an ASN.1 module:
+//!
+//! - [`Choice`]: alternatives must have distinct tags.
+//! - [`Sequence`]: each run of `OPTIONAL` and `DEFAULT` fields and the field
+//!   following it must have distinct tags. A field's tag is given by its
+//!   `context_specific` or `type` attributes, or otherwise by the [`FixedTag`]
+//!   impl of its type (of `T` for an `OPTIONAL` field of type `Option<T>`).
+//!   Fields without a known tag, e.g. of type [`AnyRef`] or of a `CHOICE`
+//!   type, and fields whose type depends on type parameters of the struct,
+//!   aren't checked.
+//!
+//! A violation fails the build with a message naming the type.
+//!
+//! The `asn1` attributes don't take OID literals, and there is no custom
+//! derive for `SET`, so neither is covered by these checks.
+//!
+//! ## Preserving encodings
+//! Decoding a value and encoding it again isn't guaranteed to reproduce the
+//! input byte for byte. When an encoding must be relayed unchanged, e.g.
+//! because it is covered by a signature, decode it as [`Exact<T>`][`Exact`]
+//! (with the `alloc` feature): it keeps the original bytes and always encodes
+//! them as is.
+//!
+//! ## Structure-aware fuzzing
+//! When the `arbitrary` feature of this crate is enabled, most ASN.1 types in
+//! the [`asn1`] module as well as [`Header`], [`Length`] and [`Tag`] impl
+//! `arbitrary::Arbitrary`, producing values which can be DER encoded. Types
+//! using the custom derive macros can `#[derive(arbitrary::Arbitrary)]` as
+//! long as their fields do.
+//!
 //! # See also
 //! For more information about ASN.1 DER we recommend the following guides:
 //!
@@ -304,6 +340,7 @@
 //! [`ContextSpecificRef`]: asn1::ContextSpecificRef
 //! [`BitString`]: asn1::BitString
 //! [`BitStringRef`]: asn1::BitStringRef
+//! [`BmpString`]: asn1::BmpString
 //! [`GeneralizedTime`]: asn1::GeneralizedTime
 //! [`Ia5StringRef`]: asn1::Ia5StringRef
 //! [`Null`]: asn1::Null
@@ -313,10 +350,12 @@
 //! [`PrintableStringRef`]: asn1::PrintableStringRef
 //! [`TeletexStringRef`]: asn1::TeletexStringRef
 //! [`VideotexStringRef`]: asn1::VideotexStringRef
+//! [`VisibleStringRef`]: asn1::VisibleStringRef
 //! [`SequenceOf`]: asn1::SequenceOf
 //! [`SetOf`]: asn1::SetOf
 //! [`SetOfVec`]: asn1::SetOfVec
 //! [`UintRef`]: asn1::UintRef
+//! [`UniversalString`]: asn1::UniversalString
 //! [`UtcTime`]: asn1::UtcTime
 //! [`Utf8StringRef`]: asn1::Utf8StringRef
 
@@ -345,30 +384,42 @@ mod str_ref;
 mod tag;
 mod writer;
 
+#[cfg(feature = "alloc")]
+mod ber;
 #[cfg(feature = "alloc")]
 mod bytes_owned;
 #[cfg(feature = "alloc")]
 mod document;
 #[cfg(feature = "alloc")]
+mod exact;
+#[cfg(feature = "alloc")]
 mod str_owned;
 
 pub use crate::{
     asn1::{AnyRef, Choice, Sequence},
     datetime::DateTime,
-    decode::{Decode, DecodeOwned, DecodeValue},
-    encode::{Encode, EncodeValue},
+    decode::{Decode, DecodeDefinedBy, DecodeOwned, DecodeValue},
+    encode::{Encode, EncodeDyn, EncodeValue},
     encode_ref::{EncodeRef, EncodeValueRef},
-    error::{Error, ErrorKind, Result},
-    header::Header,
+    error::{Error, ErrorKind, ErrorPath, FieldName, Result},
+    header::{Header, LengthForm, RawHeader},
     length::{IndefiniteLength, Length},
     ord::{DerOrd, ValueOrd},
-    reader::{nested::NestedReader, slice::SliceReader, Reader},
+    reader::{nested::NestedReader, slice::SliceReader, Reader, DEFAULT_MAX_DEPTH},
     tag::{Class, FixedTag, Tag, TagMode, TagNumber, Tagged},
     writer::{slice::SliceWriter, Writer},
 };
 
+#[doc(hidden)]
+pub use crate::tag::{FixedTagOf, NoFixedTag};
+
 #[cfg(feature = "alloc")]
-pub use crate::{asn1::Any, document::Document};
+pub use crate::{
+    asn1::Any,
+    ber::{ber_to_der, ber_to_der_with_max_depth, check_der, check_der_with_max_depth},
+    document::Document,
+    exact::Exact,
+};
 
 #[cfg(feature = "bigint")]
 pub use crypto_bigint as bigint;
@@ -388,6 +439,9 @@ pub use {
     pem_rfc7468 as pem,
 };
 
+#[cfg(all(feature = "pem", feature = "std"))]
+pub use crate::{reader::pem::PemStreamReader, writer::pem::PemStreamWriter};
+
 #[cfg(feature = "time")]
 pub use time;
 
@@ -8,13 +8,22 @@ pub(crate) mod slice;
 pub(crate) use nested::NestedReader;
 
 use crate::{
-    asn1::ContextSpecific, Decode, DecodeValue, Encode, Error, ErrorKind, FixedTag, Header, Length,
-    Result, Tag, TagMode, TagNumber,
+    asn1::ContextSpecific, Choice, Decode, DecodeValue, Encode, Error, ErrorKind, FixedTag, Header,
+    Length, Result, Tag, TagMode, TagNumber,
 };
 
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
+/// Default maximum nesting depth of a [`Reader`].
+///
+/// See [`Reader::max_depth`].
+pub const DEFAULT_MAX_DEPTH: usize = 64;
+
+/// End-of-contents octets terminating values with an indefinite length, as
+/// described in X.690 Section 8.1.5.
+pub(crate) const END_OF_CONTENTS: [u8; 2] = [0, 0];
+
 /// Reader trait which reads DER-encoded input.
 pub trait Reader<'r>: Sized {
     /// Get the length of the input.
@@ -59,6 +68,25 @@ pub trait Reader<'r>: Sized {
         T::decode(self).map_err(|e| e.nested(self.position()))
     }
 
+    /// Decode an `OPTIONAL` value of type `T`.
+    ///
+    /// Returns `None` without consuming any input if the reader is finished,
+    /// if it reads the contents of a value with an indefinite length (see
+    /// [`Reader::read_indefinite`]) and the next value is their
+    /// end-of-contents marker, or if the tag of the next value isn't one `T`
+    /// can decode.
+    fn decode_optional<T: Choice<'r>>(&mut self) -> Result<Option<T>> {
+        if self.in_indefinite_contents() && self.peek_eoc() {
+            return Ok(None);
+        }
+
+        if self.peek_byte().is_some() && T::can_decode(self.peek_tag()?) {
+            T::decode(self).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
     /// Return an error with the given [`ErrorKind`], annotating it with
     /// context about where the error occurred.
     fn error(&mut self, kind: ErrorKind) -> Error {
@@ -93,12 +121,42 @@ pub trait Reader<'r>: Sized {
         self.position()
     }
 
+    /// Are the next two octets the end-of-contents marker terminating a BER
+    /// value with an indefinite length?
+    ///
+    /// Only the octets are examined: `0x00 0x00` is only end-of-contents
+    /// where the grammar allows it, i.e. when
+    /// [`Reader::in_indefinite_contents`] is `true`. Both `0x00` octets must
+    /// be within the remaining input.
+    ///
+    /// Readers need to look two octets ahead to detect end-of-contents. The
+    /// default implementation, for readers which can't, returns `false`.
+    ///
+    /// Does not modify the decoder's state.
+    fn peek_eoc(&self) -> bool {
+        false
+    }
+
+    /// Is this reader reading the contents of a value with an indefinite
+    /// length (see [`Reader::read_indefinite`])?
+    ///
+    /// Values nested within them with a definite length are not.
+    fn in_indefinite_contents(&self) -> bool {
+        false
+    }
+
     /// Peek at the next byte in the decoder and attempt to decode it as a
     /// [`Tag`] value.
     ///
+    /// Tags in the high tag number form span several octets, in which case
+    /// the whole header is peeked.
+    ///
     /// Does not modify the decoder's state.
     fn peek_tag(&self) -> Result<Tag> {
         match self.peek_byte() {
+            Some(byte) if byte & TagNumber::MASK == TagNumber::MASK => {
+                self.peek_header().map(|header| header.tag)
+            }
             Some(byte) => byte.try_into(),
             None => Err(Error::incomplete(self.input_len())),
         }
@@ -123,7 +181,24 @@ pub trait Reader<'r>: Sized {
         Ok(buf)
     }
 
+    /// Get the nesting depth of this reader, i.e. how many values being read
+    /// with [`Reader::read_nested`] enclose its input.
+    fn depth(&self) -> usize {
+        0
+    }
+
+    /// Get the maximum nesting depth allowed when reading nested values.
+    ///
+    /// This bounds the recursion of decoders for untrusted input, regardless
+    /// of how the nested values are encoded.
+    fn max_depth(&self) -> usize {
+        DEFAULT_MAX_DEPTH
+    }
+
     /// Read nested data of the given length.
+    ///
+    /// Returns [`ErrorKind::DepthExceeded`] if reading the nested data would
+    /// exceed [`Reader::max_depth`].
     fn read_nested<'n, T, F>(&'n mut self, len: Length, f: F) -> Result<T>
     where
         F: FnOnce(&mut NestedReader<'n, Self>) -> Result<T>,
@@ -133,6 +208,34 @@ pub trait Reader<'r>: Sized {
         reader.finish(ret)
     }
 
+    /// Read the contents of a BER value with an indefinite length, whose
+    /// header has already been read, along with the end-of-contents marker
+    /// terminating them.
+    ///
+    /// Returns an error if `f` doesn't read up to the end-of-contents marker,
+    /// or [`ErrorKind::DepthExceeded`] if reading the contents would exceed
+    /// [`Reader::max_depth`].
+    fn read_indefinite<'n, T, F>(&'n mut self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut NestedReader<'n, Self>) -> Result<T>,
+    {
+        let mut reader = NestedReader::new_indefinite(self)?;
+        let ret = f(&mut reader)?;
+
+        if reader.peek_eoc() {
+            reader.read_slice(Length::new(2))?;
+            Ok(ret)
+        } else if reader.is_finished() {
+            Err(Error::incomplete(reader.offset()))
+        } else {
+            Err(ErrorKind::TrailingData {
+                decoded: reader.position(),
+                remaining: reader.remaining_len(),
+            }
+            .at(reader.offset()))
+        }
+    }
+
     /// Read a byte vector of the given length.
     #[cfg(feature = "alloc")]
     fn read_vec(&mut self, len: Length) -> Result<Vec<u8>> {
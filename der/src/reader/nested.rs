@@ -12,16 +12,41 @@ pub struct NestedReader<'i, R> {
 
     /// Position within the nested input.
     position: Length,
+
+    /// Nesting depth of this reader.
+    depth: usize,
+
+    /// Whether the nested input is the contents of a value with an indefinite
+    /// length, terminated by end-of-contents.
+    indefinite: bool,
 }
 
 impl<'i, 'r, R: Reader<'r>> NestedReader<'i, R> {
     /// Create a new nested reader which can read the given [`Length`].
     pub(crate) fn new(inner: &'i mut R, len: Length) -> Result<Self> {
-        if len <= inner.remaining_len() {
+        Self::with_length(inner, len, false)
+    }
+
+    /// Create a new nested reader for the contents of a value with an
+    /// indefinite length, which can read the remaining input.
+    pub(crate) fn new_indefinite(inner: &'i mut R) -> Result<Self> {
+        let len = inner.remaining_len();
+        Self::with_length(inner, len, true)
+    }
+
+    fn with_length(inner: &'i mut R, len: Length, indefinite: bool) -> Result<Self> {
+        let depth = inner.depth().saturating_add(1);
+        let max_depth = inner.max_depth();
+
+        if depth > max_depth {
+            Err(ErrorKind::DepthExceeded { max_depth }.at(inner.offset()))
+        } else if len <= inner.remaining_len() {
             Ok(Self {
                 inner,
                 input_len: len,
                 position: Length::ZERO,
+                depth,
+                indefinite,
             })
         } else {
             Err(ErrorKind::Incomplete {
@@ -63,6 +88,10 @@ impl<'i, 'r, R: Reader<'r>> Reader<'r> for NestedReader<'i, R> {
         }
     }
 
+    fn peek_eoc(&self) -> bool {
+        self.remaining_len() >= Length::new(2) && self.inner.peek_eoc()
+    }
+
     fn peek_header(&self) -> Result<Header> {
         if self.is_finished() {
             Err(Error::incomplete(self.offset()))
@@ -89,6 +118,18 @@ impl<'i, 'r, R: Reader<'r>> Reader<'r> for NestedReader<'i, R> {
         self.inner.offset()
     }
 
+    fn depth(&self) -> usize {
+        self.depth
+    }
+
+    fn in_indefinite_contents(&self) -> bool {
+        self.indefinite
+    }
+
+    fn max_depth(&self) -> usize {
+        self.inner.max_depth()
+    }
+
     fn read_into<'o>(&mut self, out: &'o mut [u8]) -> Result<&'o [u8]> {
         self.advance_position(Length::try_from(out.len())?)?;
         self.inner.read_into(out)
@@ -1,6 +1,6 @@
 //! Streaming PEM reader.
 
-use super::Reader;
+use super::{Reader, DEFAULT_MAX_DEPTH, END_OF_CONTENTS};
 use crate::{Decode, Error, ErrorKind, Header, Length, Result};
 use core::cell::RefCell;
 
@@ -104,6 +104,12 @@ mod utils {
 
                 let available = &self.buf[self.pos..self.cap];
                 let window_len = (buf.len() - output_pos).min(available.len());
+
+                // The decoder is exhausted
+                if window_len == 0 {
+                    return Err(Error::incomplete(Length::try_from(output_pos)?));
+                }
+
                 let window = &mut buf[output_pos..output_pos + window_len];
 
                 window.copy_from_slice(&available[..window_len]);
@@ -135,6 +141,9 @@ pub struct PemReader<'i> {
 
     /// Position in the input buffer (in bytes after Base64 decoding).
     position: Length,
+
+    /// Maximum nesting depth.
+    max_depth: usize,
 }
 
 #[cfg(feature = "pem")]
@@ -150,9 +159,18 @@ impl<'i> PemReader<'i> {
             reader: RefCell::new(reader),
             input_len,
             position: Length::ZERO,
+            max_depth: DEFAULT_MAX_DEPTH,
         })
     }
 
+    /// Set the maximum nesting depth of values read from this reader.
+    ///
+    /// Defaults to [`DEFAULT_MAX_DEPTH`].
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
     /// Get the PEM label which will be used in the encapsulation boundaries
     /// for this document.
     pub fn type_label(&self) -> &'i str {
@@ -182,16 +200,38 @@ impl<'i> Reader<'i> for PemReader<'i> {
         }
     }
 
+    fn peek_eoc(&self) -> bool {
+        let mut buf = [0xFF; END_OF_CONTENTS.len()];
+        self.remaining_len() >= Length::new(2)
+            && self
+                .clone()
+                .read_into(&mut buf)
+                .map_or(false, |bytes| bytes == END_OF_CONTENTS)
+    }
+
     fn position(&self) -> Length {
         self.position
     }
 
+    fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
     fn read_slice(&mut self, _len: Length) -> Result<&'i [u8]> {
         // Can't borrow from PEM because it requires decoding
         Err(ErrorKind::Reader.into())
     }
 
     fn read_into<'o>(&mut self, buf: &'o mut [u8]) -> Result<&'o [u8]> {
+        let len = Length::try_from(buf.len())?;
+
+        if len > self.remaining_len() {
+            return Err(self.error(ErrorKind::Incomplete {
+                expected_len: (self.position + len)?,
+                actual_len: self.input_len,
+            }));
+        }
+
         let bytes = self.reader.borrow_mut().copy_to_slice(buf)?;
 
         self.position = (self.position + bytes.len())?;
@@ -204,3 +244,380 @@ impl<'i> Reader<'i> for PemReader<'i> {
         Ok(bytes)
     }
 }
+
+#[cfg(feature = "std")]
+pub use self::stream::PemStreamReader;
+
+#[cfg(feature = "std")]
+#[allow(clippy::integer_arithmetic)]
+mod stream {
+    use super::{Reader, DEFAULT_MAX_DEPTH, END_OF_CONTENTS};
+    use crate::{Decode, Error, ErrorKind, Header, Length, Result, SliceReader};
+    use core::cell::RefCell;
+    use pem_rfc7468::{self as pem, Base64Decoder};
+    use std::{
+        io::{self, BufRead, Read},
+        string::String,
+        vec::Vec,
+    };
+
+    /// Maximum length of a line containing an encapsulation boundary.
+    const MAX_BOUNDARY_LINE_LEN: u64 = 256;
+
+    /// Incremental decoder of the encapsulated text of a PEM document.
+    struct Body<R> {
+        /// Input PEM document.
+        input: R,
+
+        /// Type label of the document.
+        label: String,
+
+        /// Decoded bytes which haven't been read yet, starting at `pos`.
+        buf: Vec<u8>,
+
+        /// Position of the head in `buf`.
+        pos: usize,
+
+        /// Base64 characters which don't form a complete block yet.
+        block: Vec<u8>,
+
+        /// Has a padded block been decoded?
+        padded: bool,
+
+        /// Is the input at the start of a line?
+        line_start: bool,
+
+        /// Has the post-encapsulation boundary been read?
+        finished: bool,
+
+        /// Error encountered while filling the buffer on behalf of a
+        /// non-mutating method.
+        error: Option<Error>,
+    }
+
+    impl<R: BufRead> Body<R> {
+        fn new(mut input: R) -> Result<Self> {
+            let mut line = Vec::new();
+
+            // Skip the explanatory text preceding the pre-encapsulation boundary
+            let label = loop {
+                line.clear();
+                (&mut input)
+                    .take(MAX_BOUNDARY_LINE_LEN)
+                    .read_until(b'\n', &mut line)?;
+
+                if line.is_empty() {
+                    return Err(pem::Error::PreEncapsulationBoundary.into());
+                }
+
+                if let Some(label) = parse_boundary(&line, b"-----BEGIN ") {
+                    break String::from(label);
+                }
+            };
+
+            Ok(Self {
+                input,
+                label,
+                buf: Vec::new(),
+                pos: 0,
+                block: Vec::with_capacity(4),
+                padded: false,
+                line_start: true,
+                finished: false,
+                error: None,
+            })
+        }
+
+        /// Number of decoded bytes available in the buffer.
+        fn buffered_len(&self) -> usize {
+            self.buf.len() - self.pos
+        }
+
+        /// Decode input until at least `len` bytes are buffered or the
+        /// encapsulated text has been fully decoded.
+        fn fill(&mut self, len: usize) -> Result<()> {
+            if self.pos == self.buf.len() {
+                self.buf.clear();
+                self.pos = 0;
+            }
+
+            while self.buffered_len() < len && !self.finished {
+                let input = self.input.fill_buf()?;
+
+                if input.is_empty() {
+                    return Err(pem::Error::PostEncapsulationBoundary.into());
+                }
+
+                if self.line_start && input[0] == b'-' {
+                    self.read_post_boundary()?;
+                    break;
+                }
+
+                let line_len = input
+                    .iter()
+                    .position(|&byte| byte == b'\n')
+                    .map_or(input.len(), |pos| pos + 1);
+
+                for &byte in &input[..line_len] {
+                    match byte {
+                        b'\n' | b'\r' | b' ' | b'\t' => (),
+                        b':' => return Err(pem::Error::HeaderDisallowed.into()),
+                        _ if self.padded => return Err(pem::Error::EncapsulatedText.into()),
+                        _ => {
+                            self.block.push(byte);
+
+                            if self.block.len() == 4 {
+                                self.padded = decode_block(&mut self.block, &mut self.buf)?;
+                            }
+                        }
+                    }
+                }
+
+                self.line_start = input[line_len - 1] == b'\n';
+                self.input.consume(line_len);
+            }
+
+            Ok(())
+        }
+
+        /// Read the post-encapsulation boundary.
+        fn read_post_boundary(&mut self) -> Result<()> {
+            let mut line = Vec::new();
+            (&mut self.input)
+                .take(MAX_BOUNDARY_LINE_LEN)
+                .read_until(b'\n', &mut line)?;
+
+            match parse_boundary(&line, b"-----END ") {
+                Some(label) if label == self.label && self.block.is_empty() => {
+                    self.finished = true;
+                    Ok(())
+                }
+                _ => Err(pem::Error::PostEncapsulationBoundary.into()),
+            }
+        }
+    }
+
+    /// Decode a complete Base64 `block`, appending the decoded bytes to `buf`.
+    ///
+    /// Returns whether the block was padded.
+    fn decode_block(block: &mut Vec<u8>, buf: &mut Vec<u8>) -> Result<bool> {
+        let mut decoder = Base64Decoder::new(block).map_err(pem::Error::from)?;
+        let mut out = [0u8; 3];
+        let decoded_len = decoder.remaining_len();
+        let decoded = decoder
+            .decode(&mut out[..decoded_len])
+            .map_err(pem::Error::from)?;
+
+        buf.extend_from_slice(decoded);
+        block.clear();
+        Ok(decoded_len < 3)
+    }
+
+    /// Parse an encapsulation boundary line with the given prefix, returning
+    /// its type label.
+    fn parse_boundary<'l>(line: &'l [u8], prefix: &[u8]) -> Option<&'l str> {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let label = line.strip_prefix(prefix)?.strip_suffix(b"-----")?;
+
+        if label
+            .iter()
+            .all(|&byte| byte.is_ascii_graphic() || byte == b' ')
+        {
+            core::str::from_utf8(label).ok()
+        } else {
+            None
+        }
+    }
+
+    /// `Reader` type which decodes PEM on-the-fly from an [`io::BufRead`]
+    /// implementation, such as a file or socket.
+    ///
+    /// Unlike [`PemReader`][`super::PemReader`], the PEM document doesn't need
+    /// to be held in memory: Base64 is decoded incrementally as the input is
+    /// read, one line at a time. The length of the input is only known once
+    /// the post-encapsulation boundary has been read, until then
+    /// [`Reader::input_len`] returns [`Length::MAX`].
+    pub struct PemStreamReader<R> {
+        /// Inner PEM decoder.
+        body: RefCell<Body<R>>,
+
+        /// Position in the input (in bytes after Base64 decoding).
+        position: Length,
+
+        /// Maximum nesting depth.
+        max_depth: usize,
+    }
+
+    impl<R: BufRead> PemStreamReader<R> {
+        /// Create a new PEM reader, reading the input up to and including
+        /// the pre-encapsulation boundary.
+        pub fn new(input: R) -> Result<Self> {
+            Ok(Self {
+                body: RefCell::new(Body::new(input)?),
+                position: Length::ZERO,
+                max_depth: DEFAULT_MAX_DEPTH,
+            })
+        }
+
+        /// Set the maximum nesting depth of values read from this reader.
+        ///
+        /// Defaults to [`DEFAULT_MAX_DEPTH`].
+        pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+            self.max_depth = max_depth;
+            self
+        }
+
+        /// Get the PEM label of the encapsulation boundaries of this document.
+        pub fn type_label(&self) -> String {
+            self.body.borrow().label.clone()
+        }
+
+        /// Read the remaining encapsulated text, which is encoded using BER,
+        /// and transcode it to DER with [`ber_to_der`][`crate::ber_to_der`],
+        /// limiting the nesting depth to the one set with
+        /// [`PemStreamReader::with_max_depth`].
+        ///
+        /// Values with an indefinite length can't be decoded by a [`Reader`].
+        /// As the length of a DER encoded value precedes its contents, the
+        /// remaining document has to be buffered in order to transcode it: at
+        /// most `max_len` decoded octets are buffered, and
+        /// [`ErrorKind::Overlength`] is returned for longer documents.
+        pub fn ber_to_der(self, max_len: usize) -> Result<Vec<u8>> {
+            let mut body = self.body.into_inner();
+
+            if let Some(err) = body.error {
+                return Err(err);
+            }
+
+            body.fill(max_len.saturating_add(1))
+                .map_err(|err| err.nested(self.position))?;
+
+            if body.buffered_len() > max_len {
+                return Err(ErrorKind::Overlength.at(self.position));
+            }
+
+            crate::ber_to_der_with_max_depth(&body.buf[body.pos..], self.max_depth)
+        }
+
+        /// Fill the buffer on behalf of a non-mutating method, recording any
+        /// error to be returned by the next read.
+        fn fill(&self, len: usize) {
+            let mut body = self.body.borrow_mut();
+
+            if body.error.is_none() {
+                if let Err(err) = body.fill(len) {
+                    body.error = Some(err.nested(self.position));
+                }
+            }
+        }
+    }
+
+    impl<'i, R: BufRead> Reader<'i> for PemStreamReader<R> {
+        fn input_len(&self) -> Length {
+            self.fill(1);
+            let body = self.body.borrow();
+
+            if body.finished {
+                (self.position + body.buffered_len()).unwrap_or(Length::MAX)
+            } else {
+                Length::MAX
+            }
+        }
+
+        fn peek_byte(&self) -> Option<u8> {
+            self.fill(1);
+            let body = self.body.borrow();
+            body.buf.get(body.pos).copied()
+        }
+
+        fn peek_eoc(&self) -> bool {
+            self.fill(END_OF_CONTENTS.len());
+            let body = self.body.borrow();
+            body.buf[body.pos..].starts_with(&END_OF_CONTENTS)
+        }
+
+        fn peek_header(&self) -> Result<Header> {
+            self.fill(Header::MAX_SIZE);
+            let body = self.body.borrow();
+
+            if let Some(err) = body.error {
+                return Err(err);
+            }
+
+            Header::decode(&mut SliceReader::new(&body.buf[body.pos..])?)
+                .map_err(|err| err.nested(self.position))
+        }
+
+        fn position(&self) -> Length {
+            self.position
+        }
+
+        fn max_depth(&self) -> usize {
+            self.max_depth
+        }
+
+        fn read_slice(&mut self, _len: Length) -> Result<&'i [u8]> {
+            // Can't borrow from PEM because it requires decoding
+            Err(ErrorKind::Reader.into())
+        }
+
+        fn read_into<'o>(&mut self, buf: &'o mut [u8]) -> Result<&'o [u8]> {
+            let body = self.body.get_mut();
+
+            if let Some(err) = body.error {
+                return Err(err);
+            }
+
+            body.fill(buf.len())
+                .map_err(|err| err.nested(self.position))?;
+
+            if body.buffered_len() < buf.len() {
+                return Err(Error::incomplete(self.position));
+            }
+
+            buf.copy_from_slice(&body.buf[body.pos..][..buf.len()]);
+            body.pos += buf.len();
+            self.position = (self.position + buf.len())?;
+            Ok(buf)
+        }
+
+        fn finish<T>(self, value: T) -> Result<T> {
+            self.fill(1);
+
+            if let Some(err) = self.body.borrow().error {
+                return Err(err);
+            }
+
+            if !self.is_finished() {
+                return Err(ErrorKind::TrailingData {
+                    decoded: self.position,
+                    remaining: self.remaining_len(),
+                }
+                .at(self.position));
+            }
+
+            Ok(value)
+        }
+    }
+
+    impl<R: BufRead> Read for PemStreamReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let body = self.body.get_mut();
+
+            if let Some(err) = body.error.take() {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, err));
+            }
+
+            body.fill(buf.len())
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+            let len = body.buffered_len().min(buf.len());
+            buf[..len].copy_from_slice(&body.buf[body.pos..][..len]);
+            body.pos += len;
+            self.position = (self.position + len)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+            Ok(len)
+        }
+    }
+}
@@ -1,6 +1,9 @@
 //! Slice reader.
 
-use crate::{BytesRef, Decode, Error, ErrorKind, Header, Length, Reader, Result, Tag};
+use crate::{
+    reader::{DEFAULT_MAX_DEPTH, END_OF_CONTENTS},
+    BytesRef, Decode, Error, ErrorKind, Header, Length, Reader, Result, Tag,
+};
 
 /// [`Reader`] which consumes an input byte slice.
 #[derive(Clone, Debug)]
@@ -13,6 +16,9 @@ pub struct SliceReader<'a> {
 
     /// Position within the decoded slice.
     position: Length,
+
+    /// Maximum nesting depth.
+    max_depth: usize,
 }
 
 impl<'a> SliceReader<'a> {
@@ -22,9 +28,18 @@ impl<'a> SliceReader<'a> {
             bytes: BytesRef::new(bytes)?,
             failed: false,
             position: Length::ZERO,
+            max_depth: DEFAULT_MAX_DEPTH,
         })
     }
 
+    /// Set the maximum nesting depth of values read from this reader.
+    ///
+    /// Defaults to [`DEFAULT_MAX_DEPTH`].
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
     /// Return an error with the given [`ErrorKind`], annotating it with
     /// context about where the error occurred.
     pub fn error(&mut self, kind: ErrorKind) -> Error {
@@ -71,6 +86,11 @@ impl<'a> Reader<'a> for SliceReader<'a> {
         Header::decode(&mut self.clone())
     }
 
+    fn peek_eoc(&self) -> bool {
+        self.remaining()
+            .map_or(false, |bytes| bytes.starts_with(&END_OF_CONTENTS))
+    }
+
     fn position(&self) -> Length {
         self.position
     }
@@ -108,6 +128,10 @@ impl<'a> Reader<'a> for SliceReader<'a> {
         kind.at(self.position)
     }
 
+    fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
     fn finish<T>(self, value: T) -> Result<T> {
         if self.is_failed() {
             Err(ErrorKind::Failed.at(self.position))
@@ -211,4 +235,32 @@ mod tests {
         assert_eq!(header.length, Length::ONE);
         assert_eq!(reader.position(), Length::ZERO); // Position unchanged
     }
+
+    #[test]
+    fn max_depth() {
+        // SEQUENCE { SEQUENCE { SEQUENCE { INTEGER: 42 } } }
+        const NESTED_MSG: &[u8] = &hex!("30073005300302012A");
+
+        fn decode_nested(reader: &mut impl Reader<'static>) -> crate::Result<u8> {
+            let len = crate::Header::decode(reader)?.length;
+            reader.read_nested(len, |r| {
+                let len = crate::Header::decode(r)?.length;
+                r.read_nested(len, |r| {
+                    let len = crate::Header::decode(r)?.length;
+                    r.read_nested(len, u8::decode)
+                })
+            })
+        }
+
+        let mut reader = SliceReader::new(NESTED_MSG).unwrap();
+        assert_eq!(decode_nested(&mut reader).unwrap(), 42);
+
+        let mut reader = SliceReader::new(NESTED_MSG).unwrap().with_max_depth(3);
+        assert_eq!(decode_nested(&mut reader).unwrap(), 42);
+
+        let mut reader = SliceReader::new(NESTED_MSG).unwrap().with_max_depth(2);
+        let err = decode_nested(&mut reader).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DepthExceeded { max_depth: 2 });
+        assert_eq!(err.position(), Some(Length::from(6u8)));
+    }
 }
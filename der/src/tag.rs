@@ -8,7 +8,7 @@ mod number;
 pub use self::{class::Class, mode::TagMode, number::TagNumber};
 
 use crate::{Decode, DerOrd, Encode, Error, ErrorKind, Length, Reader, Result, Writer};
-use core::{cmp::Ordering, fmt};
+use core::{cmp::Ordering, fmt, marker::PhantomData};
 
 /// Indicator bit for constructed form encoding (i.e. vs primitive form)
 const CONSTRUCTED_FLAG: u8 = 0b100000;
@@ -19,6 +19,30 @@ pub trait FixedTag {
     const TAG: Tag;
 }
 
+/// Tag of `T` if it's [`FixedTag`], as `FixedTagOf::<T>::TAG`.
+///
+/// Used by the custom derive macros with the `const-assertions` feature: for
+/// types which aren't [`FixedTag`], e.g. [`Any`][`crate::asn1::Any`] or
+/// `CHOICE` types, the path resolves to [`NoFixedTag::TAG`] instead, which
+/// is `None`. The [`NoFixedTag`] trait must be in scope.
+#[doc(hidden)]
+pub struct FixedTagOf<T: ?Sized>(PhantomData<T>);
+
+impl<T: FixedTag + ?Sized> FixedTagOf<T> {
+    /// ASN.1 tag of `T`.
+    pub const TAG: Option<Tag> = Some(T::TAG);
+}
+
+/// Fallback for [`FixedTagOf`] when `T` isn't [`FixedTag`]: inherent
+/// associated constants take precedence over trait ones.
+#[doc(hidden)]
+pub trait NoFixedTag {
+    /// Unknown ASN.1 tag.
+    const TAG: Option<Tag> = None;
+}
+
+impl<T: ?Sized> NoFixedTag for FixedTagOf<T> {}
+
 /// Types which have an ASN.1 [`Tag`].
 pub trait Tagged {
     /// Get the ASN.1 tag that this type is encoded with.
@@ -108,6 +132,9 @@ pub enum Tag {
     /// `VisibleString` tag: `26`.
     VisibleString,
 
+    /// `UniversalString` tag: `28`.
+    UniversalString,
+
     /// `BMPString` tag: `30`.
     BmpString,
 
@@ -140,6 +167,11 @@ pub enum Tag {
 }
 
 impl Tag {
+    /// Maximum number of octets in the encoding of a [`Tag`]: the leading identifier octet,
+    /// followed by up to 5 octets for a tag number in the high tag number form.
+    #[cfg(all(feature = "pem", feature = "std"))]
+    pub(crate) const MAX_SIZE: usize = 6;
+
     /// Assert that this [`Tag`] matches the provided expected tag.
     ///
     /// On mismatch, returns an [`Error`] with [`ErrorKind::TagUnexpected`].
@@ -152,7 +184,7 @@ impl Tag {
     }
 
     /// Get the [`Class`] that corresponds to this [`Tag`].
-    pub fn class(self) -> Class {
+    pub const fn class(self) -> Class {
         match self {
             Tag::Application { .. } => Class::Application,
             Tag::ContextSpecific { .. } => Class::ContextSpecific,
@@ -161,13 +193,18 @@ impl Tag {
         }
     }
 
-    /// Get the [`TagNumber`] (lower 6-bits) for this tag.
-    pub fn number(self) -> TagNumber {
-        TagNumber(self.octet() & TagNumber::MASK)
+    /// Get the [`TagNumber`] for this tag.
+    pub const fn number(self) -> TagNumber {
+        match self {
+            Tag::Application { number, .. }
+            | Tag::ContextSpecific { number, .. }
+            | Tag::Private { number, .. } => number,
+            _ => TagNumber::new(self.octet() & TagNumber::MASK),
+        }
     }
 
     /// Does this tag represent a constructed (as opposed to primitive) field?
-    pub fn is_constructed(self) -> bool {
+    pub const fn is_constructed(self) -> bool {
         self.octet() & CONSTRUCTED_FLAG != 0
     }
 
@@ -191,8 +228,11 @@ impl Tag {
         self.class() == Class::Universal
     }
 
-    /// Get the octet encoding for this [`Tag`].
-    pub fn octet(self) -> u8 {
+    /// Get the leading identifier octet encoding this [`Tag`].
+    ///
+    /// Tags with a number above 30 are followed by further identifier octets
+    /// encoding the tag number, see [`TagNumber`].
+    pub const fn octet(self) -> u8 {
         match self {
             Tag::Boolean => 0x01,
             Tag::Integer => 0x02,
@@ -213,6 +253,7 @@ impl Tag {
             Tag::UtcTime => 0x17,
             Tag::GeneralizedTime => 0x18,
             Tag::VisibleString => 0x1A,
+            Tag::UniversalString => 0x1C,
             Tag::BmpString => 0x1E,
             Tag::Application {
                 constructed,
@@ -229,6 +270,35 @@ impl Tag {
         }
     }
 
+    /// Assert that no two of the known `tags` have the same class and number,
+    /// panicking with `message` otherwise. Unknown tags (`None`) are skipped.
+    ///
+    /// Used by the custom derive macros with the `const-assertions` feature to
+    /// reject ambiguous `CHOICE` and `SEQUENCE` definitions at compile time.
+    #[doc(hidden)]
+    #[allow(clippy::integer_arithmetic, clippy::panic)]
+    pub const fn assert_distinct(tags: &[Option<Tag>], message: &str) {
+        let mut i = 0;
+
+        while i < tags.len() {
+            let mut j = i + 1;
+
+            while j < tags.len() {
+                if let (Some(a), Some(b)) = (tags[i], tags[j]) {
+                    if a.class() as u8 == b.class() as u8
+                        && a.number().value_u32() == b.number().value_u32()
+                    {
+                        panic!("{}", message);
+                    }
+                }
+
+                j += 1;
+            }
+
+            i += 1;
+        }
+    }
+
     /// Create an [`Error`] for an invalid [`Length`].
     pub fn length_error(self) -> Error {
         ErrorKind::Length { tag: self }.into()
@@ -260,9 +330,17 @@ impl Tag {
 impl TryFrom<u8> for Tag {
     type Error = Error;
 
+    /// Decode a tag from a single identifier octet.
+    ///
+    /// Tags in the high tag number form, whose number is encoded in subsequent
+    /// octets, are rejected: decode them with [`Decode`] instead.
     fn try_from(byte: u8) -> Result<Tag> {
+        if byte & TagNumber::MASK == TagNumber::MASK {
+            return Err(ErrorKind::TagNumberInvalid.into());
+        }
+
         let constructed = byte & CONSTRUCTED_FLAG != 0;
-        let number = TagNumber::try_from(byte & TagNumber::MASK)?;
+        let number = TagNumber::new(byte & TagNumber::MASK);
 
         match byte {
             0x01 => Ok(Tag::Boolean),
@@ -282,6 +360,7 @@ impl TryFrom<u8> for Tag {
             0x17 => Ok(Tag::UtcTime),
             0x18 => Ok(Tag::GeneralizedTime),
             0x1A => Ok(Tag::VisibleString),
+            0x1C => Ok(Tag::UniversalString),
             0x1E => Ok(Tag::BmpString),
             0x30 => Ok(Tag::Sequence), // constructed
             0x31 => Ok(Tag::Set),      // constructed
@@ -316,23 +395,56 @@ impl From<&Tag> for u8 {
 
 impl<'a> Decode<'a> for Tag {
     fn decode<R: Reader<'a>>(reader: &mut R) -> Result<Self> {
-        reader.read_byte().and_then(Self::try_from)
+        let byte = reader.read_byte()?;
+
+        if byte & TagNumber::MASK != TagNumber::MASK {
+            return Self::try_from(byte);
+        }
+
+        let constructed = byte & CONSTRUCTED_FLAG != 0;
+
+        match byte & !(CONSTRUCTED_FLAG | TagNumber::MASK) {
+            0x40 => Ok(Tag::Application {
+                constructed,
+                number: TagNumber::decode_high(reader)?,
+            }),
+            0x80 => Ok(Tag::ContextSpecific {
+                constructed,
+                number: TagNumber::decode_high(reader)?,
+            }),
+            0xC0 => Ok(Tag::Private {
+                constructed,
+                number: TagNumber::decode_high(reader)?,
+            }),
+            _ => Err(ErrorKind::TagUnknown { byte }.into()),
+        }
     }
 }
 
 impl Encode for Tag {
     fn encoded_len(&self) -> Result<Length> {
-        Ok(Length::ONE)
+        Length::ONE + Length::try_from(self.number().high_len())?
     }
 
     fn encode(&self, writer: &mut impl Writer) -> Result<()> {
-        writer.write_byte(self.into())
+        writer.write_byte(self.into())?;
+
+        if self.number().is_high() {
+            self.number().encode_high(writer)?;
+        }
+
+        Ok(())
     }
 }
 
 impl DerOrd for Tag {
     fn der_cmp(&self, other: &Self) -> Result<Ordering> {
-        Ok(self.octet().cmp(&other.octet()))
+        // Shorter encodings of the tag number in the high tag number form
+        // sort first, so comparing the numbers matches comparing the octets.
+        Ok(self
+            .octet()
+            .cmp(&other.octet())
+            .then_with(|| self.number().cmp(&other.number())))
     }
 }
 
@@ -359,6 +471,7 @@ impl fmt::Display for Tag {
             Tag::UtcTime => f.write_str("UTCTime"),
             Tag::GeneralizedTime => f.write_str("GeneralizedTime"),
             Tag::VisibleString => f.write_str("VisibleString"),
+            Tag::UniversalString => f.write_str("UniversalString"),
             Tag::BmpString => f.write_str("BMPString"),
             Tag::Sequence => f.write_str("SEQUENCE"),
             Tag::Application {
@@ -402,6 +515,8 @@ impl fmt::Debug for Tag {
 mod tests {
     use super::TagNumber;
     use super::{Class, Tag};
+    use crate::{Decode, DerOrd, Encode, ErrorKind, Length};
+    use core::cmp::Ordering;
 
     #[test]
     fn tag_class() {
@@ -457,4 +572,139 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn high_tag_number_round_trip() {
+        let cases: [(Tag, &[u8]); 5] = [
+            (TagNumber::new(31).application(false), &[0x5F, 0x1F]),
+            (TagNumber::new(33).application(true), &[0x7F, 0x21]),
+            (TagNumber::new(127).context_specific(false), &[0x9F, 0x7F]),
+            (
+                TagNumber::new(128).context_specific(true),
+                &[0xBF, 0x81, 0x00],
+            ),
+            (
+                TagNumber::new_u32(u32::MAX).private(false),
+                &[0xDF, 0x8F, 0xFF, 0xFF, 0xFF, 0x7F],
+            ),
+        ];
+
+        for (tag, der) in cases {
+            assert_eq!(
+                tag.encoded_len().unwrap(),
+                Length::try_from(der.len()).unwrap()
+            );
+            let mut buf = [0u8; 8];
+            assert_eq!(tag.encode_to_slice(&mut buf).unwrap(), der);
+            assert_eq!(Tag::from_der(der).unwrap(), tag);
+        }
+    }
+
+    #[test]
+    fn tag_number_u8_api() {
+        let number = TagNumber::new(200);
+        assert_eq!(number.value(), Some(200));
+        assert_eq!(u8::try_from(number).unwrap(), 200);
+        assert_eq!(TagNumber::from(200u8), number);
+        assert_eq!(TagNumber::new_u32(200), number);
+        assert_eq!(TagNumber::new_u32(256).value_u32(), 256);
+    }
+
+    #[test]
+    fn tag_number_value_out_of_range() {
+        let number = TagNumber::new_u32(256);
+        assert_eq!(number.value(), None);
+        assert_eq!(
+            u8::try_from(number).unwrap_err().kind(),
+            ErrorKind::TagNumberInvalid
+        );
+    }
+
+    #[test]
+    fn high_tag_number_rejects_non_canonical() {
+        for der in [
+            // Fits in a single identifier octet
+            &[0x9F, 0x1E][..],
+            // Leading `0x80` octet
+            &[0x9F, 0x80, 0x21],
+            // Overflows `u32`
+            &[0x9F, 0x90, 0x80, 0x80, 0x80, 0x00],
+        ] {
+            assert_eq!(
+                Tag::from_der(der).unwrap_err().kind(),
+                ErrorKind::TagNumberInvalid
+            );
+        }
+
+        // Universal tags are limited to the types supported by this library
+        assert_eq!(
+            Tag::from_der(&[0x1F, 0x21]).unwrap_err().kind(),
+            ErrorKind::TagUnknown { byte: 0x1F }
+        );
+
+        // The tag number must be decoded from subsequent octets
+        assert_eq!(
+            Tag::try_from(0x9F).unwrap_err().kind(),
+            ErrorKind::TagNumberInvalid
+        );
+    }
+
+    #[test]
+    fn assert_distinct() {
+        const _: () = Tag::assert_distinct(
+            &[
+                Some(Tag::Integer),
+                Some(Tag::ContextSpecific {
+                    constructed: false,
+                    number: TagNumber::N2,
+                }),
+                Some(Tag::ContextSpecific {
+                    constructed: false,
+                    number: TagNumber::new(31),
+                }),
+                None,
+                None,
+            ],
+            "distinct",
+        );
+    }
+
+    #[test]
+    fn fixed_tag_of() {
+        use super::{FixedTagOf, NoFixedTag as _};
+        use crate::AnyRef;
+
+        assert_eq!(FixedTagOf::<bool>::TAG, Some(Tag::Boolean));
+        assert_eq!(FixedTagOf::<AnyRef<'static>>::TAG, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate")]
+    fn assert_distinct_ignores_constructed_bit() {
+        Tag::assert_distinct(
+            &[
+                Some(TagNumber::N0.context_specific(false)),
+                Some(TagNumber::N0.context_specific(true)),
+            ],
+            "duplicate",
+        );
+    }
+
+    #[test]
+    fn high_tag_number_ordering() {
+        let tags = [
+            TagNumber::new(30).context_specific(false),
+            TagNumber::new(31).context_specific(false),
+            TagNumber::new(127).context_specific(false),
+            TagNumber::new(128).context_specific(false),
+        ];
+
+        for pair in tags.windows(2) {
+            assert_eq!(pair[0].der_cmp(&pair[1]).unwrap(), Ordering::Less);
+            let (mut a, mut b) = ([0u8; 8], [0u8; 8]);
+            assert!(
+                pair[0].encode_to_slice(&mut a).unwrap() < pair[1].encode_to_slice(&mut b).unwrap()
+            );
+        }
+    }
 }
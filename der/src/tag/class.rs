@@ -33,8 +33,9 @@ pub enum Class {
 impl Class {
     /// Compute the identifier octet for a tag number of this class.
     #[allow(clippy::integer_arithmetic)]
-    pub(super) fn octet(self, constructed: bool, number: TagNumber) -> u8 {
-        self as u8 | number.value() | (u8::from(constructed) * CONSTRUCTED_FLAG)
+    pub(super) const fn octet(self, constructed: bool, number: TagNumber) -> u8 {
+        let constructed_flag = if constructed { CONSTRUCTED_FLAG } else { 0 };
+        self as u8 | number.low_bits() | constructed_flag
     }
 }
 
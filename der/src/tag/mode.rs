@@ -4,6 +4,7 @@ use crate::{Error, ErrorKind, Result};
 use core::{fmt, str::FromStr};
 
 /// Tagging modes: `EXPLICIT` versus `IMPLICIT`.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
 pub enum TagMode {
     /// `EXPLICIT` tagging.
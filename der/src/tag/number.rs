@@ -1,25 +1,27 @@
 //! ASN.1 tag numbers
 
 use super::Tag;
-use crate::{Error, ErrorKind, Result};
+use crate::{Error, ErrorKind, Reader, Result, Writer};
 use core::fmt;
 
-/// ASN.1 tag numbers (i.e. lower 5 bits of a [`Tag`]).
+/// ASN.1 tag numbers.
 ///
 /// From X.690 Section 8.1.2.2:
 ///
 /// > bits 5 to 1 shall encode the number of the tag as a binary integer with
 /// > bit 5 as the most significant bit.
 ///
-/// This library supports tag numbers ranging from zero to 30 (inclusive),
-/// which can be represented as a single identifier octet.
+/// Tag numbers ranging from zero to 30 (inclusive) are represented in this
+/// way, within a single identifier octet.
 ///
-/// Section 8.1.2.4 describes how to support multi-byte tag numbers, which are
-/// encoded by using a leading tag number of 31 (`0b11111`). This library
-/// deliberately does not support this: tag numbers greater than 30 are
-/// disallowed.
+/// Section 8.1.2.4 describes how larger tag numbers are encoded: bits 5 to 1
+/// of the leading identifier octet are all set (`0b11111`) and the tag number
+/// follows in base 128 in subsequent octets, with bit 8 of each octet but the
+/// last set. This library supports such tag numbers up to [`u32::MAX`] for
+/// application, context-specific and private tags: use [`TagNumber::new_u32`]
+/// and [`TagNumber::value_u32`] for tag numbers which don't fit in a `u8`.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
-pub struct TagNumber(pub(super) u8);
+pub struct TagNumber(pub(super) u32);
 
 impl TagNumber {
     /// Tag number `0`
@@ -116,22 +118,21 @@ impl TagNumber {
     pub const N30: Self = Self(30);
 
     /// Mask value used to obtain the tag number from a tag octet.
-    pub(super) const MASK: u8 = 0b11111;
+    pub(crate) const MASK: u8 = 0b11111;
 
-    /// Maximum tag number supported (inclusive).
-    const MAX: u8 = 30;
+    /// Maximum tag number which fits in a single identifier octet (inclusive).
+    const MAX_LOW: u32 = 30;
 
     /// Create a new tag number (const-friendly).
     ///
-    /// Panics if the tag number is greater than `30`.
-    /// For a fallible conversion, use [`TryFrom`] instead.
+    /// For tag numbers greater than 255, use [`TagNumber::new_u32`].
     pub const fn new(byte: u8) -> Self {
-        #[allow(clippy::panic)]
-        if byte > Self::MAX {
-            panic!("tag number out of range");
-        }
+        Self(byte as u32)
+    }
 
-        Self(byte)
+    /// Create a new tag number up to [`u32::MAX`] (const-friendly).
+    pub const fn new_u32(number: u32) -> Self {
+        Self(number)
     }
 
     /// Create an `APPLICATION` tag with this tag number.
@@ -158,25 +159,120 @@ impl TagNumber {
         }
     }
 
-    /// Get the inner value.
-    pub fn value(self) -> u8 {
+    /// Get the inner value, if it fits in a `u8`.
+    ///
+    /// For tag numbers of any size, use [`TagNumber::value_u32`] instead.
+    pub fn value(self) -> Option<u8> {
+        u8::try_from(self.0).ok()
+    }
+
+    /// Get the inner value, of any size.
+    pub const fn value_u32(self) -> u32 {
         self.0
     }
+
+    /// Is this tag number encoded in the high tag number form, i.e. in octets
+    /// following the leading identifier octet?
+    pub(super) const fn is_high(self) -> bool {
+        self.0 > Self::MAX_LOW
+    }
+
+    /// Get the bits 5 to 1 of the leading identifier octet for this tag number.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(super) const fn low_bits(self) -> u8 {
+        if self.is_high() {
+            Self::MASK
+        } else {
+            // Low tag numbers fit in 5 bits
+            self.0 as u8
+        }
+    }
+
+    /// Number of subsequent octets this tag number is encoded in: zero unless
+    /// it is in the high tag number form.
+    pub(super) fn high_len(self) -> usize {
+        match self.0 {
+            0..=Self::MAX_LOW => 0,
+            0x1F..=0x7F => 1,
+            0x80..=0x3FFF => 2,
+            0x4000..=0x1F_FFFF => 3,
+            0x20_0000..=0xFFF_FFFF => 4,
+            _ => 5,
+        }
+    }
+
+    /// Decode a tag number in the high tag number form, from the octets
+    /// following a leading identifier octet with all tag number bits set.
+    pub(super) fn decode_high<'a, R: Reader<'a>>(reader: &mut R) -> Result<Self> {
+        let mut number = 0u32;
+
+        for i in 0..5 {
+            let octet = reader.read_byte()?;
+
+            // The leading octet must not be `0x80`, see X.690 Section 8.1.2.4.2(c)
+            if i == 0 && octet == 0x80 {
+                return Err(ErrorKind::TagNumberInvalid.into());
+            }
+
+            number = number
+                .checked_mul(0x80)
+                .ok_or(ErrorKind::TagNumberInvalid)?
+                | u32::from(octet & 0x7F);
+
+            if octet & 0x80 == 0 {
+                // Numbers which fit in a single identifier octet must be encoded there
+                return if number > Self::MAX_LOW {
+                    Ok(Self(number))
+                } else {
+                    Err(ErrorKind::TagNumberInvalid.into())
+                };
+            }
+        }
+
+        Err(ErrorKind::TagNumberInvalid.into())
+    }
+
+    /// Encode this tag number in the high tag number form.
+    pub(super) fn encode_high(self, writer: &mut impl Writer) -> Result<()> {
+        let mut leading = true;
+
+        for shift in [28, 21, 14, 7, 0] {
+            let bits = u8::try_from(self.0.checked_shr(shift).unwrap_or(0) & 0x7F)?;
+            leading &= bits == 0;
+
+            if !leading {
+                writer.write_byte(if shift == 0 { bits } else { bits | 0x80 })?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
-impl TryFrom<u8> for TagNumber {
+impl From<u8> for TagNumber {
+    fn from(byte: u8) -> Self {
+        Self(byte.into())
+    }
+}
+
+impl From<u32> for TagNumber {
+    fn from(number: u32) -> Self {
+        Self(number)
+    }
+}
+
+impl TryFrom<TagNumber> for u8 {
     type Error = Error;
 
-    fn try_from(byte: u8) -> Result<Self> {
-        match byte {
-            0..=Self::MAX => Ok(Self(byte)),
-            _ => Err(ErrorKind::TagNumberInvalid.into()),
-        }
+    fn try_from(tag_number: TagNumber) -> Result<u8> {
+        tag_number
+            .value()
+            .ok_or_else(|| ErrorKind::TagNumberInvalid.into())
     }
 }
 
-impl From<TagNumber> for u8 {
-    fn from(tag_number: TagNumber) -> u8 {
+impl From<TagNumber> for u32 {
+    fn from(tag_number: TagNumber) -> u32 {
         tag_number.0
     }
 }
@@ -192,10 +288,10 @@ impl fmt::Display for TagNumber {
 #[cfg(feature = "arbitrary")]
 impl<'a> arbitrary::Arbitrary<'a> for TagNumber {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        Ok(Self::new(u.int_in_range(0..=Self::MAX)?))
+        Ok(Self::new_u32(u.arbitrary()?))
     }
 
     fn size_hint(depth: usize) -> (usize, Option<usize>) {
-        u8::size_hint(depth)
+        u32::size_hint(depth)
     }
 }
@@ -20,10 +20,43 @@ pub trait Writer {
     }
 }
 
+/// Writer which streams encoded DER to an [`io::Write`] implementation, such
+/// as a file or socket, without buffering the full output.
+///
+/// See also [`Encode::encode_to_writer`][`crate::Encode::encode_to_writer`].
 #[cfg(feature = "std")]
 impl<W: io::Write> Writer for W {
     fn write(&mut self, slice: &[u8]) -> Result<()> {
-        <Self as io::Write>::write(self, slice)?;
+        <Self as io::Write>::write_all(self, slice)?;
         Ok(())
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{asn1::OctetStringRef, Encode};
+    use std::{io, vec::Vec};
+
+    /// Writer which accepts at most one byte per call to `write`.
+    struct ShortWriter(Vec<u8>);
+
+    impl io::Write for ShortWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.extend(buf.iter().take(1));
+            Ok(buf.len().min(1))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn encode_to_writer() {
+        let value = OctetStringRef::new(&[0x42; 300]).unwrap();
+        let mut writer = ShortWriter(Vec::new());
+        let len = value.encode_to_writer(&mut writer).unwrap();
+        assert_eq!(len, value.encoded_len().unwrap());
+        assert_eq!(writer.0, value.to_der().unwrap());
+    }
+}
@@ -4,6 +4,12 @@ use super::Writer;
 use crate::Result;
 use pem_rfc7468::{Encoder, LineEnding};
 
+#[cfg(feature = "std")]
+use {
+    pem_rfc7468::{self as pem, Base64Encoder, BASE64_WRAP_WIDTH},
+    std::{io, string::String},
+};
+
 /// `Writer` type which outputs PEM-encoded data.
 pub struct PemWriter<'w>(Encoder<'static, 'w>);
 
@@ -39,3 +45,106 @@ impl Writer for PemWriter<'_> {
         Ok(())
     }
 }
+
+/// Number of bytes encoded in a single line of Base64.
+#[cfg(feature = "std")]
+const LINE_LEN: usize = BASE64_WRAP_WIDTH / 4 * 3;
+
+/// `Writer` type which streams PEM-encoded data to an [`io::Write`]
+/// implementation, such as a file or socket.
+///
+/// Unlike [`PemWriter`], the PEM document isn't buffered: Base64 is encoded
+/// incrementally, one line at a time.
+#[cfg(feature = "std")]
+pub struct PemStreamWriter<W: io::Write> {
+    /// Output PEM document.
+    out: W,
+
+    /// Type label of the document.
+    type_label: String,
+
+    /// Line ending.
+    line_ending: LineEnding,
+
+    /// Bytes to be encoded in the next line.
+    line: [u8; LINE_LEN],
+
+    /// Number of bytes in `line`.
+    line_len: usize,
+}
+
+#[cfg(feature = "std")]
+#[allow(clippy::integer_arithmetic)]
+impl<W: io::Write> PemStreamWriter<W> {
+    /// Create a new PEM writer which streams into the provided output,
+    /// writing the pre-encapsulation boundary.
+    ///
+    /// Uses the default 64-character line wrapping.
+    pub fn new(type_label: &str, line_ending: LineEnding, mut out: W) -> Result<Self> {
+        write!(out, "-----BEGIN {}-----", type_label)?;
+        out.write_all(line_ending.as_bytes())?;
+
+        Ok(Self {
+            out,
+            type_label: type_label.into(),
+            line_ending,
+            line: [0; LINE_LEN],
+            line_len: 0,
+        })
+    }
+
+    /// Get the PEM label which will be used in the encapsulation boundaries
+    /// for this document.
+    pub fn type_label(&self) -> &str {
+        &self.type_label
+    }
+
+    /// Finish encoding PEM, writing the post-encapsulation boundary.
+    ///
+    /// On success, returns the output.
+    pub fn finish(mut self) -> Result<W> {
+        self.write_line()?;
+        write!(self.out, "-----END {}-----", self.type_label)?;
+        self.out.write_all(self.line_ending.as_bytes())?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    /// Write the buffered bytes as a line of Base64.
+    fn write_line(&mut self) -> Result<()> {
+        if self.line_len == 0 {
+            return Ok(());
+        }
+
+        let mut buf = [0u8; BASE64_WRAP_WIDTH];
+        let mut encoder = Base64Encoder::new(&mut buf).map_err(pem::Error::from)?;
+        encoder
+            .encode(&self.line[..self.line_len])
+            .map_err(pem::Error::from)?;
+
+        self.out
+            .write_all(encoder.finish().map_err(pem::Error::from)?.as_bytes())?;
+        self.out.write_all(self.line_ending.as_bytes())?;
+        self.line_len = 0;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+#[allow(clippy::integer_arithmetic)]
+impl<W: io::Write> Writer for PemStreamWriter<W> {
+    fn write(&mut self, mut slice: &[u8]) -> Result<()> {
+        while !slice.is_empty() {
+            let len = slice.len().min(self.line.len() - self.line_len);
+            self.line[self.line_len..][..len].copy_from_slice(&slice[..len]);
+            self.line_len += len;
+            slice = &slice[len..];
+
+            if self.line_len == self.line.len() {
+                self.write_line()?;
+            }
+        }
+
+        Ok(())
+    }
+}
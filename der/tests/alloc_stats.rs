@@ -0,0 +1,69 @@
+//! Tests that borrowed decoding and encoding to slices don't allocate.
+
+mod common;
+
+use common::{assert_no_alloc, CountingAllocator};
+use der::{
+    asn1::{AnyRef, BitStringRef, OctetStringRef, UintRef},
+    Decode,
+};
+use hex_literal::hex;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// `SubjectPublicKeyInfo` of an Ed25519 key, from RFC 8410 Section 10.1.
+const ED25519_SPKI: &[u8] = &hex!(
+    "302a300506032b657003210019bf44096984cdfe8541bac167dc3b96c85086aa30b6b6cb0c5c38ad703166e1"
+);
+
+#[cfg(all(feature = "derive", feature = "oid"))]
+#[derive(Clone, Debug, Eq, PartialEq, der::Sequence)]
+struct AlgorithmIdentifier<'a> {
+    algorithm: der::oid::ObjectIdentifier,
+    parameters: Option<AnyRef<'a>>,
+}
+
+#[cfg(all(feature = "derive", feature = "oid"))]
+#[derive(Clone, Debug, Eq, PartialEq, der::Sequence)]
+struct SubjectPublicKeyInfo<'a> {
+    algorithm: AlgorithmIdentifier<'a>,
+    subject_public_key: BitStringRef<'a>,
+}
+
+#[test]
+fn decode_borrowed() {
+    assert_no_alloc(|| {
+        AnyRef::from_der(ED25519_SPKI).unwrap();
+        UintRef::from_der(&hex!("0203010001")).unwrap();
+        OctetStringRef::from_der(&hex!("0403010203")).unwrap();
+        BitStringRef::from_der(&hex!("03020780")).unwrap();
+    });
+}
+
+#[cfg(all(feature = "derive", feature = "oid"))]
+#[test]
+fn decode_and_encode_spki() {
+    use der::Encode;
+
+    let spki = assert_no_alloc(|| SubjectPublicKeyInfo::from_der(ED25519_SPKI)).unwrap();
+
+    let mut buf = [0u8; 64];
+    let encoded = assert_no_alloc(|| spki.encode_to_slice(&mut buf)).unwrap();
+    assert_eq!(encoded, ED25519_SPKI);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn decode_owned() {
+    let (any, stats) = common::measure(|| der::asn1::Any::from_der(ED25519_SPKI).unwrap());
+    assert_eq!(any.value().len(), ED25519_SPKI.len() - 2);
+    assert!(stats.allocations >= 1);
+    assert!(stats.bytes >= any.value().len());
+}
+
+#[test]
+#[should_panic(expected = "expected no allocations")]
+fn assert_no_alloc_panics() {
+    assert_no_alloc(|| ED25519_SPKI.to_vec());
+}
@@ -0,0 +1,52 @@
+//! Round-trip tests for values generated with `arbitrary`.
+
+#![cfg(all(feature = "arbitrary", feature = "derive"))]
+
+use arbitrary::{Arbitrary, Unstructured};
+use der::{
+    asn1::{
+        Any, BitString, BmpString, GeneralizedTime, Ia5String, Int, Null, OctetString,
+        PrintableString, SequenceOf, SetOf, SetOfVec, Uint, UtcTime, Utf8StringRef,
+    },
+    Decode, Encode, Header, Sequence,
+};
+use proptest::{collection::vec, prelude::*};
+
+/// Structure exercising the `Arbitrary` impls of the core ASN.1 types as
+/// well as deriving `Arbitrary` alongside `Sequence`.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Sequence)]
+pub struct Example<'a> {
+    pub any: Any,
+    pub bit_string: BitString,
+    pub bmp_string: BmpString,
+    pub generalized_time: GeneralizedTime,
+    pub ia5_string: Ia5String,
+    pub int: Int,
+    pub uint: Uint,
+    pub null: Null,
+    pub octet_string: OctetString,
+    pub printable_string: PrintableString,
+    pub sequence_of: SequenceOf<bool, 4>,
+    pub set_of: SetOf<u16, 4>,
+    pub set_of_vec: SetOfVec<u8>,
+    pub utc_time: UtcTime,
+    pub utf8_string: Utf8StringRef<'a>,
+}
+
+proptest! {
+    #[test]
+    fn header_round_trip(bytes in vec(any::<u8>(), 0..16)) {
+        if let Ok(header) = Header::arbitrary(&mut Unstructured::new(&bytes)) {
+            let der = header.to_der().unwrap();
+            prop_assert_eq!(Header::from_der(&der).unwrap(), header);
+        }
+    }
+
+    #[test]
+    fn sequence_round_trip(bytes in vec(any::<u8>(), 0..1024)) {
+        if let Ok(example) = Example::arbitrary(&mut Unstructured::new(&bytes)) {
+            let der = example.to_der().unwrap();
+            prop_assert_eq!(Example::from_der(&der).unwrap(), example);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Allocation statistics, to test that decoding and encoding paths don't allocate.
+//!
+//! Install [`CountingAllocator`] as the global allocator of a test binary, then run the code
+//! under test with [`measure`] or [`assert_no_alloc`].
+//!
+//! Allocations are counted per thread, so concurrently running tests don't affect each
+//! other's statistics.
+
+#![allow(dead_code)]
+
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    cell::Cell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use std::alloc::System;
+
+/// Whether [`CountingAllocator`] is the global allocator and has served an allocation.
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+std::thread_local! {
+    static STATS: Cell<AllocStats> = const { Cell::new(AllocStats::ZERO) };
+}
+
+/// Allocation statistics of a thread.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct AllocStats {
+    /// Number of allocations, including zeroed ones.
+    pub allocations: usize,
+
+    /// Number of reallocations.
+    pub reallocations: usize,
+
+    /// Number of bytes requested by allocations and reallocations.
+    pub bytes: usize,
+}
+
+impl AllocStats {
+    const ZERO: Self = Self {
+        allocations: 0,
+        reallocations: 0,
+        bytes: 0,
+    };
+
+    /// Whether no allocation or reallocation happened.
+    pub fn is_empty(&self) -> bool {
+        self.allocations == 0 && self.reallocations == 0
+    }
+
+    fn since(&self, start: &Self) -> Self {
+        Self {
+            allocations: self.allocations.wrapping_sub(start.allocations),
+            reallocations: self.reallocations.wrapping_sub(start.reallocations),
+            bytes: self.bytes.wrapping_sub(start.bytes),
+        }
+    }
+
+    fn record(update: impl FnOnce(&mut Self)) {
+        // The statistics are unavailable while the thread is being torn down
+        let _ = STATS.try_with(|stats| {
+            let mut value = stats.get();
+            update(&mut value);
+            stats.set(value);
+        });
+    }
+}
+
+/// Global allocator delegating to [`System`] which counts the allocations of each thread.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        AllocStats::record(|stats| {
+            stats.reallocations = stats.reallocations.wrapping_add(1);
+            stats.bytes = stats.bytes.wrapping_add(new_size);
+        });
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+fn count_allocation(size: usize) {
+    INSTALLED.store(true, Ordering::Relaxed);
+    AllocStats::record(|stats| {
+        stats.allocations = stats.allocations.wrapping_add(1);
+        stats.bytes = stats.bytes.wrapping_add(size);
+    });
+}
+
+/// Run `f` and return its result along with the allocations it made on the current thread.
+///
+/// # Panics
+///
+/// If [`CountingAllocator`] isn't the global allocator, as the statistics would be
+/// meaningless.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        panic!("`CountingAllocator` is not the global allocator");
+    }
+
+    let start = STATS.with(Cell::get);
+    let result = f();
+    let stats = STATS.with(Cell::get).since(&start);
+    (result, stats)
+}
+
+/// Run `f` and return its result, asserting that it didn't allocate on the current thread.
+///
+/// Deallocations are allowed, e.g. to drop the input.
+///
+/// # Panics
+///
+/// If `f` allocated, or if [`CountingAllocator`] isn't the global allocator.
+#[track_caller]
+pub fn assert_no_alloc<R>(f: impl FnOnce() -> R) -> R {
+    let (result, stats) = measure(f);
+
+    if !stats.is_empty() {
+        panic!("expected no allocations, got {:?}", stats);
+    }
+
+    result
+}
@@ -148,6 +148,80 @@ mod choice {
             assert_eq!(TIME_DER, encoder.finish().unwrap());
         }
     }
+
+    /// `Choice` deriving `ValueOrd`.
+    mod value_ord {
+        use der::{
+            asn1::{OctetString, SetOfVec},
+            Choice, DerOrd, Encode, ValueOrd,
+        };
+
+        #[derive(Choice, Clone, Debug, Eq, PartialEq, ValueOrd)]
+        pub enum Identifier {
+            Serial(u32),
+
+            #[asn1(type = "UTF8String")]
+            Name(String),
+
+            #[asn1(context_specific = "0", tag_mode = "EXPLICIT")]
+            Explicit(u32),
+
+            #[asn1(context_specific = "1", tag_mode = "IMPLICIT")]
+            Implicit(OctetString),
+
+            #[asn1(context_specific = "33", tag_mode = "EXPLICIT")]
+            HighTag(u32),
+        }
+
+        fn identifiers() -> Vec<Identifier> {
+            vec![
+                Identifier::HighTag(2),
+                Identifier::Implicit(OctetString::new([1, 2]).unwrap()),
+                Identifier::Explicit(256),
+                Identifier::Name("example".into()),
+                Identifier::Serial(7),
+                Identifier::Explicit(5),
+                Identifier::Implicit(OctetString::new([1, 3]).unwrap()),
+                Identifier::Name("another".into()),
+                Identifier::Serial(300),
+                Identifier::Explicit(6),
+                Identifier::HighTag(1),
+            ]
+        }
+
+        #[test]
+        fn der_cmp_matches_encoding() {
+            for a in identifiers() {
+                for b in identifiers() {
+                    assert_eq!(
+                        a.der_cmp(&b).unwrap(),
+                        a.to_der().unwrap().cmp(&b.to_der().unwrap()),
+                        "{:?} vs {:?}",
+                        a,
+                        b
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn value_cmp_same_variant() {
+            let a = Identifier::Explicit(5);
+            let b = Identifier::Explicit(6);
+            assert!(a.value_cmp(&b).unwrap().is_lt());
+            assert!(a.value_cmp(&a.clone()).unwrap().is_eq());
+        }
+
+        #[test]
+        fn set_of() {
+            let set = SetOfVec::try_from(identifiers()).unwrap();
+            let encodings: Vec<_> = set.iter().map(|id| id.to_der().unwrap()).collect();
+
+            let mut sorted = encodings.clone();
+            sorted.sort();
+            assert_eq!(encodings, sorted);
+        }
+    }
 }
 
 /// Custom derive test cases for the `Enumerated` macro.
@@ -336,7 +410,6 @@ mod sequence {
     }
 
     /// X.509 extension
-    // TODO(tarcieri): tests for code derived with the `default` attribute
     #[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
     pub struct Extension<'a> {
         extn_id: ObjectIdentifier,
@@ -369,16 +442,101 @@ mod sequence {
         pub context_specific: bool,
         #[asn1(optional = "true")]
         pub optional: Option<bool>,
-        #[asn1(default = "default_false_example")]
-        pub default: bool,
         #[asn1(type = "BIT STRING", context_specific = "1")]
         pub typed_context_specific: &'a [u8],
+        #[asn1(default = "default_false_example")]
+        pub default: bool,
         #[asn1(context_specific = "2", optional = "true")]
         pub context_specific_optional: Option<bool>,
         #[asn1(context_specific = "3", default = "default_false_example")]
         pub context_specific_default: bool,
         #[asn1(type = "BIT STRING", context_specific = "4", optional = "true")]
         pub typed_context_specific_optional: Option<&'a [u8]>,
+        #[asn1(type = "OCTET STRING", default = "default_octets_example")]
+        pub typed_default: &'a [u8],
+        #[asn1(
+            type = "OCTET STRING",
+            context_specific = "5",
+            default = "default_octets_example"
+        )]
+        pub typed_context_specific_default: &'a [u8],
+    }
+
+    pub fn default_octets_example<'a>() -> &'a [u8] {
+        b"default"
+    }
+
+    /// Sequence with `DEFAULT` fields combined with ASN.1 types.
+    #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+    pub struct TypedDefaultExample<'a> {
+        #[asn1(type = "OCTET STRING", default = "default_octets_example")]
+        pub name: &'a [u8],
+        #[asn1(
+            type = "OCTET STRING",
+            context_specific = "0",
+            tag_mode = "IMPLICIT",
+            default = "default_octets_example"
+        )]
+        pub label: &'a [u8],
+    }
+
+    /// Sequence with context-specific fields in the high tag number form.
+    #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+    pub struct HighTagNumberExample {
+        #[asn1(context_specific = "5", optional = "true")]
+        pub low: Option<u8>,
+        #[asn1(context_specific = "33", optional = "true")]
+        pub high: Option<u8>,
+        #[asn1(context_specific = "200", tag_mode = "IMPLICIT", optional = "true")]
+        pub higher: Option<bool>,
+    }
+
+    #[test]
+    fn extension_default() {
+        const DER: &[u8] = &hex!("30 08 06 03 55 1d 13 04 01 00");
+
+        let ext = Extension::from_der(DER).unwrap();
+        assert!(!ext.critical);
+        assert_eq!(ext.to_der().unwrap(), DER);
+
+        let critical = Extension {
+            critical: true,
+            ..ext
+        };
+        assert_eq!(
+            critical.to_der().unwrap(),
+            hex!("30 0b 06 03 55 1d 13 01 01 ff 04 01 00")
+        );
+    }
+
+    #[test]
+    fn typed_default() {
+        let defaults = TypedDefaultExample::from_der(&hex!("30 00")).unwrap();
+        assert_eq!(defaults.name, b"default");
+        assert_eq!(defaults.label, b"default");
+        assert_eq!(defaults.to_der().unwrap(), hex!("30 00"));
+
+        const DER: &[u8] = &hex!("30 08 04 02 61 62 80 02 63 64");
+        let example = TypedDefaultExample::from_der(DER).unwrap();
+        assert_eq!(example.name, b"ab");
+        assert_eq!(example.label, b"cd");
+        assert_eq!(example.to_der().unwrap(), DER);
+    }
+
+    #[test]
+    fn high_tag_number() {
+        const DER: &[u8] = &hex!("30 10 a5 03 02 01 01 bf 21 03 02 01 02 9f 81 48 01 ff");
+
+        let example = HighTagNumberExample::from_der(DER).unwrap();
+        assert_eq!(example.low, Some(1));
+        assert_eq!(example.high, Some(2));
+        assert_eq!(example.higher, Some(true));
+        assert_eq!(example.to_der().unwrap(), DER);
+
+        let example = HighTagNumberExample::from_der(&hex!("30 05 9f 81 48 01 00")).unwrap();
+        assert_eq!(example.low, None);
+        assert_eq!(example.high, None);
+        assert_eq!(example.higher, Some(false));
     }
 
     #[test]
@@ -458,4 +616,281 @@ mod sequence {
             algorithm_identifier.to_der().unwrap()
         );
     }
+
+    mod error_path {
+        use der::{asn1::SequenceOf, Decode, ErrorKind, Sequence, Tag};
+        use hex_literal::hex;
+
+        #[derive(Debug, Sequence)]
+        pub struct Inner {
+            pub flag: bool,
+        }
+
+        #[derive(Debug, Sequence)]
+        pub struct Middle {
+            pub inners: SequenceOf<Inner, 4>,
+        }
+
+        #[derive(Debug, Sequence)]
+        pub struct Outer {
+            pub version: u8,
+            pub r#middle: Middle,
+        }
+
+        #[test]
+        fn nested_field() {
+            // The second `flag` is an INTEGER rather than a BOOLEAN
+            let err =
+                Outer::from_der(&hex!("3011020101300C300A3003010100 3003020100")).unwrap_err();
+
+            assert_eq!(
+                err.kind(),
+                ErrorKind::TagUnexpected {
+                    expected: Some(Tag::Boolean),
+                    actual: Tag::Integer
+                }
+            );
+            assert_eq!(err.path().to_string(), "Outer.middle.inners[1].flag");
+            assert!(err
+                .to_string()
+                .starts_with("Outer.middle.inners[1].flag: unexpected ASN.1 DER tag"));
+
+            let fields = err
+                .path()
+                .fields()
+                .map(|(field, index)| (field.type_name, field.field, index))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                fields,
+                [
+                    ("Outer", "middle", None),
+                    ("Middle", "inners", Some(1)),
+                    ("Inner", "flag", None)
+                ]
+            );
+        }
+
+        #[derive(Debug, Sequence)]
+        pub struct Wrapper {
+            pub outer: Outer,
+        }
+
+        #[test]
+        fn truncated() {
+            let err = Wrapper::from_der(&hex!("30133011020101300C300A3003010100 3003020100"))
+                .unwrap_err();
+
+            assert!(err.path().is_truncated());
+            assert_eq!(err.path().to_string(), "...middle.inners[1].flag");
+        }
+
+        #[test]
+        fn size() {
+            // The path is stored inline in each error
+            assert!(core::mem::size_of::<der::ErrorPath>() <= 32);
+        }
+
+        #[test]
+        fn top_level() {
+            let err = Inner::from_der(&hex!("3003020100")).unwrap_err();
+            assert_eq!(err.path().to_string(), "Inner.flag");
+
+            let err = Outer::from_der(&hex!("3100")).unwrap_err();
+            assert!(err.path().is_empty());
+        }
+    }
+
+    mod defined_by {
+        use super::{ALGORITHM_IDENTIFIER_DER, ID_EC_PUBLIC_KEY_OID, PRIME256V1_OID};
+        use der::{
+            asn1::{AnyRef, ObjectIdentifier},
+            Decode, DecodeDefinedBy, Encode, ErrorKind, Length, Reader, Sequence, Writer,
+        };
+        use hex_literal::hex;
+
+        /// Parameters of an `AlgorithmIdentifier`, as defined by its `algorithm`.
+        #[derive(Clone, Debug, Eq, PartialEq)]
+        pub enum Parameters<'a> {
+            NamedCurve(ObjectIdentifier),
+            Other(AnyRef<'a>),
+        }
+
+        impl<'a> DecodeDefinedBy<'a, ObjectIdentifier> for Parameters<'a> {
+            fn decode_defined_by<R: Reader<'a>>(
+                algorithm: &ObjectIdentifier,
+                reader: &mut R,
+            ) -> der::Result<Self> {
+                if *algorithm == ID_EC_PUBLIC_KEY_OID {
+                    reader.decode().map(Self::NamedCurve)
+                } else {
+                    reader.decode().map(Self::Other)
+                }
+            }
+        }
+
+        impl Encode for Parameters<'_> {
+            fn encoded_len(&self) -> der::Result<Length> {
+                match self {
+                    Self::NamedCurve(oid) => oid.encoded_len(),
+                    Self::Other(any) => any.encoded_len(),
+                }
+            }
+
+            fn encode(&self, writer: &mut impl Writer) -> der::Result<()> {
+                match self {
+                    Self::NamedCurve(oid) => oid.encode(writer),
+                    Self::Other(any) => any.encode(writer),
+                }
+            }
+        }
+
+        /// X.509 `AlgorithmIdentifier` with typed parameters.
+        #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+        pub struct TypedAlgorithmIdentifier<'a> {
+            pub algorithm: ObjectIdentifier,
+            #[asn1(defined_by = "algorithm")]
+            pub parameters: Option<Parameters<'a>>,
+        }
+
+        #[test]
+        fn named_curve() {
+            let algorithm_identifier =
+                TypedAlgorithmIdentifier::from_der(ALGORITHM_IDENTIFIER_DER).unwrap();
+
+            assert_eq!(ID_EC_PUBLIC_KEY_OID, algorithm_identifier.algorithm);
+            assert_eq!(
+                algorithm_identifier.parameters,
+                Some(Parameters::NamedCurve(PRIME256V1_OID))
+            );
+            assert_eq!(
+                algorithm_identifier.to_der().unwrap(),
+                ALGORITHM_IDENTIFIER_DER
+            );
+        }
+
+        #[test]
+        fn other() {
+            // rsaEncryption with NULL parameters
+            let der = hex!("300D06092A864886F70D0101010500");
+            let algorithm_identifier = TypedAlgorithmIdentifier::from_der(&der).unwrap();
+            assert_eq!(
+                algorithm_identifier.parameters,
+                Some(Parameters::Other(AnyRef::from(der::asn1::Null)))
+            );
+            assert_eq!(algorithm_identifier.to_der().unwrap(), der);
+
+            // id-ecPublicKey with parameters which aren't a named curve
+            assert!(
+                TypedAlgorithmIdentifier::from_der(&hex!("300B06072A8648CE3D02010500")).is_err()
+            );
+        }
+
+        #[test]
+        fn absent() {
+            // ecdsa-with-SHA256 without parameters
+            let der = hex!("300A06082A8648CE3D040302");
+            let algorithm_identifier = TypedAlgorithmIdentifier::from_der(&der).unwrap();
+            assert_eq!(algorithm_identifier.parameters, None);
+            assert_eq!(algorithm_identifier.to_der().unwrap(), der);
+        }
+
+        #[test]
+        fn zero_octets_in_definite_sequence() {
+            // `[UNIVERSAL 0]` with a zero length isn't end-of-contents within
+            // a `SEQUENCE` with a definite length: it's rejected as the value
+            // of the field rather than taken as its absence
+            let der = hex!("300C06082A8648CE3D0403020000");
+            let tag_unknown = ErrorKind::TagUnknown { byte: 0 };
+            let err = TypedAlgorithmIdentifier::from_der(&der).unwrap_err();
+            assert_eq!(err.kind(), tag_unknown);
+
+            #[derive(Debug, Sequence)]
+            struct OptionalParameters {
+                algorithm: ObjectIdentifier,
+                parameters: Option<der::asn1::Null>,
+            }
+
+            let err = OptionalParameters::from_der(&der).unwrap_err();
+            assert_eq!(err.kind(), tag_unknown);
+        }
+    }
+}
+
+/// Custom derive test cases for the `const-assertions` feature: the checks
+/// pass for these definitions, which would otherwise fail to compile.
+#[cfg(feature = "const-assertions")]
+mod const_assertions {
+    use der::{
+        asn1::{AnyRef, BitStringRef, ObjectIdentifier, OctetStringRef},
+        Choice, Sequence,
+    };
+
+    #[derive(Choice, Debug, Eq, PartialEq)]
+    pub enum Alternatives<'a> {
+        Oid(ObjectIdentifier),
+        Bits(BitStringRef<'a>),
+
+        #[asn1(context_specific = "0", tag_mode = "IMPLICIT")]
+        Implicit(OctetStringRef<'a>),
+
+        #[asn1(context_specific = "1", type = "UTF8String", constructed = "true")]
+        Explicit(String),
+    }
+
+    #[derive(Sequence, Debug, Eq, PartialEq)]
+    pub struct Fields<'a> {
+        #[asn1(context_specific = "0", optional = "true")]
+        pub first: Option<u8>,
+
+        #[asn1(context_specific = "1", optional = "true")]
+        pub second: Option<u8>,
+
+        #[asn1(type = "OCTET STRING")]
+        pub required: &'a [u8],
+
+        #[asn1(context_specific = "0", optional = "true")]
+        pub reused: Option<u8>,
+    }
+
+    /// Tags of fields without `context_specific` or `type` attributes are
+    /// given by their `FixedTag` impl, if any.
+    #[derive(Sequence, Debug, Eq, PartialEq)]
+    pub struct Untagged<'a> {
+        #[asn1(optional = "true")]
+        pub flag: Option<bool>,
+
+        pub number: u8,
+
+        #[asn1(optional = "true")]
+        pub any: Option<AnyRef<'a>>,
+
+        #[asn1(optional = "true")]
+        pub bits: Option<BitStringRef<'a>>,
+    }
+    #[test]
+    fn round_trip() {
+        use der::{Decode, Encode};
+
+        let fields = Fields {
+            first: None,
+            second: Some(2),
+            required: &[1, 2, 3],
+            reused: Some(0),
+        };
+        let der = fields.to_der().unwrap();
+        assert_eq!(Fields::from_der(&der).unwrap(), fields);
+
+        let untagged = Untagged {
+            flag: Some(true),
+            number: 1,
+            any: None,
+            bits: None,
+        };
+        let der = untagged.to_der().unwrap();
+        assert_eq!(Untagged::from_der(&der).unwrap(), untagged);
+
+        let choice = Alternatives::Explicit("hello".into());
+        let der = choice.to_der().unwrap();
+        assert_eq!(Alternatives::from_der(&der).unwrap(), choice);
+    }
 }
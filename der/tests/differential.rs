@@ -0,0 +1,489 @@
+//! Differential tests comparing the structure decoded by this crate with the one
+//! reported by `openssl asn1parse`.
+//!
+//! The tests comparing with OpenSSL require the `openssl` command, and are only run
+//! with the `differential-tests` feature:
+//!
+//! ```text
+//! cargo test -p der --features differential-tests --test differential
+//! ```
+//!
+//! The corpus consists of the DER and BER examples of the crates of this workspace, as
+//! well as any file in the directory named by the `DER_DIFFERENTIAL_CORPUS` environment
+//! variable, if set.
+
+#![cfg(feature = "std")]
+
+use der::{
+    Class, Decode, Encode, IndefiniteLength, Length, RawHeader, Reader, SliceReader, Tag,
+    DEFAULT_MAX_DEPTH,
+};
+use std::{
+    env, fmt, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// Extensions of the files of the corpus.
+const EXTENSIONS: &[&str] = &["bin", "cer", "cms", "crt", "der", "p7b", "pfx", "sd"];
+
+/// Element of a decoded structure, as printed on a line of `openssl asn1parse -i`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Node {
+    offset: usize,
+    depth: usize,
+    header_len: usize,
+
+    /// Length of the contents, `None` for the indefinite form.
+    len: Option<usize>,
+    constructed: bool,
+    class: Class,
+    number: u32,
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:d={} hl={} l={} {} {:?} [{}]",
+            self.offset,
+            self.depth,
+            self.header_len,
+            self.len
+                .map(|len| len.to_string())
+                .unwrap_or_else(|| "inf".into()),
+            if self.constructed { "cons" } else { "prim" },
+            self.class,
+            self.number
+        )
+    }
+}
+
+/// Decode the structure of `bytes` with this crate.
+fn der_structure(bytes: &[u8]) -> Result<Vec<Node>, String> {
+    let mut nodes = Vec::new();
+    let mut reader = SliceReader::new(bytes).map_err(|e| e.to_string())?;
+    walk(&mut reader, 0, 0, false, &mut nodes)?;
+    Ok(nodes)
+}
+
+/// Walk the elements read by `reader`, whose input starts at offset `base` of the
+/// message. Elements of indefinite length contents are walked up to their
+/// end-of-contents marker.
+fn walk(
+    reader: &mut SliceReader<'_>,
+    base: usize,
+    depth: usize,
+    indefinite: bool,
+    nodes: &mut Vec<Node>,
+) -> Result<(), String> {
+    let offset = |reader: &SliceReader<'_>| -> Result<usize, String> {
+        let position = usize::try_from(reader.position()).map_err(|e| e.to_string())?;
+        Ok(base + position)
+    };
+
+    if depth > DEFAULT_MAX_DEPTH {
+        return Err(format!("{}: nested too deeply", offset(reader)?));
+    }
+
+    while !reader.is_finished() {
+        let pos = offset(reader)?;
+
+        if indefinite && reader.peek_eoc() {
+            reader.read_slice(2u8.into()).map_err(|e| e.to_string())?;
+            nodes.push(Node {
+                offset: pos,
+                depth,
+                header_len: 2,
+                len: Some(0),
+                constructed: false,
+                class: Class::Universal,
+                number: 0,
+            });
+
+            return Ok(());
+        }
+
+        let (tag, constructed, length) =
+            read_header(reader).map_err(|e| format!("{}: {}", pos, e))?;
+        let header_len = offset(reader)? - pos;
+        let len = Option::<Length>::from(length)
+            .map(usize::try_from)
+            .transpose()
+            .map_err(|e| e.to_string())?;
+
+        nodes.push(Node {
+            offset: pos,
+            depth,
+            header_len,
+            len,
+            constructed,
+            class: tag.class(),
+            number: tag.number().value_u32(),
+        });
+
+        match Option::<Length>::from(length) {
+            Some(length) => {
+                let contents = reader
+                    .read_slice(length)
+                    .map_err(|e| format!("{}: contents: {}", pos, e))?;
+
+                if constructed {
+                    let mut contents = SliceReader::new(contents).map_err(|e| e.to_string())?;
+                    walk(&mut contents, pos + header_len, depth + 1, false, nodes)?;
+                }
+            }
+            None if constructed => walk(reader, base, depth + 1, true, nodes)?,
+            None => return Err(format!("{}: primitive encoding of indefinite length", pos)),
+        }
+    }
+
+    match indefinite {
+        true => Err(format!("{}: missing end-of-contents", offset(reader)?)),
+        false => Ok(()),
+    }
+}
+
+/// Read the tag, whether the encoding is constructed, and the length of the next
+/// element with [`RawHeader`], which also decodes the BER length forms.
+///
+/// `Tag` has no representation of the constructed encoding of string types, which BER
+/// allows: their tag is read as the one of their primitive counterpart.
+fn read_header(reader: &mut SliceReader<'_>) -> der::Result<(Tag, bool, IndefiniteLength)> {
+    let mut header_reader = reader.clone();
+
+    match RawHeader::decode(&mut header_reader) {
+        Ok(header) => {
+            *reader = header_reader;
+            Ok((header.tag, header.tag.is_constructed(), header.length))
+        }
+        Err(err) => match reader.peek_byte() {
+            Some(byte) if byte & 0x20 != 0 && byte < 0x3F => {
+                let tag = Tag::try_from(byte & !0x20).map_err(|_| err)?;
+                reader.read_byte()?;
+                Ok((tag, true, IndefiniteLength::decode(reader)?))
+            }
+            _ => Err(err),
+        },
+    }
+}
+/// Decode the structure of `bytes` with `openssl asn1parse`.
+fn openssl_structure(bytes: &[u8]) -> Result<Vec<Node>, String> {
+    let mut child = Command::new("openssl")
+        .args(["asn1parse", "-inform", "DER", "-i"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("error running 'openssl': command not found. Is it installed?");
+
+    child
+        .stdin
+        .take()
+        .expect("openssl stdin")
+        .write_all(bytes)
+        .expect("write openssl stdin");
+    let output = child.wait_with_output().expect("get openssl output");
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+
+    // Lines which don't parse are continuations of the content of string types.
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_openssl_line)
+        .collect())
+}
+
+/// Parse a line such as `   13:d=1  hl=2 l=inf  cons:  cont [ 0 ]`.
+fn parse_openssl_line(line: &str) -> Option<Node> {
+    let (offset, rest) = line.trim_start().split_once(":d=")?;
+    let (depth, rest) = rest.split_once("hl=")?;
+    let (header_len, rest) = rest.split_once("l=")?;
+    let rest = rest.trim_start();
+    let (len, rest) = rest.split_once(' ')?;
+    let rest = rest.trim_start();
+    let (constructed, rest) = match (rest.get(..5)?, rest.get(5..)?) {
+        ("cons:", rest) => (true, rest),
+        ("prim:", rest) => (false, rest),
+        _ => return None,
+    };
+
+    let name = rest.split(':').next()?;
+    let name = name.trim().trim_end_matches("[HEX DUMP]").trim_end();
+    let (class, number) = parse_openssl_tag(name)?;
+
+    Some(Node {
+        offset: offset.parse().ok()?,
+        depth: depth.trim().parse().ok()?,
+        header_len: header_len.trim().parse().ok()?,
+        len: match len {
+            "inf" => None,
+            len => Some(len.parse().ok()?),
+        },
+        constructed,
+        class,
+        number,
+    })
+}
+
+/// Parse the tag names printed by `openssl asn1parse`.
+fn parse_openssl_tag(name: &str) -> Option<(Class, u32)> {
+    let tagged = |prefix: &str| {
+        name.strip_prefix(prefix)?
+            .trim_end_matches(']')
+            .trim()
+            .parse()
+            .ok()
+    };
+
+    if let Some(number) = tagged("cont [") {
+        return Some((Class::ContextSpecific, number));
+    }
+    if let Some(number) = tagged("appl [") {
+        return Some((Class::Application, number));
+    }
+    if let Some(number) = tagged("priv [") {
+        return Some((Class::Private, number));
+    }
+    if let Some(number) = name.strip_prefix("<ASN1 ") {
+        return Some((Class::Universal, number.trim_end_matches('>').parse().ok()?));
+    }
+
+    let number = match name {
+        "EOC" => 0,
+        "BOOLEAN" => 1,
+        "INTEGER" => 2,
+        "BIT STRING" => 3,
+        "OCTET STRING" => 4,
+        "NULL" => 5,
+        "OBJECT" => 6,
+        "OBJECT DESCRIPTOR" => 7,
+        "EXTERNAL" => 8,
+        "REAL" => 9,
+        "ENUMERATED" => 10,
+        "EMBEDDED PDV" => 11,
+        "UTF8STRING" => 12,
+        "SEQUENCE" => 16,
+        "SET" => 17,
+        "NUMERICSTRING" => 18,
+        "PRINTABLESTRING" => 19,
+        "T61STRING" => 20,
+        "VIDEOTEXSTRING" => 21,
+        "IA5STRING" => 22,
+        "UTCTIME" => 23,
+        "GENERALIZEDTIME" => 24,
+        "GRAPHICSTRING" => 25,
+        "VISIBLESTRING" => 26,
+        "GENERALSTRING" => 27,
+        "UNIVERSALSTRING" => 28,
+        "BMPSTRING" => 30,
+        _ => return None,
+    };
+
+    Some((Class::Universal, number))
+}
+
+/// Structural divergence between this crate and OpenSSL.
+#[derive(Debug)]
+enum Divergence {
+    /// Only one of the decoders rejected the input.
+    Rejected {
+        der: Option<String>,
+        openssl: Option<String>,
+    },
+
+    /// Both decoders accepted the input, but disagree on an element.
+    Node {
+        der: Option<Node>,
+        openssl: Option<Node>,
+    },
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Divergence::Rejected { der, openssl } => write!(
+                f,
+                "der: {}, openssl: {}",
+                der.as_deref().unwrap_or("accepted"),
+                openssl.as_deref().map(str::trim).unwrap_or("accepted")
+            ),
+            Divergence::Node { der, openssl } => {
+                let show = |node: &Option<Node>| {
+                    node.as_ref()
+                        .map(Node::to_string)
+                        .unwrap_or_else(|| "<none>".into())
+                };
+                write!(f, "der: {}, openssl: {}", show(der), show(openssl))
+            }
+        }
+    }
+}
+
+/// Compare the structures decoded by this crate and by OpenSSL, returning the first
+/// divergence, if any.
+fn compare(bytes: &[u8]) -> Option<Divergence> {
+    match (der_structure(bytes), openssl_structure(bytes)) {
+        (Ok(der), Ok(openssl)) => {
+            let len = der.len().max(openssl.len());
+            (0..len).find_map(|i| {
+                let (der, openssl) = (der.get(i), openssl.get(i));
+                (der != openssl).then(|| Divergence::Node {
+                    der: der.cloned(),
+                    openssl: openssl.cloned(),
+                })
+            })
+        }
+        (Err(_), Err(_)) => None,
+        (der, openssl) => Some(Divergence::Rejected {
+            der: der.err(),
+            openssl: openssl.err(),
+        }),
+    }
+}
+
+/// Collect the files of the corpus.
+fn corpus() -> Vec<PathBuf> {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let mut dirs: Vec<PathBuf> = fs::read_dir(&workspace)
+        .expect("read workspace")
+        .filter_map(|entry| Some(entry.ok()?.path().join("tests").join("examples")))
+        .collect();
+
+    if let Some(dir) = env::var_os("DER_DIFFERENTIAL_CORPUS") {
+        dirs.push(dir.into());
+    }
+
+    let mut files: Vec<PathBuf> = dirs
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .map_or(false, |ext| EXTENSIONS.contains(&ext))
+        })
+        .collect();
+
+    files.sort();
+    files
+}
+
+/// Run `check` over the corpus, reporting all divergences before failing.
+fn check_corpus(check: impl Fn(&[u8]) -> Option<Divergence>) {
+    let files = corpus();
+    assert!(!files.is_empty(), "empty corpus");
+
+    let divergences: Vec<String> = files
+        .iter()
+        .filter_map(|path| {
+            let bytes = fs::read(path).expect("read corpus file");
+            check(&bytes).map(|divergence| format!("{}: {}", path.display(), divergence))
+        })
+        .collect();
+
+    for divergence in &divergences {
+        eprintln!("{}", divergence);
+    }
+
+    assert!(
+        divergences.is_empty(),
+        "{} of {} files diverge",
+        divergences.len(),
+        files.len()
+    );
+}
+
+#[test]
+#[cfg(feature = "differential-tests")]
+fn structure_matches_openssl() {
+    check_corpus(compare);
+}
+
+#[test]
+#[cfg(feature = "differential-tests")]
+fn ber_to_der_matches_openssl() {
+    check_corpus(|bytes| {
+        // Inputs rejected by either decoder are covered by `structure_matches_openssl`.
+        openssl_structure(bytes).ok()?;
+        let der = der::ber_to_der(bytes).ok()?;
+
+        // The transcoded message must be DER, and structurally agree with OpenSSL.
+        if let Err(err) = der::Any::from_der(&der).and_then(|any| any.to_der()) {
+            return Some(Divergence::Rejected {
+                der: Some(err.to_string()),
+                openssl: None,
+            });
+        }
+
+        compare(&der)
+    });
+}
+
+#[test]
+fn parse_openssl_lines() {
+    assert_eq!(
+        parse_openssl_line("   13:d=1  hl=2 l=inf  cons:  cont [ 0 ]        "),
+        Some(Node {
+            offset: 13,
+            depth: 1,
+            header_len: 2,
+            len: None,
+            constructed: true,
+            class: Class::ContextSpecific,
+            number: 0,
+        })
+    );
+    assert_eq!(
+        parse_openssl_line("    2:d=1  hl=2 l=   9 prim:  OBJECT            :pkcs7-signedData"),
+        Some(Node {
+            offset: 2,
+            depth: 1,
+            header_len: 2,
+            len: Some(9),
+            constructed: false,
+            class: Class::Universal,
+            number: 6,
+        })
+    );
+    assert_eq!(parse_openssl_line("AAAAAAAAAAAAAAAA"), None);
+
+    // multi-byte character where the encoding is expected
+    assert_eq!(parse_openssl_line("0:d=0 hl=2 l=3 pri\u{e9}:  NULL"), None);
+}
+
+#[test]
+fn walk_indefinite_length() {
+    let nodes = der_structure(&[0x30, 0x80, 0x04, 0x01, 0x41, 0x00, 0x00]).unwrap();
+    assert_eq!(nodes.len(), 3);
+    assert_eq!(nodes[0].len, None);
+    assert_eq!(nodes[2].number, 0);
+    assert!(der_structure(&[0x30, 0x80, 0x04, 0x01, 0x41]).is_err());
+
+    // end-of-contents only terminates indefinite length contents
+    assert!(der_structure(&[0x30, 0x02, 0x00, 0x00]).is_err());
+
+    // constructed encoding of a string type
+    let nodes = der_structure(&[0x24, 0x80, 0x04, 0x01, 0x41, 0x00, 0x00]).unwrap();
+    assert!(nodes[0].constructed);
+    assert_eq!(nodes[0].number, 4);
+}
+
+#[test]
+fn walk_depth_limit() {
+    let mut bytes = Vec::new();
+    for _ in 0..=DEFAULT_MAX_DEPTH {
+        bytes.extend_from_slice(&[0x30, 0x80]);
+    }
+    for _ in 0..=DEFAULT_MAX_DEPTH {
+        bytes.extend_from_slice(&[0x00, 0x00]);
+    }
+
+    assert!(der_structure(&bytes)
+        .unwrap_err()
+        .contains("nested too deeply"));
+}
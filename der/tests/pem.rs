@@ -4,7 +4,7 @@
 
 use der::{
     asn1::{BitString, ObjectIdentifier},
-    pem::{LineEnding, PemLabel},
+    pem::{LabelAlias, LabelRegistry, LineEnding, PemLabel},
     Decode, DecodePem, EncodePem, Sequence,
 };
 
@@ -65,3 +65,189 @@ fn to_pem() {
     let pem = spki.to_pem(LineEnding::LF).unwrap();
     assert_eq!(&pem, SPKI_PEM);
 }
+
+#[test]
+fn read_truncated_integer() {
+    use der::{ErrorKind, PemReader};
+
+    // `02 04 01`: an `INTEGER` claiming 4 bytes of contents, with only 1 present
+    let pem = "-----BEGIN INTEGER-----\nAgQB\n-----END INTEGER-----\n";
+    let mut reader = PemReader::new(pem.as_bytes()).unwrap();
+    let err = u32::decode(&mut reader).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::Incomplete { .. }));
+}
+
+#[cfg(feature = "std")]
+mod stream {
+    use super::{SpkiOwned, SPKI_DER, SPKI_PEM};
+    use der::{
+        asn1::{Any, OctetString},
+        pem::{self, LineEnding},
+        Decode, Encode, ErrorKind, Length, PemStreamReader, PemStreamWriter, Reader, Tagged,
+    };
+    use std::io::{BufReader, Read};
+
+    /// Decode a value of type `T` from a PEM stream read in small chunks.
+    fn decode_stream<T: for<'a> Decode<'a>>(pem: &[u8]) -> der::Result<T> {
+        let mut reader = PemStreamReader::new(BufReader::with_capacity(5, pem))?;
+        let value = T::decode(&mut reader)?;
+        reader.finish(value)
+    }
+
+    #[test]
+    fn read_spki() {
+        let mut reader = PemStreamReader::new(SPKI_PEM.as_bytes()).unwrap();
+        assert_eq!(reader.type_label(), "PUBLIC KEY");
+
+        let spki = SpkiOwned::decode(&mut reader).unwrap();
+        let spki = reader.finish(spki).unwrap();
+        assert_eq!(spki.to_der().unwrap(), SPKI_DER);
+
+        let spki: SpkiOwned = decode_stream(SPKI_PEM.as_bytes()).unwrap();
+        assert_eq!(spki.to_der().unwrap(), SPKI_DER);
+    }
+
+    #[test]
+    fn read_multiline_crlf_with_preamble() {
+        let value = OctetString::new(vec![0x42; 1000]).unwrap();
+        let der = value.to_der().unwrap();
+        let pem = pem::encode_string("DATA", LineEnding::CRLF, &der).unwrap();
+        let pem = format!("Explanatory text\r\n{}", pem);
+
+        let decoded: OctetString = decode_stream(pem.as_bytes()).unwrap();
+        assert_eq!(decoded, value);
+
+        let mut reader = PemStreamReader::new(pem.as_bytes()).unwrap();
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).unwrap();
+        assert_eq!(bytes, der);
+    }
+
+    #[test]
+    fn read_truncated() {
+        let pem = SPKI_PEM.lines().take(2).collect::<Vec<_>>().join("\n");
+        let err = decode_stream::<SpkiOwned>(pem.as_bytes()).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::Pem(pem::Error::PostEncapsulationBoundary)
+        );
+
+        let pem = SPKI_PEM.replace("END PUBLIC KEY", "END PRIVATE KEY");
+        assert!(decode_stream::<SpkiOwned>(pem.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn read_trailing_data() {
+        let mut der = SPKI_DER.to_vec();
+        der.push(0);
+        let pem = pem::encode_string("PUBLIC KEY", LineEnding::LF, &der).unwrap();
+
+        let err = decode_stream::<SpkiOwned>(pem.as_bytes()).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::TrailingData { .. }));
+    }
+
+    #[test]
+    fn read_high_tag_number() {
+        // [APPLICATION 4294967295] INTEGER 42, with the longest encoding of a tag
+        let der = [0x5F, 0x8F, 0xFF, 0xFF, 0xFF, 0x7F, 0x01, 0x2A];
+        let pem = pem::encode_string("DATA", LineEnding::LF, &der).unwrap();
+
+        // one base64 block per line, so that the header is read in 3-octet steps
+        let body = pem.lines().nth(1).unwrap().as_bytes().chunks(4);
+        let body = body.map(|block| std::str::from_utf8(block).unwrap());
+        let pem = format!(
+            "-----BEGIN DATA-----\n{}\n-----END DATA-----\n",
+            body.collect::<Vec<_>>().join("\n")
+        );
+
+        let reader = PemStreamReader::new(pem.as_bytes()).unwrap();
+        assert_eq!(reader.peek_header().unwrap().length, Length::ONE);
+
+        let any: Any = decode_stream(pem.as_bytes()).unwrap();
+        assert_eq!(any.tag().number().value_u32(), u32::MAX);
+        assert_eq!(any.value(), [0x2A]);
+    }
+
+    #[test]
+    fn read_ber_indefinite_length() {
+        // SEQUENCE (indefinite length) { INTEGER 42 }
+        let pem = "-----BEGIN DATA-----\nMIACASoAAA==\n-----END DATA-----\n";
+
+        let reader = PemStreamReader::new(pem.as_bytes()).unwrap();
+        assert_eq!(
+            reader.peek_header().unwrap_err().kind(),
+            ErrorKind::IndefiniteLength
+        );
+
+        let reader = PemStreamReader::new(pem.as_bytes()).unwrap();
+        assert_eq!(
+            reader.ber_to_der(7).unwrap(),
+            [0x30, 0x03, 0x02, 0x01, 0x2A]
+        );
+
+        let reader = PemStreamReader::new(pem.as_bytes()).unwrap();
+        assert_eq!(
+            reader.ber_to_der(6).unwrap_err().kind(),
+            ErrorKind::Overlength
+        );
+
+        let reader = PemStreamReader::new(pem.as_bytes())
+            .unwrap()
+            .with_max_depth(0);
+        assert_eq!(
+            reader.ber_to_der(7).unwrap_err().kind(),
+            ErrorKind::DepthExceeded { max_depth: 0 }
+        );
+    }
+
+    #[test]
+    fn peek_eoc() {
+        // SEQUENCE (indefinite length) { INTEGER 42 }
+        let pem = "-----BEGIN DATA-----\nMIACASoAAA==\n-----END DATA-----\n";
+
+        let mut reader = PemStreamReader::new(pem.as_bytes()).unwrap();
+        reader.read_into(&mut [0; 2]).unwrap();
+        assert!(!reader.peek_eoc());
+        assert_eq!(u8::decode(&mut reader).unwrap(), 42);
+        assert!(reader.peek_eoc());
+
+        let mut reader = der::PemReader::new(pem.as_bytes()).unwrap();
+        reader.read_into(&mut [0; 2]).unwrap();
+        assert!(!reader.peek_eoc());
+        assert_eq!(u8::decode(&mut reader).unwrap(), 42);
+        assert!(reader.peek_eoc());
+
+        // a lone `0x00` octet
+        let pem = "-----BEGIN DATA-----\nAA==\n-----END DATA-----\n";
+        assert!(!PemStreamReader::new(pem.as_bytes()).unwrap().peek_eoc());
+        assert!(!der::PemReader::new(pem.as_bytes()).unwrap().peek_eoc());
+    }
+
+    #[test]
+    fn write() {
+        for len in [0, 47, 48, 49, 1000] {
+            let value = OctetString::new(vec![0x42; len]).unwrap();
+            let der = value.to_der().unwrap();
+
+            let mut writer = PemStreamWriter::new("DATA", LineEnding::LF, Vec::new()).unwrap();
+            value.encode(&mut writer).unwrap();
+            let pem = writer.finish().unwrap();
+
+            let expected = pem::encode_string("DATA", LineEnding::LF, &der).unwrap();
+            assert_eq!(String::from_utf8(pem).unwrap(), expected);
+        }
+    }
+}
+
+#[test]
+fn from_pem_with_labels() {
+    let pem = SPKI_PEM
+        .replace("BEGIN PUBLIC KEY", "BEGIN RSA PUBLIC KEY")
+        .replace("END PUBLIC KEY", "END RSA PUBLIC KEY");
+    assert!(SpkiOwned::from_pem(&pem).is_err());
+
+    let aliases = [LabelAlias::new("RSA PUBLIC KEY", "PUBLIC KEY")];
+    let spki = SpkiOwned::from_pem_with_labels(&pem, &LabelRegistry::new(&aliases)).unwrap();
+    assert_eq!(spki, SpkiOwned::from_pem(SPKI_PEM).unwrap());
+    assert!(SpkiOwned::from_pem_with_labels(&pem, &LabelRegistry::STRICT).is_err());
+}
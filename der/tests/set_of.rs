@@ -63,3 +63,24 @@ mod ordering {
         assert_eq!(ObjectIdentifier::new("2.5.4.3").unwrap(), attr1.oid);
     }
 }
+
+/// Elements with tag numbers in the high tag number form.
+#[test]
+fn high_tag_numbers_round_trip() {
+    use der::{asn1::Any, Decode, Encode, TagNumber};
+
+    let elements = [128, 31, 30]
+        .map(|number| Any::new(TagNumber::new(number).application(false), [number]).unwrap());
+
+    let set = SetOfVec::try_from(elements).unwrap();
+    let der = set.to_der().unwrap();
+    assert_eq!(
+        der,
+        [0x31, 0x0C, 0x5E, 0x01, 0x1E, 0x5F, 0x1F, 0x01, 0x1F, 0x5F, 0x81, 0x00, 0x01, 0x80]
+    );
+    assert!(der::check_der(&der).is_ok());
+
+    let decoded = SetOfVec::<Any>::from_der(&der).unwrap();
+    assert_eq!(decoded, set);
+    assert_eq!(decoded.to_der().unwrap(), der);
+}
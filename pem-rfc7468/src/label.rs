@@ -0,0 +1,164 @@
+//! Registry of additional accepted PEM type labels.
+
+use crate::{Error, PemLabel, Result};
+
+/// Additional PEM type label accepted in place of the label expected for a type.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct LabelAlias<'a> {
+    /// Type label which is accepted, e.g. `"X509 CERTIFICATE"`.
+    pub label: &'a str,
+
+    /// Type label it is accepted for, i.e. the [`PemLabel::PEM_LABEL`] of the
+    /// expected type, e.g. `"CERTIFICATE"`.
+    pub expected: &'a str,
+}
+
+impl<'a> LabelAlias<'a> {
+    /// Accept `label` wherever `expected` is expected.
+    pub const fn new(label: &'a str, expected: &'a str) -> Self {
+        Self { label, expected }
+    }
+}
+
+/// Registry of PEM type labels accepted in addition to the ones expected by
+/// [`PemLabel`] types.
+///
+/// The default registry is strict: it accepts no other label than the expected
+/// one, like [`PemLabel::validate_pem_label`]. Additional labels, such as the
+/// legacy labels described in [RFC 7468] or vendor-specific ones, are accepted
+/// by building a registry from a list of [`LabelAlias`]es:
+///
+/// ```
+/// use pem_rfc7468::{LabelAlias, LabelRegistry};
+///
+/// const LABELS: LabelRegistry<'static> = LabelRegistry::new(&[
+///     LabelAlias::new("X509 CERTIFICATE", "CERTIFICATE"),
+///     LabelAlias::new("ACME CERTIFICATE", "CERTIFICATE"),
+/// ]);
+///
+/// assert_eq!(LABELS.resolve("ACME CERTIFICATE"), "CERTIFICATE");
+/// assert!(LABELS.validate("CERTIFICATE", "X509 CERTIFICATE").is_ok());
+/// assert!(LABELS.validate("CERTIFICATE", "PRIVATE KEY").is_err());
+/// ```
+///
+/// [RFC 7468]: https://datatracker.ietf.org/doc/html/rfc7468
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct LabelRegistry<'a> {
+    aliases: &'a [LabelAlias<'a>],
+}
+
+impl<'a> LabelRegistry<'a> {
+    /// Registry which accepts no additional labels.
+    pub const STRICT: LabelRegistry<'static> = LabelRegistry::new(&[]);
+
+    /// Registry which accepts the legacy labels that [RFC 7468] describes as
+    /// having been seen in the wild:
+    ///
+    /// - `X509 CERTIFICATE` and `X.509 CERTIFICATE` for `CERTIFICATE` ([Section 5.1])
+    /// - `NEW CERTIFICATE REQUEST` for `CERTIFICATE REQUEST` ([Section 7])
+    ///
+    /// [RFC 7468]: https://datatracker.ietf.org/doc/html/rfc7468
+    /// [Section 5.1]: https://datatracker.ietf.org/doc/html/rfc7468#section-5.1
+    /// [Section 7]: https://datatracker.ietf.org/doc/html/rfc7468#section-7
+    pub const LEGACY: LabelRegistry<'static> = LabelRegistry::new(&[
+        LabelAlias::new("X509 CERTIFICATE", "CERTIFICATE"),
+        LabelAlias::new("X.509 CERTIFICATE", "CERTIFICATE"),
+        LabelAlias::new("NEW CERTIFICATE REQUEST", "CERTIFICATE REQUEST"),
+    ]);
+
+    /// Create a registry accepting the given aliases.
+    pub const fn new(aliases: &'a [LabelAlias<'a>]) -> Self {
+        Self { aliases }
+    }
+
+    /// Get the aliases accepted by this registry.
+    pub fn aliases(&self) -> &'a [LabelAlias<'a>] {
+        self.aliases
+    }
+
+    /// Resolve `label` to the label it is accepted for, or return it as is if
+    /// it is not registered.
+    pub fn resolve<'l>(&self, label: &'l str) -> &'l str
+    where
+        'a: 'l,
+    {
+        self.aliases
+            .iter()
+            .find(|alias| alias.label == label)
+            .map(|alias| alias.expected)
+            .unwrap_or(label)
+    }
+
+    /// Validate that `actual` is either the `expected` label or registered as
+    /// an alias of it.
+    pub fn validate(&self, expected: &'static str, actual: &str) -> Result<()> {
+        let accepted = expected == actual
+            || self
+                .aliases
+                .iter()
+                .any(|alias| alias.label == actual && alias.expected == expected);
+
+        if accepted {
+            Ok(())
+        } else {
+            Err(Error::UnexpectedTypeLabel { expected })
+        }
+    }
+
+    /// Validate that `actual` is accepted as the label of `T`.
+    pub fn validate_label<T: PemLabel>(&self, actual: &str) -> Result<()> {
+        self.validate(T::PEM_LABEL, actual)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LabelAlias, LabelRegistry};
+    use crate::{Error, PemLabel};
+
+    struct Certificate;
+
+    impl PemLabel for Certificate {
+        const PEM_LABEL: &'static str = "CERTIFICATE";
+    }
+
+    #[test]
+    fn strict_registry() {
+        let labels = LabelRegistry::STRICT;
+        assert!(labels.validate_label::<Certificate>("CERTIFICATE").is_ok());
+        assert_eq!(
+            labels.validate_label::<Certificate>("X509 CERTIFICATE"),
+            Err(Error::UnexpectedTypeLabel {
+                expected: "CERTIFICATE"
+            })
+        );
+        assert_eq!(labels.resolve("X509 CERTIFICATE"), "X509 CERTIFICATE");
+    }
+
+    #[test]
+    fn legacy_registry() {
+        let labels = LabelRegistry::LEGACY;
+        assert!(labels
+            .validate_label::<Certificate>("X509 CERTIFICATE")
+            .is_ok());
+        assert!(labels
+            .validate_label::<Certificate>("NEW CERTIFICATE REQUEST")
+            .is_err());
+        assert_eq!(
+            labels.resolve("NEW CERTIFICATE REQUEST"),
+            "CERTIFICATE REQUEST"
+        );
+    }
+
+    #[test]
+    fn custom_registry() {
+        let aliases = [LabelAlias::new("ACME CERTIFICATE", "CERTIFICATE")];
+        let labels = LabelRegistry::new(&aliases);
+        assert!(labels
+            .validate_label::<Certificate>("ACME CERTIFICATE")
+            .is_ok());
+        assert!(labels
+            .validate_label::<Certificate>("X509 CERTIFICATE")
+            .is_err());
+    }
+}
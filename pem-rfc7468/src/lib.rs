@@ -61,11 +61,13 @@ mod decoder;
 mod encoder;
 mod error;
 mod grammar;
+mod label;
 
 pub use crate::{
     decoder::{decode, decode_label, Decoder},
     encoder::{encapsulated_len, encapsulated_len_wrapped, encode, encoded_len, Encoder},
     error::{Error, Result},
+    label::{LabelAlias, LabelRegistry},
 };
 pub use base64ct::LineEnding;
 
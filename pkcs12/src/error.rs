@@ -0,0 +1,69 @@
+//! Error types
+
+use core::fmt;
+use der::asn1::ObjectIdentifier;
+
+/// Result type with the `pkcs12` crate's [`Error`] type.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Error type
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors.
+    Asn1(der::Error),
+
+    /// Errors relating to password-based encryption of PFX contents.
+    EncryptedContent(pkcs8::pkcs5::Error),
+
+    /// Errors relating to PKCS#8 private keys.
+    PrivateKey(pkcs8::Error),
+
+    /// The PFX has no MAC, or the MAC is incorrect, e.g. because of a wrong password.
+    MacVerification,
+
+    /// The PFX does not contain a private key.
+    MissingPrivateKey,
+
+    /// The iteration count of the MAC key derivation exceeds the maximum accepted.
+    IterationsExceeded(u32),
+
+    /// An algorithm or content type is not supported.
+    UnsupportedAlgorithm(ObjectIdentifier),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "PKCS#12 ASN.1 error: {}", err),
+            Error::EncryptedContent(err) => write!(f, "PKCS#12 encryption error: {}", err),
+            Error::PrivateKey(err) => write!(f, "PKCS#12 private key error: {}", err),
+            Error::MacVerification => f.write_str("PKCS#12 MAC verification failed"),
+            Error::MissingPrivateKey => f.write_str("PKCS#12 PFX contains no private key"),
+            Error::IterationsExceeded(iterations) => {
+                write!(f, "PKCS#12 MAC iteration count too large: {}", iterations)
+            }
+            Error::UnsupportedAlgorithm(oid) => {
+                write!(f, "PKCS#12 unsupported algorithm or content type: {}", oid)
+            }
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(err: der::Error) -> Error {
+        Error::Asn1(err)
+    }
+}
+
+impl From<pkcs8::pkcs5::Error> for Error {
+    fn from(err: pkcs8::pkcs5::Error) -> Error {
+        Error::EncryptedContent(err)
+    }
+}
+
+impl From<pkcs8::Error> for Error {
+    fn from(err: pkcs8::Error) -> Error {
+        Error::PrivateKey(err)
+    }
+}
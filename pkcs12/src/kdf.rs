@@ -0,0 +1,92 @@
+//! PKCS#12 key derivation function as described in [RFC 7292 Appendix B.2].
+//!
+//! This KDF is only used for the integrity protection of PFX contents by [`MacData`]: new
+//! encryption keys are derived using PBES2 instead.
+//!
+//! [RFC 7292 Appendix B.2]: https://www.rfc-editor.org/rfc/rfc7292#appendix-B.2
+//! [`MacData`]: crate::pfx::MacData
+
+use alloc::vec::Vec;
+use der::{asn1::BmpString, zeroize::Zeroizing};
+use sha2::digest::{core_api::BlockSizeUser, Digest};
+
+/// Purpose of the key material derived by [`derive_key`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum Pkcs12KeyType {
+    /// Encryption key.
+    EncryptionKey = 1,
+
+    /// Initialization vector.
+    Iv = 2,
+
+    /// Integrity (MAC) key.
+    Mac = 3,
+}
+
+/// Derive `key_len` bytes of key material from a password as described in
+/// [RFC 7292 Appendix B.2].
+///
+/// The password is encoded as a NUL-terminated `BMPString` as required by
+/// [RFC 7292 Appendix B.1].
+///
+/// [RFC 7292 Appendix B.1]: https://www.rfc-editor.org/rfc/rfc7292#appendix-B.1
+/// [RFC 7292 Appendix B.2]: https://www.rfc-editor.org/rfc/rfc7292#appendix-B.2
+pub fn derive_key<D>(
+    password: &str,
+    salt: &[u8],
+    id: Pkcs12KeyType,
+    iterations: u32,
+    key_len: usize,
+) -> der::Result<Zeroizing<Vec<u8>>>
+where
+    D: Digest + BlockSizeUser,
+{
+    let bmp_password = BmpString::from_utf8(password)?;
+    let mut password = Zeroizing::new(bmp_password.as_bytes().to_vec());
+    password.extend_from_slice(&[0, 0]);
+
+    let v = D::block_size();
+    let id_block = Zeroizing::new(alloc::vec![id as u8; v]);
+
+    // I = S || P, each padded to a multiple of the block size
+    let mut i = Zeroizing::new(fill_blocks(salt, v));
+    i.extend_from_slice(&fill_blocks(&password, v));
+
+    let mut key = Zeroizing::new(Vec::with_capacity(key_len));
+
+    loop {
+        let mut a = D::new()
+            .chain_update(&*id_block)
+            .chain_update(&*i)
+            .finalize();
+        for _ in 1..iterations {
+            a = D::digest(&a);
+        }
+
+        let remaining = key_len - key.len();
+        key.extend_from_slice(&a[..remaining.min(a.len())]);
+
+        if key.len() == key_len {
+            return Ok(key);
+        }
+
+        // I_j = (I_j + B + 1) mod 2^v for each v-bit block of I, where B = A repeated
+        let b = Zeroizing::new(fill_blocks(&a, v));
+        for block in i.chunks_mut(v) {
+            let mut carry = 1u16;
+            for (x, y) in block.iter_mut().rev().zip(b.iter().rev()) {
+                let sum = u16::from(*x) + u16::from(*y) + carry;
+                // keep the low byte, the high byte is carried over to the next one
+                *x = (sum & 0xff) as u8;
+                carry = sum >> 8;
+            }
+        }
+    }
+}
+
+/// Concatenate copies of `input` to fill the least number of `v`-byte blocks which hold it.
+fn fill_blocks(input: &[u8], v: usize) -> Vec<u8> {
+    let len = v * ((input.len() + v - 1) / v);
+    input.iter().cycle().take(len).copied().collect()
+}
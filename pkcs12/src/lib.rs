@@ -14,4 +14,54 @@
     unused_qualifications
 )]
 
-//! TODO: PKCS#12 crate
+//! # Usage
+//!
+//! The [`encode_pfx`] and [`decode_pfx`] functions convert between a PKCS#8 private key
+//! held in a [`SecretDocument`] plus its certificate chain, and a password-protected PFX
+//! as produced by e.g. `openssl pkcs12 -export`.
+//!
+//! Private keys are protected using PBES2 with PBKDF2-SHA256 and AES-256-CBC, and the PFX
+//! is integrity-protected with HMAC-SHA256, matching the defaults of OpenSSL 3.
+
+extern crate alloc;
+
+mod error;
+pub mod kdf;
+pub mod pfx;
+pub mod safe_bag;
+
+pub use crate::error::{Error, Result};
+pub use crate::pfx::{decode_pfx, decode_pfx_with_max_iterations, encode_pfx};
+pub use cms;
+pub use der::{self, SecretDocument};
+pub use pkcs8;
+
+use const_oid::ObjectIdentifier;
+
+/// `keyBag` bag type (`1.2.840.113549.1.12.10.1.1`)
+pub const PKCS_12_KEY_BAG_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.12.10.1.1");
+
+/// `pkcs8ShroudedKeyBag` bag type (`1.2.840.113549.1.12.10.1.2`)
+pub const PKCS_12_PKCS8_KEY_BAG_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.12.10.1.2");
+
+/// `certBag` bag type (`1.2.840.113549.1.12.10.1.3`)
+pub const PKCS_12_CERT_BAG_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.12.10.1.3");
+
+/// `crlBag` bag type (`1.2.840.113549.1.12.10.1.4`)
+pub const PKCS_12_CRL_BAG_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.12.10.1.4");
+
+/// `secretBag` bag type (`1.2.840.113549.1.12.10.1.5`)
+pub const PKCS_12_SECRET_BAG_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.12.10.1.5");
+
+/// `safeContentsBag` bag type (`1.2.840.113549.1.12.10.1.6`)
+pub const PKCS_12_SAFE_CONTENTS_BAG_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.12.10.1.6");
+
+/// `x509Certificate` certificate type (`1.2.840.113549.1.9.22.1`)
+pub const PKCS_12_X509_CERT_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.22.1");
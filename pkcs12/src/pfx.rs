@@ -0,0 +1,365 @@
+//! PFX-related types and conversions
+
+use alloc::vec::Vec;
+use cms::content_info::ContentInfo;
+use cms::encrypted_data::EncryptedData;
+use const_oid::db::{
+    rfc5911::{ID_DATA, ID_ENCRYPTED_DATA},
+    rfc5912::{ID_SHA_1, ID_SHA_256},
+};
+use der::{
+    asn1::{Null, OctetString},
+    Any, Decode, Encode, Enumerated, SecretDocument, Sequence,
+};
+use hmac::{
+    digest::{core_api::BlockSizeUser, Digest, KeyInit},
+    Hmac, Mac,
+};
+use pkcs8::{
+    pkcs5::{pbes2, EncryptionScheme},
+    rand_core::{CryptoRng, RngCore},
+    EncryptedPrivateKeyInfo, PrivateKeyInfo,
+};
+use sha1::Sha1;
+use sha2::Sha256;
+use spki::AlgorithmIdentifierOwned;
+use x509_cert::Certificate;
+
+use crate::kdf::{derive_key, Pkcs12KeyType};
+use crate::safe_bag::{CertBag, SafeBag, SafeContents};
+use crate::{
+    Error, Result, PKCS_12_CERT_BAG_OID, PKCS_12_KEY_BAG_OID, PKCS_12_PKCS8_KEY_BAG_OID,
+    PKCS_12_X509_CERT_OID,
+};
+
+/// Iteration count used for key encryption and MAC key derivation by [`encode_pfx`].
+pub const DEFAULT_ITERATIONS: u32 = 2048;
+
+/// Maximum iteration count of the MAC key derivation accepted by [`decode_pfx`].
+///
+/// The iteration count is read from the PFX, so it must be bounded to keep untrusted inputs
+/// from stalling the key derivation, which can't be run in parallel.
+pub const DEFAULT_MAX_ITERATIONS: u32 = 1_000_000;
+
+/// Length of the salts and IVs generated by [`encode_pfx`].
+const SALT_LEN: usize = 16;
+
+/// The `Version` of a PFX is defined in [RFC 7292 Section 4].
+///
+/// ```text
+/// version    INTEGER {v3(3)}(v3,...)
+/// ```
+///
+/// [RFC 7292 Section 4]: https://www.rfc-editor.org/rfc/rfc7292#section-4
+#[derive(Clone, Debug, Copy, PartialEq, Eq, PartialOrd, Ord, Enumerated)]
+#[asn1(type = "INTEGER")]
+#[repr(u8)]
+#[allow(missing_docs)]
+pub enum Version {
+    V3 = 3,
+}
+
+/// The `PFX` type is defined in [RFC 7292 Section 4].
+///
+/// ```text
+/// PFX ::= SEQUENCE {
+///     version    INTEGER {v3(3)}(v3,...),
+///     authSafe   ContentInfo,
+///     macData    MacData OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 7292 Section 4]: https://www.rfc-editor.org/rfc/rfc7292#section-4
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct Pfx {
+    pub version: Version,
+    pub auth_safe: ContentInfo,
+    pub mac_data: Option<MacData>,
+}
+
+/// The `AuthenticatedSafe` type is defined in [RFC 7292 Section 4.1].
+///
+/// ```text
+/// AuthenticatedSafe ::= SEQUENCE OF ContentInfo
+/// ```
+///
+/// [RFC 7292 Section 4.1]: https://www.rfc-editor.org/rfc/rfc7292#section-4.1
+pub type AuthenticatedSafe = Vec<ContentInfo>;
+
+/// The `MacData` type is defined in [RFC 7292 Section 4].
+///
+/// ```text
+/// MacData ::= SEQUENCE {
+///     mac        DigestInfo,
+///     macSalt    OCTET STRING,
+///     iterations INTEGER DEFAULT 1
+/// }
+/// ```
+///
+/// [RFC 7292 Section 4]: https://www.rfc-editor.org/rfc/rfc7292#section-4
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct MacData {
+    pub mac: DigestInfo,
+    pub mac_salt: OctetString,
+    #[asn1(default = "default_iterations")]
+    pub iterations: u32,
+}
+
+/// The `DigestInfo` type is defined in [RFC 7292 Section 4].
+///
+/// ```text
+/// DigestInfo ::= SEQUENCE {
+///     digestAlgorithm DigestAlgorithmIdentifier,
+///     digest          Digest
+/// }
+/// ```
+///
+/// [RFC 7292 Section 4]: https://www.rfc-editor.org/rfc/rfc7292#section-4
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct DigestInfo {
+    pub algorithm: AlgorithmIdentifierOwned,
+    pub digest: OctetString,
+}
+
+fn default_iterations() -> u32 {
+    1
+}
+
+impl MacData {
+    /// Compute the HMAC-SHA256 MAC of the `content` of an `AuthenticatedSafe`.
+    pub fn new(password: &str, salt: &[u8], iterations: u32, content: &[u8]) -> Result<Self> {
+        let mac = Self::hmac::<Sha256, Hmac<Sha256>>(password, salt, iterations)?
+            .chain_update(content)
+            .finalize();
+
+        Ok(Self {
+            mac: DigestInfo {
+                algorithm: AlgorithmIdentifierOwned {
+                    oid: ID_SHA_256,
+                    parameters: Some(Null.into()),
+                },
+                digest: OctetString::new(mac.into_bytes().as_slice())?,
+            },
+            mac_salt: OctetString::new(salt)?,
+            iterations,
+        })
+    }
+
+    /// Verify the MAC of the `content` of an `AuthenticatedSafe`.
+    ///
+    /// HMAC-SHA256 and, for compatibility with older implementations, HMAC-SHA1 are supported.
+    /// The iteration count must not exceed [`DEFAULT_MAX_ITERATIONS`].
+    pub fn verify(&self, password: &str, content: &[u8]) -> Result<()> {
+        self.verify_with_max_iterations(password, content, DEFAULT_MAX_ITERATIONS)
+    }
+
+    /// Verify the MAC of the `content` of an `AuthenticatedSafe`, failing with
+    /// [`Error::IterationsExceeded`] if the iteration count exceeds `max_iterations`.
+    pub fn verify_with_max_iterations(
+        &self,
+        password: &str,
+        content: &[u8],
+        max_iterations: u32,
+    ) -> Result<()> {
+        if self.iterations > max_iterations {
+            return Err(Error::IterationsExceeded(self.iterations));
+        }
+
+        let salt = self.mac_salt.as_bytes();
+        let mac = match self.mac.algorithm.oid {
+            ID_SHA_1 => Self::hmac::<Sha1, Hmac<Sha1>>(password, salt, self.iterations)?
+                .chain_update(content)
+                .verify_slice(self.mac.digest.as_bytes()),
+            ID_SHA_256 => Self::hmac::<Sha256, Hmac<Sha256>>(password, salt, self.iterations)?
+                .chain_update(content)
+                .verify_slice(self.mac.digest.as_bytes()),
+            oid => return Err(Error::UnsupportedAlgorithm(oid)),
+        };
+
+        mac.map_err(|_| Error::MacVerification)
+    }
+
+    /// Initialize the HMAC `M` with the key derived from `password` using the digest `D`.
+    fn hmac<D, M>(password: &str, salt: &[u8], iterations: u32) -> Result<M>
+    where
+        D: Digest + BlockSizeUser,
+        M: Mac + KeyInit,
+    {
+        let key_len = <D as Digest>::output_size();
+        let key = derive_key::<D>(password, salt, Pkcs12KeyType::Mac, iterations, key_len)?;
+        <M as Mac>::new_from_slice(&key).map_err(|_| Error::MacVerification)
+    }
+}
+
+/// Encode a PKCS#8 private key and its certificate chain as a password-protected PFX.
+///
+/// The private key is stored in a `pkcs8ShroudedKeyBag` encrypted with PBES2 using
+/// PBKDF2-SHA256 and AES-256-CBC, and the certificates are stored in unencrypted `certBag`s.
+/// The UTF-8 encoded `password` is used for encryption, and its `BMPString` encoding for
+/// the HMAC-SHA256 integrity protection of the PFX.
+pub fn encode_pfx(
+    mut rng: impl CryptoRng + RngCore,
+    private_key: &SecretDocument,
+    certificates: &[Certificate],
+    password: &str,
+) -> Result<Vec<u8>> {
+    let mut auth_safe = AuthenticatedSafe::new();
+
+    if !certificates.is_empty() {
+        let cert_bags = certificates
+            .iter()
+            .map(|cert| {
+                let cert_bag = CertBag {
+                    cert_id: PKCS_12_X509_CERT_OID,
+                    cert_value: Any::encode_from(&OctetString::new(cert.to_der()?)?)?,
+                };
+
+                Ok(SafeBag {
+                    bag_id: PKCS_12_CERT_BAG_OID,
+                    bag_value: Any::encode_from(&cert_bag)?,
+                    bag_attributes: None,
+                })
+            })
+            .collect::<der::Result<SafeContents>>()?;
+
+        auth_safe.push(data_content_info(&cert_bags.to_der()?)?);
+    }
+
+    let mut salt = [0u8; SALT_LEN];
+    rng.fill_bytes(&mut salt);
+
+    let mut iv = [0u8; SALT_LEN];
+    rng.fill_bytes(&mut iv);
+
+    let params = pbes2::Parameters::pbkdf2_sha256_aes256cbc(DEFAULT_ITERATIONS, &salt, &iv)?;
+    let encrypted_key =
+        PrivateKeyInfo::try_from(private_key.as_bytes())?.encrypt_with_params(params, password)?;
+
+    let key_bags: SafeContents = alloc::vec![SafeBag {
+        bag_id: PKCS_12_PKCS8_KEY_BAG_OID,
+        bag_value: Any::from_der(encrypted_key.as_bytes())?,
+        bag_attributes: None,
+    }];
+    auth_safe.push(data_content_info(&key_bags.to_der()?)?);
+
+    let content = auth_safe.to_der()?;
+
+    let mut mac_salt = [0u8; SALT_LEN];
+    rng.fill_bytes(&mut mac_salt);
+
+    let pfx = Pfx {
+        version: Version::V3,
+        auth_safe: data_content_info(&content)?,
+        mac_data: Some(MacData::new(
+            password,
+            &mac_salt,
+            DEFAULT_ITERATIONS,
+            &content,
+        )?),
+    };
+
+    Ok(pfx.to_der()?)
+}
+
+/// Decode a password-protected PFX, returning the PKCS#8 private key and the certificates.
+///
+/// The MAC of the PFX must be present and is verified before decrypting its contents. Only
+/// HMAC-SHA256 or HMAC-SHA1 MACs and PBES2-encrypted contents are supported, as produced by
+/// OpenSSL 3. If the PFX holds multiple private keys the first one is returned.
+///
+/// The iteration count of the MAC must not exceed [`DEFAULT_MAX_ITERATIONS`]: use
+/// [`decode_pfx_with_max_iterations`] to choose another limit.
+pub fn decode_pfx(pfx: &[u8], password: &str) -> Result<(SecretDocument, Vec<Certificate>)> {
+    decode_pfx_with_max_iterations(pfx, password, DEFAULT_MAX_ITERATIONS)
+}
+
+/// Decode a password-protected PFX like [`decode_pfx`], failing with
+/// [`Error::IterationsExceeded`] if the iteration count of its MAC exceeds `max_iterations`.
+pub fn decode_pfx_with_max_iterations(
+    pfx: &[u8],
+    password: &str,
+    max_iterations: u32,
+) -> Result<(SecretDocument, Vec<Certificate>)> {
+    let pfx = Pfx::from_der(pfx)?;
+
+    let content = data_content(&pfx.auth_safe)?;
+    pfx.mac_data
+        .as_ref()
+        .ok_or(Error::MacVerification)?
+        .verify_with_max_iterations(password, &content, max_iterations)?;
+
+    let mut private_key = None;
+    let mut certificates = Vec::new();
+
+    for content_info in AuthenticatedSafe::from_der(&content)? {
+        let safe_contents = match content_info.content_type {
+            ID_DATA => data_content(&content_info)?,
+            ID_ENCRYPTED_DATA => encrypted_data_content(&content_info, password)?,
+            other => return Err(Error::UnsupportedAlgorithm(other)),
+        };
+
+        for bag in SafeContents::from_der(&safe_contents)? {
+            match bag.bag_id {
+                PKCS_12_PKCS8_KEY_BAG_OID if private_key.is_none() => {
+                    let encrypted = bag.bag_value.decode_as::<EncryptedPrivateKeyInfo<'_>>()?;
+                    private_key = Some(encrypted.decrypt(password)?);
+                }
+                PKCS_12_KEY_BAG_OID if private_key.is_none() => {
+                    let key = bag.bag_value.to_der()?;
+                    PrivateKeyInfo::try_from(key.as_slice())?;
+                    private_key = Some(SecretDocument::try_from(key)?);
+                }
+                PKCS_12_CERT_BAG_OID => {
+                    let cert_bag = bag.bag_value.decode_as::<CertBag>()?;
+                    if cert_bag.cert_id == PKCS_12_X509_CERT_OID {
+                        let cert = cert_bag.cert_value.decode_as::<OctetString>()?;
+                        certificates.push(Certificate::from_der(cert.as_bytes())?);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    let private_key = private_key.ok_or(Error::MissingPrivateKey)?;
+    Ok((private_key, certificates))
+}
+
+/// Wrap `content` in a `ContentInfo` of type `id-data`.
+fn data_content_info(content: &[u8]) -> Result<ContentInfo> {
+    Ok(ContentInfo {
+        content_type: ID_DATA,
+        content: Any::encode_from(&OctetString::new(content)?)?,
+    })
+}
+
+/// Get the content of a `ContentInfo` of type `id-data`.
+fn data_content(content_info: &ContentInfo) -> Result<Vec<u8>> {
+    if content_info.content_type != ID_DATA {
+        return Err(Error::UnsupportedAlgorithm(content_info.content_type));
+    }
+
+    Ok(content_info
+        .content
+        .decode_as::<OctetString>()?
+        .into_bytes())
+}
+
+/// Decrypt the content of a password-encrypted `ContentInfo` of type `id-encryptedData`.
+fn encrypted_data_content(content_info: &ContentInfo, password: &str) -> Result<Vec<u8>> {
+    let encrypted_data = content_info.content.decode_as::<EncryptedData>()?;
+    let enc_content_info = &encrypted_data.enc_content_info;
+
+    let alg = enc_content_info.content_enc_alg.to_der()?;
+    let scheme = EncryptionScheme::from_der(&alg)?;
+
+    let ciphertext = enc_content_info
+        .encrypted_content
+        .as_ref()
+        .ok_or(Error::UnsupportedAlgorithm(enc_content_info.content_type))?;
+
+    Ok(scheme.decrypt(password, ciphertext.as_bytes())?)
+}
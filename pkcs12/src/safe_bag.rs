@@ -0,0 +1,77 @@
+//! SafeBag-related types
+
+use alloc::vec::Vec;
+use const_oid::ObjectIdentifier;
+use der::{Any, Sequence};
+use x509_cert::attr::{
+    pkcs9::{FriendlyName, LocalKeyId, Pkcs9Attribute},
+    Attributes,
+};
+
+/// The `SafeContents` type is defined in [RFC 7292 Section 4.2].
+///
+/// ```text
+/// SafeContents ::= SEQUENCE OF SafeBag
+/// ```
+///
+/// [RFC 7292 Section 4.2]: https://www.rfc-editor.org/rfc/rfc7292#section-4.2
+pub type SafeContents = Vec<SafeBag>;
+
+/// The `SafeBag` type is defined in [RFC 7292 Section 4.2].
+///
+/// ```text
+/// SafeBag ::= SEQUENCE {
+///     bagId          BAG-TYPE.&id ({PKCS12BagSet}),
+///     bagValue       [0] EXPLICIT BAG-TYPE.&Type({PKCS12BagSet}{@bagId}),
+///     bagAttributes  SET OF PKCS12Attribute OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 7292 Section 4.2]: https://www.rfc-editor.org/rfc/rfc7292#section-4.2
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct SafeBag {
+    pub bag_id: ObjectIdentifier,
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT")]
+    pub bag_value: Any,
+    pub bag_attributes: Option<Attributes>,
+}
+
+impl SafeBag {
+    /// Get the `friendlyName` bag attribute, if present.
+    pub fn friendly_name(&self) -> der::Result<Option<FriendlyName>> {
+        self.bag_attribute()
+    }
+
+    /// Get the `localKeyId` bag attribute, if present.
+    ///
+    /// The local key identifier matches a private key with its certificate within a PFX.
+    pub fn local_key_id(&self) -> der::Result<Option<LocalKeyId>> {
+        self.bag_attribute()
+    }
+
+    fn bag_attribute<T: Pkcs9Attribute>(&self) -> der::Result<Option<T>> {
+        match &self.bag_attributes {
+            Some(attributes) => T::from_attributes(attributes),
+            None => Ok(None),
+        }
+    }
+}
+
+/// The `CertBag` type is defined in [RFC 7292 Section 4.2.3].
+///
+/// ```text
+/// CertBag ::= SEQUENCE {
+///     certId      BAG-TYPE.&id   ({CertTypes}),
+///     certValue   [0] EXPLICIT BAG-TYPE.&Type ({CertTypes}{@certId})
+/// }
+/// ```
+///
+/// [RFC 7292 Section 4.2.3]: https://www.rfc-editor.org/rfc/rfc7292#section-4.2.3
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct CertBag {
+    pub cert_id: ObjectIdentifier,
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT")]
+    pub cert_value: Any,
+}
@@ -0,0 +1,57 @@
+//! PKCS#12 KDF tests
+//!
+//! Test vectors generated with `openssl kdf PKCS12KDF`, which takes the password
+//! pre-encoded as a NUL-terminated `BMPString`.
+
+use hex_literal::hex;
+use pkcs12::kdf::{derive_key, Pkcs12KeyType};
+use sha2::Sha256;
+
+#[test]
+fn sha256_mac_key() {
+    let key = derive_key::<Sha256>(
+        "hunter42",
+        &hex!("0102030405060708"),
+        Pkcs12KeyType::Mac,
+        2048,
+        32,
+    )
+    .unwrap();
+
+    assert_eq!(
+        key.as_slice(),
+        hex!("BBEB263BEA9C890E912EC3C573A5C879157F608A72095BC66E99DB10D81A3B28")
+    );
+}
+
+#[test]
+fn sha256_multi_block_key() {
+    let key = derive_key::<Sha256>(
+        "ge@äheim",
+        &hex!("0102030405060708"),
+        Pkcs12KeyType::EncryptionKey,
+        100,
+        80,
+    )
+    .unwrap();
+
+    assert_eq!(
+        key.as_slice(),
+        hex!(
+            "FAE4D4957A3CC781E1180B9D4FB79C1E0C8579B746A3177E5B0768A3118BF863"
+            "556535829F36CDEF612A5E881490FE95F51C779470F21F6CC171D251121749C1"
+            "8B8A2B290642F47FCF9C8A8E0EB492A4"
+        )
+    );
+}
+
+#[test]
+fn sha256_empty_password() {
+    let key =
+        derive_key::<Sha256>("", &hex!("0102030405060708"), Pkcs12KeyType::Iv, 1, 20).unwrap();
+
+    assert_eq!(
+        key.as_slice(),
+        hex!("B0B50B88C528534658B323AE28C4E54366F76625")
+    );
+}
@@ -0,0 +1,119 @@
+//! PFX conversion tests
+
+use der::{Decode, Encode};
+use pkcs12::pfx::Pfx;
+use pkcs12::{decode_pfx, decode_pfx_with_max_iterations, encode_pfx, Error};
+use pkcs8::rand_core::OsRng;
+use x509_cert::Certificate;
+
+/// PFX created with:
+/// `openssl pkcs12 -export -inkey key.pem -in cert.pem -passout pass:hunter42 -out example.pfx`
+const PFX_EXAMPLE: &[u8] = include_bytes!("examples/example.pfx");
+
+/// PFX created with:
+/// `openssl pkcs12 -export -inkey key.pem -in cert.pem -macalg sha1 -passout pass:hunter42 -out example_sha1.pfx`
+const PFX_SHA1_EXAMPLE: &[u8] = include_bytes!("examples/example_sha1.pfx");
+const KEY_EXAMPLE: &[u8] = include_bytes!("examples/key.der");
+const CERT_EXAMPLE: &[u8] = include_bytes!("examples/cert.der");
+
+const PASSWORD: &str = "hunter42"; // Bad password; don't actually use outside tests!
+
+#[test]
+fn decode_openssl_pfx() {
+    let (key, certs) = decode_pfx(PFX_EXAMPLE, PASSWORD).unwrap();
+    assert_eq!(key.as_bytes(), KEY_EXAMPLE);
+    assert_eq!(certs, [Certificate::from_der(CERT_EXAMPLE).unwrap()]);
+}
+
+#[test]
+fn decode_pfx_wrong_password() {
+    let err = decode_pfx(PFX_EXAMPLE, "hunter43").unwrap_err();
+    assert_eq!(err, Error::MacVerification);
+}
+
+#[test]
+fn decode_openssl_pfx_sha1_mac() {
+    let (key, certs) = decode_pfx(PFX_SHA1_EXAMPLE, PASSWORD).unwrap();
+    assert_eq!(key.as_bytes(), KEY_EXAMPLE);
+    assert_eq!(certs, [Certificate::from_der(CERT_EXAMPLE).unwrap()]);
+
+    let err = decode_pfx(PFX_SHA1_EXAMPLE, "hunter43").unwrap_err();
+    assert_eq!(err, Error::MacVerification);
+}
+
+#[test]
+fn decode_pfx_max_iterations() {
+    let err = decode_pfx_with_max_iterations(PFX_EXAMPLE, PASSWORD, 2047).unwrap_err();
+    assert_eq!(err, Error::IterationsExceeded(2048));
+    decode_pfx_with_max_iterations(PFX_EXAMPLE, PASSWORD, 2048).unwrap();
+
+    // The iteration count is checked before deriving the MAC key
+    let mut pfx = Pfx::from_der(PFX_EXAMPLE).unwrap();
+    pfx.mac_data.as_mut().unwrap().iterations = u32::MAX;
+    let err = decode_pfx(&pfx.to_der().unwrap(), PASSWORD).unwrap_err();
+    assert_eq!(err, Error::IterationsExceeded(u32::MAX));
+}
+
+#[test]
+fn pfx_reencode() {
+    let pfx = Pfx::from_der(PFX_EXAMPLE).unwrap();
+    assert_eq!(pfx.mac_data.as_ref().unwrap().iterations, 2048);
+    assert_eq!(pfx.to_der().unwrap(), PFX_EXAMPLE);
+}
+
+#[test]
+fn encode_decode_round_trip() {
+    let key = der::SecretDocument::try_from(KEY_EXAMPLE).unwrap();
+    let cert = Certificate::from_der(CERT_EXAMPLE).unwrap();
+
+    let pfx = encode_pfx(OsRng, &key, core::slice::from_ref(&cert), PASSWORD).unwrap();
+    let (decoded_key, certs) = decode_pfx(&pfx, PASSWORD).unwrap();
+    assert_eq!(decoded_key.as_bytes(), KEY_EXAMPLE);
+    assert_eq!(certs, [cert]);
+
+    assert_eq!(decode_pfx(&pfx, "").unwrap_err(), Error::MacVerification);
+}
+
+#[test]
+fn encode_key_only() {
+    let key = der::SecretDocument::try_from(KEY_EXAMPLE).unwrap();
+
+    let pfx = encode_pfx(OsRng, &key, &[], PASSWORD).unwrap();
+    let (decoded_key, certs) = decode_pfx(&pfx, PASSWORD).unwrap();
+    assert_eq!(decoded_key.as_bytes(), KEY_EXAMPLE);
+    assert!(certs.is_empty());
+}
+
+#[test]
+fn safe_bag_attributes() {
+    use der::asn1::{BmpString, OctetString};
+    use pkcs12::safe_bag::SafeBag;
+    use x509_cert::attr::{
+        pkcs9::{FriendlyName, LocalKeyId, Pkcs9Attribute},
+        Attributes,
+    };
+
+    let friendly_name = FriendlyName(BmpString::from_utf8("my key").unwrap());
+    let local_key_id = LocalKeyId(OctetString::new([1, 2, 3, 4]).unwrap());
+    let bag = SafeBag {
+        bag_id: pkcs12::PKCS_12_KEY_BAG_OID,
+        bag_value: der::Any::from_der(KEY_EXAMPLE).unwrap(),
+        bag_attributes: Some(
+            Attributes::try_from([
+                friendly_name.to_attribute().unwrap(),
+                local_key_id.to_attribute().unwrap(),
+            ])
+            .unwrap(),
+        ),
+    };
+
+    let bag = SafeBag::from_der(&bag.to_der().unwrap()).unwrap();
+    assert_eq!(bag.friendly_name().unwrap(), Some(friendly_name));
+    assert_eq!(bag.local_key_id().unwrap(), Some(local_key_id));
+
+    let bag = SafeBag {
+        bag_attributes: None,
+        ..bag
+    };
+    assert_eq!(bag.friendly_name().unwrap(), None);
+}
@@ -138,3 +138,15 @@ pub trait EncodePrivateKey {
         Ok(doc.write_pem_file(path, PrivateKeyInfo::PEM_LABEL, line_ending)?)
     }
 }
+
+/// Serialize a [`SecretDocument`] which contains a PKCS#8 private key.
+///
+/// This permits converting between unencrypted and encrypted PEM/DER
+/// encodings of a private key without parsing the key itself.
+#[cfg(feature = "alloc")]
+impl EncodePrivateKey for SecretDocument {
+    fn to_pkcs8_der(&self) -> Result<SecretDocument> {
+        PrivateKeyInfo::try_from(self.as_bytes())?;
+        Ok(self.clone())
+    }
+}
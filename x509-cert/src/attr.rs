@@ -1,8 +1,11 @@
 //! Attribute-related definitions as defined in X.501 (and updated by RFC 5280).
 
+pub mod pkcs9;
+
 use alloc::vec::Vec;
 use const_oid::db::{
-    rfc4519::{COUNTRY_NAME, DOMAIN_COMPONENT, SERIAL_NUMBER},
+    rfc3280::EMAIL_ADDRESS,
+    rfc4519::{COUNTRY_NAME, DN_QUALIFIER, DOMAIN_COMPONENT, SERIAL_NUMBER},
     Database, DB,
 };
 use core::{
@@ -178,7 +181,11 @@ impl AttributeTypeAndValue {
     }
 
     /// Parses the string value in the `NAME=STRING` format.
-    fn from_delimited_str(oid: ObjectIdentifier, val: &str) -> Result<Self, Error> {
+    fn from_delimited_str(
+        oid: ObjectIdentifier,
+        val: &str,
+        encoding: StringEncoding,
+    ) -> Result<Self, Error> {
         // Undo escaping.
         let mut parser = Parser::new();
         for c in val.bytes() {
@@ -186,18 +193,46 @@ impl AttributeTypeAndValue {
         }
 
         let tag = match oid {
-            COUNTRY_NAME => Tag::PrintableString,
-            DOMAIN_COMPONENT => Tag::Ia5String,
             // Serial numbers are formatted as Printable String as per RFC 5280 Appendix A.1:
             // https://datatracker.ietf.org/doc/html/rfc5280#appendix-A.1
-            SERIAL_NUMBER => Tag::PrintableString,
+            COUNTRY_NAME | SERIAL_NUMBER => Tag::PrintableString,
+            DOMAIN_COMPONENT => Tag::Ia5String,
+            DN_QUALIFIER if encoding == StringEncoding::Standard => Tag::PrintableString,
+            EMAIL_ADDRESS if encoding == StringEncoding::Standard => Tag::Ia5String,
             _ => Tag::Utf8String,
         };
 
-        Ok(Self {
-            oid,
-            value: Any::new(tag, parser.as_bytes())?,
-        })
+        let value = match tag {
+            Tag::PrintableString => PrintableStringRef::new(parser.as_bytes())?.into(),
+            Tag::Ia5String => Ia5StringRef::new(parser.as_bytes())?.into(),
+            _ => Any::new(tag, parser.as_bytes())?,
+        };
+
+        Ok(Self { oid, value })
+    }
+
+    /// Parses an [`AttributeTypeAndValue`] string, encoding the value with the given
+    /// [`StringEncoding`].
+    ///
+    /// This function follows the rules in [RFC 4514].
+    ///
+    /// [RFC 4514]: https://datatracker.ietf.org/doc/html/rfc4514
+    pub fn from_str_with(s: &str, encoding: StringEncoding) -> Result<Self, Error> {
+        let idx = s.find('=').ok_or_else(|| Error::from(ErrorKind::Failed))?;
+        let (key, val) = s.split_at(idx);
+        let val = &val[1..];
+
+        // Either decode or lookup the OID for the given key.
+        let oid = match DB.by_name(key) {
+            Some(oid) => *oid,
+            None => ObjectIdentifier::new(key)?,
+        };
+
+        // If the value is hex-encoded DER...
+        match val.strip_prefix('#') {
+            Some(val) => Self::from_hex(oid, val),
+            None => Self::from_delimited_str(oid, val, encoding),
+        }
     }
 
     /// Converts an AttributeTypeAndValue string into an encoded AttributeTypeAndValue
@@ -223,24 +258,31 @@ impl FromStr for AttributeTypeAndValue {
     type Err = Error;
 
     fn from_str(s: &str) -> der::Result<Self> {
-        let idx = s.find('=').ok_or_else(|| Error::from(ErrorKind::Failed))?;
-        let (key, val) = s.split_at(idx);
-        let val = &val[1..];
-
-        // Either decode or lookup the OID for the given key.
-        let oid = match DB.by_name(key) {
-            Some(oid) => *oid,
-            None => ObjectIdentifier::new(key)?,
-        };
-
-        // If the value is hex-encoded DER...
-        match val.strip_prefix('#') {
-            Some(val) => Self::from_hex(oid, val),
-            None => Self::from_delimited_str(oid, val),
-        }
+        Self::from_str_with(s, StringEncoding::default())
     }
 }
 
+/// String types used to encode attribute values parsed from their string representation.
+///
+/// Some CAs and relying parties reject names whose attribute values don't use the string
+/// type of the attribute's ASN.1 definition, while some legacy systems expect every value
+/// to be a `UTF8String`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum StringEncoding {
+    /// Use the string type of the attribute's definition:
+    ///
+    /// - `PrintableString` for `countryName`, `serialNumber` and `dnQualifier`,
+    /// - `IA5String` for `domainComponent` and `emailAddress`,
+    /// - `UTF8String` for other attributes, whose values are `DirectoryString`s.
+    #[default]
+    Standard,
+
+    /// Use `UTF8String` for all values except those of `countryName` and `serialNumber`,
+    /// which are always `PrintableString`s, and `domainComponent`, which is always an
+    /// `IA5String`.
+    Utf8,
+}
+
 /// Serializes the structure according to the rules in [RFC 4514].
 ///
 /// [RFC 4514]: https://datatracker.ietf.org/doc/html/rfc4514
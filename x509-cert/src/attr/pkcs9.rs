@@ -0,0 +1,384 @@
+//! PKCS#9 attributes as defined in [RFC 2985].
+//!
+//! These attributes are shared by PKCS#10 certification requests, CMS signed attributes and
+//! PKCS#12 bag attributes. Each value type carries the OID of its attribute and implements
+//! [`Pkcs9Attribute`] to convert to and from [`Attribute`]s.
+//!
+//! [RFC 2985]: https://www.rfc-editor.org/rfc/rfc2985
+
+use crate::{
+    attr::{Attribute, Attributes},
+    ext::pkix::name::DirectoryString,
+    request::ExtensionReq,
+    time::Time,
+};
+
+use alloc::string::String;
+use const_oid::{
+    db::{
+        rfc3280::EMAIL_ADDRESS,
+        rfc5911::{ID_CONTENT_TYPE, ID_MESSAGE_DIGEST, ID_SIGNING_TIME},
+    },
+    AssociatedOid, ObjectIdentifier,
+};
+use der::{
+    asn1::{
+        Any, BmpString, Ia5String, OctetString, PrintableString, SetOfVec, TeletexString,
+        UniversalString,
+    },
+    Choice, Decode, DecodeOwned, Encode, Tag, ValueOrd,
+};
+
+// The following OIDs are missing from `const_oid::db`.
+
+/// `pkcs-9-at-unstructuredName` as defined in [RFC 2985 Section 5.2.2].
+///
+/// [RFC 2985 Section 5.2.2]: https://www.rfc-editor.org/rfc/rfc2985#section-5.2.2
+pub const PKCS_9_AT_UNSTRUCTURED_NAME: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.2");
+
+/// `pkcs-9-at-challengePassword` as defined in [RFC 2985 Section 5.4.1].
+///
+/// [RFC 2985 Section 5.4.1]: https://www.rfc-editor.org/rfc/rfc2985#section-5.4.1
+pub const PKCS_9_AT_CHALLENGE_PASSWORD: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.7");
+
+/// `pkcs-9-at-friendlyName` as defined in [RFC 2985 Section 5.5.1].
+///
+/// [RFC 2985 Section 5.5.1]: https://www.rfc-editor.org/rfc/rfc2985#section-5.5.1
+pub const PKCS_9_AT_FRIENDLY_NAME: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.20");
+
+/// `pkcs-9-at-localKeyId` as defined in [RFC 2985 Section 5.5.2].
+///
+/// [RFC 2985 Section 5.5.2]: https://www.rfc-editor.org/rfc/rfc2985#section-5.5.2
+pub const PKCS_9_AT_LOCAL_KEY_ID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.21");
+
+/// Value of a PKCS#9 attribute.
+///
+/// All the attributes supported by this module are single-valued, and a set of attributes
+/// must not include multiple instances of them.
+pub trait Pkcs9Attribute: AssociatedOid + Encode + DecodeOwned {
+    /// Create an attribute holding this value.
+    fn to_attribute(&self) -> der::Result<Attribute> {
+        Ok(Attribute {
+            oid: Self::OID,
+            values: SetOfVec::try_from([Any::from_der(&self.to_der()?)?])?,
+        })
+    }
+
+    /// Get the value of the attribute of this type in `attributes`.
+    ///
+    /// Returns `Ok(None)` if the attribute is absent, and an error if it occurs multiple
+    /// times or doesn't hold exactly one value.
+    fn from_attributes(attributes: &Attributes) -> der::Result<Option<Self>> {
+        let mut matching = attributes.iter().filter(|attr| attr.oid == Self::OID);
+
+        let attr = match matching.next() {
+            Some(attr) => attr,
+            None => return Ok(None),
+        };
+
+        match attr.values.get(0) {
+            Some(value) if matching.next().is_none() && attr.values.len() == 1 => {
+                Self::from_der(&value.to_der()?).map(Some)
+            }
+            _ => Err(Tag::Set.value_error()),
+        }
+    }
+}
+
+/// Implements the following traits for a newtype of a `der` `CHOICE`:
+///
+/// - `From` conversions to/from the inner type
+/// - `AsRef` and `AsMut`
+/// - `Decode`, `EncodeValue` and `Tagged`
+/// - `ValueOrd`
+macro_rules! impl_choice_newtype {
+    ($newtype:ty, $inner:ty) => {
+        impl From<$inner> for $newtype {
+            #[inline]
+            fn from(value: $inner) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$newtype> for $inner {
+            #[inline]
+            fn from(value: $newtype) -> Self {
+                value.0
+            }
+        }
+
+        impl AsRef<$inner> for $newtype {
+            #[inline]
+            fn as_ref(&self) -> &$inner {
+                &self.0
+            }
+        }
+
+        impl AsMut<$inner> for $newtype {
+            #[inline]
+            fn as_mut(&mut self) -> &mut $inner {
+                &mut self.0
+            }
+        }
+
+        impl<'a> ::der::Decode<'a> for $newtype {
+            fn decode<R: ::der::Reader<'a>>(reader: &mut R) -> ::der::Result<Self> {
+                Ok(Self(<$inner as ::der::Decode>::decode(reader)?))
+            }
+        }
+
+        impl ::der::EncodeValue for $newtype {
+            fn encode_value(&self, encoder: &mut impl ::der::Writer) -> ::der::Result<()> {
+                self.0.encode_value(encoder)
+            }
+
+            fn value_len(&self) -> ::der::Result<::der::Length> {
+                self.0.value_len()
+            }
+        }
+
+        impl ::der::Tagged for $newtype {
+            fn tag(&self) -> ::der::Tag {
+                self.0.tag()
+            }
+        }
+
+        impl ::der::ValueOrd for $newtype {
+            fn value_cmp(&self, other: &Self) -> ::der::Result<::core::cmp::Ordering> {
+                self.0.value_cmp(&other.0)
+            }
+        }
+    };
+}
+
+/// `PKCS9String` as defined in [RFC 2985 Section 5.2.2].
+///
+/// ```text
+/// PKCS9String ::= CHOICE {
+///     ia5String        IA5String (SIZE(1..pkcs-9-ub-pkcs9String)),
+///     directoryString  DirectoryString {pkcs-9-ub-pkcs9String}
+/// }
+/// ```
+///
+/// The `DirectoryString` alternatives are flattened into this type.
+///
+/// [RFC 2985 Section 5.2.2]: https://www.rfc-editor.org/rfc/rfc2985#section-5.2.2
+#[derive(Clone, Debug, Eq, PartialEq, Choice, ValueOrd)]
+#[allow(missing_docs)]
+pub enum Pkcs9String {
+    #[asn1(type = "IA5String")]
+    Ia5String(Ia5String),
+
+    #[asn1(type = "PrintableString")]
+    PrintableString(PrintableString),
+
+    #[asn1(type = "TeletexString")]
+    TeletexString(TeletexString),
+
+    #[asn1(type = "UTF8String")]
+    Utf8String(String),
+
+    UniversalString(UniversalString),
+
+    BmpString(BmpString),
+}
+
+/// `emailAddress` attribute value as defined in [RFC 2985 Section 5.2.1].
+///
+/// ```text
+/// emailAddress ATTRIBUTE ::= {
+///     WITH SYNTAX IA5String (SIZE(1..pkcs-9-ub-emailAddress))
+///     EQUALITY MATCHING RULE pkcs9CaseIgnoreMatch
+///     ID pkcs-9-at-emailAddress
+/// }
+/// ```
+///
+/// [RFC 2985 Section 5.2.1]: https://www.rfc-editor.org/rfc/rfc2985#section-5.2.1
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmailAddress(pub Ia5String);
+
+impl AssociatedOid for EmailAddress {
+    const OID: ObjectIdentifier = EMAIL_ADDRESS;
+}
+
+impl_newtype!(EmailAddress, Ia5String);
+
+impl Pkcs9Attribute for EmailAddress {}
+
+/// `unstructuredName` attribute value as defined in [RFC 2985 Section 5.2.2].
+///
+/// ```text
+/// unstructuredName ATTRIBUTE ::= {
+///     WITH SYNTAX PKCS9String {pkcs-9-ub-unstructuredName}
+///     EQUALITY MATCHING RULE pkcs9CaseIgnoreMatch
+///     ID pkcs-9-at-unstructuredName
+/// }
+/// ```
+///
+/// [RFC 2985 Section 5.2.2]: https://www.rfc-editor.org/rfc/rfc2985#section-5.2.2
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnstructuredName(pub Pkcs9String);
+
+impl AssociatedOid for UnstructuredName {
+    const OID: ObjectIdentifier = PKCS_9_AT_UNSTRUCTURED_NAME;
+}
+
+impl_choice_newtype!(UnstructuredName, Pkcs9String);
+
+impl Pkcs9Attribute for UnstructuredName {}
+
+/// `contentType` attribute value as defined in [RFC 2985 Section 5.3.1].
+///
+/// ```text
+/// contentType ATTRIBUTE ::= {
+///     WITH SYNTAX ContentType
+///     EQUALITY MATCHING RULE objectIdentifierMatch
+///     SINGLE VALUE TRUE
+///     ID pkcs-9-at-contentType
+/// }
+/// ```
+///
+/// [RFC 2985 Section 5.3.1]: https://www.rfc-editor.org/rfc/rfc2985#section-5.3.1
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ContentType(pub ObjectIdentifier);
+
+impl AssociatedOid for ContentType {
+    const OID: ObjectIdentifier = ID_CONTENT_TYPE;
+}
+
+impl_newtype!(ContentType, ObjectIdentifier);
+
+impl Pkcs9Attribute for ContentType {}
+
+/// `messageDigest` attribute value as defined in [RFC 2985 Section 5.3.2].
+///
+/// ```text
+/// messageDigest ATTRIBUTE ::= {
+///     WITH SYNTAX MessageDigest
+///     EQUALITY MATCHING RULE octetStringMatch
+///     SINGLE VALUE TRUE
+///     ID pkcs-9-at-messageDigest
+/// }
+/// ```
+///
+/// [RFC 2985 Section 5.3.2]: https://www.rfc-editor.org/rfc/rfc2985#section-5.3.2
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MessageDigest(pub OctetString);
+
+impl AssociatedOid for MessageDigest {
+    const OID: ObjectIdentifier = ID_MESSAGE_DIGEST;
+}
+
+impl_newtype!(MessageDigest, OctetString);
+
+impl Pkcs9Attribute for MessageDigest {}
+
+/// `signingTime` attribute value as defined in [RFC 2985 Section 5.3.3].
+///
+/// ```text
+/// signingTime ATTRIBUTE ::= {
+///     WITH SYNTAX SigningTime
+///     EQUALITY MATCHING RULE signingTimeMatch
+///     SINGLE VALUE TRUE
+///     ID pkcs-9-at-signingTime
+/// }
+/// ```
+///
+/// [RFC 2985 Section 5.3.3]: https://www.rfc-editor.org/rfc/rfc2985#section-5.3.3
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SigningTime(pub Time);
+
+impl AssociatedOid for SigningTime {
+    const OID: ObjectIdentifier = ID_SIGNING_TIME;
+}
+
+impl_choice_newtype!(SigningTime, Time);
+
+impl Pkcs9Attribute for SigningTime {}
+
+/// `challengePassword` attribute value as defined in [RFC 2985 Section 5.4.1].
+///
+/// ```text
+/// challengePassword ATTRIBUTE ::= {
+///     WITH SYNTAX DirectoryString {pkcs-9-ub-challengePassword}
+///     EQUALITY MATCHING RULE caseExactMatch
+///     SINGLE VALUE TRUE
+///     ID pkcs-9-at-challengePassword
+/// }
+/// ```
+///
+/// [RFC 2985 Section 5.4.1]: https://www.rfc-editor.org/rfc/rfc2985#section-5.4.1
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChallengePassword(pub DirectoryString);
+
+impl AssociatedOid for ChallengePassword {
+    const OID: ObjectIdentifier = PKCS_9_AT_CHALLENGE_PASSWORD;
+}
+
+impl_choice_newtype!(ChallengePassword, DirectoryString);
+
+impl Pkcs9Attribute for ChallengePassword {}
+
+/// The `extensionRequest` attribute value is [`ExtensionReq`], see
+/// [RFC 2985 Section 5.4.2].
+///
+/// ```text
+/// extensionRequest ATTRIBUTE ::= {
+///     WITH SYNTAX ExtensionRequest
+///     SINGLE VALUE TRUE
+///     ID pkcs-9-at-extensionRequest
+/// }
+/// ```
+///
+/// [RFC 2985 Section 5.4.2]: https://www.rfc-editor.org/rfc/rfc2985#section-5.4.2
+impl Pkcs9Attribute for ExtensionReq {}
+
+/// `friendlyName` attribute value as defined in [RFC 2985 Section 5.5.1].
+///
+/// ```text
+/// friendlyName ATTRIBUTE ::= {
+///     WITH SYNTAX BMPString (SIZE(1..pkcs-9-ub-friendlyName))
+///     EQUALITY MATCHING RULE caseIgnoreMatch
+///     SINGLE VALUE TRUE
+///     ID pkcs-9-at-friendlyName
+/// }
+/// ```
+///
+/// [RFC 2985 Section 5.5.1]: https://www.rfc-editor.org/rfc/rfc2985#section-5.5.1
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FriendlyName(pub BmpString);
+
+impl AssociatedOid for FriendlyName {
+    const OID: ObjectIdentifier = PKCS_9_AT_FRIENDLY_NAME;
+}
+
+impl_newtype!(FriendlyName, BmpString);
+
+impl Pkcs9Attribute for FriendlyName {}
+
+/// `localKeyId` attribute value as defined in [RFC 2985 Section 5.5.2].
+///
+/// ```text
+/// localKeyId ATTRIBUTE ::= {
+///     WITH SYNTAX OCTET STRING
+///     EQUALITY MATCHING RULE octetStringMatch
+///     SINGLE VALUE TRUE
+///     ID pkcs-9-at-localKeyId
+/// }
+/// ```
+///
+/// [RFC 2985 Section 5.5.2]: https://www.rfc-editor.org/rfc/rfc2985#section-5.5.2
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LocalKeyId(pub OctetString);
+
+impl AssociatedOid for LocalKeyId {
+    const OID: ObjectIdentifier = PKCS_9_AT_LOCAL_KEY_ID;
+}
+
+impl_newtype!(LocalKeyId, OctetString);
+
+impl Pkcs9Attribute for LocalKeyId {}
@@ -0,0 +1,249 @@
+//! Attribute certificate types as defined in [RFC 5755].
+//!
+//! [RFC 5755]: https://www.rfc-editor.org/rfc/rfc5755
+
+use alloc::vec::Vec;
+use const_oid::ObjectIdentifier;
+use der::asn1::{BitString, GeneralizedTime};
+use der::{Choice, Enumerated, Sequence, ValueOrd};
+use spki::AlgorithmIdentifierOwned;
+
+use crate::attr::Attribute;
+use crate::ext::pkix::name::GeneralNames;
+use crate::ext::Extensions;
+use crate::serial_number::SerialNumber;
+
+/// Attribute certificate version as defined in [RFC 5755 Section 4.1].
+///
+/// ```text
+/// AttCertVersion ::= INTEGER { v2(1) }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://www.rfc-editor.org/rfc/rfc5755#section-4.1
+#[derive(Clone, Debug, Default, Copy, PartialEq, Eq, PartialOrd, Ord, Enumerated)]
+#[asn1(type = "INTEGER")]
+#[repr(u8)]
+pub enum AttCertVersion {
+    /// Version 2
+    #[default]
+    V2 = 1,
+}
+
+impl ValueOrd for AttCertVersion {
+    fn value_cmp(&self, other: &Self) -> der::Result<core::cmp::Ordering> {
+        (*self as u8).value_cmp(&(*other as u8))
+    }
+}
+
+/// Attribute certificate as defined in [RFC 5755 Section 4.1].
+///
+/// ```text
+/// AttributeCertificate ::= SEQUENCE {
+///     acinfo               AttributeCertificateInfo,
+///     signatureAlgorithm   AlgorithmIdentifier,
+///     signatureValue       BIT STRING
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://www.rfc-editor.org/rfc/rfc5755#section-4.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct AttributeCertificate {
+    pub acinfo: AttributeCertificateInfo,
+    pub signature_algorithm: AlgorithmIdentifierOwned,
+    pub signature_value: BitString,
+}
+
+/// Attribute certificate information as defined in [RFC 5755 Section 4.1].
+///
+/// ```text
+/// AttributeCertificateInfo ::= SEQUENCE {
+///     version                 AttCertVersion, -- version is v2
+///     holder                  Holder,
+///     issuer                  AttCertIssuer,
+///     signature               AlgorithmIdentifier,
+///     serialNumber            CertificateSerialNumber,
+///     attrCertValidityPeriod  AttCertValidityPeriod,
+///     attributes              SEQUENCE OF Attribute,
+///     issuerUniqueID          UniqueIdentifier OPTIONAL,
+///     extensions              Extensions OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://www.rfc-editor.org/rfc/rfc5755#section-4.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct AttributeCertificateInfo {
+    pub version: AttCertVersion,
+    pub holder: Holder,
+    pub issuer: AttCertIssuer,
+    pub signature: AlgorithmIdentifierOwned,
+    pub serial_number: SerialNumber,
+    pub attr_cert_validity_period: AttCertValidityPeriod,
+    pub attributes: Vec<Attribute>,
+    pub issuer_unique_id: Option<BitString>,
+    pub extensions: Option<Extensions>,
+}
+
+/// Holder of an attribute certificate as defined in [RFC 5755 Section 4.1].
+///
+/// ```text
+/// Holder ::= SEQUENCE {
+///     baseCertificateID   [0] IssuerSerial OPTIONAL,
+///         -- the issuer and serial number of
+///         -- the holder's Public Key Certificate
+///     entityName          [1] GeneralNames OPTIONAL,
+///         -- the name of the claimant or role
+///     objectDigestInfo    [2] ObjectDigestInfo OPTIONAL
+///         -- used to directly authenticate the holder,
+///         -- for example, an executable
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://www.rfc-editor.org/rfc/rfc5755#section-4.1
+#[derive(Clone, Debug, Default, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct Holder {
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", optional = "true")]
+    pub base_certificate_id: Option<IssuerSerial>,
+
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    pub entity_name: Option<GeneralNames>,
+
+    #[asn1(context_specific = "2", tag_mode = "IMPLICIT", optional = "true")]
+    pub object_digest_info: Option<ObjectDigestInfo>,
+}
+
+/// Type of the object digested in an [`ObjectDigestInfo`] as defined in
+/// [RFC 5755 Section 4.1].
+///
+/// ```text
+/// digestedObjectType  ENUMERATED {
+///     publicKey            (0),
+///     publicKeyCert        (1),
+///     otherObjectTypes     (2) }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://www.rfc-editor.org/rfc/rfc5755#section-4.1
+#[derive(Clone, Debug, Copy, PartialEq, Eq, PartialOrd, Ord, Enumerated)]
+#[repr(u32)]
+#[allow(missing_docs)]
+pub enum DigestedObjectType {
+    PublicKey = 0,
+    PublicKeyCert = 1,
+    OtherObjectTypes = 2,
+}
+
+impl ValueOrd for DigestedObjectType {
+    fn value_cmp(&self, other: &Self) -> der::Result<core::cmp::Ordering> {
+        (*self as u32).value_cmp(&(*other as u32))
+    }
+}
+
+/// Object digest information as defined in [RFC 5755 Section 4.1].
+///
+/// ```text
+/// ObjectDigestInfo ::= SEQUENCE {
+///     digestedObjectType  ENUMERATED {
+///         publicKey            (0),
+///         publicKeyCert        (1),
+///         otherObjectTypes     (2) },
+///     otherObjectTypeID   OBJECT IDENTIFIER  OPTIONAL,
+///     digestAlgorithm     AlgorithmIdentifier,
+///     objectDigest        BIT STRING
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://www.rfc-editor.org/rfc/rfc5755#section-4.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct ObjectDigestInfo {
+    pub digested_object_type: DigestedObjectType,
+    pub other_object_type_id: Option<ObjectIdentifier>,
+    pub digest_algorithm: AlgorithmIdentifierOwned,
+    pub object_digest: BitString,
+}
+
+/// Issuer of an attribute certificate as defined in [RFC 5755 Section 4.1].
+///
+/// ```text
+/// AttCertIssuer ::= CHOICE {
+///     v1Form   GeneralNames,  -- MUST NOT be used in this
+///                             -- profile
+///     v2Form   [0] V2Form     -- v2 only
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://www.rfc-editor.org/rfc/rfc5755#section-4.1
+#[derive(Clone, Debug, Eq, PartialEq, Choice, ValueOrd)]
+#[allow(missing_docs)]
+#[allow(clippy::large_enum_variant)]
+pub enum AttCertIssuer {
+    V1Form(GeneralNames),
+
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", constructed = "true")]
+    V2Form(V2Form),
+}
+
+/// Issuer of an attribute certificate as defined in [RFC 5755 Section 4.1].
+///
+/// ```text
+/// V2Form ::= SEQUENCE {
+///     issuerName            GeneralNames  OPTIONAL,
+///     baseCertificateID     [0] IssuerSerial  OPTIONAL,
+///     objectDigestInfo      [1] ObjectDigestInfo  OPTIONAL
+///         -- issuerName MUST be present in this profile
+///         -- baseCertificateID and objectDigestInfo MUST
+///         -- NOT be present in this profile
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://www.rfc-editor.org/rfc/rfc5755#section-4.1
+#[derive(Clone, Debug, Default, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct V2Form {
+    pub issuer_name: Option<GeneralNames>,
+
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", optional = "true")]
+    pub base_certificate_id: Option<IssuerSerial>,
+
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    pub object_digest_info: Option<ObjectDigestInfo>,
+}
+
+/// Issuer and serial number of a public key certificate as defined in
+/// [RFC 5755 Section 4.1].
+///
+/// ```text
+/// IssuerSerial  ::=  SEQUENCE {
+///     issuer         GeneralNames,
+///     serial         CertificateSerialNumber,
+///     issuerUID      UniqueIdentifier OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://www.rfc-editor.org/rfc/rfc5755#section-4.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct IssuerSerial {
+    pub issuer: GeneralNames,
+    pub serial: SerialNumber,
+    pub issuer_uid: Option<BitString>,
+}
+
+/// Validity period of an attribute certificate as defined in [RFC 5755 Section 4.1].
+///
+/// ```text
+/// AttCertValidityPeriod  ::= SEQUENCE {
+///     notBeforeTime  GeneralizedTime,
+///     notAfterTime   GeneralizedTime
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://www.rfc-editor.org/rfc/rfc5755#section-4.1
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct AttCertValidityPeriod {
+    pub not_before_time: GeneralizedTime,
+    pub not_after_time: GeneralizedTime,
+}
@@ -10,11 +10,13 @@ use spki::{
 };
 use std::vec::Vec;
 
+use crate::attr::{Attribute, Attributes};
 use crate::{
     certificate::{Certificate, TbsCertificate, Version},
     ext::{
         pkix::{
-            AuthorityKeyIdentifier, BasicConstraints, KeyUsage, KeyUsages, SubjectKeyIdentifier,
+            AuthorityKeyIdentifier, BasicConstraints, ExtendedKeyUsage, KeyUsage, KeyUsages,
+            OcspNoCheck, SubjectKeyIdentifier,
         },
         AsExtension, Extension, Extensions,
     },
@@ -23,7 +25,6 @@ use crate::{
     serial_number::SerialNumber,
     time::Validity,
 };
-use crate::attr::{Attribute, Attributes};
 
 /// Error type
 #[derive(Debug)]
@@ -70,10 +71,15 @@ impl From<signature::Error> for Error {
     }
 }
 
-type Result<T> = core::result::Result<T, Error>;
+/// Result type
+pub type Result<T> = core::result::Result<T, Error>;
 
 /// The type of certificate to build
+///
+/// New profiles, such as [`Profile::OcspResponder`], may be added: matches on this enum
+/// outside of this crate need a wildcard arm.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum Profile {
     /// Build a root CA certificate
     Root,
@@ -104,6 +110,19 @@ pub enum Profile {
         #[cfg(feature = "hazmat")]
         include_subject_key_identifier: bool,
     },
+    /// Build a delegated OCSP responder certificate
+    ///
+    /// As described in [RFC 6960 Section 4.2.2.2], the certificate is issued directly by the
+    /// CA whose certificates the responder provides status for. It holds the
+    /// `id-kp-OCSPSigning` extended key usage and the `id-pkix-ocsp-nocheck` extension, so
+    /// that clients don't check its revocation status.
+    ///
+    /// [RFC 6960 Section 4.2.2.2]: https://www.rfc-editor.org/rfc/rfc6960#section-4.2.2.2
+    OcspResponder {
+        /// issuer   Name,
+        /// represents the name signing the certificate
+        issuer: Name,
+    },
     #[cfg(feature = "hazmat")]
     /// Opt-out of the default extensions
     Manual {
@@ -120,6 +139,7 @@ impl Profile {
             Profile::Root => subject.clone(),
             Profile::SubCA { issuer, .. } => issuer.clone(),
             Profile::Leaf { issuer, .. } => issuer.clone(),
+            Profile::OcspResponder { issuer } => issuer.clone(),
             #[cfg(feature = "hazmat")]
             Profile::Manual { issuer, .. } => issuer.as_ref().unwrap_or(subject).clone(),
         }
@@ -176,7 +196,7 @@ impl Profile {
                 path_len_constraint: *path_len_constraint,
             }
             .to_extension(&tbs.subject, &extensions)?,
-            Profile::Leaf { .. } => BasicConstraints {
+            Profile::Leaf { .. } | Profile::OcspResponder { .. } => BasicConstraints {
                 ca: false,
                 path_len_constraint: None,
             }
@@ -208,14 +228,51 @@ impl Profile {
 
                 extensions.push(KeyUsage(key_usage).to_extension(&tbs.subject, &extensions)?);
             }
+            Profile::OcspResponder { .. } => {
+                extensions.push(
+                    KeyUsage(KeyUsages::DigitalSignature.into())
+                        .to_extension(&tbs.subject, &extensions)?,
+                );
+            }
             #[cfg(feature = "hazmat")]
             Profile::Manual { .. } => unreachable!(),
         }
 
+        // Build OCSP responder extensions
+        if let Profile::OcspResponder { .. } = self {
+            extensions.push(
+                ExtendedKeyUsage::new()
+                    .ocsp_signing()
+                    .to_extension(&tbs.subject, &extensions)?,
+            );
+            extensions.push(OcspNoCheck.to_extension(&tbs.subject, &extensions)?);
+        }
+
         Ok(extensions)
     }
 }
 
+/// Order of the extensions emitted by the builders
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum ExtensionOrder {
+    /// Emit the extensions in the order they were added, starting with the ones set by the
+    /// [`Profile`].
+    #[default]
+    Insertion,
+
+    /// Emit the extensions sorted by OID, comparing arc by arc, regardless of the order they
+    /// were added in. Extensions with the same OID keep their insertion order.
+    Canonical,
+}
+
+impl ExtensionOrder {
+    fn apply(self, extensions: &mut [Extension]) {
+        if self == ExtensionOrder::Canonical {
+            extensions.sort_by(|a, b| a.extn_id.arcs().cmp(b.extn_id.arcs()));
+        }
+    }
+}
+
 /// X509 Certificate builder
 ///
 /// ```
@@ -260,6 +317,7 @@ impl Profile {
 pub struct CertificateBuilder<'s, S> {
     tbs: TbsCertificate,
     extensions: Extensions,
+    extension_order: ExtensionOrder,
     cert_signer: &'s S,
 }
 
@@ -315,10 +373,17 @@ where
         Ok(Self {
             tbs,
             extensions,
+            extension_order: ExtensionOrder::default(),
             cert_signer,
         })
     }
 
+    /// Set the order of the extensions in the certificate, which defaults to
+    /// [`ExtensionOrder::Insertion`].
+    pub fn set_extension_order(&mut self, order: ExtensionOrder) {
+        self.extension_order = order;
+    }
+
     /// Add an extension to this certificate
     pub fn add_extension<E: AsExtension>(&mut self, extension: &E) -> Result<()> {
         let ext = extension.to_extension(&self.tbs.subject, &self.extensions)?;
@@ -326,6 +391,56 @@ where
 
         Ok(())
     }
+
+    /// Copy extensions, such as the ones requested in a certificate request (see
+    /// [`CertReqInfo::requested_extensions`]), to this certificate.
+    ///
+    /// `select` is called for each extension and returns whether it is copied, and with which
+    /// criticality: `Some(extension.critical)` copies it as is, `None` leaves it out. The
+    /// value of copied extensions is kept byte for byte, including for extensions unknown to
+    /// this crate.
+    ///
+    /// Extensions already present in the certificate, such as those added by the
+    /// [`Profile`], take precedence and aren't copied.
+    ///
+    /// ```
+    /// # use x509_cert::{builder::CertificateBuilder, request::CertReq};
+    /// # use const_oid::db::rfc5280::ID_CE_SUBJECT_ALT_NAME;
+    /// # fn copy<S>(builder: &mut CertificateBuilder<'_, S>, csr: &CertReq) -> x509_cert::builder::Result<()>
+    /// # where
+    /// #     S: signature::Keypair + spki::DynSignatureAlgorithmIdentifier,
+    /// #     S::VerifyingKey: spki::EncodePublicKey,
+    /// # {
+    /// // Copy the requested subject alternative names as non-critical
+    /// builder.copy_extensions(&csr.info.requested_extensions()?, |extension| {
+    ///     (extension.extn_id == ID_CE_SUBJECT_ALT_NAME).then_some(false)
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn copy_extensions<F>(&mut self, extensions: &[Extension], mut select: F) -> Result<()>
+    where
+        F: FnMut(&Extension) -> Option<bool>,
+    {
+        for extension in extensions {
+            if self
+                .extensions
+                .iter()
+                .any(|ext| ext.extn_id == extension.extn_id)
+            {
+                continue;
+            }
+
+            if let Some(critical) = select(extension) {
+                self.extensions.push(Extension {
+                    critical,
+                    ..extension.clone()
+                });
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Builder for X509 Certificate Requests
@@ -360,6 +475,7 @@ where
 pub struct RequestBuilder<'s, S> {
     info: CertReqInfo,
     extension_req: ExtensionReq,
+    extension_order: ExtensionOrder,
     req_signer: &'s S,
     attributes_req: Vec<Attribute>,
 }
@@ -388,11 +504,18 @@ where
                 attributes,
             },
             extension_req,
+            extension_order: ExtensionOrder::default(),
             req_signer,
             attributes_req,
         })
     }
 
+    /// Set the order of the extensions in the `extensionRequest` attribute, which defaults to
+    /// [`ExtensionOrder::Insertion`].
+    pub fn set_extension_order(&mut self, order: ExtensionOrder) {
+        self.extension_order = order;
+    }
+
     /// Add an extension to this certificate request
     pub fn add_extension<E: AsExtension>(&mut self, extension: &E) -> Result<()> {
         let ext = extension.to_extension(&self.info.subject, &self.extension_req.0)?;
@@ -429,33 +552,82 @@ pub trait Builder: Sized {
     /// Finalize and return a serialization of the object for signature.
     fn finalize(&mut self) -> der::Result<vec::Vec<u8>>;
 
+    /// Finalize the object and return the serialization to be signed along with the
+    /// builder, so that the signature can be produced outside of this process.
+    ///
+    /// This is meant for signers which do not have access to the private key, such as
+    /// a handle to a key held in an HSM or a KMS: the builder only needs the signer to
+    /// provide its public key and signature algorithm identifier.
+    fn prepare(mut self) -> Result<TbsToSign<Self>> {
+        let tbs = self.finalize()?;
+
+        Ok(TbsToSign { builder: self, tbs })
+    }
+
     /// Run the object through the signer and build it.
-    fn build<Signature>(mut self) -> Result<Self::Output>
+    fn build<Signature>(self) -> Result<Self::Output>
     where
         Self::Signer: Signer<Signature>,
         Signature: SignatureBitStringEncoding,
     {
-        let blob = self.finalize()?;
+        let prepared = self.prepare()?;
 
-        let signature = self.signer().try_sign(&blob)?.to_bitstring()?;
+        let signature = prepared
+            .builder
+            .signer()
+            .try_sign(prepared.tbs())?
+            .to_bitstring()?;
 
-        self.assemble(signature)
+        prepared.attach_signature(signature)
     }
 
     /// Run the object through the signer and build it.
-    fn build_with_rng<Signature>(mut self, rng: &mut impl CryptoRngCore) -> Result<Self::Output>
+    fn build_with_rng<Signature>(self, rng: &mut impl CryptoRngCore) -> Result<Self::Output>
     where
         Self::Signer: RandomizedSigner<Signature>,
         Signature: SignatureBitStringEncoding,
     {
-        let blob = self.finalize()?;
+        let prepared = self.prepare()?;
 
-        let signature = self
+        let signature = prepared
+            .builder
             .signer()
-            .try_sign_with_rng(rng, &blob)?
+            .try_sign_with_rng(rng, prepared.tbs())?
             .to_bitstring()?;
 
-        self.assemble(signature)
+        prepared.attach_signature(signature)
+    }
+}
+
+/// Finalized object awaiting its signature, as returned by [`Builder::prepare`].
+///
+/// The bytes returned by [`TbsToSign::tbs`] are handed to the external signer, and the
+/// resulting signature is attached with [`TbsToSign::attach_signature`].
+pub struct TbsToSign<B> {
+    builder: B,
+    tbs: Vec<u8>,
+}
+
+impl<B> TbsToSign<B>
+where
+    B: Builder,
+{
+    /// DER serialization of the to-be-signed object.
+    pub fn tbs(&self) -> &[u8] {
+        &self.tbs
+    }
+
+    /// Return a reference to the signer of the builder.
+    pub fn signer(&self) -> &B::Signer {
+        self.builder.signer()
+    }
+
+    /// Assemble the final object from a signature computed over [`TbsToSign::tbs`].
+    ///
+    /// The signature is not verified: it is up to the caller to make sure it was
+    /// produced by the key identified by the signer.
+    pub fn attach_signature(self, signature: BitString) -> Result<B::Output> {
+        self.builder.assemble(signature)
     }
 }
 
@@ -473,7 +645,9 @@ where
 
     fn finalize(&mut self) -> der::Result<vec::Vec<u8>> {
         if !self.extensions.is_empty() {
-            self.tbs.extensions = Some(self.extensions.clone());
+            let mut extensions = self.extensions.clone();
+            self.extension_order.apply(&mut extensions);
+            self.tbs.extensions = Some(extensions);
         }
 
         if self.tbs.extensions.is_none() {
@@ -511,7 +685,9 @@ where
     }
 
     fn finalize(&mut self) -> der::Result<vec::Vec<u8>> {
-        self.attributes_req.push(self.extension_req.clone().try_into()?);
+        let mut extension_req = self.extension_req.clone();
+        self.extension_order.apply(&mut extension_req.0);
+        self.attributes_req.push(extension_req.try_into()?);
         self.info.attributes = Attributes::try_from(self.attributes_req.clone())?;
 
         self.info.to_der()
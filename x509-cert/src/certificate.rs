@@ -1,17 +1,23 @@
 //! Certificate types
 
-use crate::{name::Name, serial_number::SerialNumber, time::Validity};
+use crate::{
+    ext::pkix::TlsFeatures, name::Name, public_key::PublicKeySummary, serial_number::SerialNumber,
+    time::Validity,
+};
 use alloc::vec::Vec;
 use const_oid::AssociatedOid;
 use core::{cmp::Ordering, fmt::Debug};
 use der::asn1::BitString;
-use der::{Decode, Enumerated, Error, ErrorKind, Sequence, Tag, ValueOrd};
+use der::{Decode, Enumerated, Error, ErrorKind, Reader, Sequence, SliceReader, Tag, ValueOrd};
 use spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned};
 
 #[cfg(feature = "pem")]
-use der::{
-    pem::{self, PemLabel},
-    DecodePem,
+use {
+    alloc::string::String,
+    der::{
+        pem::{self, LineEnding, PemLabel},
+        DecodePem, EncodePem,
+    },
 };
 
 /// [`Profile`] allows the consumer of this crate to customize the behavior when parsing
@@ -198,6 +204,49 @@ pub struct CertificateInner<P: Profile = Rfc5280> {
     pub signature: BitString,
 }
 
+impl<P: Profile> CertificateInner<P> {
+    /// Decode a DER-encoded certificate, also returning the exact encoding of
+    /// its `tbsCertificate`.
+    ///
+    /// Signatures should be verified over these bytes rather than over a
+    /// re-encoding of [`CertificateInner::tbs_certificate`], which may differ
+    /// from the original for certificates produced by non-conforming encoders.
+    /// The `tbsCertificate` is therefore decoded leniently: BER encodings
+    /// inside of it, such as non-canonical `BOOLEAN`s, are accepted.
+    pub fn from_der_with_tbs(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let mut reader = SliceReader::new(bytes)?;
+
+        let result = reader.sequence(|reader| {
+            let tbs_der = reader.tlv_bytes()?;
+            let cert = Self {
+                tbs_certificate: TbsCertificateInner::from_der(&der::ber_to_der(tbs_der)?)?,
+                signature_algorithm: reader.decode()?,
+                signature: reader.decode()?,
+            };
+
+            Ok((cert, tbs_der))
+        })?;
+
+        reader.finish(result)
+    }
+
+    /// Summarize the algorithm, size and parameters of the subject public key.
+    pub fn public_key_summary(&self) -> Result<PublicKeySummary, Error> {
+        PublicKeySummary::try_from(&self.tbs_certificate.subject_public_key_info)
+    }
+
+    /// Does this certificate require an OCSP response to be stapled in TLS handshakes?
+    ///
+    /// This is the case when a [`TlsFeatures`] extension holds the `status_request` or
+    /// `status_request_v2` features.
+    pub fn is_must_staple(&self) -> Result<bool, Error> {
+        Ok(self
+            .tbs_certificate
+            .get::<TlsFeatures>()?
+            .map_or(false, |(_, features)| features.is_must_staple()))
+    }
+}
+
 #[cfg(feature = "pem")]
 impl<P: Profile> PemLabel for CertificateInner<P> {
     const PEM_LABEL: &'static str = "CERTIFICATE";
@@ -251,7 +300,7 @@ impl<P: Profile> CertificateInner<P> {
             }
         }
 
-        while position < input.len() - 1 {
+        while position < input.len().saturating_sub(1) {
             let rest = &input[position..];
             let end_pos = find_boundary(rest, end_boundary)
                 .ok_or(pem::Error::PostEncapsulationBoundary)?
@@ -267,3 +316,69 @@ impl<P: Profile> CertificateInner<P> {
         Ok(certs)
     }
 }
+
+/// PEM bundle serialization of a [`PkiPath`].
+///
+/// PEM bundles, as used by e.g. TLS servers, contain the certificates of a
+/// chain in the reverse order of a [`PkiPath`]: the leaf certificate comes
+/// first and every subsequent certificate issued the one preceding it.
+#[cfg(feature = "pem")]
+pub trait PemBundle: Sized {
+    /// Parse a leaf-first PEM bundle.
+    ///
+    /// Returns an error if a certificate of the bundle was not issued by the
+    /// certificate following it.
+    fn from_pem_bundle(input: &[u8]) -> Result<Self, Error>;
+
+    /// Serialize as a leaf-first PEM bundle.
+    ///
+    /// When `exclude_root` is set, a self-issued top-most certificate is
+    /// omitted from the bundle.
+    ///
+    /// Returns an error if a certificate of the path was not issued by the
+    /// certificate preceding it.
+    fn to_pem_bundle(&self, line_ending: LineEnding, exclude_root: bool) -> Result<String, Error>;
+}
+
+#[cfg(feature = "pem")]
+impl PemBundle for PkiPath {
+    fn from_pem_bundle(input: &[u8]) -> Result<Self, Error> {
+        let mut path = Certificate::load_pem_chain(input)?;
+        path.reverse();
+        check_path_order(&path)?;
+        Ok(path)
+    }
+
+    fn to_pem_bundle(&self, line_ending: LineEnding, exclude_root: bool) -> Result<String, Error> {
+        check_path_order(self)?;
+
+        let skip = match self.first() {
+            Some(root)
+                if exclude_root && root.tbs_certificate.issuer == root.tbs_certificate.subject =>
+            {
+                1
+            }
+            _ => 0,
+        };
+
+        let mut bundle = String::new();
+
+        for cert in self.iter().skip(skip).rev() {
+            bundle.push_str(&cert.to_pem(line_ending)?);
+        }
+
+        Ok(bundle)
+    }
+}
+
+/// Check that every certificate of `path` was issued by the one preceding it.
+#[cfg(feature = "pem")]
+fn check_path_order(path: &[Certificate]) -> Result<(), Error> {
+    for pair in path.windows(2) {
+        if pair[1].tbs_certificate.issuer != pair[0].tbs_certificate.subject {
+            return Err(Tag::Sequence.value_error());
+        }
+    }
+
+    Ok(())
+}
@@ -4,6 +4,7 @@ use const_oid::AssociatedOid;
 use der::{asn1::OctetString, Sequence, ValueOrd};
 use spki::ObjectIdentifier;
 
+pub mod microsoft;
 pub mod pkix;
 
 /// Extension as defined in [RFC 5280 Section 4.1.2.9].
@@ -0,0 +1,92 @@
+//! Microsoft-specific X.509 Certificate Extensions
+//!
+//! These extensions are emitted by Active Directory Certificate Services (AD CS) and are
+//! described in [MS-WCCE].
+//!
+//! [MS-WCCE]: https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-wcce/
+
+use alloc::vec::Vec;
+
+use const_oid::{AssociatedOid, ObjectIdentifier};
+use der::asn1::BmpString;
+use der::Sequence;
+
+use crate::ext::pkix::certpolicy::PolicyInformation;
+
+/// `szOID_ENROLL_CERTTYPE_EXTENSION` (`1.3.6.1.4.1.311.20.2`)
+pub const MS_ENROLL_CERTTYPE_EXTENSION: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.4.1.311.20.2");
+
+/// `szOID_CERTIFICATE_TEMPLATE` (`1.3.6.1.4.1.311.21.7`)
+pub const MS_CERTIFICATE_TEMPLATE: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.4.1.311.21.7");
+
+/// `szOID_APPLICATION_CERT_POLICIES` (`1.3.6.1.4.1.311.21.10`)
+pub const MS_APPLICATION_CERT_POLICIES: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.4.1.311.21.10");
+
+/// CertificateTemplateName as defined in [MS-WCCE].
+///
+/// This extension identifies a version 1 certificate template by name.
+///
+/// ```text
+/// CertificateTemplateName ::= BMPString
+/// ```
+///
+/// [MS-WCCE]: https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-wcce/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CertificateTemplateName(pub BmpString);
+
+impl AssociatedOid for CertificateTemplateName {
+    const OID: ObjectIdentifier = MS_ENROLL_CERTTYPE_EXTENSION;
+}
+
+impl_newtype!(CertificateTemplateName, BmpString);
+impl_extension!(CertificateTemplateName, critical = false);
+
+/// CertificateTemplate as defined in [MS-WCCE].
+///
+/// This extension identifies a version 2 (or later) certificate template by OID.
+///
+/// ```text
+/// CertificateTemplateOID ::= SEQUENCE {
+///     templateID              OBJECT IDENTIFIER,
+///     templateMajorVersion    INTEGER (0..4294967295),
+///     templateMinorVersion    INTEGER (0..4294967295) OPTIONAL
+/// }
+/// ```
+///
+/// [MS-WCCE]: https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-wcce/
+#[derive(Clone, Debug, PartialEq, Eq, Sequence)]
+#[allow(missing_docs)]
+pub struct CertificateTemplate {
+    pub template_id: ObjectIdentifier,
+    pub template_major_version: u32,
+    pub template_minor_version: Option<u32>,
+}
+
+impl AssociatedOid for CertificateTemplate {
+    const OID: ObjectIdentifier = MS_CERTIFICATE_TEMPLATE;
+}
+
+impl_extension!(CertificateTemplate, critical = false);
+
+/// ApplicationCertPolicies as defined in [MS-WCCE].
+///
+/// Application policies share the syntax of the `CertificatePolicies` extension, with the
+/// policy identifiers holding extended key usage OIDs.
+///
+/// ```text
+/// ApplicationCertPolicies ::= SEQUENCE SIZE (1..MAX) OF PolicyInformation
+/// ```
+///
+/// [MS-WCCE]: https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-wcce/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ApplicationCertPolicies(pub Vec<PolicyInformation>);
+
+impl AssociatedOid for ApplicationCertPolicies {
+    const OID: ObjectIdentifier = MS_APPLICATION_CERT_POLICIES;
+}
+
+impl_newtype!(ApplicationCertPolicies, Vec<PolicyInformation>);
+impl_extension!(ApplicationCertPolicies, critical = false);
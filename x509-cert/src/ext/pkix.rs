@@ -8,7 +8,9 @@ pub mod name;
 mod access;
 mod authkeyid;
 mod keyusage;
+mod ocsp;
 mod policymap;
+mod tlsfeature;
 
 use crate::attr::AttributeTypeAndValue;
 
@@ -22,7 +24,9 @@ pub use crl::{
     IssuingDistributionPoint,
 };
 pub use keyusage::{ExtendedKeyUsage, KeyUsage, KeyUsages, PrivateKeyUsagePeriod};
+pub use ocsp::OcspNoCheck;
 pub use policymap::{PolicyMapping, PolicyMappings};
+pub use tlsfeature::{TlsFeature, TlsFeatures, ID_PE_TLS_FEATURE};
 
 pub use const_oid::db::rfc5280::{
     ID_CE_INHIBIT_ANY_POLICY, ID_CE_ISSUER_ALT_NAME, ID_CE_SUBJECT_ALT_NAME,
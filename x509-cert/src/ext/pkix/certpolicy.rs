@@ -2,10 +2,14 @@
 
 use alloc::{string::String, vec::Vec};
 
-use const_oid::db::rfc5912::ID_CE_CERTIFICATE_POLICIES;
+use core::{cmp::Ordering, fmt};
+
+use const_oid::db::rfc5912::{ID_CE_CERTIFICATE_POLICIES, ID_QT_CPS, ID_QT_UNOTICE};
 use const_oid::AssociatedOid;
-use der::asn1::{GeneralizedTime, Ia5String, ObjectIdentifier, Uint};
-use der::{Any, Choice, Sequence, ValueOrd};
+use der::asn1::{BmpString, Ia5String, ObjectIdentifier, Uint, VisibleString};
+use der::{
+    Any, Choice, DecodeDefinedBy, DerOrd, Encode, Length, Reader, Sequence, Tag, ValueOrd, Writer,
+};
 
 /// CertificatePolicies as defined in [RFC 5280 Section 4.2.1.4].
 ///
@@ -61,7 +65,87 @@ pub struct PolicyInformation {
 #[allow(missing_docs)]
 pub struct PolicyQualifierInfo {
     pub policy_qualifier_id: ObjectIdentifier,
-    pub qualifier: Option<Any>,
+    #[asn1(defined_by = "policy_qualifier_id")]
+    pub qualifier: Option<PolicyQualifier>,
+}
+
+impl PolicyQualifierInfo {
+    /// Create an `id-qt-cps` qualifier pointing to the certification practice
+    /// statement published at `uri`.
+    pub fn cps_uri(uri: &str) -> der::Result<Self> {
+        Ok(Self {
+            policy_qualifier_id: ID_QT_CPS,
+            qualifier: Some(PolicyQualifier::CpsUri(CpsUri::new(uri)?)),
+        })
+    }
+
+    /// Create an `id-qt-unotice` qualifier holding a user notice with the
+    /// given `explicit_text`.
+    pub fn user_notice(explicit_text: &str) -> der::Result<Self> {
+        Ok(Self {
+            policy_qualifier_id: ID_QT_UNOTICE,
+            qualifier: Some(PolicyQualifier::UserNotice(UserNotice {
+                notice_ref: None,
+                explicit_text: Some(DisplayText::new(explicit_text)?),
+            })),
+        })
+    }
+}
+
+/// Qualifier of a [`PolicyQualifierInfo`], whose type is selected by its
+/// `policyQualifierId`.
+///
+/// The qualifiers defined in [RFC 5280 Section 4.2.1.4] are decoded to their
+/// respective types, any other qualifier is retained as [`Any`].
+///
+/// [RFC 5280 Section 4.2.1.4]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.4
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PolicyQualifier {
+    /// `id-qt-cps` qualifier
+    CpsUri(CpsUri),
+
+    /// `id-qt-unotice` qualifier
+    UserNotice(UserNotice),
+
+    /// Qualifier of any other type
+    Other(Any),
+}
+
+impl<'a> DecodeDefinedBy<'a, ObjectIdentifier> for PolicyQualifier {
+    fn decode_defined_by<R: Reader<'a>>(
+        policy_qualifier_id: &ObjectIdentifier,
+        reader: &mut R,
+    ) -> der::Result<Self> {
+        match *policy_qualifier_id {
+            ID_QT_CPS => reader.decode().map(Self::CpsUri),
+            ID_QT_UNOTICE => reader.decode().map(Self::UserNotice),
+            _ => reader.decode().map(Self::Other),
+        }
+    }
+}
+
+impl Encode for PolicyQualifier {
+    fn encoded_len(&self) -> der::Result<Length> {
+        match self {
+            Self::CpsUri(cps_uri) => cps_uri.encoded_len(),
+            Self::UserNotice(user_notice) => user_notice.encoded_len(),
+            Self::Other(any) => any.encoded_len(),
+        }
+    }
+
+    fn encode(&self, writer: &mut impl Writer) -> der::Result<()> {
+        match self {
+            Self::CpsUri(cps_uri) => cps_uri.encode(writer),
+            Self::UserNotice(user_notice) => user_notice.encode(writer),
+            Self::Other(any) => any.encode(writer),
+        }
+    }
+}
+
+impl DerOrd for PolicyQualifier {
+    fn der_cmp(&self, other: &Self) -> der::Result<Ordering> {
+        Ok(self.to_der()?.cmp(&other.to_der()?))
+    }
 }
 
 /// CpsUri as defined in [RFC 5280 Section 4.2.1.4].
@@ -86,7 +170,7 @@ pub type CpsUri = Ia5String;
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct UserNotice {
-    pub notice_ref: Option<GeneralizedTime>,
+    pub notice_ref: Option<NoticeReference>,
     pub explicit_text: Option<DisplayText>,
 }
 
@@ -103,7 +187,7 @@ pub struct UserNotice {
 #[allow(missing_docs)]
 pub struct NoticeReference {
     pub organization: DisplayText,
-    pub notice_numbers: Option<Vec<Uint>>,
+    pub notice_numbers: Vec<Uint>,
 }
 
 /// DisplayText as defined in [RFC 5280 Section 4.2.1.4].
@@ -117,8 +201,6 @@ pub struct NoticeReference {
 /// }
 /// ```
 ///
-/// Only the ia5String and utf8String options are currently supported.
-///
 /// [RFC 5280 Section 4.2.1.4]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.4
 #[derive(Choice, Clone, Debug, Eq, PartialEq)]
 #[allow(missing_docs)]
@@ -126,6 +208,39 @@ pub enum DisplayText {
     #[asn1(type = "IA5String")]
     Ia5String(Ia5String),
 
+    VisibleString(VisibleString),
+
+    BmpString(BmpString),
+
     #[asn1(type = "UTF8String")]
     Utf8String(String),
 }
+
+impl DisplayText {
+    /// Maximum length of a `DisplayText` in characters.
+    pub const MAX_LEN: usize = 200;
+
+    /// Create a `DisplayText` holding `text` as a `UTF8String`, which is the
+    /// encoding conforming CAs SHOULD use.
+    ///
+    /// Returns an error if `text` is empty or longer than [`Self::MAX_LEN`]
+    /// characters.
+    pub fn new(text: &str) -> der::Result<Self> {
+        if text.is_empty() || text.chars().count() > Self::MAX_LEN {
+            return Err(Tag::Utf8String.length_error());
+        }
+
+        Ok(Self::Utf8String(text.into()))
+    }
+}
+
+impl fmt::Display for DisplayText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ia5String(text) => f.write_str(text.as_str()),
+            Self::VisibleString(text) => f.write_str(text.as_str()),
+            Self::BmpString(text) => fmt::Display::fmt(text, f),
+            Self::Utf8String(text) => f.write_str(text),
+        }
+    }
+}
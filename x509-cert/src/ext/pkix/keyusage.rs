@@ -1,7 +1,10 @@
 use alloc::vec::Vec;
+use core::{fmt, ops::BitOr};
 
 use const_oid::db::rfc5280::{
     ANY_EXTENDED_KEY_USAGE, ID_CE_EXT_KEY_USAGE, ID_CE_KEY_USAGE, ID_CE_PRIVATE_KEY_USAGE_PERIOD,
+    ID_KP_CLIENT_AUTH, ID_KP_CODE_SIGNING, ID_KP_EMAIL_PROTECTION, ID_KP_OCSP_SIGNING,
+    ID_KP_SERVER_AUTH, ID_KP_TIME_STAMPING,
 };
 use const_oid::AssociatedOid;
 use der::asn1::{GeneralizedTime, ObjectIdentifier};
@@ -113,6 +116,66 @@ impl KeyUsage {
     }
 }
 
+impl KeyUsages {
+    /// Name of this key usage bit, as in the ASN.1 definition of `KeyUsage`.
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyUsages::DigitalSignature => "digitalSignature",
+            KeyUsages::NonRepudiation => "nonRepudiation",
+            KeyUsages::KeyEncipherment => "keyEncipherment",
+            KeyUsages::DataEncipherment => "dataEncipherment",
+            KeyUsages::KeyAgreement => "keyAgreement",
+            KeyUsages::KeyCertSign => "keyCertSign",
+            KeyUsages::CRLSign => "cRLSign",
+            KeyUsages::EncipherOnly => "encipherOnly",
+            KeyUsages::DecipherOnly => "decipherOnly",
+        }
+    }
+}
+
+impl fmt::Display for KeyUsages {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl From<KeyUsages> for KeyUsage {
+    fn from(usage: KeyUsages) -> Self {
+        Self(usage.into())
+    }
+}
+
+impl BitOr for KeyUsage {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOr<KeyUsages> for KeyUsage {
+    type Output = Self;
+
+    fn bitor(self, rhs: KeyUsages) -> Self {
+        Self(self.0 | rhs)
+    }
+}
+
+/// Formats the asserted bits by name, separated by commas, e.g.
+/// `digitalSignature, keyEncipherment`.
+impl fmt::Display for KeyUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, usage) in self.0.into_iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", usage)?;
+        }
+
+        Ok(())
+    }
+}
+
 /// ExtKeyUsageSyntax as defined in [RFC 5280 Section 4.2.1.12].
 ///
 /// Many extended key usage values include:
@@ -162,6 +225,95 @@ impl crate::ext::AsExtension for ExtendedKeyUsage {
     }
 }
 
+/// Fluent construction of the extension, e.g.
+/// `ExtendedKeyUsage::new().server_auth().client_auth()`.
+impl ExtendedKeyUsage {
+    /// Create an empty list of key purposes.
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Add the given key purpose, unless it is already present.
+    pub fn with(mut self, key_purpose: ObjectIdentifier) -> Self {
+        if !self.contains(&key_purpose) {
+            self.0.push(key_purpose);
+        }
+        self
+    }
+
+    /// Add `anyExtendedKeyUsage`.
+    pub fn any(self) -> Self {
+        self.with(ANY_EXTENDED_KEY_USAGE)
+    }
+
+    /// Add `id-kp-serverAuth`: TLS WWW server authentication.
+    pub fn server_auth(self) -> Self {
+        self.with(ID_KP_SERVER_AUTH)
+    }
+
+    /// Add `id-kp-clientAuth`: TLS WWW client authentication.
+    pub fn client_auth(self) -> Self {
+        self.with(ID_KP_CLIENT_AUTH)
+    }
+
+    /// Add `id-kp-codeSigning`: signing of downloadable executable code.
+    pub fn code_signing(self) -> Self {
+        self.with(ID_KP_CODE_SIGNING)
+    }
+
+    /// Add `id-kp-emailProtection`: email protection.
+    pub fn email_protection(self) -> Self {
+        self.with(ID_KP_EMAIL_PROTECTION)
+    }
+
+    /// Add `id-kp-timeStamping`: binding the hash of an object to a time.
+    pub fn time_stamping(self) -> Self {
+        self.with(ID_KP_TIME_STAMPING)
+    }
+
+    /// Add `id-kp-OCSPSigning`: signing OCSP responses.
+    pub fn ocsp_signing(self) -> Self {
+        self.with(ID_KP_OCSP_SIGNING)
+    }
+
+    /// Whether the given key purpose is present.
+    pub fn contains(&self, key_purpose: &ObjectIdentifier) -> bool {
+        self.0.contains(key_purpose)
+    }
+}
+
+impl Default for ExtendedKeyUsage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats the key purposes by name, separated by commas, e.g.
+/// `serverAuth, clientAuth`, falling back on the dotted notation for unknown
+/// key purposes.
+impl fmt::Display for ExtendedKeyUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, key_purpose) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+
+            match *key_purpose {
+                ANY_EXTENDED_KEY_USAGE => f.write_str("anyExtendedKeyUsage")?,
+                ID_KP_SERVER_AUTH => f.write_str("serverAuth")?,
+                ID_KP_CLIENT_AUTH => f.write_str("clientAuth")?,
+                ID_KP_CODE_SIGNING => f.write_str("codeSigning")?,
+                ID_KP_EMAIL_PROTECTION => f.write_str("emailProtection")?,
+                ID_KP_TIME_STAMPING => f.write_str("timeStamping")?,
+                ID_KP_OCSP_SIGNING => f.write_str("OCSPSigning")?,
+                other => write!(f, "{}", other)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// PrivateKeyUsagePeriod as defined in [RFC 3280 Section 4.2.1.4].
 ///
 /// RFC 5280 states "use of this ISO standard extension is neither deprecated nor recommended for use in the Internet PKI."
@@ -193,6 +345,7 @@ impl_extension!(PrivateKeyUsagePeriod, critical = false);
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::string::ToString;
 
     #[test]
     fn digital_signature_contains_digital_signature() {
@@ -217,4 +370,48 @@ mod tests {
         let key_usage = KeyUsage(None.into());
         assert!(!key_usage.digital_signature());
     }
+
+    #[test]
+    fn key_usage_bitor() {
+        let key_usage = KeyUsage::from(KeyUsages::DigitalSignature) | KeyUsages::KeyEncipherment;
+        assert!(key_usage.digital_signature());
+        assert!(key_usage.key_encipherment());
+        assert!(!key_usage.key_cert_sign());
+        assert_eq!(
+            key_usage | KeyUsage::from(KeyUsages::KeyCertSign),
+            KeyUsage(
+                KeyUsages::DigitalSignature | KeyUsages::KeyEncipherment | KeyUsages::KeyCertSign
+            )
+        );
+    }
+
+    #[test]
+    fn key_usage_display() {
+        let key_usage = KeyUsage(KeyUsages::KeyCertSign | KeyUsages::CRLSign);
+        assert_eq!(key_usage.to_string(), "keyCertSign, cRLSign");
+        assert_eq!(KeyUsage(None.into()).to_string(), "");
+    }
+
+    #[test]
+    fn extended_key_usage_fluent() {
+        let eku = ExtendedKeyUsage::new()
+            .server_auth()
+            .client_auth()
+            .server_auth();
+        assert_eq!(eku.0, [ID_KP_SERVER_AUTH, ID_KP_CLIENT_AUTH]);
+        assert!(eku.contains(&ID_KP_CLIENT_AUTH));
+        assert!(!eku.contains(&ID_KP_CODE_SIGNING));
+    }
+
+    #[test]
+    fn extended_key_usage_display() {
+        let eku = ExtendedKeyUsage::new()
+            .server_auth()
+            .ocsp_signing()
+            .with(ObjectIdentifier::new_unwrap("1.3.6.1.4.1.311.10.3.4"));
+        assert_eq!(
+            eku.to_string(),
+            "serverAuth, OCSPSigning, 1.3.6.1.4.1.311.10.3.4"
+        );
+    }
 }
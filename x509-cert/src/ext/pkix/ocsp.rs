@@ -0,0 +1,53 @@
+use const_oid::{db::rfc6960::ID_PKIX_OCSP_NOCHECK, AssociatedOid, ObjectIdentifier};
+use der::{
+    asn1::Null, DecodeValue, EncodeValue, FixedTag, Header, Length, Reader, Tag, ValueOrd, Writer,
+};
+
+/// OcspNoCheck as defined in [RFC 6960 Section 4.2.2.2.1].
+///
+/// Included in the certificate of a delegated OCSP responder, it indicates that clients can
+/// trust the responder for the lifetime of its certificate, without checking its revocation
+/// status.
+///
+/// ```text
+/// id-pkix-ocsp-nocheck OBJECT IDENTIFIER ::= { id-pkix-ocsp 5 }
+///
+/// OcspNoCheck ::= NULL
+/// ```
+///
+/// [RFC 6960 Section 4.2.2.2.1]: https://www.rfc-editor.org/rfc/rfc6960#section-4.2.2.2.1
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct OcspNoCheck;
+
+impl AssociatedOid for OcspNoCheck {
+    const OID: ObjectIdentifier = ID_PKIX_OCSP_NOCHECK;
+}
+
+impl_extension!(OcspNoCheck, critical = false);
+
+impl FixedTag for OcspNoCheck {
+    const TAG: Tag = Tag::Null;
+}
+
+impl<'a> DecodeValue<'a> for OcspNoCheck {
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        Null::decode_value(reader, header)?;
+        Ok(Self)
+    }
+}
+
+impl EncodeValue for OcspNoCheck {
+    fn value_len(&self) -> der::Result<Length> {
+        Ok(Length::ZERO)
+    }
+
+    fn encode_value(&self, _writer: &mut impl Writer) -> der::Result<()> {
+        Ok(())
+    }
+}
+
+impl ValueOrd for OcspNoCheck {
+    fn value_cmp(&self, _other: &Self) -> der::Result<core::cmp::Ordering> {
+        Ok(core::cmp::Ordering::Equal)
+    }
+}
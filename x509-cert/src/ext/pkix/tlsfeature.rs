@@ -0,0 +1,113 @@
+use alloc::vec::Vec;
+
+use const_oid::{AssociatedOid, ObjectIdentifier};
+use der::{DecodeValue, EncodeValue, FixedTag, Header, Length, Reader, Tag, ValueOrd, Writer};
+
+/// `id-pe-tlsfeature` as defined in [RFC 7633 Section 6].
+///
+/// [RFC 7633 Section 6]: https://www.rfc-editor.org/rfc/rfc7633#section-6
+pub const ID_PE_TLS_FEATURE: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.1.24");
+
+/// TlsFeatures as defined in [RFC 7633 Section 6].
+///
+/// A certificate holding the `status_request` feature is commonly referred to as
+/// "OCSP must-staple": TLS servers using it must staple an OCSP response in the handshake.
+///
+/// ```text
+/// Features ::= SEQUENCE OF INTEGER
+/// ```
+///
+/// [RFC 7633 Section 6]: https://www.rfc-editor.org/rfc/rfc7633#section-6
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TlsFeatures(pub Vec<TlsFeature>);
+
+impl TlsFeatures {
+    /// OCSP must-staple features, i.e. the `status_request` TLS extension.
+    pub fn must_staple() -> Self {
+        Self(alloc::vec![TlsFeature::StatusRequest])
+    }
+
+    /// Does this extension require an OCSP response to be stapled?
+    pub fn is_must_staple(&self) -> bool {
+        self.0.iter().any(|feature| {
+            matches!(
+                feature,
+                TlsFeature::StatusRequest | TlsFeature::StatusRequestV2
+            )
+        })
+    }
+}
+
+impl AssociatedOid for TlsFeatures {
+    const OID: ObjectIdentifier = ID_PE_TLS_FEATURE;
+}
+
+impl_newtype!(TlsFeatures, Vec<TlsFeature>);
+impl_extension!(TlsFeatures, critical = false);
+
+/// TLS extension identified by a [`TlsFeatures`] extension.
+///
+/// The values are those of the [TLS ExtensionType Values] registry.
+///
+/// [TLS ExtensionType Values]: https://www.iana.org/assignments/tls-extensiontype-values
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TlsFeature {
+    /// `status_request` (5) as defined in [RFC 6066 Section 8].
+    ///
+    /// [RFC 6066 Section 8]: https://www.rfc-editor.org/rfc/rfc6066#section-8
+    StatusRequest,
+
+    /// `status_request_v2` (17) as defined in [RFC 6961].
+    ///
+    /// [RFC 6961]: https://www.rfc-editor.org/rfc/rfc6961
+    StatusRequestV2,
+
+    /// Any other TLS extension.
+    Other(u16),
+}
+
+impl From<u16> for TlsFeature {
+    fn from(value: u16) -> Self {
+        match value {
+            5 => Self::StatusRequest,
+            17 => Self::StatusRequestV2,
+            other => Self::Other(other),
+        }
+    }
+}
+
+impl From<TlsFeature> for u16 {
+    fn from(feature: TlsFeature) -> Self {
+        match feature {
+            TlsFeature::StatusRequest => 5,
+            TlsFeature::StatusRequestV2 => 17,
+            TlsFeature::Other(other) => other,
+        }
+    }
+}
+
+impl FixedTag for TlsFeature {
+    const TAG: Tag = Tag::Integer;
+}
+
+impl<'a> DecodeValue<'a> for TlsFeature {
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        u16::decode_value(reader, header).map(Self::from)
+    }
+}
+
+impl EncodeValue for TlsFeature {
+    fn value_len(&self) -> der::Result<Length> {
+        u16::from(*self).value_len()
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        u16::from(*self).encode_value(writer)
+    }
+}
+
+impl ValueOrd for TlsFeature {
+    fn value_cmp(&self, other: &Self) -> der::Result<core::cmp::Ordering> {
+        u16::from(*self).value_cmp(&u16::from(*other))
+    }
+}
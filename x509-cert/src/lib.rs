@@ -25,10 +25,12 @@ mod macros;
 
 pub mod anchor;
 pub mod attr;
+pub mod attr_cert;
 pub mod certificate;
 pub mod crl;
 pub mod ext;
 pub mod name;
+pub mod public_key;
 pub mod request;
 pub mod serial_number;
 pub mod time;
@@ -36,6 +38,18 @@ pub mod time;
 #[cfg(feature = "builder")]
 pub mod builder;
 
+#[cfg(feature = "summary")]
+pub mod summary;
+
+#[cfg(feature = "test-pki")]
+pub mod test_pki;
+
+#[cfg(feature = "verify")]
+pub mod verify;
+
 pub use certificate::{Certificate, PkiPath, TbsCertificate, Version};
+
+#[cfg(feature = "pem")]
+pub use certificate::PemBundle;
 pub use der;
 pub use spki;
@@ -1,6 +1,6 @@
 //! Name-related definitions as defined in X.501 (and updated by RFC 5280).
 
-use crate::attr::AttributeTypeAndValue;
+use crate::attr::{AttributeTypeAndValue, StringEncoding};
 use alloc::vec::Vec;
 use core::{fmt, str::FromStr};
 use der::{asn1::SetOfVec, Encode};
@@ -36,6 +36,20 @@ impl RdnSequence {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Parse an [`RdnSequence`] string, encoding the attribute values with the given
+    /// [`StringEncoding`].
+    ///
+    /// Follows the rules in [RFC 4514].
+    ///
+    /// [RFC 4514]: https://datatracker.ietf.org/doc/html/rfc4514
+    pub fn from_str_with(s: &str, encoding: StringEncoding) -> der::Result<Self> {
+        let mut parts = split(s, b',')
+            .map(|part| RelativeDistinguishedName::from_str_with(part, encoding))
+            .collect::<der::Result<Vec<_>>>()?;
+        parts.reverse();
+        Ok(Self(parts))
+    }
 }
 
 /// Parse an [`RdnSequence`] string.
@@ -47,11 +61,7 @@ impl FromStr for RdnSequence {
     type Err = der::Error;
 
     fn from_str(s: &str) -> der::Result<Self> {
-        let mut parts = split(s, b',')
-            .map(RelativeDistinguishedName::from_str)
-            .collect::<der::Result<Vec<_>>>()?;
-        parts.reverse();
-        Ok(Self(parts))
+        Self::from_str_with(s, StringEncoding::default())
     }
 }
 
@@ -149,6 +159,20 @@ impl RelativeDistinguishedName {
     pub fn encode_from_string(s: &str) -> Result<Vec<u8>, der::Error> {
         Self::from_str(s)?.to_der()
     }
+
+    /// Parse a [`RelativeDistinguishedName`] string, encoding the attribute values with
+    /// the given [`StringEncoding`].
+    ///
+    /// This function follows the rules in [RFC 4514].
+    ///
+    /// [RFC 4514]: https://datatracker.ietf.org/doc/html/rfc4514
+    pub fn from_str_with(s: &str, encoding: StringEncoding) -> der::Result<Self> {
+        split(s, b'+')
+            .map(|part| AttributeTypeAndValue::from_str_with(part, encoding))
+            .collect::<der::Result<Vec<_>>>()?
+            .try_into()
+            .map(Self)
+    }
 }
 
 /// Parse a [`RelativeDistinguishedName`] string.
@@ -160,11 +184,7 @@ impl FromStr for RelativeDistinguishedName {
     type Err = der::Error;
 
     fn from_str(s: &str) -> der::Result<Self> {
-        split(s, b'+')
-            .map(AttributeTypeAndValue::from_str)
-            .collect::<der::Result<Vec<_>>>()?
-            .try_into()
-            .map(Self)
+        Self::from_str_with(s, StringEncoding::default())
     }
 }
 
@@ -0,0 +1,171 @@
+//! Public key summary
+
+use const_oid::{db::rfc5912, ObjectIdentifier};
+use der::{asn1::UintRef, Any, Decode, Sequence};
+use spki::SubjectPublicKeyInfoOwned;
+
+// RFC 8410 algorithm identifiers
+const ID_X_25519: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.110");
+const ID_X_448: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.111");
+const ID_ED_25519: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+const ID_ED_448: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.113");
+
+/// Algorithm family of a subject public key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PublicKeyAlgorithm {
+    /// RSA (`rsaEncryption`)
+    Rsa,
+
+    /// RSA restricted to RSASSA-PSS signatures (`id-RSASSA-PSS`)
+    RsaPss,
+
+    /// DSA (`id-dsa`)
+    Dsa,
+
+    /// Elliptic curve keys (`id-ecPublicKey`)
+    Ec,
+
+    /// Ed25519 (`id-Ed25519`)
+    Ed25519,
+
+    /// Ed448 (`id-Ed448`)
+    Ed448,
+
+    /// X25519 (`id-X25519`)
+    X25519,
+
+    /// X448 (`id-X448`)
+    X448,
+
+    /// Any other algorithm
+    Other(ObjectIdentifier),
+}
+
+impl From<ObjectIdentifier> for PublicKeyAlgorithm {
+    fn from(oid: ObjectIdentifier) -> Self {
+        match oid {
+            rfc5912::RSA_ENCRYPTION => Self::Rsa,
+            rfc5912::ID_RSASSA_PSS => Self::RsaPss,
+            rfc5912::ID_DSA => Self::Dsa,
+            rfc5912::ID_EC_PUBLIC_KEY => Self::Ec,
+            ID_ED_25519 => Self::Ed25519,
+            ID_ED_448 => Self::Ed448,
+            ID_X_25519 => Self::X25519,
+            ID_X_448 => Self::X448,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// Summary of the algorithm, size and parameters of a subject public key.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PublicKeySummary {
+    /// Algorithm family of the key.
+    pub algorithm: PublicKeyAlgorithm,
+
+    /// Size of the key in bits, if known.
+    ///
+    /// This is the size of the modulus for RSA keys, of the prime `p` for DSA keys and of
+    /// the underlying field for elliptic curve keys.
+    pub key_size: Option<u32>,
+
+    /// Named curve of elliptic curve keys.
+    pub curve: Option<ObjectIdentifier>,
+
+    /// Algorithm parameters, as found in the `SubjectPublicKeyInfo`.
+    pub parameters: Option<Any>,
+}
+
+impl TryFrom<&SubjectPublicKeyInfoOwned> for PublicKeySummary {
+    type Error = der::Error;
+
+    fn try_from(spki: &SubjectPublicKeyInfoOwned) -> der::Result<Self> {
+        let algorithm = PublicKeyAlgorithm::from(spki.algorithm.oid);
+        let parameters = spki.algorithm.parameters.clone();
+        let mut curve = None;
+
+        let key_size = match algorithm {
+            PublicKeyAlgorithm::Rsa | PublicKeyAlgorithm::RsaPss => {
+                let key = RsaPublicKey::from_der(spki.subject_public_key.raw_bytes())?;
+                Some(bit_len(key.modulus))
+            }
+            PublicKeyAlgorithm::Dsa => match &parameters {
+                Some(params) => Some(bit_len(params.decode_as::<DssParms<'_>>()?.p)),
+                // parameters inherited from the issuer
+                None => None,
+            },
+            PublicKeyAlgorithm::Ec => {
+                // `namedCurve`: explicit `ECParameters` are not supported by RFC 5480
+                curve = parameters
+                    .as_ref()
+                    .map(|params| params.decode_as::<ObjectIdentifier>())
+                    .transpose()?;
+                curve.and_then(curve_size)
+            }
+            PublicKeyAlgorithm::Ed25519 | PublicKeyAlgorithm::X25519 => Some(256),
+            PublicKeyAlgorithm::Ed448 => Some(456),
+            PublicKeyAlgorithm::X448 => Some(448),
+            PublicKeyAlgorithm::Other(_) => None,
+        };
+
+        Ok(Self {
+            algorithm,
+            key_size,
+            curve,
+            parameters,
+        })
+    }
+}
+
+/// `RSAPublicKey` as defined in [RFC 8017 Appendix A.1.1].
+///
+/// [RFC 8017 Appendix A.1.1]: https://www.rfc-editor.org/rfc/rfc8017#appendix-A.1.1
+#[derive(Sequence)]
+#[allow(dead_code)]
+struct RsaPublicKey<'a> {
+    modulus: UintRef<'a>,
+    public_exponent: UintRef<'a>,
+}
+
+/// `Dss-Parms` as defined in [RFC 3279 Section 2.3.2].
+///
+/// [RFC 3279 Section 2.3.2]: https://www.rfc-editor.org/rfc/rfc3279#section-2.3.2
+#[derive(Sequence)]
+#[allow(dead_code)]
+struct DssParms<'a> {
+    p: UintRef<'a>,
+    q: UintRef<'a>,
+    g: UintRef<'a>,
+}
+
+/// Number of significant bits of an unsigned integer.
+fn bit_len(uint: UintRef<'_>) -> u32 {
+    let bytes = uint.as_bytes();
+    match bytes.first() {
+        Some(first) => (bytes.len() as u32 - 1) * 8 + (8 - first.leading_zeros()),
+        None => 0,
+    }
+}
+
+/// Field size of well-known named curves.
+fn curve_size(curve: ObjectIdentifier) -> Option<u32> {
+    const SECP_192_R_1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.1");
+    const SECP_256_K_1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.10");
+    const BRAINPOOL_P_256_R_1: ObjectIdentifier =
+        ObjectIdentifier::new_unwrap("1.3.36.3.3.2.8.1.1.7");
+    const BRAINPOOL_P_384_R_1: ObjectIdentifier =
+        ObjectIdentifier::new_unwrap("1.3.36.3.3.2.8.1.1.11");
+    const BRAINPOOL_P_512_R_1: ObjectIdentifier =
+        ObjectIdentifier::new_unwrap("1.3.36.3.3.2.8.1.1.13");
+
+    match curve {
+        SECP_192_R_1 => Some(192),
+        rfc5912::SECP_224_R_1 => Some(224),
+        rfc5912::SECP_256_R_1 | SECP_256_K_1 | BRAINPOOL_P_256_R_1 => Some(256),
+        rfc5912::SECP_384_R_1 | BRAINPOOL_P_384_R_1 => Some(384),
+        rfc5912::SECP_521_R_1 => Some(521),
+        BRAINPOOL_P_512_R_1 => Some(512),
+        _ => None,
+    }
+}
@@ -60,6 +60,28 @@ pub struct CertReqInfo {
     pub attributes: Attributes,
 }
 
+impl CertReqInfo {
+    /// Get the extensions requested in the `extensionRequest` attributes.
+    ///
+    /// The extensions are decoded as they were encoded in the request, including the value
+    /// of extensions which are unknown to this crate.
+    pub fn requested_extensions(&self) -> der::Result<Vec<Extension>> {
+        let mut extensions = Vec::new();
+
+        for attribute in self
+            .attributes
+            .iter()
+            .filter(|attr| attr.oid == ID_EXTENSION_REQ)
+        {
+            for value in attribute.values.iter() {
+                extensions.extend(value.decode_as::<Vec<Extension>>()?);
+            }
+        }
+
+        Ok(extensions)
+    }
+}
+
 /// PKCS#10 `CertificationRequest` as defined in [RFC 2986 Section 4].
 ///
 /// ```text
@@ -1,6 +1,7 @@
 //! X.509 serial number
 
-use core::{fmt::Display, marker::PhantomData};
+use alloc::vec::Vec;
+use core::{cmp::Ordering, fmt::Display, marker::PhantomData, str::FromStr};
 
 use der::{
     asn1::{self, Int},
@@ -26,7 +27,10 @@ use crate::certificate::{Profile, Rfc5280};
 ///   Note: Non-conforming CAs may issue certificates with serial numbers
 ///   that are negative or zero.  Certificate users SHOULD be prepared to
 ///   gracefully handle such certificates.
-#[derive(Clone, Debug, Eq, PartialEq, ValueOrd, PartialOrd, Ord)]
+///
+/// [`Ord`] compares serial numbers by their numeric value, whereas [`ValueOrd`]
+/// compares their DER encodings as required to sort a `SET OF`.
+#[derive(Clone, Debug, Eq, PartialEq, ValueOrd)]
 pub struct SerialNumber<P: Profile = Rfc5280> {
     pub(crate) inner: Int,
     _profile: PhantomData<P>,
@@ -95,6 +99,31 @@ impl<P: Profile> FixedTag for SerialNumber<P> {
     const TAG: Tag = <Int as FixedTag>::TAG;
 }
 
+impl<P: Profile> Ord for SerialNumber<P> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = (self.as_bytes(), other.as_bytes());
+        let is_negative = |bytes: &[u8]| bytes.first().map_or(false, |byte| byte & 0x80 != 0);
+
+        // Integers are encoded in two's complement with a minimal number of
+        // bytes: longer encodings have larger magnitudes, and encodings of the
+        // same length and sign compare like their bytes.
+        match (is_negative(a), is_negative(b)) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => a.len().cmp(&b.len()).then_with(|| a.cmp(b)),
+            (true, true) => b.len().cmp(&a.len()).then_with(|| a.cmp(b)),
+        }
+    }
+}
+
+impl<P: Profile> PartialOrd for SerialNumber<P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Format the serial number as uppercase hexadecimal bytes separated by colons,
+/// e.g. `01:AB:CD`.
 impl Display for SerialNumber {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let mut iter = self.as_bytes().iter().peekable();
@@ -110,6 +139,57 @@ impl Display for SerialNumber {
     }
 }
 
+/// Parse a positive serial number from hexadecimal, either as bytes separated by
+/// colons like its [`Display`] output (e.g. `01:AB:CD`), or as a single string of
+/// digits (e.g. `1ABCD`). Digits are case insensitive.
+impl FromStr for SerialNumber {
+    type Err = der::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        fn hex_digit(c: u8) -> Result<u8> {
+            match c {
+                b'0'..=b'9' => Ok(c - b'0'),
+                b'A'..=b'F' => Ok(c - b'A' + 10),
+                b'a'..=b'f' => Ok(c - b'a' + 10),
+                _ => Err(Tag::Integer.value_error()),
+            }
+        }
+
+        // Big endian digits of a single byte, or of the whole serial number,
+        // with an odd number of digits read as having a leading zero.
+        fn push_digits(bytes: &mut Vec<u8>, digits: &[u8]) -> Result<()> {
+            if digits.is_empty() {
+                return Err(Tag::Integer.value_error());
+            }
+
+            let (first, rest) = digits.split_at(digits.len() % 2);
+            if let Some(&digit) = first.first() {
+                bytes.push(hex_digit(digit)?);
+            }
+            for pair in rest.chunks_exact(2) {
+                bytes.push(hex_digit(pair[0])? << 4 | hex_digit(pair[1])?);
+            }
+
+            Ok(())
+        }
+
+        let mut bytes = Vec::with_capacity(s.len() / 2 + 1);
+
+        if s.contains(':') {
+            for group in s.split(':') {
+                if group.len() > 2 {
+                    return Err(Tag::Integer.value_error());
+                }
+                push_digits(&mut bytes, group.as_bytes())?;
+            }
+        } else {
+            push_digits(&mut bytes, s.as_bytes())?;
+        }
+
+        Self::new(&bytes)
+    }
+}
+
 macro_rules! impl_from {
     ($source:ty) => {
         impl From<$source> for SerialNumber {
@@ -149,6 +229,7 @@ impl<'a, P: Profile> arbitrary::Arbitrary<'a> for SerialNumber<P> {
 #[cfg(test)]
 mod tests {
     use alloc::string::ToString;
+    use der::Decode;
 
     use super::*;
 
@@ -170,6 +251,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn serial_number_from_str() {
+        let sn = SerialNumber::from_str("11:22:33").unwrap();
+        assert_eq!(sn.as_bytes(), &[0x11, 0x22, 0x33]);
+        assert_eq!(sn, SerialNumber::from_str("112233").unwrap());
+        assert_eq!(sn, SerialNumber::from_str("00:11:22:33").unwrap());
+
+        let sn = SerialNumber::from_str("aB:cD:1").unwrap();
+        assert_eq!(sn.to_string(), "00:AB:CD:01");
+        assert_eq!(sn, SerialNumber::from_str(&sn.to_string()).unwrap());
+        assert_eq!(
+            SerialNumber::from_str("abc").unwrap().as_bytes(),
+            &[0x0A, 0xBC]
+        );
+
+        for invalid in ["", ":", "11::22", "11:", "123:45", "0x11", "1G", "11 22"] {
+            assert!(SerialNumber::from_str(invalid).is_err(), "{invalid}");
+        }
+        assert!(SerialNumber::from_str(&"FF".repeat(21)).is_err());
+    }
+
+    #[test]
+    fn serial_number_ordering() {
+        let sn = |bytes: &[u8]| SerialNumber::<Rfc5280>::new(bytes).unwrap();
+        let neg = |der: &[u8]| SerialNumber::<Rfc5280>::from_der(der).unwrap();
+
+        assert!(sn(&[0x01]) < sn(&[0xFF]));
+        assert!(sn(&[0x7F]) < sn(&[0x01, 0x00]));
+        assert!(sn(&[0x01, 0x00]) < sn(&[0x01, 0x01]));
+        assert_eq!(sn(&[0x00, 0x01]).cmp(&sn(&[0x01])), Ordering::Equal);
+
+        // -256 < -129 < -1 < 0
+        let minus_256 = neg(&[0x02, 0x02, 0xFF, 0x00]);
+        let minus_129 = neg(&[0x02, 0x02, 0xFF, 0x7F]);
+        let minus_1 = neg(&[0x02, 0x01, 0xFF]);
+        let zero = neg(&[0x02, 0x01, 0x00]);
+        assert!(minus_256 < minus_129);
+        assert!(minus_129 < minus_1);
+        assert!(minus_1 < zero);
+        assert!(zero < sn(&[0x01]));
+    }
+
     #[test]
     fn serial_number_display() {
         {
@@ -0,0 +1,259 @@
+//! Human-readable certificate summaries
+
+use crate::{
+    certificate::{CertificateInner, Profile, Version},
+    ext::pkix::{name::GeneralName, SubjectAltName},
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use const_oid::{db::DB, ObjectIdentifier};
+use core::fmt::{self, Write};
+use der::{Decode, Encode};
+use serde::Serialize;
+use sha1::{Digest, Sha1};
+use sha2::Sha256;
+
+/// Summary of the contents of a certificate, similar to the output of `openssl x509 -text`.
+///
+/// All values are formatted for display, and can be serialized with `serde`, e.g. as JSON.
+/// OIDs are formatted by name when they are registered in the [`const_oid::db::DB`]
+/// database, and in dotted notation otherwise.
+///
+/// The [`fmt::Display`] implementation lists the fields on separate lines.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct CertificateSummary {
+    /// Version number, i.e. `3` for v3 certificates.
+    pub version: u8,
+
+    /// Serial number as uppercase hexadecimal bytes separated by colons.
+    pub serial_number: String,
+
+    /// Signature algorithm of the issuer.
+    pub signature_algorithm: String,
+
+    /// Issuer name in the RFC 4514 string representation.
+    pub issuer: String,
+
+    /// Subject name in the RFC 4514 string representation.
+    pub subject: String,
+
+    /// Start of the validity period in RFC 3339 format.
+    pub not_before: String,
+
+    /// End of the validity period in RFC 3339 format.
+    pub not_after: String,
+
+    /// Subject alternative names, prefixed by their type like in OpenSSL, e.g.
+    /// `DNS:example.com` or `IP Address:192.0.2.1`.
+    pub subject_alt_names: Vec<String>,
+
+    /// Subject public key.
+    pub public_key: PublicKeyInfo,
+
+    /// Fingerprints of the encoding of the certificate.
+    pub fingerprints: Fingerprints,
+
+    /// Extensions, in the order of the certificate.
+    pub extensions: Vec<ExtensionInfo>,
+}
+
+/// Subject public key of a [`CertificateSummary`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct PublicKeyInfo {
+    /// Algorithm of the key.
+    pub algorithm: String,
+
+    /// Size of the key in bits, if known.
+    pub key_size: Option<u32>,
+
+    /// Named curve of elliptic curve keys.
+    pub curve: Option<String>,
+}
+
+/// Fingerprints of a certificate, as uppercase hexadecimal bytes separated by colons.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Fingerprints {
+    /// SHA-1 fingerprint.
+    pub sha1: String,
+
+    /// SHA-256 fingerprint.
+    pub sha256: String,
+}
+
+/// Extension of a [`CertificateSummary`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ExtensionInfo {
+    /// Extension ID in dotted notation.
+    pub oid: String,
+
+    /// Name of the extension, if known.
+    pub name: Option<&'static str>,
+
+    /// Whether the extension is critical.
+    pub critical: bool,
+}
+
+impl<P: Profile> CertificateInner<P> {
+    /// Decode a DER-encoded certificate and summarize its contents for display.
+    ///
+    /// The fingerprints are computed over `der`, as done by other tools, so prefer this
+    /// function to [`CertificateInner::summary`] when the original encoding is available.
+    pub fn summary_from_der(der: &[u8]) -> der::Result<CertificateSummary> {
+        Self::from_der(der)?.summarize(der)
+    }
+
+    /// Summarize the contents of this certificate for display.
+    ///
+    /// The fingerprints are computed over the encoding of the certificate by this crate, which
+    /// may differ from the original encoding of certificates produced by non-conforming
+    /// encoders: use [`CertificateInner::summary_from_der`] to compute them over the original
+    /// encoding instead.
+    pub fn summary(&self) -> der::Result<CertificateSummary> {
+        self.summarize(&self.to_der()?)
+    }
+
+    /// Summarize the contents of this certificate, whose encoding is `der`.
+    fn summarize(&self, der: &[u8]) -> der::Result<CertificateSummary> {
+        let tbs = &self.tbs_certificate;
+        let public_key = self.public_key_summary()?;
+
+        let subject_alt_names = match tbs.get::<SubjectAltName>()? {
+            Some((_, san)) => san.0.iter().map(general_name).collect(),
+            None => Vec::new(),
+        };
+
+        let extensions = tbs
+            .extensions
+            .iter()
+            .flatten()
+            .map(|ext| ExtensionInfo {
+                oid: ext.extn_id.to_string(),
+                name: DB.by_oid(&ext.extn_id),
+                critical: ext.critical,
+            })
+            .collect();
+
+        Ok(CertificateSummary {
+            version: match tbs.version {
+                Version::V1 => 1,
+                Version::V2 => 2,
+                Version::V3 => 3,
+            },
+            serial_number: hex(tbs.serial_number.as_bytes()),
+            signature_algorithm: oid_name(self.signature_algorithm.oid),
+            issuer: tbs.issuer.to_string(),
+            subject: tbs.subject.to_string(),
+            not_before: tbs.validity.not_before.to_string(),
+            not_after: tbs.validity.not_after.to_string(),
+            subject_alt_names,
+            public_key: PublicKeyInfo {
+                algorithm: oid_name(tbs.subject_public_key_info.algorithm.oid),
+                key_size: public_key.key_size,
+                curve: public_key.curve.map(oid_name),
+            },
+            fingerprints: Fingerprints {
+                sha1: hex(&Sha1::digest(der)),
+                sha256: hex(&Sha256::digest(der)),
+            },
+            extensions,
+        })
+    }
+}
+
+impl fmt::Display for CertificateSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Version: {}", self.version)?;
+        writeln!(f, "Serial Number: {}", self.serial_number)?;
+        writeln!(f, "Signature Algorithm: {}", self.signature_algorithm)?;
+        writeln!(f, "Issuer: {}", self.issuer)?;
+        writeln!(f, "Not Before: {}", self.not_before)?;
+        writeln!(f, "Not After: {}", self.not_after)?;
+        writeln!(f, "Subject: {}", self.subject)?;
+        write!(f, "Public Key Algorithm: {}", self.public_key.algorithm)?;
+        if let Some(curve) = &self.public_key.curve {
+            write!(f, " ({})", curve)?;
+        }
+        if let Some(key_size) = self.public_key.key_size {
+            write!(f, ", {} bit", key_size)?;
+        }
+        writeln!(f)?;
+
+        if !self.subject_alt_names.is_empty() {
+            writeln!(
+                f,
+                "Subject Alternative Names: {}",
+                self.subject_alt_names.join(", ")
+            )?;
+        }
+
+        for ext in &self.extensions {
+            write!(f, "Extension: {}", ext.name.unwrap_or(&ext.oid))?;
+            if ext.critical {
+                f.write_str(" (critical)")?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(f, "SHA-1 Fingerprint: {}", self.fingerprints.sha1)?;
+        write!(f, "SHA-256 Fingerprint: {}", self.fingerprints.sha256)
+    }
+}
+
+/// Format `bytes` as uppercase hexadecimal bytes separated by colons.
+fn hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 3);
+
+    for (i, byte) in bytes.iter().enumerate() {
+        if i != 0 {
+            hex.push(':');
+        }
+        // Writing to a `String` is infallible
+        let _ = write!(hex, "{:02X}", byte);
+    }
+
+    hex
+}
+
+/// Format `oid` by name if it is registered, in dotted notation otherwise.
+fn oid_name(oid: ObjectIdentifier) -> String {
+    match DB.by_oid(&oid) {
+        Some(name) => name.to_string(),
+        None => oid.to_string(),
+    }
+}
+
+/// Format a subject alternative name like OpenSSL.
+fn general_name(name: &GeneralName) -> String {
+    match name {
+        GeneralName::OtherName(other) => format!("othername:{}", oid_name(other.type_id)),
+        GeneralName::Rfc822Name(email) => format!("email:{}", email),
+        GeneralName::DnsName(dns) => format!("DNS:{}", dns),
+        GeneralName::DirectoryName(name) => format!("DirName:{}", name),
+        GeneralName::EdiPartyName(_) => "EdiPartyName".to_string(),
+        GeneralName::UniformResourceIdentifier(uri) => format!("URI:{}", uri),
+        GeneralName::IpAddress(ip) => format!("IP Address:{}", ip_address(ip.as_bytes())),
+        GeneralName::RegisteredId(oid) => format!("Registered ID:{}", oid_name(*oid)),
+    }
+}
+
+/// Format an IP address, falling back on hexadecimal for unexpected lengths.
+fn ip_address(bytes: &[u8]) -> String {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    match bytes.len() {
+        4 => {
+            let mut octets = [0u8; 4];
+            octets.copy_from_slice(bytes);
+            IpAddr::from(Ipv4Addr::from(octets)).to_string()
+        }
+        16 => {
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(bytes);
+            IpAddr::from(Ipv6Addr::from(octets)).to_string()
+        }
+        _ => hex(bytes),
+    }
+}
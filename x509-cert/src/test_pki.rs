@@ -0,0 +1,400 @@
+//! Throwaway PKI hierarchies for tests
+//!
+//! [`TestPki`] generates a root CA, an intermediate CA and a leaf certificate, along with their
+//! private keys, entirely in memory. Tests can build a fresh hierarchy on each run instead of
+//! relying on static fixtures, which eventually expire.
+//!
+//! ```
+//! use x509_cert::test_pki::{KeyAlgorithm, TestPki};
+//!
+//! let pki = TestPki::builder()
+//!     .leaf_key_algorithm(KeyAlgorithm::Rsa { bits: 2048 })
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(
+//!     pki.leaf.certificate.tbs_certificate.issuer,
+//!     pki.intermediate.certificate.tbs_certificate.subject,
+//! );
+//! ```
+//!
+//! The keys are generated with the operating system's random number generator. **They are
+//! meant for tests only and must never be used to secure anything.**
+
+use alloc::{format, vec, vec::Vec};
+use core::{str::FromStr, time::Duration};
+use der::{asn1::Ia5String, SecretDocument};
+use p256::pkcs8::EncodePrivateKey;
+use rand_core::{OsRng, RngCore};
+use sha2::Sha256;
+use signature::{Keypair, Signer};
+use spki::{
+    DynSignatureAlgorithmIdentifier, EncodePublicKey, SignatureBitStringEncoding,
+    SubjectPublicKeyInfoOwned,
+};
+
+use crate::{
+    builder::{Builder, CertificateBuilder, Error, Profile, Result},
+    ext::{
+        pkix::{name::GeneralName, ExtendedKeyUsage, SubjectAltName},
+        AsExtension, Extension,
+    },
+    name::Name,
+    serial_number::SerialNumber,
+    time::Validity,
+    Certificate,
+};
+
+/// Subject of the default root CA certificate.
+pub const ROOT_SUBJECT: &str = "CN=Test Root CA,O=x509-cert test PKI";
+
+/// Subject of the default intermediate CA certificate.
+pub const INTERMEDIATE_SUBJECT: &str = "CN=Test Intermediate CA,O=x509-cert test PKI";
+
+/// DNS name of the default leaf certificate, used as its common name and subject alternative
+/// name.
+pub const LEAF_DNS_NAME: &str = "localhost";
+
+/// Validity of the certificates unless set with [`TestPkiBuilder::validity`].
+const DEFAULT_VALIDITY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Algorithm of a generated key.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum KeyAlgorithm {
+    /// ECDSA with the NIST P-256 curve, signing with SHA-256.
+    #[default]
+    EcdsaP256,
+
+    /// RSA with a modulus of `bits` bits, signing with PKCS#1 v1.5 and SHA-256.
+    Rsa {
+        /// Size of the modulus in bits.
+        bits: usize,
+    },
+}
+
+/// Private key generated for a [`TestPki`] certificate.
+#[derive(Clone, Debug)]
+#[allow(clippy::large_enum_variant)]
+#[non_exhaustive]
+pub enum PrivateKey {
+    /// ECDSA key with the NIST P-256 curve.
+    EcdsaP256(p256::ecdsa::SigningKey),
+
+    /// RSA key.
+    Rsa(rsa::RsaPrivateKey),
+}
+
+impl PrivateKey {
+    /// Generates a new random key for `algorithm`.
+    pub fn generate(algorithm: KeyAlgorithm) -> Result<Self> {
+        match algorithm {
+            KeyAlgorithm::EcdsaP256 => {
+                Ok(Self::EcdsaP256(p256::ecdsa::SigningKey::random(&mut OsRng)))
+            }
+            KeyAlgorithm::Rsa { bits } => rsa::RsaPrivateKey::new(&mut OsRng, bits)
+                .map(Self::Rsa)
+                .map_err(|_| Error::Signature(signature::Error::new())),
+        }
+    }
+
+    /// Algorithm of this key.
+    pub fn algorithm(&self) -> KeyAlgorithm {
+        match self {
+            Self::EcdsaP256(_) => KeyAlgorithm::EcdsaP256,
+            Self::Rsa(key) => KeyAlgorithm::Rsa {
+                bits: rsa::traits::PublicKeyParts::size(key) * 8,
+            },
+        }
+    }
+
+    /// Public key, as found in the certificate.
+    pub fn public_key(&self) -> Result<SubjectPublicKeyInfoOwned> {
+        let der = match self {
+            Self::EcdsaP256(key) => key.verifying_key().to_public_key_der()?,
+            Self::Rsa(key) => key.to_public_key().to_public_key_der()?,
+        };
+
+        Ok(der.decode_msg::<SubjectPublicKeyInfoOwned>()?)
+    }
+
+    /// Encodes this key as an unencrypted PKCS#8 `PrivateKeyInfo`, e.g. to configure a TLS
+    /// server under test.
+    pub fn to_pkcs8_der(&self) -> p256::pkcs8::Result<SecretDocument> {
+        match self {
+            Self::EcdsaP256(key) => key.to_pkcs8_der(),
+            Self::Rsa(key) => key.to_pkcs8_der(),
+        }
+    }
+
+    /// Issues a certificate for `spec` signed by this key.
+    fn issue(
+        &self,
+        profile: Profile,
+        validity: Validity,
+        spec: &CertificateSpec,
+        public_key: SubjectPublicKeyInfoOwned,
+    ) -> Result<Certificate> {
+        match self {
+            Self::EcdsaP256(key) => {
+                issue::<_, p256::ecdsa::DerSignature>(key, profile, validity, spec, public_key)
+            }
+            Self::Rsa(key) => {
+                let key = rsa::pkcs1v15::SigningKey::<Sha256>::new(key.clone());
+                issue::<_, rsa::pkcs1v15::Signature>(&key, profile, validity, spec, public_key)
+            }
+        }
+    }
+}
+
+/// Subject, key algorithm and extensions of a [`TestPki`] certificate.
+#[derive(Clone, Debug)]
+pub struct CertificateSpec {
+    subject: Name,
+    key_algorithm: KeyAlgorithm,
+    extensions: Vec<Extension>,
+}
+
+impl CertificateSpec {
+    /// Creates a spec for a certificate issued to `subject`, with a P-256 key and only the
+    /// extensions set by the [`Profile`].
+    pub fn new(subject: Name) -> Self {
+        Self {
+            subject,
+            key_algorithm: KeyAlgorithm::default(),
+            extensions: Vec::new(),
+        }
+    }
+
+    /// Sets the algorithm of the subject key.
+    pub fn key_algorithm(mut self, algorithm: KeyAlgorithm) -> Self {
+        self.key_algorithm = algorithm;
+        self
+    }
+
+    /// Adds an extension to the certificate.
+    ///
+    /// Extensions set by the [`Profile`], such as the basic constraints and key usage, take
+    /// precedence over extensions with the same OID.
+    pub fn extension<E: AsExtension>(mut self, extension: &E) -> Result<Self> {
+        let extension = extension.to_extension(&self.subject, &self.extensions)?;
+        self.extensions.push(extension);
+        Ok(self)
+    }
+}
+
+/// Certificate of a [`TestPki`] along with the private key of its subject.
+#[derive(Clone, Debug)]
+pub struct CertifiedKey {
+    /// Certificate.
+    pub certificate: Certificate,
+
+    /// Private key matching the public key of the certificate.
+    pub key: PrivateKey,
+}
+
+/// Root CA, intermediate CA and leaf certificates with their private keys.
+///
+/// The intermediate is issued by the root with a path length constraint of zero, and the leaf
+/// is issued by the intermediate.
+#[derive(Clone, Debug)]
+pub struct TestPki {
+    /// Self-signed root CA.
+    pub root: CertifiedKey,
+
+    /// Intermediate CA issued by [`TestPki::root`].
+    pub intermediate: CertifiedKey,
+
+    /// End entity issued by [`TestPki::intermediate`].
+    pub leaf: CertifiedKey,
+}
+
+impl TestPki {
+    /// Generates a hierarchy with the default settings of [`TestPkiBuilder`].
+    pub fn generate() -> Result<Self> {
+        Self::builder().build()
+    }
+
+    /// Creates a builder to customize the generated hierarchy.
+    pub fn builder() -> TestPkiBuilder {
+        TestPkiBuilder::default()
+    }
+
+    /// Certification path from the leaf to the root.
+    pub fn chain(&self) -> [&Certificate; 3] {
+        [
+            &self.leaf.certificate,
+            &self.intermediate.certificate,
+            &self.root.certificate,
+        ]
+    }
+}
+
+/// Builder for a [`TestPki`].
+///
+/// By default, all keys use [`KeyAlgorithm::EcdsaP256`] and the certificates are valid for a
+/// day starting now. The CAs are issued to [`ROOT_SUBJECT`] and [`INTERMEDIATE_SUBJECT`], and
+/// the leaf to [`LEAF_DNS_NAME`], with a matching subject alternative name and the
+/// `id-kp-serverAuth` and `id-kp-clientAuth` extended key usages.
+#[derive(Clone, Debug)]
+pub struct TestPkiBuilder {
+    root: CertificateSpec,
+    intermediate: CertificateSpec,
+    leaf: CertificateSpec,
+    validity: Option<Validity>,
+}
+
+impl Default for TestPkiBuilder {
+    fn default() -> Self {
+        let leaf_subject =
+            Name::from_str(&format!("CN={}", LEAF_DNS_NAME)).expect("valid leaf subject");
+        let leaf_dns_name = Ia5String::new(LEAF_DNS_NAME).expect("valid DNS name");
+
+        Self {
+            root: CertificateSpec::new(Name::from_str(ROOT_SUBJECT).expect("valid root subject")),
+            intermediate: CertificateSpec::new(
+                Name::from_str(INTERMEDIATE_SUBJECT).expect("valid intermediate subject"),
+            ),
+            leaf: CertificateSpec::new(leaf_subject)
+                .extension(&SubjectAltName(vec![GeneralName::DnsName(leaf_dns_name)]))
+                .and_then(|spec| {
+                    spec.extension(&ExtendedKeyUsage::new().server_auth().client_auth())
+                })
+                .expect("valid leaf extensions"),
+            validity: None,
+        }
+    }
+}
+
+impl TestPkiBuilder {
+    /// Sets the algorithm of all keys.
+    pub fn key_algorithm(self, algorithm: KeyAlgorithm) -> Self {
+        self.root_key_algorithm(algorithm)
+            .intermediate_key_algorithm(algorithm)
+            .leaf_key_algorithm(algorithm)
+    }
+
+    /// Sets the algorithm of the root CA key.
+    pub fn root_key_algorithm(mut self, algorithm: KeyAlgorithm) -> Self {
+        self.root.key_algorithm = algorithm;
+        self
+    }
+
+    /// Sets the algorithm of the intermediate CA key.
+    pub fn intermediate_key_algorithm(mut self, algorithm: KeyAlgorithm) -> Self {
+        self.intermediate.key_algorithm = algorithm;
+        self
+    }
+
+    /// Sets the algorithm of the leaf key.
+    pub fn leaf_key_algorithm(mut self, algorithm: KeyAlgorithm) -> Self {
+        self.leaf.key_algorithm = algorithm;
+        self
+    }
+
+    /// Replaces the spec of the root CA certificate.
+    pub fn root(mut self, spec: CertificateSpec) -> Self {
+        self.root = spec;
+        self
+    }
+
+    /// Replaces the spec of the intermediate CA certificate.
+    pub fn intermediate(mut self, spec: CertificateSpec) -> Self {
+        self.intermediate = spec;
+        self
+    }
+
+    /// Replaces the spec of the leaf certificate, including its default extensions.
+    pub fn leaf(mut self, spec: CertificateSpec) -> Self {
+        self.leaf = spec;
+        self
+    }
+
+    /// Sets the validity of all certificates, e.g. to test expired certificates.
+    pub fn validity(mut self, validity: Validity) -> Self {
+        self.validity = Some(validity);
+        self
+    }
+
+    /// Generates the keys and issues the certificates.
+    pub fn build(self) -> Result<TestPki> {
+        let validity = match self.validity {
+            Some(validity) => validity,
+            None => Validity::from_now(DEFAULT_VALIDITY)?,
+        };
+
+        let root_key = PrivateKey::generate(self.root.key_algorithm)?;
+        let intermediate_key = PrivateKey::generate(self.intermediate.key_algorithm)?;
+        let leaf_key = PrivateKey::generate(self.leaf.key_algorithm)?;
+
+        let root = root_key.issue(Profile::Root, validity, &self.root, root_key.public_key()?)?;
+
+        let intermediate = root_key.issue(
+            Profile::SubCA {
+                issuer: self.root.subject.clone(),
+                path_len_constraint: Some(0),
+            },
+            validity,
+            &self.intermediate,
+            intermediate_key.public_key()?,
+        )?;
+
+        let leaf = intermediate_key.issue(
+            Profile::Leaf {
+                issuer: self.intermediate.subject.clone(),
+                enable_key_agreement: matches!(leaf_key, PrivateKey::EcdsaP256(_)),
+                enable_key_encipherment: matches!(leaf_key, PrivateKey::Rsa(_)),
+                #[cfg(feature = "hazmat")]
+                include_subject_key_identifier: true,
+            },
+            validity,
+            &self.leaf,
+            leaf_key.public_key()?,
+        )?;
+
+        Ok(TestPki {
+            root: CertifiedKey {
+                certificate: root,
+                key: root_key,
+            },
+            intermediate: CertifiedKey {
+                certificate: intermediate,
+                key: intermediate_key,
+            },
+            leaf: CertifiedKey {
+                certificate: leaf,
+                key: leaf_key,
+            },
+        })
+    }
+}
+
+/// Issues a certificate for `spec` signed by `signer`, with a random serial number.
+fn issue<S, Signature>(
+    signer: &S,
+    profile: Profile,
+    validity: Validity,
+    spec: &CertificateSpec,
+    public_key: SubjectPublicKeyInfoOwned,
+) -> Result<Certificate>
+where
+    S: Keypair + DynSignatureAlgorithmIdentifier + Signer<Signature>,
+    S::VerifyingKey: EncodePublicKey,
+    Signature: SignatureBitStringEncoding,
+{
+    // Positive 127-bit serial number, well within the 20 octets allowed by RFC 5280
+    let mut serial_number = [0u8; 16];
+    OsRng.fill_bytes(&mut serial_number);
+    serial_number[0] &= 0x7f;
+
+    let mut builder = CertificateBuilder::new(
+        profile,
+        SerialNumber::new(&serial_number)?,
+        validity,
+        spec.subject.clone(),
+        public_key,
+        signer,
+    )?;
+    builder.copy_extensions(&spec.extensions, |extension| Some(extension.critical))?;
+    builder.build::<Signature>()
+}
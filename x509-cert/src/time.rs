@@ -41,6 +41,15 @@ impl Time {
     pub const INFINITY: Time =
         Time::GeneralTime(GeneralizedTime::from_date_time(DateTime::INFINITY));
 
+    /// Create a [`Time`] given a [`Duration`] since `UNIX_EPOCH`, encoded as a
+    /// [`GeneralizedTime`] like times converted from [`SystemTime`].
+    ///
+    /// Unlike [`SystemTime`], this is usable on targets without a system clock such as
+    /// `wasm32-unknown-unknown`.
+    pub fn from_unix_duration(unix_duration: Duration) -> der::Result<Self> {
+        Ok(GeneralizedTime::from_unix_duration(unix_duration)?.into())
+    }
+
     /// Get duration since `UNIX_EPOCH`.
     pub fn to_unix_duration(self) -> Duration {
         match self {
@@ -143,7 +152,13 @@ pub struct Validity {
 
 impl Validity {
     /// Creates a `Validity` which starts now and lasts for `duration`.
-    #[cfg(feature = "std")]
+    ///
+    /// This is unavailable on `wasm32-unknown-unknown`, where the current time can't be
+    /// read with [`SystemTime::now`]: use [`Validity::from_unix_duration`] instead.
+    #[cfg(all(
+        feature = "std",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
     pub fn from_now(duration: Duration) -> der::Result<Self> {
         let now = SystemTime::now();
         let then = now + duration;
@@ -153,4 +168,17 @@ impl Validity {
             not_after: Time::try_from(then)?,
         })
     }
+
+    /// Creates a `Validity` which starts at `not_before`, a [`Duration`] since `UNIX_EPOCH`,
+    /// and lasts for `duration`.
+    pub fn from_unix_duration(not_before: Duration, duration: Duration) -> der::Result<Self> {
+        let not_after = not_before
+            .checked_add(duration)
+            .ok_or(der::ErrorKind::DateTime)?;
+
+        Ok(Self {
+            not_before: Time::from_unix_duration(not_before)?,
+            not_after: Time::from_unix_duration(not_after)?,
+        })
+    }
 }
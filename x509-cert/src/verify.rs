@@ -0,0 +1,473 @@
+//! Verification of simple certification paths.
+//!
+//! This module covers the common case of a TLS server presenting a leaf certificate issued
+//! by a single intermediate CA, itself issued by a trusted root. It is not a general
+//! implementation of the path validation algorithm of [RFC 5280 Section 6]: policies and name
+//! constraints are not processed, and certificates carrying them as critical extensions are
+//! rejected. Revocation checking is left to the application, through a
+//! [`RevocationChecker`].
+//!
+//! [RFC 5280 Section 6]: https://www.rfc-editor.org/rfc/rfc5280#section-6
+
+use crate::{
+    certificate::Certificate,
+    ext::pkix::{
+        name::GeneralName, BasicConstraints, ExtendedKeyUsage, KeyUsage, OcspNoCheck,
+        SubjectAltName,
+    },
+    time::Time,
+};
+use alloc::string::String;
+use const_oid::{
+    db::rfc5280::{
+        ANY_EXTENDED_KEY_USAGE, ID_CE_BASIC_CONSTRAINTS, ID_CE_EXT_KEY_USAGE, ID_CE_KEY_USAGE,
+        ID_CE_SUBJECT_ALT_NAME, ID_KP_OCSP_SIGNING, ID_KP_SERVER_AUTH,
+    },
+    ObjectIdentifier,
+};
+use core::fmt;
+use spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned};
+use std::net::IpAddr;
+
+/// Position of a certificate in the certification path.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ChainPosition {
+    /// End-entity certificate.
+    Leaf,
+
+    /// Intermediate CA certificate, issuer of the leaf.
+    Intermediate,
+
+    /// Trusted root CA certificate, issuer of the intermediate.
+    Root,
+}
+
+impl fmt::Display for ChainPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainPosition::Leaf => f.write_str("leaf"),
+            ChainPosition::Intermediate => f.write_str("intermediate"),
+            ChainPosition::Root => f.write_str("root"),
+        }
+    }
+}
+
+/// Error type
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors.
+    Asn1(der::Error),
+
+    /// The signature of the certificate is invalid.
+    Signature(ChainPosition, signature::Error),
+
+    /// The `signature` field of the `TBSCertificate` doesn't match the `signatureAlgorithm`
+    /// of the certificate.
+    AlgorithmMismatch(ChainPosition),
+
+    /// The issuer name of the certificate doesn't match the subject name of the next
+    /// certificate of the path.
+    IssuerMismatch(ChainPosition),
+
+    /// The certificate is not valid at the verification time.
+    Validity(ChainPosition),
+
+    /// The certificate is not a CA certificate, or its path length constraint is exceeded.
+    BasicConstraints(ChainPosition),
+
+    /// The key usage extension of the certificate doesn't allow its use.
+    KeyUsage(ChainPosition),
+
+    /// The extended key usage extension of the certificate doesn't allow TLS server
+    /// authentication.
+    ExtendedKeyUsage(ChainPosition),
+
+    /// The certificate is revoked.
+    Revoked(ChainPosition),
+
+    /// The certificate carries a critical extension which is not processed.
+    UnhandledCriticalExtension(ChainPosition, ObjectIdentifier),
+
+    /// The leaf certificate is not valid for the hostname.
+    HostnameMismatch,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::Signature(pos, err) => write!(f, "{} signature error: {}", pos, err),
+            Error::AlgorithmMismatch(pos) => {
+                write!(f, "{} signature algorithm mismatch", pos)
+            }
+            Error::IssuerMismatch(pos) => write!(f, "{} issuer name mismatch", pos),
+            Error::Validity(pos) => write!(f, "{} certificate is expired or not yet valid", pos),
+            Error::BasicConstraints(pos) => {
+                write!(f, "{} certificate violates basic constraints", pos)
+            }
+            Error::KeyUsage(pos) => write!(f, "{} key usage doesn't allow its use", pos),
+            Error::ExtendedKeyUsage(pos) => {
+                write!(
+                    f,
+                    "{} extended key usage doesn't allow server authentication",
+                    pos
+                )
+            }
+            Error::Revoked(pos) => write!(f, "{} certificate is revoked", pos),
+            Error::UnhandledCriticalExtension(pos, oid) => {
+                write!(
+                    f,
+                    "{} certificate has unhandled critical extension {}",
+                    pos, oid
+                )
+            }
+            Error::HostnameMismatch => write!(f, "leaf certificate doesn't match the hostname"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl From<der::Error> for Error {
+    fn from(err: der::Error) -> Error {
+        Error::Asn1(err)
+    }
+}
+
+type Result<T> = core::result::Result<T, Error>;
+
+/// Verifier of the signatures made by the key of an issuer certificate.
+///
+/// The algorithms supported by the application are selected by implementations of this
+/// trait. It is implemented for closures with the same signature as
+/// [`SignatureVerifier::verify_signature`].
+pub trait SignatureVerifier {
+    /// Verify `signature` over `message`, made with `algorithm` by the key `issuer_key`.
+    fn verify_signature(
+        &self,
+        issuer_key: &SubjectPublicKeyInfoOwned,
+        algorithm: &AlgorithmIdentifierOwned,
+        message: &[u8],
+        signature: &[u8],
+    ) -> core::result::Result<(), signature::Error>;
+}
+
+impl<F> SignatureVerifier for F
+where
+    F: Fn(
+        &SubjectPublicKeyInfoOwned,
+        &AlgorithmIdentifierOwned,
+        &[u8],
+        &[u8],
+    ) -> core::result::Result<(), signature::Error>,
+{
+    fn verify_signature(
+        &self,
+        issuer_key: &SubjectPublicKeyInfoOwned,
+        algorithm: &AlgorithmIdentifierOwned,
+        message: &[u8],
+        signature: &[u8],
+    ) -> core::result::Result<(), signature::Error> {
+        self(issuer_key, algorithm, message, signature)
+    }
+}
+
+/// Checker of the revocation status of certificates, e.g. with CRLs or OCSP.
+///
+/// It is implemented for closures with the same signature as
+/// [`RevocationChecker::is_revoked`].
+pub trait RevocationChecker {
+    /// Whether `cert`, issued by `issuer`, is revoked.
+    ///
+    /// Implementations decide whether certificates whose status can't be determined are
+    /// treated as revoked.
+    fn is_revoked(&self, cert: &Certificate, issuer: &Certificate) -> bool;
+}
+
+impl<F> RevocationChecker for F
+where
+    F: Fn(&Certificate, &Certificate) -> bool,
+{
+    fn is_revoked(&self, cert: &Certificate, issuer: &Certificate) -> bool {
+        self(cert, issuer)
+    }
+}
+
+/// Parse the DER-encoded certificates of a TLS server and verify that the leaf is issued by
+/// the intermediate, itself issued by the trusted root, and that the leaf is valid for
+/// `hostname` at `time`.
+///
+/// Returns the parsed leaf certificate. The following checks are performed:
+///
+/// - the issuer names chain up to the root, and the signatures of the leaf and the
+///   intermediate are verified with `verifier` over their original `TBSCertificate` encoding;
+/// - all three certificates are valid at `time`;
+/// - the intermediate and the root are CA certificates allowed to sign certificates, with
+///   path length constraints allowing one intermediate;
+/// - the extended key usages of the leaf and intermediate, if present, include
+///   `id-kp-serverAuth` or `anyExtendedKeyUsage`, and the key usage of the leaf, if present,
+///   allows TLS key exchange;
+/// - `hostname` is matched against the DNS names and IP addresses of the leaf's subject
+///   alternative names, as described in [RFC 6125 Section 6.4]. Wildcards are only
+///   supported as the complete left-most label, and the common name is ignored;
+/// - no certificate carries a critical extension other than basic constraints, key usage,
+///   extended key usage and subject alternative name;
+/// - the leaf and the intermediate are not revoked according to `revocation`. Certificates
+///   for which [`requires_revocation_check`] returns `false`, i.e. delegated OCSP responders,
+///   are not checked.
+///
+/// The root is a trust anchor: its signature is not verified.
+///
+/// [RFC 6125 Section 6.4]: https://www.rfc-editor.org/rfc/rfc6125#section-6.4
+pub fn verify_leaf_against_intermediate_and_root(
+    leaf: &[u8],
+    intermediate: &[u8],
+    root: &[u8],
+    time: Time,
+    hostname: &str,
+    verifier: &impl SignatureVerifier,
+    revocation: &impl RevocationChecker,
+) -> Result<Certificate> {
+    let (leaf, leaf_tbs) = Certificate::from_der_with_tbs(leaf)?;
+    let (intermediate, intermediate_tbs) = Certificate::from_der_with_tbs(intermediate)?;
+    let root = <Certificate as der::Decode>::from_der(root)?;
+
+    let chain = [
+        (ChainPosition::Leaf, &leaf),
+        (ChainPosition::Intermediate, &intermediate),
+        (ChainPosition::Root, &root),
+    ];
+
+    for (position, cert) in chain {
+        check_validity(position, cert, time)?;
+        check_critical_extensions(position, cert)?;
+    }
+
+    check_signature(
+        ChainPosition::Leaf,
+        &leaf,
+        leaf_tbs,
+        &intermediate,
+        verifier,
+    )?;
+    check_signature(
+        ChainPosition::Intermediate,
+        &intermediate,
+        intermediate_tbs,
+        &root,
+        verifier,
+    )?;
+
+    check_ca(ChainPosition::Intermediate, &intermediate, 0)?;
+    check_ca(ChainPosition::Root, &root, 1)?;
+
+    check_server_auth(ChainPosition::Leaf, &leaf)?;
+    check_server_auth(ChainPosition::Intermediate, &intermediate)?;
+
+    let tbs = &leaf.tbs_certificate;
+    if let Some((_, key_usage)) = tbs.get::<KeyUsage>()? {
+        if !(key_usage.digital_signature()
+            || key_usage.key_encipherment()
+            || key_usage.key_agreement())
+        {
+            return Err(Error::KeyUsage(ChainPosition::Leaf));
+        }
+    }
+
+    if let Some((_, basic_constraints)) = tbs.get::<BasicConstraints>()? {
+        if basic_constraints.ca {
+            return Err(Error::BasicConstraints(ChainPosition::Leaf));
+        }
+    }
+
+    if !matches_hostname(&leaf, hostname)? {
+        return Err(Error::HostnameMismatch);
+    }
+
+    check_revocation(ChainPosition::Leaf, &leaf, &intermediate, revocation)?;
+    check_revocation(
+        ChainPosition::Intermediate,
+        &intermediate,
+        &root,
+        revocation,
+    )?;
+
+    Ok(leaf)
+}
+
+/// Whether relying parties have to check the revocation status of `cert`.
+///
+/// Returns `false` for delegated OCSP responder certificates, which hold both the
+/// `id-kp-OCSPSigning` extended key usage and the [`OcspNoCheck`] extension: as described in
+/// [RFC 6960 Section 4.2.2.2.1], clients trust such responders for the lifetime of their
+/// certificate. The responder must still be authorized by the CA issuing the certificates it
+/// provides status for, which is left to the caller.
+///
+/// [RFC 6960 Section 4.2.2.2.1]: https://www.rfc-editor.org/rfc/rfc6960#section-4.2.2.2.1
+pub fn requires_revocation_check(cert: &Certificate) -> Result<bool> {
+    let tbs = &cert.tbs_certificate;
+    let ocsp_signing = match tbs.get::<ExtendedKeyUsage>()? {
+        Some((_, eku)) => eku.contains(&ID_KP_OCSP_SIGNING),
+        None => false,
+    };
+
+    Ok(!(ocsp_signing && tbs.get::<OcspNoCheck>()?.is_some()))
+}
+
+/// Check the revocation status of `cert`, issued by `issuer`, if required.
+fn check_revocation(
+    position: ChainPosition,
+    cert: &Certificate,
+    issuer: &Certificate,
+    revocation: &impl RevocationChecker,
+) -> Result<()> {
+    if requires_revocation_check(cert)? && revocation.is_revoked(cert, issuer) {
+        return Err(Error::Revoked(position));
+    }
+
+    Ok(())
+}
+
+fn check_validity(position: ChainPosition, cert: &Certificate, time: Time) -> Result<()> {
+    let validity = &cert.tbs_certificate.validity;
+    let time = time.to_unix_duration();
+
+    if time < validity.not_before.to_unix_duration() || time > validity.not_after.to_unix_duration()
+    {
+        return Err(Error::Validity(position));
+    }
+
+    Ok(())
+}
+
+fn check_critical_extensions(position: ChainPosition, cert: &Certificate) -> Result<()> {
+    const HANDLED: [ObjectIdentifier; 4] = [
+        ID_CE_BASIC_CONSTRAINTS,
+        ID_CE_KEY_USAGE,
+        ID_CE_EXT_KEY_USAGE,
+        ID_CE_SUBJECT_ALT_NAME,
+    ];
+
+    match cert
+        .tbs_certificate
+        .extensions
+        .iter()
+        .flatten()
+        .find(|ext| ext.critical && !HANDLED.contains(&ext.extn_id))
+    {
+        Some(ext) => Err(Error::UnhandledCriticalExtension(position, ext.extn_id)),
+        None => Ok(()),
+    }
+}
+
+/// Check the signature of `cert`, whose `TBSCertificate` is encoded as `tbs`, by `issuer`.
+fn check_signature(
+    position: ChainPosition,
+    cert: &Certificate,
+    tbs: &[u8],
+    issuer: &Certificate,
+    verifier: &impl SignatureVerifier,
+) -> Result<()> {
+    if cert.tbs_certificate.issuer != issuer.tbs_certificate.subject {
+        return Err(Error::IssuerMismatch(position));
+    }
+
+    if cert.tbs_certificate.signature != cert.signature_algorithm {
+        return Err(Error::AlgorithmMismatch(position));
+    }
+
+    let signature = cert
+        .signature
+        .as_bytes()
+        .ok_or_else(|| Error::Signature(position, signature::Error::new()))?;
+
+    verifier
+        .verify_signature(
+            &issuer.tbs_certificate.subject_public_key_info,
+            &cert.signature_algorithm,
+            tbs,
+            signature,
+        )
+        .map_err(|err| Error::Signature(position, err))
+}
+
+/// Check that `cert` may issue certificates followed by `intermediates` CA certificates.
+fn check_ca(position: ChainPosition, cert: &Certificate, intermediates: u8) -> Result<()> {
+    let tbs = &cert.tbs_certificate;
+
+    match tbs.get::<BasicConstraints>()? {
+        Some((_, basic_constraints))
+            if basic_constraints.ca
+                && basic_constraints
+                    .path_len_constraint
+                    .map_or(true, |path_len| path_len >= intermediates) => {}
+        _ => return Err(Error::BasicConstraints(position)),
+    }
+
+    if let Some((_, key_usage)) = tbs.get::<KeyUsage>()? {
+        if !key_usage.key_cert_sign() {
+            return Err(Error::KeyUsage(position));
+        }
+    }
+
+    Ok(())
+}
+
+fn check_server_auth(position: ChainPosition, cert: &Certificate) -> Result<()> {
+    match cert.tbs_certificate.get::<ExtendedKeyUsage>()? {
+        Some((_, eku))
+            if !eku.contains(&ID_KP_SERVER_AUTH) && !eku.contains(&ANY_EXTENDED_KEY_USAGE) =>
+        {
+            Err(Error::ExtendedKeyUsage(position))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Match `hostname` against the subject alternative names of `cert`.
+fn matches_hostname(cert: &Certificate, hostname: &str) -> Result<bool> {
+    let names = match cert.tbs_certificate.get::<SubjectAltName>()? {
+        Some((_, san)) => san.0,
+        None => return Ok(false),
+    };
+
+    if let Ok(ip) = hostname.parse::<IpAddr>() {
+        return Ok(names.iter().any(|name| match (name, ip) {
+            (GeneralName::IpAddress(addr), IpAddr::V4(ip)) => addr.as_bytes() == ip.octets(),
+            (GeneralName::IpAddress(addr), IpAddr::V6(ip)) => addr.as_bytes() == ip.octets(),
+            _ => false,
+        }));
+    }
+
+    let hostname = normalize_dns_name(hostname);
+    Ok(names.iter().any(|name| match name {
+        GeneralName::DnsName(pattern) => {
+            matches_dns_name(&normalize_dns_name(pattern.as_str()), &hostname)
+        }
+        _ => false,
+    }))
+}
+
+/// Lowercase a DNS name and remove its trailing dot.
+fn normalize_dns_name(name: &str) -> String {
+    name.strip_suffix('.').unwrap_or(name).to_ascii_lowercase()
+}
+
+/// Match a normalized DNS name against a normalized reference identifier.
+fn matches_dns_name(pattern: &str, hostname: &str) -> bool {
+    if hostname.is_empty() {
+        return false;
+    }
+
+    match pattern.strip_prefix("*.") {
+        // The wildcard matches a single label, and must be followed by at least two labels
+        Some(suffix) if suffix.contains('.') => match hostname.split_once('.') {
+            Some((label, rest)) => !label.is_empty() && rest == suffix,
+            None => false,
+        },
+        Some(_) => false,
+        None => pattern == hostname,
+    }
+}
@@ -0,0 +1,112 @@
+//! Attribute certificate tests
+
+use core::time::Duration;
+use std::str::FromStr;
+
+use der::asn1::{BitString, GeneralizedTime, SetOfVec, Utf8StringRef};
+use der::{Any, Decode, Encode, Tag, Tagged};
+use spki::AlgorithmIdentifierOwned;
+use x509_cert::attr::Attribute;
+use x509_cert::attr_cert::*;
+use x509_cert::ext::pkix::name::GeneralName;
+use x509_cert::name::Name;
+use x509_cert::serial_number::SerialNumber;
+
+const ID_AT_ROLE: der::asn1::ObjectIdentifier = der::asn1::ObjectIdentifier::new_unwrap("2.5.4.72");
+const ECDSA_WITH_SHA256: der::asn1::ObjectIdentifier =
+    der::asn1::ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+
+fn attribute_certificate() -> AttributeCertificate {
+    let holder_issuer = Name::from_str("CN=Holder CA,O=Example").unwrap();
+    let ac_issuer = Name::from_str("CN=Attribute Authority,O=Example").unwrap();
+    let signature = AlgorithmIdentifierOwned {
+        oid: ECDSA_WITH_SHA256,
+        parameters: None,
+    };
+
+    let role = Any::encode_from(&Utf8StringRef::new("operator").unwrap()).unwrap();
+
+    AttributeCertificate {
+        acinfo: AttributeCertificateInfo {
+            version: AttCertVersion::V2,
+            holder: Holder {
+                base_certificate_id: Some(IssuerSerial {
+                    issuer: vec![GeneralName::DirectoryName(holder_issuer)],
+                    serial: SerialNumber::from(42u32),
+                    issuer_uid: None,
+                }),
+                ..Default::default()
+            },
+            issuer: AttCertIssuer::V2Form(V2Form {
+                issuer_name: Some(vec![GeneralName::DirectoryName(ac_issuer)]),
+                ..Default::default()
+            }),
+            signature: signature.clone(),
+            serial_number: SerialNumber::from(1u32),
+            attr_cert_validity_period: AttCertValidityPeriod {
+                not_before_time: GeneralizedTime::from_unix_duration(Duration::from_secs(
+                    1_700_000_000,
+                ))
+                .unwrap(),
+                not_after_time: GeneralizedTime::from_unix_duration(Duration::from_secs(
+                    1_800_000_000,
+                ))
+                .unwrap(),
+            },
+            attributes: vec![Attribute {
+                oid: ID_AT_ROLE,
+                values: SetOfVec::try_from(vec![role]).unwrap(),
+            }],
+            issuer_unique_id: None,
+            extensions: None,
+        },
+        signature_algorithm: signature,
+        signature_value: BitString::from_bytes(&[0xAB; 64]).unwrap(),
+    }
+}
+
+#[test]
+fn attribute_certificate_round_trip() {
+    let ac = attribute_certificate();
+    let der = ac.to_der().unwrap();
+
+    let decoded = AttributeCertificate::from_der(&der).unwrap();
+    assert_eq!(decoded, ac);
+    assert_eq!(decoded.to_der().unwrap(), der);
+}
+
+#[test]
+fn attribute_certificate_tagging() {
+    let ac = attribute_certificate();
+
+    // baseCertificateID is [0] IMPLICIT IssuerSerial
+    let holder = ac.acinfo.holder.to_der().unwrap();
+    assert_eq!(holder[2], 0xA0);
+
+    // v2Form is [0] IMPLICIT V2Form, containing an untagged GeneralNames
+    let issuer = ac.acinfo.issuer.to_der().unwrap();
+    assert_eq!(
+        ac.acinfo.issuer.tag(),
+        Tag::ContextSpecific {
+            constructed: true,
+            number: der::TagNumber::N0,
+        }
+    );
+    assert_eq!(issuer[0], 0xA0);
+    assert_eq!(issuer[2], 0x30);
+
+    // version v2 is encoded as INTEGER 1
+    let acinfo = Vec::<Any>::from_der(&ac.acinfo.to_der().unwrap()).unwrap();
+    assert_eq!(acinfo[0].to_der().unwrap(), [0x02, 0x01, 0x01]);
+}
+
+#[test]
+fn v1_form_issuer() {
+    let issuer = AttCertIssuer::V1Form(vec![GeneralName::DirectoryName(
+        Name::from_str("CN=Attribute Authority").unwrap(),
+    )]);
+
+    let der = issuer.to_der().unwrap();
+    assert_eq!(der[0], 0x30);
+    assert_eq!(AttCertIssuer::from_der(&der).unwrap(), issuer);
+}
@@ -1,6 +1,6 @@
 #![cfg(all(feature = "builder", feature = "pem"))]
 
-use der::{pem::LineEnding, Decode, Encode, EncodePem, Tag};
+use der::{pem::LineEnding, Decode, Encode, EncodePem};
 use p256::{ecdsa::DerSignature, pkcs8::DecodePrivateKey, NistP256};
 use rsa::pkcs1::DecodeRsaPrivateKey;
 use rsa::pkcs1v15::SigningKey;
@@ -8,16 +8,22 @@ use sha2::Sha256;
 use spki::SubjectPublicKeyInfoOwned;
 use std::{str::FromStr, time::Duration};
 use const_oid::ObjectIdentifier;
-use der::asn1::SetOfVec;
+use der::asn1::{BitString, SetOfVec};
 
 use x509_cert::{
     builder::{Builder, CertificateBuilder, Profile, RequestBuilder},
-    ext::pkix::{name::GeneralName, SubjectAltName},
+    ext::pkix::{
+        name::{DirectoryString, GeneralName},
+        ExtendedKeyUsage, KeyUsage, OcspNoCheck, SubjectAltName, TlsFeatures,
+    },
     name::Name,
     serial_number::SerialNumber,
     time::Validity,
 };
-use x509_cert::attr::{Attribute, AttributeValue};
+use x509_cert::attr::{
+    pkcs9::{ChallengePassword, Pkcs9Attribute},
+    Attribute,
+};
 use x509_cert_test_support::{openssl, zlint};
 
 const RSA_2048_DER_EXAMPLE: &[u8] = include_bytes!("examples/rsa2048-pub.der");
@@ -194,6 +200,79 @@ fn leaf_certificate() {
     }
 }
 
+#[test]
+fn must_staple_leaf_certificate() {
+    let serial_number = SerialNumber::from(42u32);
+    let validity = Validity::from_now(Duration::new(5, 0)).unwrap();
+
+    let issuer =
+        Name::from_str("CN=World domination corporation,O=World domination Inc,C=US").unwrap();
+    let profile = Profile::Leaf {
+        issuer,
+        enable_key_agreement: false,
+        enable_key_encipherment: false,
+        #[cfg(feature = "hazmat")]
+        include_subject_key_identifier: true,
+    };
+
+    let subject = Name::from_str("CN=service.domination.world").unwrap();
+    let pub_key =
+        SubjectPublicKeyInfoOwned::try_from(RSA_2048_DER_EXAMPLE).expect("get rsa pub key");
+
+    let signer = ecdsa_signer();
+    let mut builder =
+        CertificateBuilder::new(profile, serial_number, validity, subject, pub_key, &signer)
+            .expect("Create certificate");
+    builder
+        .add_extension(&TlsFeatures::must_staple())
+        .expect("add TLS feature extension");
+
+    let certificate = builder.build::<DerSignature>().unwrap();
+    assert!(certificate.is_must_staple().unwrap());
+
+    let pem = certificate.to_pem(LineEnding::LF).expect("generate pem");
+    println!("{}", openssl::check_certificate(pem.as_bytes()));
+}
+
+#[test]
+fn ocsp_responder_certificate() {
+    let serial_number = SerialNumber::from(42u32);
+    let validity = Validity::from_now(Duration::new(5, 0)).unwrap();
+
+    let issuer =
+        Name::from_str("CN=World domination corporation,O=World domination Inc,C=US").unwrap();
+    let profile = Profile::OcspResponder {
+        issuer: issuer.clone(),
+    };
+
+    let subject = Name::from_str("CN=ocsp.domination.world").unwrap();
+    let pub_key =
+        SubjectPublicKeyInfoOwned::try_from(RSA_2048_DER_EXAMPLE).expect("get rsa pub key");
+
+    let signer = ecdsa_signer();
+    let builder =
+        CertificateBuilder::new(profile, serial_number, validity, subject, pub_key, &signer)
+            .expect("Create certificate");
+
+    let certificate = builder.build::<DerSignature>().unwrap();
+    let tbs = &certificate.tbs_certificate;
+    assert_eq!(tbs.issuer, issuer);
+
+    let (_, key_usage) = tbs.get::<KeyUsage>().unwrap().unwrap();
+    assert!(key_usage.digital_signature());
+    assert!(!key_usage.key_cert_sign());
+
+    let (critical, eku) = tbs.get::<ExtendedKeyUsage>().unwrap().unwrap();
+    assert!(critical);
+    assert_eq!(eku, ExtendedKeyUsage::new().ocsp_signing());
+
+    let (critical, _) = tbs.get::<OcspNoCheck>().unwrap().unwrap();
+    assert!(!critical);
+
+    let pem = certificate.to_pem(LineEnding::LF).expect("generate pem");
+    println!("{}", openssl::check_certificate(pem.as_bytes()));
+}
+
 #[test]
 fn pss_certificate() {
     let serial_number = SerialNumber::from(42u32);
@@ -282,15 +361,11 @@ fn certificate_request() {
         ))]))
         .unwrap();
 
-    let challenge_password = "Ch@113ng3 9@zzw0rd";
-    let challenge_password_attribute_value = AttributeValue::new(Tag::Utf8String, challenge_password.as_bytes()).unwrap();
-    let mut values = SetOfVec::new();
-    values.insert(challenge_password_attribute_value).unwrap();
-    let oid_pkcs9_challengepassword = ObjectIdentifier::new("1.2.840.113549.1.9.7").unwrap();
-    builder.add_attribute(Attribute {
-        oid: oid_pkcs9_challengepassword,
-        values,
-    }).unwrap();
+    let challenge_password =
+        ChallengePassword(DirectoryString::Utf8String("Ch@113ng3 9@zzw0rd".into()));
+    builder
+        .add_attribute(challenge_password.to_attribute().unwrap())
+        .unwrap();
 
     let cert_req = builder.build::<DerSignature>().unwrap();
     let pem = cert_req.to_pem(LineEnding::LF).expect("generate pem");
@@ -300,3 +375,324 @@ fn certificate_request() {
     file.write_all(pem.as_bytes()).expect("Create pem file");
     println!("{}", openssl::check_request(pem.as_bytes()));
 }
+
+#[test]
+fn copy_requested_extensions() {
+    use std::net::{IpAddr, Ipv4Addr};
+    use x509_cert::{
+        ext::{AsExtension, Extension},
+        request::{CertReqInfo, ExtensionReq},
+    };
+
+    let subject = Name::from_str("CN=service.domination.world").unwrap();
+    let san = SubjectAltName(vec![GeneralName::from(IpAddr::V4(Ipv4Addr::new(
+        192, 0, 2, 0,
+    )))])
+    .to_extension(&subject, &[])
+    .unwrap();
+    // Private extension unknown to this crate, with a non-DER (indefinite length) value
+    let unknown = Extension {
+        extn_id: ObjectIdentifier::new_unwrap("1.3.6.1.4.1.99999.1"),
+        critical: true,
+        extn_value: der::asn1::OctetString::new(&[0x30, 0x80, 0x05, 0x00, 0x00, 0x00][..])
+            .unwrap(),
+    };
+    // Requested key usage is overridden by the one set by the profile
+    let key_usage = Extension {
+        extn_id: const_oid::db::rfc5280::ID_CE_KEY_USAGE,
+        critical: false,
+        extn_value: der::asn1::OctetString::new(&[0x03, 0x02, 0x00, 0xff][..]).unwrap(),
+    };
+
+    let extension_req = ExtensionReq(vec![san.clone(), unknown.clone(), key_usage]);
+    let mut attributes = SetOfVec::new();
+    attributes
+        .insert(Attribute::try_from(extension_req).unwrap())
+        .unwrap();
+    let info = CertReqInfo {
+        version: Default::default(),
+        subject: subject.clone(),
+        public_key: SubjectPublicKeyInfoOwned::try_from(PKCS8_PUBLIC_KEY_DER).unwrap(),
+        attributes,
+    };
+    let info = CertReqInfo::from_der(&info.to_der().unwrap()).unwrap();
+    let requested = info.requested_extensions().unwrap();
+    assert_eq!(requested.len(), 3);
+
+    let issuer =
+        Name::from_str("CN=World domination corporation,O=World domination Inc,C=US").unwrap();
+    let profile = Profile::Leaf {
+        issuer,
+        enable_key_agreement: false,
+        enable_key_encipherment: false,
+        #[cfg(feature = "hazmat")]
+        include_subject_key_identifier: true,
+    };
+    let signer = ecdsa_signer();
+    let mut builder = CertificateBuilder::new(
+        profile,
+        SerialNumber::from(42u32),
+        Validity::from_now(Duration::new(5, 0)).unwrap(),
+        info.subject.clone(),
+        info.public_key.clone(),
+        &signer,
+    )
+    .unwrap();
+    builder
+        .copy_extensions(&requested, |extension| {
+            if extension.extn_id == unknown.extn_id {
+                Some(false)
+            } else {
+                Some(extension.critical)
+            }
+        })
+        .unwrap();
+
+    let certificate = builder.build::<DerSignature>().unwrap();
+    let certificate = x509_cert::Certificate::from_der(&certificate.to_der().unwrap()).unwrap();
+    let extensions = certificate.tbs_certificate.extensions.unwrap();
+
+    assert!(extensions.contains(&san));
+    let copied = extensions
+        .iter()
+        .find(|ext| ext.extn_id == unknown.extn_id)
+        .unwrap();
+    assert!(!copied.critical);
+    assert_eq!(copied.extn_value, unknown.extn_value);
+    let key_usages: Vec<_> = extensions
+        .iter()
+        .filter(|ext| ext.extn_id == const_oid::db::rfc5280::ID_CE_KEY_USAGE)
+        .collect();
+    assert_eq!(key_usages.len(), 1);
+    assert!(key_usages[0].critical);
+}
+
+/// Leaf certificate builder with fixed inputs and two extensions added after the profile's.
+fn fixed_leaf_builder(
+    signer: &ecdsa::SigningKey<NistP256>,
+) -> CertificateBuilder<'_, ecdsa::SigningKey<NistP256>> {
+    use std::net::{IpAddr, Ipv4Addr};
+    use x509_cert::ext::pkix::TlsFeature;
+    use x509_cert::time::Time;
+
+    let not_before =
+        der::asn1::UtcTime::from_unix_duration(Duration::from_secs(1_600_000_000)).unwrap();
+    let not_after =
+        der::asn1::UtcTime::from_unix_duration(Duration::from_secs(1_700_000_000)).unwrap();
+    let validity = Validity {
+        not_before: Time::from(not_before),
+        not_after: Time::from(not_after),
+    };
+    let profile = Profile::Leaf {
+        issuer: Name::from_str("CN=World domination corporation,O=World domination Inc,C=US")
+            .unwrap(),
+        enable_key_agreement: false,
+        enable_key_encipherment: false,
+        #[cfg(feature = "hazmat")]
+        include_subject_key_identifier: true,
+    };
+
+    let mut builder = CertificateBuilder::new(
+        profile,
+        SerialNumber::from(42u32),
+        validity,
+        Name::from_str("CN=service.domination.world").unwrap(),
+        SubjectPublicKeyInfoOwned::try_from(PKCS8_PUBLIC_KEY_DER).unwrap(),
+        signer,
+    )
+    .unwrap();
+    builder
+        .add_extension(&SubjectAltName(vec![GeneralName::from(IpAddr::V4(
+            Ipv4Addr::new(192, 0, 2, 0),
+        ))]))
+        .unwrap();
+    builder
+        .add_extension(&TlsFeatures(vec![TlsFeature::StatusRequest]))
+        .unwrap();
+    builder
+}
+
+#[test]
+fn extension_order() {
+    use const_oid::db::rfc5280::{
+        ID_CE_AUTHORITY_KEY_IDENTIFIER, ID_CE_BASIC_CONSTRAINTS, ID_CE_KEY_USAGE,
+        ID_CE_SUBJECT_ALT_NAME, ID_CE_SUBJECT_KEY_IDENTIFIER,
+    };
+    use x509_cert::builder::ExtensionOrder;
+    use x509_cert::ext::pkix::ID_PE_TLS_FEATURE;
+
+    let signer = ecdsa_signer();
+
+    let insertion = fixed_leaf_builder(&signer).build::<DerSignature>().unwrap();
+    let insertion_extensions = insertion.tbs_certificate.extensions.as_ref().unwrap();
+    assert_eq!(
+        insertion_extensions
+            .iter()
+            .map(|ext| ext.extn_id)
+            .collect::<Vec<_>>(),
+        [
+            ID_CE_SUBJECT_KEY_IDENTIFIER,
+            ID_CE_AUTHORITY_KEY_IDENTIFIER,
+            ID_CE_BASIC_CONSTRAINTS,
+            ID_CE_KEY_USAGE,
+            ID_CE_SUBJECT_ALT_NAME,
+            ID_PE_TLS_FEATURE,
+        ]
+    );
+
+    let mut builder = fixed_leaf_builder(&signer);
+    builder.set_extension_order(ExtensionOrder::Canonical);
+    let canonical = builder.build::<DerSignature>().unwrap();
+    let canonical_extensions = canonical.tbs_certificate.extensions.as_ref().unwrap();
+    assert_eq!(
+        canonical_extensions
+            .iter()
+            .map(|ext| ext.extn_id)
+            .collect::<Vec<_>>(),
+        [
+            ID_PE_TLS_FEATURE,
+            ID_CE_SUBJECT_KEY_IDENTIFIER,
+            ID_CE_KEY_USAGE,
+            ID_CE_SUBJECT_ALT_NAME,
+            ID_CE_BASIC_CONSTRAINTS,
+            ID_CE_AUTHORITY_KEY_IDENTIFIER,
+        ]
+    );
+
+    // golden bytes, which must not change from one build to another
+    assert_eq!(
+        insertion_extensions.to_der().unwrap(),
+        hex_literal::hex!(
+            "308182"
+            "301d0603551d0e041604148d07d84f5a4e486a0961f8f8802581615b309780"
+            "301f0603551d230418301680148d07d84f5a4e486a0961f8f8802581615b309780"
+            "300c0603551d130101ff04023000"
+            "300e0603551d0f0101ff0404030206c0"
+            "300f0603551d11040830068704c0000200"
+            "301106082b0601050507011804053003020105"
+        )
+        .to_vec()
+    );
+    assert_eq!(
+        canonical_extensions.to_der().unwrap(),
+        hex_literal::hex!(
+            "308182"
+            "301106082b0601050507011804053003020105"
+            "301d0603551d0e041604148d07d84f5a4e486a0961f8f8802581615b309780"
+            "300e0603551d0f0101ff0404030206c0"
+            "300f0603551d11040830068704c0000200"
+            "300c0603551d130101ff04023000"
+            "301f0603551d230418301680148d07d84f5a4e486a0961f8f8802581615b309780"
+        )
+        .to_vec()
+    );
+    assert_eq!(
+        insertion.to_der().unwrap(),
+        fixed_leaf_builder(&signer)
+            .build::<DerSignature>()
+            .unwrap()
+            .to_der()
+            .unwrap()
+    );
+}
+
+#[test]
+fn request_extension_order() {
+    use x509_cert::builder::ExtensionOrder;
+    use x509_cert::ext::pkix::TlsFeature;
+
+    let subject = Name::from_str("CN=service.domination.world").unwrap();
+    let signer = ecdsa_signer();
+    let mut builder = RequestBuilder::new(subject, &signer).unwrap();
+    builder
+        .add_extension(&SubjectAltName(vec![GeneralName::DnsName(
+            der::asn1::Ia5String::new("service.domination.world").unwrap(),
+        )]))
+        .unwrap();
+    builder
+        .add_extension(&TlsFeatures(vec![TlsFeature::StatusRequest]))
+        .unwrap();
+    builder.set_extension_order(ExtensionOrder::Canonical);
+
+    let cert_req = builder.build::<DerSignature>().unwrap();
+    let extensions = cert_req.info.requested_extensions().unwrap();
+    assert_eq!(
+        extensions.iter().map(|ext| ext.extn_id).collect::<Vec<_>>(),
+        [
+            x509_cert::ext::pkix::ID_PE_TLS_FEATURE,
+            const_oid::db::rfc5280::ID_CE_SUBJECT_ALT_NAME,
+        ]
+    );
+}
+
+/// Handle to a key held by an external signer, exposing only its public half.
+struct ExternalKey {
+    verifying_key: p256::ecdsa::VerifyingKey,
+}
+
+impl signature::Keypair for ExternalKey {
+    type VerifyingKey = p256::ecdsa::VerifyingKey;
+
+    fn verifying_key(&self) -> Self::VerifyingKey {
+        self.verifying_key
+    }
+}
+
+impl spki::DynSignatureAlgorithmIdentifier for ExternalKey {
+    fn signature_algorithm_identifier(&self) -> spki::Result<spki::AlgorithmIdentifierOwned> {
+        self.verifying_key.signature_algorithm_identifier()
+    }
+}
+
+#[test]
+fn external_signing() {
+    use p256::ecdsa::signature::{Signer, Verifier};
+    use spki::DynSignatureAlgorithmIdentifier;
+
+    let hsm = ecdsa_signer();
+    let key = ExternalKey {
+        verifying_key: *hsm.verifying_key(),
+    };
+
+    let serial_number = SerialNumber::from(42u32);
+    let validity = Validity::from_now(Duration::new(5, 0)).unwrap();
+    let subject = Name::from_str("CN=World domination corporation,O=World domination Inc,C=US")
+        .unwrap();
+    let pub_key = SubjectPublicKeyInfoOwned::try_from(PKCS8_PUBLIC_KEY_DER).unwrap();
+
+    let builder =
+        CertificateBuilder::new(Profile::Root, serial_number, validity, subject, pub_key, &key)
+            .expect("Create certificate");
+    let prepared = builder.prepare().unwrap();
+
+    let signature: DerSignature = hsm.sign(prepared.tbs());
+    let certificate = prepared
+        .attach_signature(BitString::from_bytes(signature.as_bytes()).unwrap())
+        .unwrap();
+
+    let tbs = certificate.tbs_certificate.to_der().unwrap();
+    let signature =
+        DerSignature::from_bytes(certificate.signature.as_bytes().unwrap()).unwrap();
+    key.verifying_key.verify(&tbs, &signature).unwrap();
+
+    let reparsed = x509_cert::Certificate::from_der(&certificate.to_der().unwrap()).unwrap();
+    assert_eq!(reparsed, certificate);
+
+    let subject = Name::from_str("CN=service.domination.world").unwrap();
+    let prepared = RequestBuilder::new(subject, &key)
+        .unwrap()
+        .prepare()
+        .unwrap();
+    let signature: DerSignature = hsm.sign(prepared.tbs());
+    let cert_req = prepared
+        .attach_signature(BitString::from_bytes(signature.as_bytes()).unwrap())
+        .unwrap();
+
+    let info = cert_req.info.to_der().unwrap();
+    let signature = DerSignature::from_bytes(cert_req.signature.as_bytes().unwrap()).unwrap();
+    key.verifying_key.verify(&info, &signature).unwrap();
+    assert_eq!(
+        cert_req.algorithm,
+        key.signature_algorithm_identifier().unwrap()
+    );
+}
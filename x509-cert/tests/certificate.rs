@@ -160,6 +160,35 @@ fn reencode_cert() {
     assert_eq!(&parsed_coverage_tbs.extensions[4..], encoded_extensions);
 }
 
+#[test]
+fn decode_cert_with_tbs() {
+    let der_encoded_cert =
+        include_bytes!("examples/026EDA6FA1EDFA8C253936C75B5EEBD954BFF452.fake.der");
+    let defer_cert = DeferDecodeCertificate::from_der(der_encoded_cert).unwrap();
+
+    let (cert, tbs_der) = Certificate::from_der_with_tbs(der_encoded_cert).unwrap();
+    assert_eq!(cert, Certificate::from_der(der_encoded_cert).unwrap());
+    assert_eq!(tbs_der, defer_cert.tbs_certificate);
+
+    let mut trailing = der_encoded_cert.to_vec();
+    trailing.push(0);
+    assert!(Certificate::from_der_with_tbs(&trailing).is_err());
+
+    // BER `BOOLEAN` true in the `critical` field of an extension
+    let mut ber = der_encoded_cert.to_vec();
+    let offset = ber
+        .windows(3)
+        .position(|w| w == [0x01, 0x01, 0xFF])
+        .unwrap();
+    ber[offset + 2] = 0x01;
+    assert!(Certificate::from_der(&ber).is_err());
+
+    let (ber_cert, ber_tbs) = Certificate::from_der_with_tbs(&ber).unwrap();
+    assert_eq!(ber_cert, cert);
+    assert_eq!(ber_tbs.len(), tbs_der.len());
+    assert_ne!(ber_tbs, tbs_der);
+}
+
 #[test]
 fn decode_oversized_oids() {
     let o1parse = ObjectIdentifier::from_der(&hex!(
@@ -446,3 +475,44 @@ fn load_certificate_chains() {
 
     assert_eq!(chain.len(), 4, "4 certificates are expected in this chain");
 }
+
+#[cfg(feature = "pem")]
+#[test]
+fn pem_bundle() {
+    use der::{pem::LineEnding, EncodePem};
+
+    let pem_encoded_chain = include_bytes!("examples/crates.io-chain.pem");
+
+    let path = PkiPath::from_pem_bundle(pem_encoded_chain).expect("parse PEM bundle");
+    assert_eq!(path.len(), 4);
+    assert_eq!(
+        path[0].tbs_certificate.subject.to_string(),
+        "CN=Starfield Services Root Certificate Authority - G2,O=Starfield Technologies\\, Inc.,L=Scottsdale,ST=Arizona,C=US"
+    );
+    assert_eq!(path[3].tbs_certificate.subject.to_string(), "CN=crates.io");
+
+    // The top-most certificate is not self-issued, so it is kept
+    let bundle = path.to_pem_bundle(LineEnding::LF, true).unwrap();
+    assert_eq!(bundle.matches("-----BEGIN CERTIFICATE-----").count(), 4);
+    assert_eq!(PkiPath::from_pem_bundle(bundle.as_bytes()).unwrap(), path);
+
+    // Root-first input is rejected
+    let mut reversed = path.clone();
+    reversed.reverse();
+    assert!(reversed.to_pem_bundle(LineEnding::LF, false).is_err());
+    let bundle = path[2..]
+        .iter()
+        .chain(&path[..2])
+        .map(|cert| cert.to_pem(LineEnding::LF).unwrap())
+        .collect::<String>();
+    assert!(PkiPath::from_pem_bundle(bundle.as_bytes()).is_err());
+
+    // Self-issued roots can be excluded
+    let root = Certificate::from_pem(include_bytes!("examples/rsa2048-crt.pem")).unwrap();
+    let path = vec![root];
+    assert_eq!(path.to_pem_bundle(LineEnding::LF, true).unwrap(), "");
+    assert_eq!(
+        path.to_pem_bundle(LineEnding::LF, false).unwrap(),
+        path[0].to_pem(LineEnding::LF).unwrap()
+    );
+}
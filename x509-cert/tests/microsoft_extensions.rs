@@ -0,0 +1,79 @@
+//! Microsoft-specific extension tests
+use const_oid::db::rfc5280::{ID_KP_CLIENT_AUTH, ID_KP_SERVER_AUTH};
+use const_oid::{AssociatedOid, ObjectIdentifier};
+use der::asn1::BmpString;
+use der::{Decode, Encode};
+use hex_literal::hex;
+use x509_cert::ext::microsoft::{
+    ApplicationCertPolicies, CertificateTemplate, CertificateTemplateName,
+};
+use x509_cert::ext::pkix::certpolicy::PolicyInformation;
+use x509_cert::ext::AsExtension;
+use x509_cert::name::Name;
+
+#[test]
+fn decode_certificate_template_name() {
+    let der = hex!("1e200044006f006d00610069006e0043006f006e00740072006f006c006c00650072");
+
+    let name = CertificateTemplateName::from_der(&der).unwrap();
+    assert_eq!(name.0.to_string(), "DomainController");
+    assert_eq!(name.to_der().unwrap(), der);
+
+    let built = CertificateTemplateName(BmpString::from_utf8("DomainController").unwrap());
+    assert_eq!(built, name);
+}
+
+#[test]
+fn decode_certificate_template() {
+    let der = hex!(
+        "302906212b060104018237150887d59a76818dbd588287c72885b79f3a878bca1c8147010e020164020104"
+    );
+
+    let template = CertificateTemplate::from_der(&der).unwrap();
+    assert_eq!(
+        template.template_id,
+        ObjectIdentifier::new_unwrap(
+            "1.3.6.1.4.1.311.21.8.16076150.2318040.4318120.11390906.14869788.199.1.14"
+        )
+    );
+    assert_eq!(template.template_major_version, 100);
+    assert_eq!(template.template_minor_version, Some(4));
+    assert_eq!(template.to_der().unwrap(), der);
+}
+
+#[test]
+fn decode_certificate_template_without_minor_version() {
+    let der = hex!("3014060b2b06010401823715080102020500b2d05e00");
+
+    let template = CertificateTemplate::from_der(&der).unwrap();
+    assert_eq!(template.template_major_version, 3_000_000_000);
+    assert_eq!(template.template_minor_version, None);
+    assert_eq!(template.to_der().unwrap(), der);
+}
+
+#[test]
+fn decode_application_cert_policies() {
+    let der = hex!("3018300a06082b06010505070302300a06082b06010505070301");
+
+    let policies = ApplicationCertPolicies::from_der(&der).unwrap();
+    let ids: Vec<_> = policies.0.iter().map(|p| p.policy_identifier).collect();
+    assert_eq!(ids, [ID_KP_CLIENT_AUTH, ID_KP_SERVER_AUTH]);
+    assert!(policies.0.iter().all(|p| p.policy_qualifiers.is_none()));
+    assert_eq!(policies.to_der().unwrap(), der);
+}
+
+#[test]
+fn application_cert_policies_as_extension() {
+    let policies = ApplicationCertPolicies(vec![PolicyInformation {
+        policy_identifier: ID_KP_CLIENT_AUTH,
+        policy_qualifiers: None,
+    }]);
+
+    let ext = policies.to_extension(&Name::default(), &[]).unwrap();
+    assert_eq!(ext.extn_id, ApplicationCertPolicies::OID);
+    assert!(!ext.critical);
+    assert_eq!(
+        ApplicationCertPolicies::from_der(ext.extn_value.as_bytes()).unwrap(),
+        policies
+    );
+}
@@ -4,7 +4,8 @@ use const_oid::ObjectIdentifier;
 use der::asn1::{Ia5StringRef, OctetStringRef, PrintableStringRef, SetOfVec, Utf8StringRef};
 use der::{Any, Decode, Encode, Tag, Tagged};
 use hex_literal::hex;
-use x509_cert::attr::AttributeTypeAndValue;
+use std::str::FromStr;
+use x509_cert::attr::{AttributeTypeAndValue, StringEncoding};
 use x509_cert::name::{Name, RdnSequence, RelativeDistinguishedName};
 
 #[test]
@@ -368,3 +369,47 @@ fn rdns_serde() {
         }
     }
 }
+
+#[test]
+fn rdns_string_types() {
+    let name = Name::from_str(
+        "emailAddress=admin@example.com,DC=example,dnQualifier=q1,serialNumber=42,CN=Test,C=US",
+    )
+    .unwrap();
+
+    let tags: Vec<_> = name
+        .0
+        .iter()
+        .map(|rdn| rdn.0.get(0).unwrap().value.tag())
+        .collect();
+    assert_eq!(
+        tags,
+        [
+            Tag::PrintableString,
+            Tag::Utf8String,
+            Tag::PrintableString,
+            Tag::PrintableString,
+            Tag::Ia5String,
+            Tag::Ia5String,
+        ]
+    );
+    assert_eq!(
+        name.to_string(),
+        "EMAIL=admin@example.com,DC=example,DNQUALIFIER=q1,SERIALNUMBER=42,CN=Test,C=US"
+    );
+
+    let name =
+        Name::from_str_with("emailAddress=admin@example.com,C=US", StringEncoding::Utf8).unwrap();
+    let tags: Vec<_> = name
+        .0
+        .iter()
+        .map(|rdn| rdn.0.get(0).unwrap().value.tag())
+        .collect();
+    assert_eq!(tags, [Tag::PrintableString, Tag::Utf8String]);
+
+    // Values must be valid for the string type of the attribute
+    assert!(Name::from_str("emailAddress=ädmin@example.com").is_err());
+    assert!(Name::from_str("C=U_").is_err());
+    assert!(Name::from_str_with("emailAddress=ädmin@example.com", StringEncoding::Utf8).is_ok());
+    assert!(Name::from_str_with("C=U_", StringEncoding::Utf8).is_err());
+}
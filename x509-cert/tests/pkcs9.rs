@@ -0,0 +1,128 @@
+//! PKCS#9 attribute tests
+
+use der::asn1::{BmpString, Ia5String, OctetString, SetOfVec, UniversalString};
+use der::{Any, DateTime, Decode, Encode, Tag, Tagged};
+use hex_literal::hex;
+use x509_cert::attr::pkcs9::{
+    ChallengePassword, ContentType, EmailAddress, FriendlyName, LocalKeyId, MessageDigest,
+    Pkcs9Attribute, Pkcs9String, SigningTime, UnstructuredName, PKCS_9_AT_CHALLENGE_PASSWORD,
+    PKCS_9_AT_FRIENDLY_NAME,
+};
+use x509_cert::attr::{Attribute, Attributes};
+use x509_cert::ext::pkix::name::DirectoryString;
+use x509_cert::request::ExtensionReq;
+use x509_cert::time::Time;
+
+fn round_trip<T: Pkcs9Attribute + core::fmt::Debug + PartialEq>(value: T) -> Attribute {
+    let attribute = value.to_attribute().unwrap();
+    assert_eq!(attribute.oid, T::OID);
+    assert_eq!(attribute.values.len(), 1);
+
+    let attributes = Attributes::try_from([attribute.clone()]).unwrap();
+    assert_eq!(T::from_attributes(&attributes).unwrap(), Some(value));
+    attribute
+}
+
+#[test]
+fn email_address() {
+    let attribute = round_trip(EmailAddress(Ia5String::new("user@example.com").unwrap()));
+    assert_eq!(attribute.values.get(0).unwrap().tag(), Tag::Ia5String);
+}
+
+#[test]
+fn unstructured_name() {
+    round_trip(UnstructuredName(Pkcs9String::Ia5String(
+        Ia5String::new("host.example.com").unwrap(),
+    )));
+    round_trip(UnstructuredName(Pkcs9String::Utf8String(
+        "host.example.com".into(),
+    )));
+
+    let attribute = round_trip(UnstructuredName(Pkcs9String::BmpString(
+        BmpString::from_utf8("host").unwrap(),
+    )));
+    assert_eq!(attribute.values.get(0).unwrap().tag(), Tag::BmpString);
+
+    let attribute = round_trip(UnstructuredName(Pkcs9String::UniversalString(
+        UniversalString::from_utf8("host").unwrap(),
+    )));
+    assert_eq!(attribute.values.get(0).unwrap().tag(), Tag::UniversalString);
+}
+
+#[test]
+fn content_type() {
+    let attribute = round_trip(ContentType(const_oid::db::rfc5911::ID_DATA));
+    assert_eq!(
+        attribute.to_der().unwrap(),
+        hex!("301806092a864886f70d010903310b06092a864886f70d010701")
+    );
+}
+
+#[test]
+fn message_digest() {
+    round_trip(MessageDigest(OctetString::new(hex!("01020304")).unwrap()));
+}
+
+#[test]
+fn signing_time() {
+    let date_time = DateTime::new(2023, 5, 1, 12, 0, 0).unwrap();
+    let attribute = round_trip(SigningTime(Time::UtcTime(date_time.try_into().unwrap())));
+    assert_eq!(attribute.values.get(0).unwrap().tag(), Tag::UtcTime);
+    round_trip(SigningTime(Time::GeneralTime(date_time.into())));
+}
+
+#[test]
+fn challenge_password() {
+    let attribute = round_trip(ChallengePassword(DirectoryString::Utf8String(
+        "hunter42".into(),
+    )));
+    assert_eq!(
+        attribute.to_der().unwrap(),
+        hex!("301706092a864886f70d010907310a0c0868756e7465723432")
+    );
+}
+
+#[test]
+fn extension_request() {
+    let attribute = round_trip(ExtensionReq::default());
+    assert_eq!(
+        attribute,
+        Attribute::try_from(ExtensionReq::default()).unwrap()
+    );
+}
+
+#[test]
+fn friendly_name() {
+    let attribute = round_trip(FriendlyName(BmpString::from_utf8("my key").unwrap()));
+    assert_eq!(attribute.oid, PKCS_9_AT_FRIENDLY_NAME);
+    assert_eq!(attribute.values.get(0).unwrap().tag(), Tag::BmpString);
+}
+
+#[test]
+fn local_key_id() {
+    round_trip(LocalKeyId(OctetString::new(hex!("01020304")).unwrap()));
+}
+
+#[test]
+fn absent_attribute() {
+    let attributes = Attributes::new();
+    assert_eq!(LocalKeyId::from_attributes(&attributes).unwrap(), None);
+}
+
+#[test]
+fn multi_valued_attribute() {
+    let mut values = SetOfVec::new();
+    for password in ["hunter42", "hunter43"] {
+        let value = ChallengePassword(DirectoryString::Utf8String(password.into()));
+        values
+            .insert(Any::from_der(&value.to_der().unwrap()).unwrap())
+            .unwrap();
+    }
+
+    let attributes = Attributes::try_from([Attribute {
+        oid: PKCS_9_AT_CHALLENGE_PASSWORD,
+        values,
+    }])
+    .unwrap();
+    assert!(ChallengePassword::from_attributes(&attributes).is_err());
+}
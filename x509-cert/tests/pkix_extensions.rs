@@ -1,8 +1,11 @@
 //! Certificate tests
 use const_oid::AssociatedOid;
-use der::asn1::{Ia5StringRef, OctetString, PrintableStringRef, Utf8StringRef};
+use der::asn1::{ObjectIdentifier, OctetString, PrintableStringRef, Utf8StringRef};
 use der::{Decode, Encode, ErrorKind, Length, Tag, Tagged};
 use hex_literal::hex;
+use x509_cert::ext::pkix::certpolicy::{
+    DisplayText, PolicyInformation, PolicyQualifier, PolicyQualifierInfo,
+};
 use x509_cert::ext::pkix::crl::dp::{DistributionPoint, ReasonFlags, Reasons};
 use x509_cert::ext::pkix::name::{DistributionPointName, GeneralName, GeneralNames};
 use x509_cert::ext::pkix::*;
@@ -341,15 +344,24 @@ fn decode_cert() {
                     for pqi in pq.iter() {
                         if 0 == counter_pq {
                             assert_eq!("1.3.6.1.5.5.7.2.1", pqi.policy_qualifier_id.to_string());
-                            let cpsval =
-                                Ia5StringRef::try_from(pqi.qualifier.as_ref().unwrap()).unwrap();
+                            let Some(PolicyQualifier::CpsUri(cpsval)) = &pqi.qualifier else {
+                                panic!("expected a CPS URI qualifier");
+                            };
                             assert_eq!(
                                 "https://secure.identrust.com/certificates/policy/IGC/index.html",
                                 cpsval.to_string()
                             );
                         } else if 1 == counter_pq {
                             assert_eq!("1.3.6.1.5.5.7.2.2", pqi.policy_qualifier_id.to_string());
-                            // TODO VisibleString
+                            let Some(PolicyQualifier::UserNotice(notice)) = &pqi.qualifier else {
+                                panic!("expected a user notice qualifier");
+                            };
+                            let text = notice.explicit_text.as_ref().unwrap();
+                            assert!(matches!(text, DisplayText::VisibleString(_)));
+                            assert!(notice.notice_ref.is_none());
+                            assert!(text
+                                .to_string()
+                                .starts_with("This is a test certificate. Do Not Rely."));
                         }
                         counter_pq += 1;
                     }
@@ -1218,3 +1230,59 @@ fn decode_idp() {
         err.kind()
     );
 }
+
+#[test]
+fn decode_tls_feature() {
+    let features = TlsFeatures::from_der(&hex!("3003020105")).unwrap();
+    assert_eq!(features.0, [TlsFeature::StatusRequest]);
+    assert!(features.is_must_staple());
+    assert_eq!(features.to_der().unwrap(), hex!("3003020105"));
+
+    let features = TlsFeatures::from_der(&hex!("3007020111020200FF")).unwrap();
+    assert_eq!(
+        features.0,
+        [TlsFeature::StatusRequestV2, TlsFeature::Other(255)]
+    );
+    assert!(features.is_must_staple());
+
+    let features = TlsFeatures::from_der(&hex!("3003020101")).unwrap();
+    assert!(!features.is_must_staple());
+
+    let cert = Certificate::from_der(include_bytes!("examples/must-staple.der")).unwrap();
+    assert!(cert.is_must_staple().unwrap());
+    let (critical, features) = cert.tbs_certificate.get::<TlsFeatures>().unwrap().unwrap();
+    assert!(!critical);
+    assert_eq!(features, TlsFeatures::must_staple());
+
+    let cert = Certificate::from_der(include_bytes!("examples/amazon.der")).unwrap();
+    assert!(!cert.is_must_staple().unwrap());
+}
+
+#[test]
+fn policy_qualifiers_round_trip() {
+    let policies = CertificatePolicies(vec![PolicyInformation {
+        policy_identifier: ObjectIdentifier::new_unwrap("2.23.140.1.2.1"),
+        policy_qualifiers: Some(vec![
+            PolicyQualifierInfo::cps_uri("https://example.com/cps").unwrap(),
+            PolicyQualifierInfo::user_notice("Relying parties must read the CPS").unwrap(),
+        ]),
+    }]);
+
+    let der = policies.to_der().unwrap();
+    let decoded = CertificatePolicies::from_der(&der).unwrap();
+    assert_eq!(decoded, policies);
+
+    let qualifiers = decoded.0[0].policy_qualifiers.as_ref().unwrap();
+    assert_eq!(qualifiers[0].policy_qualifier_id, ID_QT_CPS);
+    assert_eq!(qualifiers[1].policy_qualifier_id, ID_QT_UNOTICE);
+    let Some(PolicyQualifier::UserNotice(notice)) = &qualifiers[1].qualifier else {
+        panic!("expected a user notice qualifier");
+    };
+    assert_eq!(
+        notice.explicit_text.as_ref().unwrap().to_string(),
+        "Relying parties must read the CPS"
+    );
+
+    assert!(PolicyQualifierInfo::user_notice("").is_err());
+    assert!(PolicyQualifierInfo::user_notice(&"x".repeat(201)).is_err());
+}
@@ -0,0 +1,83 @@
+//! Public key summary tests
+
+use const_oid::db::rfc5912::{SECP_256_R_1, SECP_384_R_1};
+use der::Decode;
+use hex_literal::hex;
+use spki::SubjectPublicKeyInfoOwned;
+use x509_cert::public_key::{PublicKeyAlgorithm, PublicKeySummary};
+use x509_cert::Certificate;
+
+fn summarize(spki_der: &[u8]) -> PublicKeySummary {
+    let spki = SubjectPublicKeyInfoOwned::from_der(spki_der).unwrap();
+    PublicKeySummary::try_from(&spki).unwrap()
+}
+
+#[test]
+fn rsa_certificate() {
+    let cert = Certificate::from_der(include_bytes!("examples/rsa2048-crt.der")).unwrap();
+    let summary = cert.public_key_summary().unwrap();
+
+    assert_eq!(summary.algorithm, PublicKeyAlgorithm::Rsa);
+    assert_eq!(summary.key_size, Some(2048));
+    assert_eq!(summary.curve, None);
+}
+
+#[test]
+fn ec_p256() {
+    let summary = summarize(include_bytes!("examples/p256-pub.der"));
+
+    assert_eq!(summary.algorithm, PublicKeyAlgorithm::Ec);
+    assert_eq!(summary.key_size, Some(256));
+    assert_eq!(summary.curve, Some(SECP_256_R_1));
+}
+
+#[test]
+fn ec_p384() {
+    let summary = summarize(&hex!(
+        "3076301006072a8648ce3d020106052b8104002203620004f07029cd5faac91b"
+        "3c7f29518c3d8d8ba42a9683c2721cb70fc5e7b77e00dd2715866fb8d7691a51"
+        "73703c9dac7a51f202ba5ebb780c9cd20d972b0cbff7ccd2fc0425015e87bb27"
+        "c4836d0b40aa25a7f6eb5f626f2baabe546d2a99eb25545c"
+    ));
+
+    assert_eq!(summary.algorithm, PublicKeyAlgorithm::Ec);
+    assert_eq!(summary.key_size, Some(384));
+    assert_eq!(summary.curve, Some(SECP_384_R_1));
+}
+
+#[test]
+fn ed25519() {
+    let summary = summarize(&hex!(
+        "302a300506032b657003210078e5a2570ae3b1ddd5ce95824deadcc6a093fc37"
+        "21af0fc3047899dd7358898c"
+    ));
+
+    assert_eq!(summary.algorithm, PublicKeyAlgorithm::Ed25519);
+    assert_eq!(summary.key_size, Some(256));
+    assert_eq!(summary.parameters, None);
+}
+
+#[test]
+fn dsa_1024() {
+    let summary = summarize(&hex!(
+        "308201bf3082013306072a8648ce3804013082012602818100ded2ba8a04898b"
+        "653c548f9736bc7108a2f5bd0a93ea117925d3ab5e827eb598afb562811f841b"
+        "d52b8088f36c426155b845dcef694ac239c3b0b1ef9006bc6d8902ffa3f0c51d"
+        "d37aac6eca01a2aeb048dc197b92ed4670fff074e9a5851fd7831d8049775a05"
+        "f48ba481e4ae87ff69a414df6b196f6a4f5b605f18d0f02b91021d00bb8d5d6b"
+        "ef60c97b3de5f7d36728983ccecbfff7c6ea4a23ced9b5470281805063fa42b9"
+        "361ad2fc8b3ef146694360be069c6f6acc23868ef376a5e7b517fca032f506d5"
+        "d04da58e9e451cd84410f37fb9e46e4c7033dec447f70ae94b904ec5aefe80a4"
+        "51ea6c598d2afa216be59a003420b1c74ee619a0d0f000fa0e2248b37d4fd1c2"
+        "2b11959cb02b10eafad0275c3f12b93d6c9cef8a45f5af88822a5b0381850002"
+        "818100cc17be008f4f731d3db94082638700de7678381cdece0e65a7685318a6"
+        "c71c815299950d2b9ef7590b663018a55aa98a200a4758e18b54a0c73a0cb823"
+        "5e9b49ac7d974249d80d1c0fae91b231884d60f4db3f4a1cead0b15e95944c0b"
+        "43b5d56c1f682edbf5fb54ae23009ea5c42e8b237dfd07af5652ecee655348a0"
+        "83d47a"
+    ));
+
+    assert_eq!(summary.algorithm, PublicKeyAlgorithm::Dsa);
+    assert_eq!(summary.key_size, Some(1024));
+    assert!(summary.parameters.is_some());
+}
@@ -0,0 +1,104 @@
+//! Certificate summary tests
+
+#![cfg(feature = "summary")]
+
+use der::Decode;
+use x509_cert::Certificate;
+
+#[test]
+fn summary_rsa() {
+    let cert = Certificate::from_der(include_bytes!("examples/amazon.der")).unwrap();
+    let summary = cert.summary().unwrap();
+
+    assert_eq!(summary.version, 3);
+    assert_eq!(
+        summary.serial_number,
+        "0E:42:39:AB:85:E2:E6:A2:7C:52:C6:DE:9B:90:78:D9"
+    );
+    assert_eq!(summary.signature_algorithm, "sha256WithRSAEncryption");
+    assert_eq!(
+        summary.issuer,
+        "CN=DigiCert Global CA G2,O=DigiCert Inc,C=US"
+    );
+    assert_eq!(summary.subject, "CN=*.peg.a2z.com");
+    assert_eq!(summary.not_before, "2021-10-06T00:00:00Z");
+    assert_eq!(summary.not_after, "2022-09-19T23:59:59Z");
+
+    assert_eq!(summary.subject_alt_names.len(), 36);
+    assert_eq!(summary.subject_alt_names[0], "DNS:amazon.co.uk");
+    assert_eq!(summary.subject_alt_names[4], "DNS:*.peg.a2z.com");
+
+    assert_eq!(summary.public_key.algorithm, "rsaEncryption");
+    assert_eq!(summary.public_key.key_size, Some(2048));
+    assert_eq!(summary.public_key.curve, None);
+
+    // `openssl x509 -noout -fingerprint -sha1` and `-sha256`
+    assert_eq!(
+        summary.fingerprints.sha1,
+        "08:04:07:55:C8:B6:85:2A:5D:B9:45:A2:B3:80:57:11:11:DE:FD:2D"
+    );
+    assert_eq!(
+        summary.fingerprints.sha256,
+        "5B:F3:D7:E0:E6:92:7F:77:3D:51:06:C8:22:C5:3F:6F:52:C1:99:F7:EB:1B:3B:81:54:B4:1F:29:24:39:1C:75"
+    );
+
+    let extensions: Vec<_> = summary
+        .extensions
+        .iter()
+        .map(|ext| (ext.name.unwrap_or(&ext.oid), ext.critical))
+        .collect();
+    assert_eq!(
+        extensions,
+        [
+            ("id-ce-authorityKeyIdentifier", false),
+            ("id-ce-subjectKeyIdentifier", false),
+            ("id-ce-subjectAltName", false),
+            ("id-ce-keyUsage", true),
+            ("id-ce-extKeyUsage", false),
+            ("id-ce-cRLDistributionPoints", false),
+            ("id-ce-certificatePolicies", false),
+            ("id-pe-authorityInfoAccess", false),
+            ("id-ce-basicConstraints", true),
+            ("1.3.6.1.4.1.11129.2.4.2", false),
+        ]
+    );
+}
+
+#[test]
+fn summary_from_der() {
+    let der = include_bytes!("examples/amazon.der");
+    let cert = Certificate::from_der(der).unwrap();
+    let summary = Certificate::summary_from_der(der).unwrap();
+
+    assert_eq!(summary, cert.summary().unwrap());
+    assert!(Certificate::summary_from_der(&der[..der.len() - 1]).is_err());
+}
+
+#[test]
+fn summary_json() {
+    let cert = Certificate::from_der(include_bytes!("examples/amazon.der")).unwrap();
+    let summary = cert.summary().unwrap();
+    let json: serde_json::Value = serde_json::to_value(&summary).unwrap();
+
+    assert_eq!(json["subject"], "CN=*.peg.a2z.com");
+    assert_eq!(json["public_key"]["key_size"], 2048);
+    assert_eq!(json["public_key"]["curve"], serde_json::Value::Null);
+    assert_eq!(json["extensions"][3]["oid"], "2.5.29.15");
+    assert_eq!(json["extensions"][3]["critical"], true);
+    assert_eq!(json["subject_alt_names"][0], "DNS:amazon.co.uk");
+}
+
+#[test]
+fn summary_display() {
+    let cert = Certificate::from_der(include_bytes!("examples/amazon.der")).unwrap();
+    let text = cert.summary().unwrap().to_string();
+
+    assert!(text.starts_with(
+        "Version: 3\nSerial Number: 0E:42:39:AB:85:E2:E6:A2:7C:52:C6:DE:9B:90:78:D9\n"
+    ));
+    assert!(text.contains("\nPublic Key Algorithm: rsaEncryption, 2048 bit\n"));
+    assert!(text.contains("\nExtension: id-ce-keyUsage (critical)\n"));
+    assert!(text.ends_with(
+        "SHA-256 Fingerprint: 5B:F3:D7:E0:E6:92:7F:77:3D:51:06:C8:22:C5:3F:6F:52:C1:99:F7:EB:1B:3B:81:54:B4:1F:29:24:39:1C:75"
+    ));
+}
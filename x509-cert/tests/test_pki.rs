@@ -0,0 +1,216 @@
+//! Test PKI generation tests
+
+#![cfg(feature = "test-pki")]
+
+use const_oid::db::rfc5912::{
+    ECDSA_WITH_SHA_256, ID_EC_PUBLIC_KEY, RSA_ENCRYPTION, SHA_256_WITH_RSA_ENCRYPTION,
+};
+use der::asn1::Ia5String;
+use std::{str::FromStr, time::Duration};
+use x509_cert::{
+    ext::pkix::{name::GeneralName, BasicConstraints, ExtendedKeyUsage, SubjectAltName},
+    name::Name,
+    test_pki::{CertificateSpec, KeyAlgorithm, PrivateKey, TestPki, INTERMEDIATE_SUBJECT},
+    time::Validity,
+};
+
+#[test]
+fn default_hierarchy() {
+    let pki = TestPki::generate().unwrap();
+    let [leaf, intermediate, root] = pki.chain();
+
+    assert_eq!(root.tbs_certificate.issuer, root.tbs_certificate.subject);
+    assert_eq!(
+        intermediate.tbs_certificate.issuer,
+        root.tbs_certificate.subject
+    );
+    assert_eq!(
+        intermediate.tbs_certificate.subject,
+        Name::from_str(INTERMEDIATE_SUBJECT).unwrap()
+    );
+    assert_eq!(
+        leaf.tbs_certificate.issuer,
+        intermediate.tbs_certificate.subject
+    );
+    assert_ne!(
+        leaf.tbs_certificate.serial_number,
+        intermediate.tbs_certificate.serial_number
+    );
+
+    let (_, constraints) = intermediate
+        .tbs_certificate
+        .get::<BasicConstraints>()
+        .unwrap()
+        .unwrap();
+    assert!(constraints.ca);
+    assert_eq!(constraints.path_len_constraint, Some(0));
+
+    let (_, san) = leaf
+        .tbs_certificate
+        .get::<SubjectAltName>()
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        san.0,
+        [GeneralName::DnsName(Ia5String::new("localhost").unwrap())]
+    );
+
+    for cert in pki.chain() {
+        assert_eq!(cert.signature_algorithm.oid, ECDSA_WITH_SHA_256);
+        assert_eq!(
+            cert.tbs_certificate.subject_public_key_info.algorithm.oid,
+            ID_EC_PUBLIC_KEY
+        );
+    }
+
+    for certified in [&pki.leaf, &pki.intermediate, &pki.root] {
+        assert_eq!(
+            certified.key.public_key().unwrap(),
+            certified
+                .certificate
+                .tbs_certificate
+                .subject_public_key_info
+        );
+    }
+}
+
+#[test]
+fn mixed_algorithms() {
+    let pki = TestPki::builder()
+        .root_key_algorithm(KeyAlgorithm::Rsa { bits: 2048 })
+        .build()
+        .unwrap();
+
+    assert_eq!(pki.root.key.algorithm(), KeyAlgorithm::Rsa { bits: 2048 });
+    assert_eq!(
+        pki.root
+            .certificate
+            .tbs_certificate
+            .subject_public_key_info
+            .algorithm
+            .oid,
+        RSA_ENCRYPTION
+    );
+
+    // The root signs itself and the intermediate
+    assert_eq!(
+        pki.root.certificate.signature_algorithm.oid,
+        SHA_256_WITH_RSA_ENCRYPTION
+    );
+    assert_eq!(
+        pki.intermediate.certificate.signature_algorithm.oid,
+        SHA_256_WITH_RSA_ENCRYPTION
+    );
+    assert_eq!(
+        pki.leaf.certificate.signature_algorithm.oid,
+        ECDSA_WITH_SHA_256
+    );
+}
+
+#[test]
+fn custom_leaf() {
+    let spec = CertificateSpec::new(Name::from_str("CN=client").unwrap())
+        .extension(&ExtendedKeyUsage::new().client_auth())
+        .unwrap();
+    let pki = TestPki::builder().leaf(spec).build().unwrap();
+    let tbs = &pki.leaf.certificate.tbs_certificate;
+
+    assert_eq!(tbs.subject, Name::from_str("CN=client").unwrap());
+    assert!(tbs.get::<SubjectAltName>().unwrap().is_none());
+
+    let (_, eku) = tbs.get::<ExtendedKeyUsage>().unwrap().unwrap();
+    assert_eq!(eku, ExtendedKeyUsage::new().client_auth());
+}
+
+#[test]
+fn pkcs8_private_key() {
+    let key = PrivateKey::generate(KeyAlgorithm::EcdsaP256).unwrap();
+    let der = key.to_pkcs8_der().unwrap();
+    let PrivateKey::EcdsaP256(key) = key else {
+        panic!("unexpected key type");
+    };
+
+    let decoded =
+        <p256::ecdsa::SigningKey as p256::pkcs8::DecodePrivateKey>::from_pkcs8_der(der.as_bytes())
+            .unwrap();
+    assert_eq!(decoded, key);
+}
+
+#[cfg(feature = "verify")]
+mod verify {
+    use super::*;
+    use der::Encode;
+    use ecdsa::signature::Verifier;
+    use p256::pkcs8::DecodePublicKey;
+    use spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned};
+    use std::time::SystemTime;
+    use x509_cert::{
+        time::Time,
+        verify::{verify_leaf_against_intermediate_and_root, ChainPosition, Error},
+        Certificate,
+    };
+
+    fn verify_signature(
+        issuer_key: &SubjectPublicKeyInfoOwned,
+        algorithm: &AlgorithmIdentifierOwned,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), ecdsa::signature::Error> {
+        let issuer_key = issuer_key.to_der().unwrap();
+
+        match algorithm.oid {
+            ECDSA_WITH_SHA_256 => {
+                let key = p256::ecdsa::VerifyingKey::from_public_key_der(&issuer_key).unwrap();
+                key.verify(message, &p256::ecdsa::DerSignature::try_from(signature)?)
+            }
+            SHA_256_WITH_RSA_ENCRYPTION => {
+                let key = rsa::RsaPublicKey::from_public_key_der(&issuer_key).unwrap();
+                let key = rsa::pkcs1v15::VerifyingKey::<sha2::Sha256>::new(key);
+                key.verify(message, &rsa::pkcs1v15::Signature::try_from(signature)?)
+            }
+            _ => Err(ecdsa::signature::Error::new()),
+        }
+    }
+
+    fn not_revoked(_cert: &Certificate, _issuer: &Certificate) -> bool {
+        false
+    }
+
+    fn verify_at(pki: &TestPki, time: SystemTime) -> Result<(), Error> {
+        let [leaf, intermediate, root] = pki.chain().map(|cert| cert.to_der().unwrap());
+
+        verify_leaf_against_intermediate_and_root(
+            &leaf,
+            &intermediate,
+            &root,
+            Time::try_from(time).unwrap(),
+            "localhost",
+            &verify_signature,
+            &not_revoked,
+        )
+        .map(drop)
+    }
+
+    #[test]
+    fn verify_chain() {
+        for algorithm in [KeyAlgorithm::EcdsaP256, KeyAlgorithm::Rsa { bits: 2048 }] {
+            let pki = TestPki::builder().key_algorithm(algorithm).build().unwrap();
+            verify_at(&pki, SystemTime::now()).unwrap();
+        }
+    }
+
+    #[test]
+    fn verify_expired() {
+        let pki = TestPki::builder()
+            .validity(
+                Validity::from_unix_duration(Duration::ZERO, Duration::from_secs(3600)).unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            verify_at(&pki, SystemTime::now()),
+            Err(Error::Validity(ChainPosition::Leaf))
+        ));
+    }
+}
@@ -1,8 +1,9 @@
 //! Validity tests
 
-use der::{Decode, Encode};
+use core::time::Duration;
+use der::{asn1::GeneralizedTime, Decode, Encode};
 use hex_literal::hex;
-use x509_cert::time::Validity;
+use x509_cert::time::{Time, Validity};
 
 #[test]
 fn decode_validity() {
@@ -134,3 +135,27 @@ fn encode_validity() {
         &hex!("3020180F32303032303130313132303130305A170D3330313233313038333030305A")[..]
     );
 }
+
+#[test]
+fn validity_from_unix_duration() {
+    let validity = Validity::from_unix_duration(
+        Duration::from_secs(1262334600),
+        Duration::from_secs(365 * 24 * 60 * 60),
+    )
+    .unwrap();
+
+    assert_eq!(
+        validity.not_before,
+        Time::GeneralTime(
+            GeneralizedTime::from_unix_duration(Duration::from_secs(1262334600)).unwrap()
+        )
+    );
+    assert_eq!(validity.not_before.to_unix_duration().as_secs(), 1262334600);
+    assert_eq!(validity.not_after.to_unix_duration().as_secs(), 1293870600);
+    assert_eq!(validity.not_after.to_string(), "2011-01-01T08:30:00Z");
+
+    assert!(Validity::from_unix_duration(Duration::MAX, Duration::from_secs(1)).is_err());
+    assert!(
+        Validity::from_unix_duration(Duration::ZERO, Duration::from_secs(u64::MAX / 2)).is_err()
+    );
+}
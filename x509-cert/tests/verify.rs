@@ -0,0 +1,297 @@
+//! Certification path verification tests
+
+#![cfg(all(feature = "builder", feature = "verify"))]
+
+use der::{asn1::Ia5String, Decode, Encode};
+use ecdsa::signature::Verifier;
+use p256::{
+    ecdsa::{DerSignature, SigningKey, VerifyingKey},
+    pkcs8::{DecodePublicKey, EncodePublicKey},
+};
+use spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned};
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    str::FromStr,
+    time::{Duration, SystemTime},
+};
+use x509_cert::{
+    builder::{Builder, CertificateBuilder, Profile},
+    ext::pkix::{name::GeneralName, ExtendedKeyUsage, OcspNoCheck, SubjectAltName},
+    name::Name,
+    serial_number::SerialNumber,
+    time::{Time, Validity},
+    verify::{
+        requires_revocation_check, verify_leaf_against_intermediate_and_root, ChainPosition, Error,
+        RevocationChecker,
+    },
+    Certificate,
+};
+
+const ROOT_NAME: &str = "CN=World domination root,O=World domination Inc,C=US";
+const INTERMEDIATE_NAME: &str = "CN=World domination CA,O=World domination Inc,C=US";
+
+/// DER-encoded certification path.
+struct Chain {
+    leaf: Vec<u8>,
+    intermediate: Vec<u8>,
+    root: Vec<u8>,
+}
+
+impl Chain {
+    fn new(leaf_eku: ExtendedKeyUsage) -> Self {
+        Self::with_ocsp_no_check(leaf_eku, false)
+    }
+
+    /// Build a chain whose leaf carries the `id-pkix-ocsp-nocheck` extension if
+    /// `ocsp_no_check` is set.
+    fn with_ocsp_no_check(leaf_eku: ExtendedKeyUsage, ocsp_no_check: bool) -> Self {
+        let root_key = SigningKey::random(&mut rand::thread_rng());
+        let intermediate_key = SigningKey::random(&mut rand::thread_rng());
+        let leaf_key = SigningKey::random(&mut rand::thread_rng());
+        let validity = Validity::from_now(Duration::from_secs(3600)).unwrap();
+
+        let root = CertificateBuilder::new(
+            Profile::Root,
+            SerialNumber::from(1u32),
+            validity,
+            Name::from_str(ROOT_NAME).unwrap(),
+            spki(&root_key),
+            &root_key,
+        )
+        .unwrap()
+        .build::<DerSignature>()
+        .unwrap();
+
+        let intermediate = CertificateBuilder::new(
+            Profile::SubCA {
+                issuer: Name::from_str(ROOT_NAME).unwrap(),
+                path_len_constraint: Some(0),
+            },
+            SerialNumber::from(2u32),
+            validity,
+            Name::from_str(INTERMEDIATE_NAME).unwrap(),
+            spki(&intermediate_key),
+            &root_key,
+        )
+        .unwrap()
+        .build::<DerSignature>()
+        .unwrap();
+
+        let mut builder = CertificateBuilder::new(
+            Profile::Leaf {
+                issuer: Name::from_str(INTERMEDIATE_NAME).unwrap(),
+                enable_key_agreement: false,
+                enable_key_encipherment: false,
+                #[cfg(feature = "hazmat")]
+                include_subject_key_identifier: true,
+            },
+            SerialNumber::from(3u32),
+            validity,
+            Name::from_str("CN=service.domination.world").unwrap(),
+            spki(&leaf_key),
+            &intermediate_key,
+        )
+        .unwrap();
+        builder
+            .add_extension(&SubjectAltName(vec![
+                GeneralName::DnsName(Ia5String::new("service.domination.world").unwrap()),
+                GeneralName::DnsName(Ia5String::new("*.cdn.domination.world").unwrap()),
+                GeneralName::from(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1))),
+            ]))
+            .unwrap();
+        builder.add_extension(&leaf_eku).unwrap();
+        if ocsp_no_check {
+            builder.add_extension(&OcspNoCheck).unwrap();
+        }
+        let leaf = builder.build::<DerSignature>().unwrap();
+
+        Self {
+            leaf: leaf.to_der().unwrap(),
+            intermediate: intermediate.to_der().unwrap(),
+            root: root.to_der().unwrap(),
+        }
+    }
+
+    fn verify(&self, hostname: &str) -> Result<(), Error> {
+        self.verify_at(now(), hostname)
+    }
+
+    fn verify_at(&self, time: Time, hostname: &str) -> Result<(), Error> {
+        verify_leaf_against_intermediate_and_root(
+            &self.leaf,
+            &self.intermediate,
+            &self.root,
+            time,
+            hostname,
+            &verify_p256,
+            &not_revoked,
+        )
+        .map(drop)
+    }
+
+    fn verify_with_revocation(&self, revocation: &impl RevocationChecker) -> Result<(), Error> {
+        verify_leaf_against_intermediate_and_root(
+            &self.leaf,
+            &self.intermediate,
+            &self.root,
+            now(),
+            "service.domination.world",
+            &verify_p256,
+            revocation,
+        )
+        .map(drop)
+    }
+}
+
+fn not_revoked(_cert: &Certificate, _issuer: &Certificate) -> bool {
+    false
+}
+
+fn spki(key: &SigningKey) -> SubjectPublicKeyInfoOwned {
+    let der = key.verifying_key().to_public_key_der().unwrap();
+    SubjectPublicKeyInfoOwned::try_from(der.as_bytes()).unwrap()
+}
+
+fn now() -> Time {
+    Time::try_from(SystemTime::now()).unwrap()
+}
+
+fn verify_p256(
+    issuer_key: &SubjectPublicKeyInfoOwned,
+    algorithm: &AlgorithmIdentifierOwned,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<(), ecdsa::signature::Error> {
+    assert_eq!(algorithm.oid, const_oid::db::rfc5912::ECDSA_WITH_SHA_256);
+
+    let key = VerifyingKey::from_public_key_der(&issuer_key.to_der().unwrap())
+        .map_err(|_| ecdsa::signature::Error::new())?;
+    key.verify(message, &DerSignature::try_from(signature)?)
+}
+
+#[test]
+fn verify_chain() {
+    let chain = Chain::new(ExtendedKeyUsage::new().server_auth());
+
+    chain.verify("service.domination.world").unwrap();
+    chain.verify("SERVICE.domination.world.").unwrap();
+    chain.verify("eu.cdn.domination.world").unwrap();
+    chain.verify("192.0.2.1").unwrap();
+}
+
+#[test]
+fn verify_hostname_mismatch() {
+    let chain = Chain::new(ExtendedKeyUsage::new().server_auth());
+
+    for hostname in [
+        "domination.world",
+        "cdn.domination.world",
+        "a.b.cdn.domination.world",
+        "192.0.2.2",
+        "",
+    ] {
+        assert!(
+            matches!(chain.verify(hostname), Err(Error::HostnameMismatch)),
+            "{hostname}"
+        );
+    }
+}
+
+#[test]
+fn verify_extended_key_usage() {
+    let chain = Chain::new(ExtendedKeyUsage::new().client_auth());
+    assert!(matches!(
+        chain.verify("service.domination.world"),
+        Err(Error::ExtendedKeyUsage(ChainPosition::Leaf))
+    ));
+}
+
+#[test]
+fn verify_validity() {
+    let chain = Chain::new(ExtendedKeyUsage::new().server_auth());
+    let later = Time::try_from(SystemTime::now() + Duration::from_secs(7200)).unwrap();
+    assert!(matches!(
+        chain.verify_at(later, "service.domination.world"),
+        Err(Error::Validity(ChainPosition::Leaf))
+    ));
+}
+
+#[test]
+fn verify_issuer_mismatch() {
+    let chain = Chain::new(ExtendedKeyUsage::new().server_auth());
+    let err = verify_leaf_against_intermediate_and_root(
+        &chain.leaf,
+        &chain.root,
+        &chain.intermediate,
+        now(),
+        "service.domination.world",
+        &verify_p256,
+        &not_revoked,
+    )
+    .unwrap_err();
+    assert!(matches!(err, Error::IssuerMismatch(ChainPosition::Leaf)));
+}
+
+#[test]
+fn verify_tampered_signature() {
+    let mut chain = Chain::new(ExtendedKeyUsage::new().server_auth());
+    let other = Chain::new(ExtendedKeyUsage::new().server_auth());
+
+    // Same names, different keys
+    chain.intermediate = other.intermediate;
+    assert!(matches!(
+        chain.verify("service.domination.world"),
+        Err(Error::Signature(ChainPosition::Leaf, _))
+    ));
+}
+
+#[test]
+fn ocsp_responder_revocation_check() {
+    let ca_key = SigningKey::random(&mut rand::thread_rng());
+    let responder_key = SigningKey::random(&mut rand::thread_rng());
+    let validity = Validity::from_now(Duration::from_secs(3600)).unwrap();
+
+    let responder = CertificateBuilder::new(
+        Profile::OcspResponder {
+            issuer: Name::from_str(INTERMEDIATE_NAME).unwrap(),
+        },
+        SerialNumber::from(4u32),
+        validity,
+        Name::from_str("CN=ocsp.domination.world").unwrap(),
+        spki(&responder_key),
+        &ca_key,
+    )
+    .unwrap()
+    .build::<DerSignature>()
+    .unwrap();
+    assert!(!requires_revocation_check(&responder).unwrap());
+
+    // The OCSP signing key purpose alone isn't enough
+    let chain = Chain::new(ExtendedKeyUsage::new().server_auth().ocsp_signing());
+    let leaf = Certificate::from_der(&chain.leaf).unwrap();
+    assert!(requires_revocation_check(&leaf).unwrap());
+}
+
+#[test]
+fn verify_revoked() {
+    let revoked_serial = |serial: u32| {
+        move |cert: &Certificate, _issuer: &Certificate| {
+            cert.tbs_certificate.serial_number == SerialNumber::from(serial)
+        }
+    };
+
+    let chain = Chain::new(ExtendedKeyUsage::new().server_auth());
+    assert!(matches!(
+        chain.verify_with_revocation(&revoked_serial(3)),
+        Err(Error::Revoked(ChainPosition::Leaf))
+    ));
+    assert!(matches!(
+        chain.verify_with_revocation(&revoked_serial(2)),
+        Err(Error::Revoked(ChainPosition::Intermediate))
+    ));
+
+    // The revocation status of a delegated OCSP responder isn't checked
+    let chain =
+        Chain::with_ocsp_no_check(ExtendedKeyUsage::new().server_auth().ocsp_signing(), true);
+    chain.verify_with_revocation(&revoked_serial(3)).unwrap();
+}
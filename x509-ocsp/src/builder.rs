@@ -0,0 +1,185 @@
+//! OCSP request builder and signature verification
+
+use alloc::vec::Vec;
+use core::fmt;
+use der::Encode;
+use signature::{SignatureEncoding, Signer, Verifier};
+use spki::{DynSignatureAlgorithmIdentifier, SignatureBitStringEncoding};
+use x509_cert::ext::pkix::name::GeneralName;
+use x509_cert::ext::Extensions;
+use x509_cert::Certificate;
+
+use crate::{OcspRequest, Request, Signature, TbsRequest};
+
+/// Error type
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors.
+    Asn1(der::Error),
+
+    /// Public key errors propagated from the [`spki::Error`] type.
+    PublicKey(spki::Error),
+
+    /// Signing or verification error propagated for the [`signature::Error`] type.
+    Signature(signature::Error),
+
+    /// The request carries no `optionalSignature`.
+    MissingSignature,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::PublicKey(err) => write!(f, "public key error: {}", err),
+            Error::Signature(err) => write!(f, "signature error: {}", err),
+            Error::MissingSignature => write!(f, "OCSP request is not signed"),
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(err: der::Error) -> Error {
+        Error::Asn1(err)
+    }
+}
+
+impl From<spki::Error> for Error {
+    fn from(err: spki::Error) -> Error {
+        Error::PublicKey(err)
+    }
+}
+
+impl From<signature::Error> for Error {
+    fn from(err: signature::Error) -> Error {
+        Error::Signature(err)
+    }
+}
+
+type Result<T> = core::result::Result<T, Error>;
+
+/// Builder for [`OcspRequest`]s.
+///
+/// ```text
+/// OCSPRequest ::= SEQUENCE {
+///    tbsRequest              TBSRequest,
+///    optionalSignature   [0] EXPLICIT Signature OPTIONAL }
+/// ```
+///
+/// Requests are left unsigned by [`OcspRequestBuilder::build`]. Responders which answer
+/// unsigned requests with `sigRequired` expect them to be signed by the requestor with
+/// [`OcspRequestBuilder::sign`], as described in [RFC 6960 Section 4.1.2].
+///
+/// [RFC 6960 Section 4.1.2]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.1.2
+#[derive(Clone, Debug, Default)]
+pub struct OcspRequestBuilder<'a> {
+    tbs_request: TbsRequest<'a>,
+    certs: Vec<Certificate>,
+}
+
+impl<'a> OcspRequestBuilder<'a> {
+    /// Creates a builder for a request without any `Request` entries.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a `Request` to the `requestList`.
+    pub fn with_request(mut self, request: Request<'a>) -> Self {
+        self.tbs_request.request_list.push(request);
+        self
+    }
+
+    /// Adds a `Request` to the `requestList` for each of `requests`, to check the status of
+    /// several certificates at once.
+    ///
+    /// The responses are matched back to the requests with
+    /// [`BasicOcspResponse::match_responses`][crate::BasicOcspResponse::match_responses].
+    pub fn with_requests(mut self, requests: impl IntoIterator<Item = Request<'a>>) -> Self {
+        self.tbs_request.request_list.extend(requests);
+        self
+    }
+
+    /// Sets the `requestExtensions`, such as a nonce.
+    pub fn with_extensions(mut self, extensions: Extensions) -> Self {
+        self.tbs_request.request_extensions = Some(extensions);
+        self
+    }
+
+    /// Adds a certificate to be included alongside the requestor's certificate in the
+    /// `certs` of the signature, to help the responder build a path to it.
+    pub fn with_certificate(mut self, cert: Certificate) -> Self {
+        self.certs.push(cert);
+        self
+    }
+
+    /// Builds an unsigned request.
+    pub fn build(self) -> OcspRequest<'a> {
+        OcspRequest {
+            tbs_request: self.tbs_request,
+            optional_signature: None,
+        }
+    }
+
+    /// Builds a request signed by the requestor.
+    ///
+    /// The `requestorName` is set to the subject of `requestor_cert`, as required for signed
+    /// requests, and the certificate is included first in the `certs` of the signature.
+    pub fn sign<S, Sig>(
+        mut self,
+        signer: &S,
+        requestor_cert: Certificate,
+    ) -> Result<OcspRequest<'a>>
+    where
+        S: Signer<Sig> + DynSignatureAlgorithmIdentifier,
+        Sig: SignatureBitStringEncoding,
+    {
+        self.tbs_request.requestor_name = Some(GeneralName::DirectoryName(
+            requestor_cert.tbs_certificate.subject.clone(),
+        ));
+
+        let signature_algorithm = signer.signature_algorithm_identifier()?;
+        let tbs = self.tbs_request.to_der()?;
+        let signature = signer.try_sign(&tbs)?.to_bitstring()?;
+
+        let mut certs = Vec::with_capacity(self.certs.len() + 1);
+        certs.push(requestor_cert);
+        certs.extend(self.certs);
+
+        Ok(OcspRequest {
+            tbs_request: self.tbs_request,
+            optional_signature: Some(Signature {
+                signature_algorithm,
+                signature,
+                certs: Some(certs),
+            }),
+        })
+    }
+}
+
+impl OcspRequest<'_> {
+    /// Verifies the `optionalSignature` of this request over `tbs_der`, the exact encoding of
+    /// the `tbsRequest` returned by [`OcspRequest::from_der_with_tbs`].
+    ///
+    /// The verifying key is that of the requestor, usually taken from the first of the `certs`
+    /// carried by the signature. Matching the key with the signature algorithm identifier and
+    /// validating the requestor's certificate are left to the caller.
+    pub fn verify_signature<V, Sig>(&self, tbs_der: &[u8], verifying_key: &V) -> Result<()>
+    where
+        V: Verifier<Sig>,
+        Sig: SignatureEncoding,
+    {
+        let signature = self
+            .optional_signature
+            .as_ref()
+            .ok_or(Error::MissingSignature)?;
+
+        let bytes = signature
+            .signature
+            .as_bytes()
+            .ok_or_else(|| der::Tag::BitString.value_error())?;
+        let signature = Sig::try_from(bytes).map_err(|_| signature::Error::new())?;
+
+        Ok(verifying_key.verify(tbs_der, &signature)?)
+    }
+}
@@ -13,9 +13,12 @@
 
 extern crate alloc;
 
-use der::asn1::{BitStringRef, Ia5StringRef, ObjectIdentifier, OctetStringRef, UintRef};
+#[cfg(feature = "builder")]
+pub mod builder;
+
+use der::asn1::{BitString, BitStringRef, Ia5StringRef, ObjectIdentifier, OctetStringRef, UintRef};
 use der::asn1::{GeneralizedTime, Null};
-use der::{AnyRef, Choice, Enumerated, Sequence};
+use der::{AnyRef, Choice, Decode, Enumerated, Reader, Sequence, SliceReader, TagMode, TagNumber};
 use spki::{AlgorithmIdentifierOwned, AlgorithmIdentifierRef};
 use x509_cert::ext::pkix::name::GeneralName;
 use x509_cert::ext::pkix::{AuthorityInfoAccessSyntax, CrlReason};
@@ -24,6 +27,7 @@ use x509_cert::name::Name;
 use x509_cert::serial_number::SerialNumber;
 use x509_cert::Certificate;
 
+use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
 use core::default::Default;
 use core::option::Option;
@@ -54,7 +58,31 @@ pub struct OcspRequest<'a> {
     pub tbs_request: TbsRequest<'a>,
 
     #[asn1(context_specific = "0", optional = "true", tag_mode = "EXPLICIT")]
-    pub optional_signature: Option<Signature<'a>>,
+    pub optional_signature: Option<Signature>,
+}
+
+impl<'a> OcspRequest<'a> {
+    /// Decode a DER-encoded request, also returning the exact encoding of its
+    /// `tbsRequest`.
+    ///
+    /// The signature should be verified over these bytes rather than over a
+    /// re-encoding of [`OcspRequest::tbs_request`], which may differ from the
+    /// original for requests produced by non-conforming encoders.
+    pub fn from_der_with_tbs(bytes: &'a [u8]) -> der::Result<(Self, &'a [u8])> {
+        let mut reader = SliceReader::new(bytes)?;
+
+        let result = reader.sequence(|reader| {
+            let tbs_der = reader.tlv_bytes()?;
+            let request = Self {
+                tbs_request: TbsRequest::from_der(tbs_der)?,
+                optional_signature: reader.context_specific(TagNumber::N0, TagMode::Explicit)?,
+            };
+
+            Ok((request, tbs_der))
+        })?;
+
+        reader.finish(result)
+    }
 }
 
 /// TBSRequest structure as defined in [RFC 6960 Section 4.1.1].
@@ -68,7 +96,7 @@ pub struct OcspRequest<'a> {
 /// ```
 ///
 /// [RFC 6960 Section 4.1.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.1.1
-#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct TbsRequest<'a> {
     #[asn1(
@@ -99,9 +127,9 @@ pub struct TbsRequest<'a> {
 /// [RFC 6960 Section 4.1.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.1.1
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
-pub struct Signature<'a> {
-    pub signature_algorithm: AlgorithmIdentifierRef<'a>,
-    pub signature: BitStringRef<'a>,
+pub struct Signature {
+    pub signature_algorithm: AlgorithmIdentifierOwned,
+    pub signature: BitString,
 
     #[asn1(context_specific = "0", optional = "true", tag_mode = "EXPLICIT")]
     pub certs: Option<Vec<Certificate>>,
@@ -166,6 +194,24 @@ pub struct CertId<'a> {
     pub serial_number: SerialNumber,
 }
 
+impl CertId<'_> {
+    /// Whether this `CertID` identifies the same certificate as `other`.
+    ///
+    /// Hash algorithms are compared by OID, with absent and `NULL` parameters considered
+    /// equal, as responders don't always echo the parameters of the request.
+    pub fn matches(&self, other: &CertId<'_>) -> bool {
+        fn parameters<'p>(alg: &AlgorithmIdentifierRef<'p>) -> Option<AnyRef<'p>> {
+            alg.parameters.filter(|params| !params.is_null())
+        }
+
+        self.hash_algorithm.oid == other.hash_algorithm.oid
+            && parameters(&self.hash_algorithm) == parameters(&other.hash_algorithm)
+            && self.issuer_name_hash == other.issuer_name_hash
+            && self.issuer_key_hash == other.issuer_key_hash
+            && self.serial_number == other.serial_number
+    }
+}
+
 /// OCSPResponse structure as defined in [RFC 6960 Section 4.2.1].
 ///
 /// ```text
@@ -227,6 +273,26 @@ pub struct ResponseBytes<'a> {
     pub response: OctetStringRef<'a>,
 }
 
+impl<'a> OcspResponse<'a> {
+    /// Decode the [`BasicOcspResponse`] carried by this response.
+    ///
+    /// Returns `None` if the response bytes are absent, as for unsuccessful responses, or
+    /// are of a response type other than `id-pkix-ocsp-basic`.
+    pub fn basic_response(&self) -> der::Result<Option<BasicOcspResponse<'a>>> {
+        match &self.response_bytes {
+            Some(bytes) if bytes.response_type == ID_PKIX_OCSP_BASIC => {
+                BasicOcspResponse::from_der(bytes.response.as_bytes()).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// `id-pkix-ocsp-basic` response type as defined in [RFC 6960 Section 4.2.1].
+///
+/// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+const ID_PKIX_OCSP_BASIC: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.48.1.1");
+
 /// BasicOcspResponse structure as defined in [RFC 6960 Section 4.2.1].
 ///
 /// ```text
@@ -249,6 +315,46 @@ pub struct BasicOcspResponse<'a> {
     pub certs: Option<Vec<AnyRef<'a>>>,
 }
 
+impl<'a> BasicOcspResponse<'a> {
+    /// Find the [`SingleResponse`] for the certificate identified by `cert_id`.
+    pub fn find_response(&self, cert_id: &CertId<'_>) -> Option<&SingleResponse<'a>> {
+        self.tbs_response_data
+            .responses
+            .iter()
+            .find(|response| response.cert_id.matches(cert_id))
+    }
+
+    /// Match the responses with the certificates of a batched request.
+    ///
+    /// `cert_ids` pairs a key, such as the index of the certificate in a chain, with the
+    /// `CertID` it was requested with. The returned map holds the response for each key,
+    /// and leaves out certificates the responder didn't answer for.
+    ///
+    /// ```
+    /// # use x509_ocsp::{BasicOcspResponse, OcspRequest};
+    /// # fn check(request: &OcspRequest<'_>, response: &BasicOcspResponse<'_>) {
+    /// let cert_ids = request
+    ///     .tbs_request
+    ///     .request_list
+    ///     .iter()
+    ///     .map(|req| &req.req_cert)
+    ///     .enumerate();
+    /// let statuses = response.match_responses(cert_ids);
+    /// assert_eq!(statuses.len(), request.tbs_request.request_list.len());
+    /// # }
+    /// ```
+    pub fn match_responses<'c, 'i: 'c, K, I>(&self, cert_ids: I) -> BTreeMap<K, &SingleResponse<'a>>
+    where
+        K: Ord,
+        I: IntoIterator<Item = (K, &'c CertId<'i>)>,
+    {
+        cert_ids
+            .into_iter()
+            .filter_map(|(key, cert_id)| Some((key, self.find_response(cert_id)?)))
+            .collect()
+    }
+}
+
 /// ResponseData structure as defined in [RFC 6960 Section 4.2.1].
 ///
 /// ```text
@@ -0,0 +1,101 @@
+//! OCSP request builder tests
+
+#![cfg(feature = "builder")]
+
+use der::{Decode, Encode};
+use hex_literal::hex;
+use p256::{
+    ecdsa::{DerSignature, SigningKey, VerifyingKey},
+    pkcs8::DecodePrivateKey,
+};
+use x509_cert::ext::pkix::name::GeneralName;
+use x509_cert::Certificate;
+use x509_ocsp::builder::{Error, OcspRequestBuilder};
+use x509_ocsp::*;
+
+const P256_PRIV_DER: &[u8] = include_bytes!("examples/p256-priv.der");
+const REQUESTOR_CERT_DER: &[u8] = include_bytes!("examples/ValidCertificatePathTest1EE.crt");
+
+// Unsigned request generated with OpenSSL, see `decode_ocsp_req_ca_signed`
+const UNSIGNED_REQ_DER: [u8; 83] = hex!("3051304F304D304B3049300906052B0E03021A05000414A87E303106E4E88565CFE952598FA6DA7C00532F0414246E2B2DD06A925151256901AA9A47A689E7402002100E4239AB85E2E6A27C52C6DE9B9078D9");
+
+#[test]
+fn build_unsigned_request() {
+    let unsigned = OcspRequest::from_der(&UNSIGNED_REQ_DER).unwrap();
+    let request = unsigned.tbs_request.request_list[0].clone();
+
+    let built = OcspRequestBuilder::new().with_request(request).build();
+    assert_eq!(built.to_der().unwrap(), UNSIGNED_REQ_DER);
+    assert!(matches!(
+        built.verify_signature::<VerifyingKey, DerSignature>(
+            &built.tbs_request.to_der().unwrap(),
+            SigningKey::from_pkcs8_der(P256_PRIV_DER)
+                .unwrap()
+                .verifying_key()
+        ),
+        Err(Error::MissingSignature)
+    ));
+}
+
+#[test]
+fn sign_and_verify_request() {
+    let signer = SigningKey::from_pkcs8_der(P256_PRIV_DER).unwrap();
+    let requestor_cert = Certificate::from_der(REQUESTOR_CERT_DER).unwrap();
+    let unsigned = OcspRequest::from_der(&UNSIGNED_REQ_DER).unwrap();
+
+    let signed = OcspRequestBuilder::new()
+        .with_request(unsigned.tbs_request.request_list[0].clone())
+        .sign::<_, DerSignature>(&signer, requestor_cert.clone())
+        .unwrap();
+
+    assert_eq!(
+        signed.tbs_request.requestor_name,
+        Some(GeneralName::DirectoryName(
+            requestor_cert.tbs_certificate.subject.clone()
+        ))
+    );
+    let signature = signed.optional_signature.as_ref().unwrap();
+    assert_eq!(
+        signature.signature_algorithm.oid,
+        const_oid::db::rfc5912::ECDSA_WITH_SHA_256
+    );
+    assert_eq!(signature.certs.as_deref(), Some(&[requestor_cert][..]));
+
+    // round trip through DER before verifying
+    let der = signed.to_der().unwrap();
+    let (decoded, tbs_der) = OcspRequest::from_der_with_tbs(&der).unwrap();
+    assert_eq!(decoded, signed);
+    assert_eq!(tbs_der, signed.tbs_request.to_der().unwrap());
+    decoded
+        .verify_signature::<_, DerSignature>(tbs_der, signer.verifying_key())
+        .unwrap();
+
+    // signature does not cover a modified request
+    let mut tampered = decoded.tbs_request.clone();
+    tampered
+        .request_list
+        .push(unsigned.tbs_request.request_list[0].clone());
+    assert!(matches!(
+        decoded.verify_signature::<_, DerSignature>(
+            &tampered.to_der().unwrap(),
+            signer.verifying_key()
+        ),
+        Err(Error::Signature(_))
+    ));
+}
+
+#[test]
+fn build_batched_request() {
+    let unsigned = OcspRequest::from_der(&UNSIGNED_REQ_DER).unwrap();
+    let request = &unsigned.tbs_request.request_list[0];
+    let mut other = request.clone();
+    other.req_cert.serial_number = x509_cert::serial_number::SerialNumber::from(42u32);
+
+    let built = OcspRequestBuilder::new()
+        .with_requests([request.clone(), other.clone()])
+        .build();
+    assert_eq!(built.tbs_request.request_list, [request.clone(), other]);
+
+    let der = built.to_der().unwrap();
+    assert_eq!(OcspRequest::from_der(&der).unwrap(), built);
+}